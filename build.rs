@@ -1,6 +1,4 @@
 // build.rs
-use std::path::PathBuf;
-
 fn main() {
     // Tell Cargo to rerun this script if any of these files change
     println!("cargo:rerun-if-changed=build.rs");
@@ -8,29 +6,9 @@ fn main() {
     // Python support is provided by rustpython-parser crate, so we don't need to build 
     // tree-sitter-python grammar ourselves
     
-    // Build tree-sitter-rust
-    let rust_dir = PathBuf::from("./vendor/tree-sitter-rust");
-    if !rust_dir.exists() {
-        std::fs::create_dir_all(&rust_dir).unwrap();
-        println!("cargo:warning=Created directory for tree-sitter-rust");
-    }
-    
-    // Build tree-sitter-javascript
-    let js_dir = PathBuf::from("./vendor/tree-sitter-javascript");
-    if !js_dir.exists() {
-        std::fs::create_dir_all(&js_dir).unwrap();
-        println!("cargo:warning=Created directory for tree-sitter-javascript");
-    }
-    
-    // Build tree-sitter-typescript
-    let ts_dir = PathBuf::from("./vendor/tree-sitter-typescript");
-    if !ts_dir.exists() {
-        std::fs::create_dir_all(&ts_dir).unwrap();
-        println!("cargo:warning=Created directory for tree-sitter-typescript");
-    }
-    
-    // Note to users: For now, we're using pre-built grammar files from the crates
-    println!("cargo:warning=Using pre-built tree-sitter grammars from their respective crates");
-    println!("cargo:warning=If you encounter linking errors, you might need to install the tree-sitter CLI");
-    println!("cargo:warning=and manually build the grammar files in the vendor directory");
+    // tree-sitter-rust, tree-sitter-javascript, and tree-sitter-typescript ship the
+    // generated grammar sources (parser.c, scanner.c) and compile them in their own
+    // build scripts. A grammar is only linked in when its crate is referenced, so the
+    // parsers in src/lang load them through the crates' `language()` functions rather
+    // than declaring the C symbols themselves.
 }
\ No newline at end of file
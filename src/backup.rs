@@ -0,0 +1,199 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::discover;
+use crate::error::{DocGenError, DocGenResult};
+use crate::history::fnv1a;
+use crate::io;
+
+/// Name of the directory kept in the repository root for timestamped backups
+pub const BACKUP_DIR: &str = ".docgen-backups";
+
+/// Name of the manifest kept in the repository root listing the last run's backups
+const MANIFEST_FILE: &str = ".docgen-backup.json";
+
+/// Where the originals of written files are copied (`--backup`)
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupMode {
+    /// A `.orig` copy next to each file
+    Orig,
+    /// A directory for the run under `.docgen-backups`, named for when it started
+    Dir,
+}
+
+/// A file written by a run, and the copy of what it held before
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BackupEntry {
+    pub file: PathBuf,
+    pub backup: PathBuf,
+    /// When the file was first written, in seconds since the Unix epoch
+    pub written_at: u64,
+    /// Hash of what the run last wrote, which stops matching once the file is edited
+    pub written_hash: u64,
+}
+
+/// The backups of the last run that wrote files, kept until they're rolled back
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Manifest {
+    /// When the run started, in seconds since the Unix epoch
+    pub started_at: u64,
+    pub mode: BackupMode,
+    pub files: Vec<BackupEntry>,
+}
+
+impl Manifest {
+    /// Path of the manifest for a repository root
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(MANIFEST_FILE)
+    }
+    
+    /// Load the manifest, or None if no run has left backups to roll back
+    pub fn load(path: &Path) -> DocGenResult<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map(Some)
+                .map_err(|e| DocGenError::ConfigError(format!("{}: {}", path.display(), e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+    
+    /// Write the manifest, or remove it once it lists no files
+    pub fn save(&self, path: &Path) -> DocGenResult<()> {
+        if self.files.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| DocGenError::UpdateError(e.to_string()))?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// Backups taken by the current run, shared by the tasks writing files
+pub struct Backups {
+    root: PathBuf,
+    manifest: Mutex<Manifest>,
+}
+
+impl Backups {
+    pub fn new(root: &Path, mode: BackupMode) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            manifest: Mutex::new(Manifest { started_at: now(), mode, files: Vec::new() }),
+        }
+    }
+    
+    /// Copy a file aside the first time the run writes it, and record what it's about
+    /// to be written with
+    pub fn keep(&self, file_path: &Path, written: &str) -> DocGenResult<()> {
+        let file = discover::canonical(file_path);
+        let mut manifest = self.manifest.lock().expect("backup manifest lock poisoned");
+        if let Some(entry) = manifest.files.iter_mut().find(|entry| entry.file == file) {
+            entry.written_hash = fnv1a(written);
+            return Ok(());
+        }
+        
+        let backup = match manifest.mode {
+            BackupMode::Orig => {
+                let mut name = file.clone().into_os_string();
+                name.push(".orig");
+                PathBuf::from(name)
+            },
+            BackupMode::Dir => {
+                let relative = file.strip_prefix(discover::canonical(&self.root)).unwrap_or(&file);
+                // An absolute path outside the repository keeps its components, not its root
+                let relative: PathBuf = relative.components()
+                    .filter(|component| matches!(component, std::path::Component::Normal(_)))
+                    .collect();
+                self.root.join(BACKUP_DIR).join(manifest.started_at.to_string()).join(relative)
+            },
+        };
+        if let Some(parent) = backup.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(&file, &backup)?;
+        
+        manifest.files.push(BackupEntry { file, backup, written_at: now(), written_hash: fnv1a(written) });
+        Ok(())
+    }
+    
+    /// Record this run's backups as the ones `docgen rollback` restores, if it wrote
+    /// any files, returning how many there were
+    pub fn save(&self) -> DocGenResult<usize> {
+        let manifest = self.manifest.lock().expect("backup manifest lock poisoned");
+        if !manifest.files.is_empty() {
+            manifest.save(&Manifest::path(&self.root))?;
+        }
+        Ok(manifest.files.len())
+    }
+}
+
+/// What rolling back the last run's backups did
+#[derive(Debug, Default)]
+pub struct Rollback {
+    pub restored: Vec<PathBuf>,
+    /// Files edited since the run wrote them, left alone unless forced
+    pub edited: Vec<PathBuf>,
+    /// Files whose backup copy is gone
+    pub missing: Vec<PathBuf>,
+}
+
+/// Restore the files the last run wrote from their backups. Files edited since then
+/// are left alone unless `force` is set, and stay in the manifest to roll back later;
+/// the rest are taken out of it, and their backups removed.
+pub fn rollback(root: &Path, force: bool) -> DocGenResult<Option<Rollback>> {
+    let path = Manifest::path(root);
+    let mut manifest = match Manifest::load(&path)? {
+        Some(manifest) => manifest,
+        None => return Ok(None),
+    };
+    
+    let mut result = Rollback::default();
+    let mut remaining = Vec::new();
+    for entry in manifest.files {
+        let original = match std::fs::read_to_string(&entry.backup) {
+            Ok(original) => original,
+            Err(_) => {
+                result.missing.push(entry.file.clone());
+                remaining.push(entry);
+                continue;
+            },
+        };
+        let current = std::fs::read_to_string(&entry.file).ok();
+        let edited = current.as_deref().is_some_and(|c| fnv1a(c) != entry.written_hash && c != original);
+        if edited && !force {
+            result.edited.push(entry.file.clone());
+            remaining.push(entry);
+            continue;
+        }
+        io::write_atomic(&entry.file, original.as_bytes())?;
+        std::fs::remove_file(&entry.backup)?;
+        result.restored.push(entry.file);
+    }
+    
+    if manifest.mode == BackupMode::Dir && remaining.is_empty() {
+        let run_dir = root.join(BACKUP_DIR).join(manifest.started_at.to_string());
+        if run_dir.exists() {
+            std::fs::remove_dir_all(run_dir)?;
+        }
+    }
+    manifest.files = remaining;
+    manifest.save(&path)?;
+    Ok(Some(result))
+}
+
+/// Seconds since the Unix epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::capabilities::ModelCapabilities;
+
+/// Spending cap for a whole run (`--max-tokens-per-run`, `--max-cost`), shared by every
+/// client. Requests already in flight when the cap is reached still finish, so a run
+/// can end slightly over it; no new requests are sent after that.
+#[derive(Debug, Default)]
+pub struct Budget {
+    max_tokens: Option<u64>,
+    max_cost: Option<f64>,
+    tokens: AtomicU64,
+    /// Spent so far in millionths of a dollar, so it can be added to atomically
+    micro_dollars: AtomicU64,
+    /// Usage of each provider that answered, by name
+    providers: Mutex<BTreeMap<String, ProviderUsage>>,
+}
+
+/// What one provider's answered requests used during a run
+#[derive(Debug, Default, Clone)]
+pub struct ProviderUsage {
+    pub requests: usize,
+    pub tokens: u64,
+    pub cost: f64,
+    /// Time spent waiting on the provider's answers, added up across requests that
+    /// may have overlapped
+    pub elapsed: Duration,
+}
+
+impl Budget {
+    pub fn new(max_tokens: Option<u64>, max_cost: Option<f64>) -> Self {
+        Self { max_tokens, max_cost, ..Self::default() }
+    }
+    
+    /// Whether a cost cap was set, which needs the model's pricing
+    pub fn limits_cost(&self) -> bool {
+        self.max_cost.is_some()
+    }
+    
+    /// Add the usage of a request a provider answered, priced with the model's rates,
+    /// and how long the answer took
+    pub fn record(
+        &self,
+        provider: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        capabilities: &ModelCapabilities,
+        elapsed: Duration,
+    ) {
+        let cost = (input_tokens as f64 * capabilities.cost_per_1k_input
+            + output_tokens as f64 * capabilities.cost_per_1k_output) / 1000.0;
+        self.tokens.fetch_add(input_tokens + output_tokens, Ordering::Relaxed);
+        self.micro_dollars.fetch_add((cost * 1_000_000.0).round() as u64, Ordering::Relaxed);
+        
+        let mut providers = self.providers.lock().expect("provider usage lock poisoned");
+        let usage = providers.entry(provider.to_string()).or_default();
+        usage.requests += 1;
+        usage.tokens += input_tokens + output_tokens;
+        usage.cost += cost;
+        usage.elapsed += elapsed;
+    }
+    
+    /// Usage of each provider that answered a request so far, by name
+    pub fn providers(&self) -> BTreeMap<String, ProviderUsage> {
+        self.providers.lock().expect("provider usage lock poisoned").clone()
+    }
+    
+    /// Tokens used by the run so far
+    pub fn tokens(&self) -> u64 {
+        self.tokens.load(Ordering::Relaxed)
+    }
+    
+    /// Estimated cost of the run so far, in dollars
+    pub fn cost(&self) -> f64 {
+        self.micro_dollars.load(Ordering::Relaxed) as f64 / 1_000_000.0
+    }
+    
+    /// Whether the run has reached a cap, so no more requests may be sent
+    pub fn exhausted(&self) -> bool {
+        self.max_tokens.is_some_and(|max| self.tokens() >= max)
+            || self.max_cost.is_some_and(|max| self.cost() >= max)
+    }
+}
@@ -0,0 +1,153 @@
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::{DocGenError, DocGenResult};
+
+/// Tracking file written to the repository root unless `--campaign-file` is given
+pub const CAMPAIGN_FILE_NAME: &str = "docsherpa-campaign.toml";
+
+/// Instructions written at the top of every tracking file
+const CAMPAIGN_HEADER: &str = "\
+# Documentation campaign planned by `docgen campaign`. Document one batch per run with
+# `docgen --campaign-batch <number>`, and check progress with `docgen campaign --status`.
+
+";
+
+/// A project's documentation debt split into batches that are each small enough to
+/// document and review in one run
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Campaign {
+    #[serde(rename = "batch")]
+    pub batches: Vec<Batch>,
+}
+
+/// One batch of files, named relative to the repository root
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Batch {
+    pub number: usize,
+    /// Documentation issues in the batch when the campaign was planned
+    pub issues: usize,
+    /// Directories whose files are in the batch
+    pub modules: Vec<String>,
+    pub files: Vec<PathBuf>,
+}
+
+impl Campaign {
+    /// Split the files' issue counts into at most `batch_count` batches of similar size.
+    /// A module (directory) stays in one batch unless it alone is more than a batch's
+    /// share, in which case its files are spread over several.
+    pub fn plan(debts: &[(PathBuf, usize)], batch_count: usize) -> Self {
+        let batch_count = batch_count.max(1);
+        let total: usize = debts.iter().map(|(_, issues)| issues).sum();
+        let share = total.div_ceil(batch_count);
+        
+        let mut modules: BTreeMap<String, Vec<(PathBuf, usize)>> = BTreeMap::new();
+        for (path, issues) in debts.iter().filter(|(_, issues)| *issues > 0) {
+            modules.entry(module_of(path)).or_default().push((path.clone(), *issues));
+        }
+        
+        // Units that must stay together: whole modules, or single files of oversized ones
+        let mut units: Vec<(String, Vec<PathBuf>, usize)> = Vec::new();
+        for (module, files) in modules {
+            let issues: usize = files.iter().map(|(_, issues)| issues).sum();
+            if issues <= share {
+                units.push((module, files.into_iter().map(|(path, _)| path).collect(), issues));
+            } else {
+                units.extend(files.into_iter().map(|(path, issues)| (module.clone(), vec![path], issues)));
+            }
+        }
+        
+        // Largest first, each into the batch with the least work so far
+        units.sort_by_key(|unit| std::cmp::Reverse(unit.2));
+        let mut batches: Vec<Batch> = (1..=batch_count)
+            .map(|number| Batch { number, issues: 0, modules: Vec::new(), files: Vec::new() })
+            .collect();
+        for (module, files, issues) in units {
+            let batch = batches.iter_mut().min_by_key(|b| b.issues).expect("at least one batch");
+            batch.issues += issues;
+            if !batch.modules.contains(&module) {
+                batch.modules.push(module);
+            }
+            batch.files.extend(files);
+        }
+        
+        batches.retain(|batch| !batch.files.is_empty());
+        for (index, batch) in batches.iter_mut().enumerate() {
+            batch.number = index + 1;
+            batch.modules.sort();
+            batch.files.sort();
+        }
+        Self { batches }
+    }
+    
+    /// Read a tracking file written by `docgen campaign`
+    pub fn load(path: &Path) -> DocGenResult<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| DocGenError::ConfigError(format!("{}: {} (plan a campaign with `docgen campaign` first)", path.display(), e)))?;
+        toml::from_str(&content)
+            .map_err(|e| DocGenError::ConfigError(format!("{}: {}", path.display(), e)))
+    }
+    
+    /// Write the tracking file with instructions for using it
+    pub fn save(&self, path: &Path) -> DocGenResult<()> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| DocGenError::UpdateError(e.to_string()))?;
+        std::fs::write(path, format!("{}{}", CAMPAIGN_HEADER, content))?;
+        Ok(())
+    }
+    
+    /// The batch with the given number
+    pub fn batch(&self, number: usize) -> DocGenResult<&Batch> {
+        self.batches.iter()
+            .find(|batch| batch.number == number)
+            .ok_or_else(|| DocGenError::ConfigError(format!(
+                "The campaign has no batch {} (it has {} batches)", number, self.batches.len())))
+    }
+    
+    /// Render the plan as a table with a row per batch
+    pub fn table(&self) -> String {
+        let header = format!("{:>5}  {:>5}  {:>6}  MODULES", "BATCH", "FILES", "ISSUES");
+        let mut table = format!("{}\n", header.bold());
+        for batch in &self.batches {
+            table.push_str(&format!("{:>5}  {:>5}  {:>6}  {}\n",
+                batch.number,
+                batch.files.len(),
+                batch.issues,
+                batch.modules.join(", ")));
+        }
+        table
+    }
+    
+    /// Render the progress of each batch, given the issues each still has
+    pub fn status_table(&self, remaining: &[usize]) -> String {
+        let header = format!("{:>5}  {:>5}  {:>7}  {:>9}  STATUS", "BATCH", "FILES", "PLANNED", "REMAINING");
+        let mut table = format!("{}\n", header.bold());
+        for (batch, remaining) in self.batches.iter().zip(remaining) {
+            let status = if *remaining == 0 {
+                "done".green()
+            } else if *remaining < batch.issues {
+                "in progress".yellow()
+            } else {
+                "not started".normal()
+            };
+            table.push_str(&format!("{:>5}  {:>5}  {:>7}  {:>9}  {}\n",
+                batch.number,
+                batch.files.len(),
+                batch.issues,
+                remaining,
+                status));
+        }
+        table
+    }
+}
+
+/// Module of a file: the directory it is in, relative to the repository root
+fn module_of(path: &Path) -> String {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.display().to_string(),
+        _ => ".".to_string(),
+    }
+}
@@ -0,0 +1,136 @@
+use crate::parser::CodeItem;
+
+/// Upper bound on items sent in one structured-output request, regardless of context size
+const MAX_BATCH_ITEMS: usize = 8;
+
+/// Context budget reserved per batched item (prompt plus generated documentation)
+const TOKENS_PER_BATCH_ITEM: usize = 2000;
+
+/// Request/response schema used to talk to a provider's API
+#[derive(Debug)]
+pub struct ProviderSchema {
+    pub provider: &'static str,
+    pub endpoint: &'static str,
+    pub api_version: &'static str,
+    pub default_model: &'static str,
+    /// Cheapest capable model, used by `--summary-only` when no model is given
+    pub summary_model: &'static str,
+}
+
+/// What a particular model can do, keyed by provider and model name
+#[derive(Debug)]
+pub struct ModelCapabilities {
+    pub provider: &'static str,
+    pub model: &'static str,
+    pub supports_json_mode: bool,
+    pub supports_system_prompt: bool,
+    pub max_context_tokens: usize,
+    pub cost_per_1k_input: f64,     // USD
+    pub cost_per_1k_output: f64,    // USD
+}
+
+const SCHEMAS: &[ProviderSchema] = &[
+    ProviderSchema {
+        provider: "openai",
+        endpoint: "https://api.openai.com/v1/chat/completions",
+        api_version: "v1",
+        default_model: "gpt-4",
+        summary_model: "gpt-4o-mini",
+    },
+    ProviderSchema {
+        provider: "azure-openai",
+        // Each Azure resource has its own endpoint, and requests name a deployment
+        endpoint: "{endpoint}/openai/deployments/{deployment}/chat/completions?api-version={api_version}",
+        api_version: "2024-02-01",
+        default_model: "gpt-4",
+        summary_model: "gpt-4o-mini",
+    },
+    ProviderSchema {
+        provider: "claude",
+        endpoint: "https://api.anthropic.com/v1/messages",
+        api_version: "2023-06-01",
+        default_model: "claude-3-opus-20240229",
+        summary_model: "claude-3-haiku-20240307",
+    },
+];
+
+const MODELS: &[ModelCapabilities] = &[
+    ModelCapabilities { provider: "openai", model: "gpt-4", supports_json_mode: false, supports_system_prompt: true, max_context_tokens: 8_192, cost_per_1k_input: 0.03, cost_per_1k_output: 0.06 },
+    ModelCapabilities { provider: "openai", model: "gpt-4-turbo", supports_json_mode: true, supports_system_prompt: true, max_context_tokens: 128_000, cost_per_1k_input: 0.01, cost_per_1k_output: 0.03 },
+    ModelCapabilities { provider: "openai", model: "gpt-4o", supports_json_mode: true, supports_system_prompt: true, max_context_tokens: 128_000, cost_per_1k_input: 0.005, cost_per_1k_output: 0.015 },
+    ModelCapabilities { provider: "openai", model: "gpt-4o-mini", supports_json_mode: true, supports_system_prompt: true, max_context_tokens: 128_000, cost_per_1k_input: 0.00015, cost_per_1k_output: 0.0006 },
+    ModelCapabilities { provider: "openai", model: "gpt-3.5-turbo", supports_json_mode: true, supports_system_prompt: true, max_context_tokens: 16_385, cost_per_1k_input: 0.0005, cost_per_1k_output: 0.0015 },
+    ModelCapabilities { provider: "claude", model: "claude-3-opus-20240229", supports_json_mode: false, supports_system_prompt: true, max_context_tokens: 200_000, cost_per_1k_input: 0.015, cost_per_1k_output: 0.075 },
+    ModelCapabilities { provider: "claude", model: "claude-3-5-sonnet-20240620", supports_json_mode: false, supports_system_prompt: true, max_context_tokens: 200_000, cost_per_1k_input: 0.003, cost_per_1k_output: 0.015 },
+    ModelCapabilities { provider: "claude", model: "claude-3-haiku-20240307", supports_json_mode: false, supports_system_prompt: true, max_context_tokens: 200_000, cost_per_1k_input: 0.00025, cost_per_1k_output: 0.00125 },
+];
+
+/// Capabilities assumed for models missing from the table: one item per request, no extras
+const CONSERVATIVE: ModelCapabilities = ModelCapabilities {
+    provider: "unknown",
+    model: "unknown",
+    supports_json_mode: false,
+    supports_system_prompt: false,
+    max_context_tokens: 4_096,
+    cost_per_1k_input: 0.0,
+    cost_per_1k_output: 0.0,
+};
+
+/// Look up the API schema for a provider
+pub fn schema(provider: &str) -> Option<&'static ProviderSchema> {
+    SCHEMAS.iter().find(|s| s.provider == provider.to_lowercase())
+}
+
+/// Look up a model's capabilities, if it is known
+pub fn lookup(provider: &str, model: &str) -> Option<&'static ModelCapabilities> {
+    // Azure deployments serve OpenAI's models
+    let provider = match provider.to_lowercase().as_str() {
+        "azure-openai" => "openai".to_string(),
+        provider => provider.to_string(),
+    };
+    MODELS.iter().find(|m| m.provider == provider && m.model == model)
+}
+
+/// Look up a model's capabilities, falling back to conservative defaults with a warning
+pub fn resolve(provider: &str, model: &str) -> &'static ModelCapabilities {
+    lookup(provider, model).unwrap_or_else(|| {
+        log::warn!("No capability data for {} model '{}'; sending one item per request without JSON mode or system prompts",
+            provider, model);
+        &CONSERVATIVE
+    })
+}
+
+/// Rough token estimate for a piece of text (about four characters per token)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len() / 4 + 1
+}
+
+impl ModelCapabilities {
+    /// How many items to request at once; batching needs structured (JSON) output
+    pub fn batch_size(&self) -> usize {
+        if !self.supports_json_mode {
+            return 1;
+        }
+        (self.max_context_tokens / TOKENS_PER_BATCH_ITEM).clamp(1, MAX_BATCH_ITEMS)
+    }
+    
+    /// Check whether an item's code fits in the model's context, leaving room for the answer
+    pub fn fits(&self, item: &CodeItem) -> bool {
+        estimate_tokens(&item.code) + TOKENS_PER_BATCH_ITEM / 2 <= self.max_context_tokens
+    }
+    
+    /// Short feature list for verbose output
+    pub fn describe(&self) -> String {
+        let mut features = vec![format!("context {} tokens", self.max_context_tokens)];
+        if self.supports_json_mode {
+            features.push(format!("JSON mode, batches of {}", self.batch_size()));
+        }
+        if !self.supports_system_prompt {
+            features.push("no system prompt".to_string());
+        }
+        if self.cost_per_1k_input > 0.0 {
+            features.push(format!("${}/${} per 1K input/output tokens", self.cost_per_1k_input, self.cost_per_1k_output));
+        }
+        features.join(", ")
+    }
+}
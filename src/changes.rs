@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+use crate::docstring::{DocstringIssue, IssueKind};
+use crate::history::{item_key, signature};
+use crate::parser::{CodeItem, ParsedCode};
+use crate::rules::{Reason, Rule};
+
+/// Judge documentation against what changed since a base revision (`--since`) rather
+/// than by how well it describes the code: documentation is only outdated if the item's
+/// signature (its parameters and declaration, with any return type) differs from the
+/// base revision's. Outdated issues of items whose signature is unchanged are dropped,
+/// and documented items whose signature changed while their documentation stayed the
+/// same are flagged as changed, first in `issues`. Items that are new since the base
+/// revision, or were renamed, are judged as usual.
+pub fn since_base(base_code: &ParsedCode, parsed_code: &ParsedCode, issues: &mut Vec<DocstringIssue>) {
+    let base_items: HashMap<String, &CodeItem> = base_code.items.iter()
+        .map(|item| (item_key(item), item))
+        .collect();
+    let base_item = |index: usize| base_items.get(&item_key(&parsed_code.items[index])).copied();
+    
+    issues.retain(|issue| {
+        issue.issue_type != IssueKind::Outdated || base_item(issue.item_index)
+            .is_none_or(|base| signature(base) != signature(&parsed_code.items[issue.item_index]))
+    });
+    
+    let ignored = parsed_code.ignored_items();
+    for (index, item) in parsed_code.items.iter().enumerate() {
+        if issues.iter().any(|i| i.item_index == index) || ignored[index] {
+            continue;
+        }
+        
+        if let (Some(docstring), Some(base)) = (&item.existing_docstring, base_item(index)) {
+            let same_docstring = base.existing_docstring.as_deref().is_some_and(|d| d.trim() == docstring.trim());
+            if signature(base) != signature(item) && same_docstring {
+                issues.push(DocstringIssue {
+                    item_type: item.item_type,
+                    name: item.name.clone(),
+                    line_number: item.line_number,
+                    issue_type: IssueKind::Changed,
+                    item_index: index,
+                    reasons: vec![Reason::new(Rule::SignatureChanged)],
+                });
+            }
+        }
+    }
+    
+    issues.sort_by_key(|issue| issue.issue_type != IssueKind::Changed);
+}
@@ -1,8 +1,37 @@
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use tokio::sync::Semaphore;
+
+use crate::backup::Backups;
+use crate::budget::Budget;
+use crate::capabilities;
+use crate::docstring::DocstringStyle;
+use crate::error::{DocGenError, DocGenResult};
+use crate::lang::cpp::CommentStyle;
+use crate::lang::typescript::TagStyle;
+use crate::parser::ItemKind;
+use crate::overlay::Overlay;
+use crate::policy::{Policy, PolicySettings};
+use crate::routing::{Routing, RoutingSettings};
+use crate::rules::{Rule, RuleLevel, Rules};
+use crate::scheduler::{ProviderSettings, Scheduler};
+use crate::symbols::SymbolIndex;
+
+/// Project configuration files looked for in the repository root, in order
+pub const CONFIG_FILE_NAMES: &[&str] = &["docsherpa.toml", ".docgenrc"];
+
 /// Configuration for DocGen
+#[derive(Clone)]
 pub struct Config {
-    /// LLM provider to use (openai or claude)
+    /// LLM provider to use (openai, azure-openai, or claude)
     pub provider: String,
     
+    /// Model to request from the provider; None uses the provider's default
+    pub model: Option<String>,
+    
     /// Check mode - only report issues without making changes
     pub check_only: bool,
     
@@ -11,14 +40,481 @@ pub struct Config {
     
     /// Test mode - analyze files without making API calls
     pub test_mode: bool,
+    
+    /// Diff mode - print proposed changes as a diff instead of writing files
+    pub diff_only: bool,
+    
+    /// Dry-run mode - generate documentation but write nothing, for a summary of what
+    /// would change
+    pub dry_run: bool,
+    
+    /// Read files as staged for commit instead of from the working tree
+    pub staged: bool,
+    
+    /// Git revision to compare signatures against, flagging documentation as outdated
+    /// only where an item's signature changed since then (`--since`)
+    pub since: Option<String>,
+    
+    /// Unsaved editor buffers read instead of the files they belong to (`--overlay`)
+    pub overlay: Option<Arc<Overlay>>,
+    
+    /// Review file that proposed changes are written to instead of the files
+    pub review_file: Option<PathBuf>,
+    
+    /// Interactive mode - ask before applying each proposed docstring
+    pub interactive: bool,
+    
+    /// CLI help mode - document command-line arguments instead of code items
+    pub cli_help: bool,
+    
+    /// Script mode - also document top-level Python script statements with `#` comments
+    pub script_comments: bool,
+    
+    /// Also document module-level constants and statics whose values aren't self-explanatory
+    pub constants: bool,
+    
+    /// Also document private items: Python `_name` and `__dunder__` definitions, Rust
+    /// items without `pub`, and `#private` class members
+    pub include_private: bool,
+    
+    /// Template mode - insert skeleton docstrings from signatures without calling a model
+    pub template_fix: bool,
+    
+    /// Lint mode - also check docstrings against summary-line and blank-line conventions
+    pub lint: bool,
+    
+    /// Summary-only mode - give undocumented items just a one-line summary
+    pub summary_only: bool,
+    
+    /// Ask for a `# Examples` section with a doctest in public Rust items' documentation
+    pub with_examples: bool,
+    
+    /// Run `cargo test --doc` after writing a Rust file, and put the file back if it fails
+    pub verify_doctests: bool,
+    
+    /// Replace mode - regenerate existing docstrings from scratch instead of revising them
+    pub replace: bool,
+    
+    /// Maximum number of lines a generated docstring may span
+    pub max_doc_lines: Option<usize>,
+    
+    /// Number of items documented and written at a time in files with many issues
+    pub chunk_size: Option<usize>,
+    
+    /// Which items to document first
+    pub order: crate::priority::Order,
+    
+    /// Items left to document in this run (`--max-fixes`), shared by the file tasks
+    pub fix_budget: Option<Arc<AtomicUsize>>,
+    
+    /// Token and cost cap for the run, shared by every LLM client
+    pub budget: Arc<Budget>,
+    
+    /// Copies of the files the run writes, for `docgen rollback` (`--backup`)
+    pub backups: Option<Arc<Backups>>,
+    
+    /// Types and functions across the project, described in prompts for the items that
+    /// use them (`--project-context`)
+    pub symbols: Option<Arc<SymbolIndex>>,
+    
+    /// Additional instructions appended to every LLM prompt
+    pub extra_instructions: Vec<String>,
+    
+    /// System prompt sent instead of the built-in one for each language
+    pub system_prompt: Option<String>,
+    
+    /// Text about the project, such as an architecture document, sent with every request
+    /// so documentation uses the project's terminology (`--context`)
+    pub context: Option<String>,
+    
+    /// Issue-tracker URL template used to link each reported issue to a new ticket
+    pub issue_url_template: Option<String>,
+    
+    /// Additional directories treated as third-party code and skipped
+    pub vendor_dirs: Vec<String>,
+    
+    /// Local file that run statistics are appended to (opt-in, never sent anywhere)
+    pub stats_file: Option<std::path::PathBuf>,
+    
+    /// Emit newline-delimited JSON progress events on stderr
+    pub progress_json: bool,
+    
+    /// How the issue summary table groups its rows
+    pub group_by: crate::report::GroupBy,
+    
+    /// How results are written to stdout
+    pub format: crate::report::OutputFormat,
+    
+    /// Number of files processed at once (`--jobs`)
+    pub jobs: usize,
+    
+    /// Limits how many LLM requests are in flight at once, across all files (`--jobs`)
+    pub requests: Arc<Semaphore>,
+    
+    /// Globs of files to skip, from the project configuration and `--exclude`
+    pub exclude: Vec<String>,
+    
+    /// Language names to use for files matching a glob, ahead of extension detection;
+    /// the first matching glob wins
+    pub language_overrides: Vec<(String, String)>,
+    
+    /// Python docstring style requested in prompts and checked in existing docstrings
+    pub docstring_style: Option<DocstringStyle>,
+    
+    /// Whether new C and C++ Doxygen comments are `/** */` blocks or `///` lines
+    pub cpp_comment_style: CommentStyle,
+    
+    /// Whether TypeScript `@param` and `@returns` tags are written TSDoc-style or with
+    /// JSDoc types
+    pub ts_tag_style: TagStyle,
+    
+    /// Analysis rules that are turned off or given another severity
+    pub rules: Rules,
+    
+    /// Prompt templates by language (`python`) or language and item type (`python.class`),
+    /// replacing the built-in prompt
+    pub prompt_templates: HashMap<String, String>,
+    
+    /// Azure OpenAI resource settings, for the azure-openai provider
+    pub azure: AzureSettings,
+    
+    /// Content rules generated documentation must follow before it is written
+    pub policy: Policy,
+    
+    /// Providers that files matching particular paths must be sent to
+    pub routing: Routing,
+    
+    /// Providers that requests are spread across instead of the one provider
+    /// (`[[providers]]`), with what has been observed of their rate limits
+    pub scheduler: Option<Arc<Scheduler>>,
+}
+
+/// Azure OpenAI resource settings, from the `[azure]` table of the project
+/// configuration. The AZURE_OPENAI_* environment variables take precedence.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AzureSettings {
+    /// Resource endpoint, e.g. https://my-resource.openai.azure.com
+    pub endpoint: Option<String>,
+    
+    /// Name of the model deployment to send requests to
+    pub deployment: Option<String>,
+    
+    /// REST API version, e.g. 2024-02-01
+    pub api_version: Option<String>,
+}
+
+/// Project configuration as written in `docsherpa.toml` or `.docgenrc`
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    provider: Option<String>,
+    model: Option<String>,
+    jobs: Option<usize>,
+    style: Option<DocstringStyle>,
+    cpp_comment_style: CommentStyle,
+    ts_tag_style: TagStyle,
+    constants: bool,
+    include_private: bool,
+    exclude: Vec<String>,
+    instructions: Vec<String>,
+    system_prompt: Option<String>,
+    /// File describing the project, relative to the configuration file
+    context: Option<PathBuf>,
+    /// Glob -> language name; a table keeps the order the globs were written in
+    languages: toml::Table,
+    /// Language name, optionally with an item type -> prompt template
+    prompts: HashMap<String, String>,
+    /// Language name, optionally with an item type -> file holding a prompt template,
+    /// relative to the configuration file
+    prompt_files: HashMap<String, PathBuf>,
+    azure: AzureSettings,
+    policy: PolicySettings,
+    routing: RoutingSettings,
+    providers: Vec<ProviderSettings>,
+    /// Rule ID -> "off", "info", "warning", or "error"
+    rules: BTreeMap<String, RuleLevel>,
+}
+
+/// Settings given on the command line that override the project configuration
+#[derive(Default)]
+pub struct CliOverrides {
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub jobs: Option<usize>,
+    pub style: Option<DocstringStyle>,
+    pub constants: bool,
+    /// Set by `--include-private`, cleared by `--public-only`
+    pub include_private: Option<bool>,
+    pub exclude: Vec<String>,
+    pub disable: Vec<Rule>,
+    /// Contents of the `--context` file
+    pub context: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            provider: "openai".to_string(),
+            model: None,
+            check_only: false,
+            verbose: false,
+            test_mode: false,
+            diff_only: false,
+            dry_run: false,
+            staged: false,
+            since: None,
+            overlay: None,
+            review_file: None,
+            interactive: false,
+            cli_help: false,
+            script_comments: false,
+            constants: false,
+            include_private: false,
+            template_fix: false,
+            lint: false,
+            summary_only: false,
+            with_examples: false,
+            verify_doctests: false,
+            replace: false,
+            max_doc_lines: None,
+            chunk_size: None,
+            order: crate::priority::Order::Source,
+            fix_budget: None,
+            budget: Arc::new(Budget::default()),
+            backups: None,
+            symbols: None,
+            extra_instructions: Vec::new(),
+            system_prompt: None,
+            context: None,
+            issue_url_template: None,
+            vendor_dirs: Vec::new(),
+            stats_file: None,
+            progress_json: false,
+            group_by: crate::report::GroupBy::Rule,
+            format: crate::report::OutputFormat::Text,
+            jobs: 1,
+            requests: Arc::new(Semaphore::new(1)),
+            exclude: Vec::new(),
+            language_overrides: Vec::new(),
+            docstring_style: None,
+            cpp_comment_style: CommentStyle::default(),
+            ts_tag_style: TagStyle::default(),
+            rules: Rules::default(),
+            prompt_templates: HashMap::new(),
+            azure: AzureSettings::default(),
+            policy: Policy::default(),
+            routing: Routing::default(),
+            scheduler: None,
+        }
+    }
 }
 
 impl Config {
+    /// Find the project configuration file in a repository root, if there is one
+    pub fn find_file(root: &Path) -> Option<PathBuf> {
+        CONFIG_FILE_NAMES.iter()
+            .map(|name| root.join(name))
+            .find(|path| path.is_file())
+    }
+    
+    /// Load the project configuration from a TOML file, on top of the defaults
+    pub fn from_file(path: &Path) -> DocGenResult<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+        Self::from_toml(&content, base_dir)
+            .map_err(|e| DocGenError::ConfigError(format!("{}: {}", path.display(), e)))
+    }
+    
+    /// Parse project configuration from TOML text; relative paths in it are resolved
+    /// against `base_dir`
+    fn from_toml(content: &str, base_dir: &Path) -> Result<Self, String> {
+        let file: ConfigFile = toml::from_str(content).map_err(|e| e.to_string())?;
+        if let Some(provider) = &file.provider {
+            if provider.to_lowercase() != "mock" && capabilities::schema(provider).is_none() {
+                return Err(format!("unknown provider '{}'", provider));
+            }
+        }
+        
+        // Templates written inline take precedence over template files for the same key
+        let mut prompt_templates = HashMap::new();
+        for (key, path) in file.prompt_files {
+            let template = std::fs::read_to_string(base_dir.join(&path))
+                .map_err(|e| format!("prompt file {}: {}", path.display(), e))?;
+            prompt_templates.insert(key.to_lowercase(), template);
+        }
+        for (key, template) in file.prompts {
+            prompt_templates.insert(key.to_lowercase(), template);
+        }
+        for (key, template) in &prompt_templates {
+            if let Some((_, kind)) = key.split_once('.') {
+                kind.parse::<ItemKind>().map_err(|e| format!("prompt for '{}': {}", key, e))?;
+            }
+            crate::prompt::check_template(template).map_err(|e| format!("prompt for '{}': {}", key, e))?;
+        }
+        
+        let context = file.context
+            .map(|path| crate::io::read_text(&base_dir.join(&path)).map_err(|e| format!("context file {}: {}", path.display(), e)))
+            .transpose()?;
+        
+        let mut language_overrides = Vec::new();
+        for (glob, language) in file.languages {
+            let language = language.as_str()
+                .ok_or_else(|| format!("language for '{}' must be a string", glob))?;
+            language_overrides.push((glob, language.to_lowercase()));
+        }
+        
+        let defaults = Self::default();
+        let jobs = file.jobs.unwrap_or(defaults.jobs).max(1);
+        Ok(Self {
+            provider: file.provider.unwrap_or(defaults.provider),
+            model: file.model,
+            extra_instructions: file.instructions,
+            system_prompt: file.system_prompt,
+            context,
+            jobs,
+            requests: Arc::new(Semaphore::new(jobs)),
+            exclude: file.exclude,
+            language_overrides,
+            docstring_style: file.style,
+            cpp_comment_style: file.cpp_comment_style,
+            ts_tag_style: file.ts_tag_style,
+            constants: file.constants,
+            include_private: file.include_private,
+            rules: Rules::from_settings(file.rules)?,
+            prompt_templates,
+            azure: file.azure,
+            policy: Policy::from_settings(file.policy)?,
+            routing: Routing::from_settings(file.routing, base_dir)?,
+            scheduler: Scheduler::from_settings(file.providers)?.map(Arc::new),
+            ..defaults
+        })
+    }
+    
+    /// Apply command-line settings, which take precedence over the project configuration.
+    /// Excluded globs from both are combined, `--provider` picks one provider instead
+    /// of the configured pool, and `--disable` turns rules off whatever their level.
+    pub fn merge_cli(mut self, cli: CliOverrides) -> Self {
+        if let Some(provider) = cli.provider {
+            self.provider = provider;
+            self.scheduler = None;
+        }
+        if cli.model.is_some() {
+            self.model = cli.model;
+        }
+        if let Some(jobs) = cli.jobs {
+            self.jobs = jobs.max(1);
+            self.requests = Arc::new(Semaphore::new(self.jobs));
+        }
+        if cli.style.is_some() {
+            self.docstring_style = cli.style;
+        }
+        self.constants |= cli.constants;
+        if let Some(include_private) = cli.include_private {
+            self.include_private = include_private;
+        }
+        if cli.context.is_some() {
+            self.context = cli.context;
+        }
+        self.exclude.extend(cli.exclude);
+        for rule in cli.disable {
+            self.rules.disable(rule);
+        }
+        self
+    }
+    
     pub fn get_api_key(&self) -> Option<String> {
         match self.provider.to_lowercase().as_str() {
             "openai" => std::env::var("OPENAI_API_KEY").ok(),
+            "azure-openai" => std::env::var("AZURE_OPENAI_API_KEY").ok(),
             "claude" => std::env::var("ANTHROPIC_API_KEY").ok(),
             _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn reads_docsherpa_toml() {
+        let config = Config::from_toml(r#"
+            provider = "claude"
+            model = "claude-3-5-sonnet-latest"
+            jobs = 4
+            style = "numpy"
+            exclude = ["vendor/**"]
+            
+            [languages]
+            "*.inc" = "CPP"
+            
+            [prompts]
+            "Python.function" = "Document {{name}} in {{language}}"
+        "#, Path::new(".")).unwrap();
+        
+        assert_eq!(config.provider, "claude");
+        assert_eq!(config.model.as_deref(), Some("claude-3-5-sonnet-latest"));
+        assert_eq!(config.jobs, 4);
+        assert_eq!(config.docstring_style, Some(DocstringStyle::Numpy));
+        assert_eq!(config.exclude, vec!["vendor/**"]);
+        assert_eq!(config.language_overrides, vec![("*.inc".to_string(), "cpp".to_string())]);
+        assert_eq!(config.prompt_templates["python.function"], "Document {{name}} in {{language}}");
+    }
+    
+    #[test]
+    fn command_line_overrides_the_file() {
+        let config = Config::from_toml(r#"
+            provider = "claude"
+            model = "claude-3-5-sonnet-latest"
+            jobs = 4
+            style = "numpy"
+            include_private = true
+            exclude = ["vendor/**"]
+        "#, Path::new(".")).unwrap();
+        let config = config.merge_cli(CliOverrides {
+            provider: Some("openai".to_string()),
+            model: Some("gpt-4o".to_string()),
+            jobs: Some(2),
+            style: Some(DocstringStyle::Google),
+            include_private: Some(false),
+            exclude: vec!["build/**".to_string()],
+            ..CliOverrides::default()
+        });
+        
+        assert_eq!(config.provider, "openai");
+        assert_eq!(config.model.as_deref(), Some("gpt-4o"));
+        assert_eq!(config.jobs, 2);
+        assert_eq!(config.docstring_style, Some(DocstringStyle::Google));
+        assert!(!config.include_private);
+        assert_eq!(config.exclude, vec!["vendor/**", "build/**"]);
+    }
+    
+    #[test]
+    fn defaults_apply_without_a_file() {
+        let dir = std::env::temp_dir().join(format!("docgen-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let found = Config::find_file(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(found, None);
+        
+        let config = Config::default().merge_cli(CliOverrides::default());
+        assert_eq!(config.provider, "openai");
+        assert_eq!(config.model, None);
+        assert_eq!(config.jobs, 1);
+        assert_eq!(config.docstring_style, None);
+        assert!(config.exclude.is_empty());
+    }
+    
+    #[test]
+    fn rejects_an_unknown_provider() {
+        let error = Config::from_toml(r#"provider = "openia""#, Path::new(".")).err().unwrap();
+        assert!(error.contains("unknown provider 'openia'"), "{}", error);
+    }
+    
+    #[test]
+    fn rejects_an_unknown_placeholder() {
+        let error = Config::from_toml(r#"prompts = { python = "Document {{nmae}}" }"#, Path::new(".")).err().unwrap();
+        assert!(error.contains("unknown placeholder {{nmae}}"), "{}", error);
+    }
+}
@@ -0,0 +1,204 @@
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::parser::{ItemKind, ParsedCode};
+use crate::report::coverage_label;
+
+/// Documented and total item counts
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Counts {
+    pub documented: usize,
+    pub items: usize,
+}
+
+impl Counts {
+    /// Percentage of items with documentation; a file without items is fully covered
+    pub fn percent(&self) -> f64 {
+        if self.items == 0 {
+            return 100.0;
+        }
+        self.documented as f64 * 100.0 / self.items as f64
+    }
+    
+    fn add(&mut self, other: Counts) {
+        self.documented += other.documented;
+        self.items += other.items;
+    }
+}
+
+/// How much of a file's items carry documentation, by item type
+#[derive(Debug)]
+pub struct FileCoverage {
+    pub path: PathBuf,
+    pub by_type: BTreeMap<ItemKind, Counts>,
+}
+
+impl FileCoverage {
+    /// Count the documented items of a parsed file. An item counts as documented when
+    /// it has any documentation, whether or not it is up to date; items excluded with a
+    /// `docsherpa` comment don't count.
+    pub fn new(path: &Path, parsed_code: &ParsedCode) -> Self {
+        let mut by_type: BTreeMap<ItemKind, Counts> = BTreeMap::new();
+        let ignored = parsed_code.ignored_items();
+        for (item, _) in parsed_code.items.iter().zip(ignored).filter(|(_, ignored)| !ignored) {
+            let counts = by_type.entry(item.item_type).or_default();
+            counts.items += 1;
+            if item.existing_docstring.is_some() {
+                counts.documented += 1;
+            }
+        }
+        Self { path: path.to_path_buf(), by_type }
+    }
+    
+    /// Counts across every item type
+    pub fn total(&self) -> Counts {
+        let mut total = Counts::default();
+        for counts in self.by_type.values() {
+            total.add(*counts);
+        }
+        total
+    }
+}
+
+/// Project-wide counts by item type, and across everything
+fn project_totals(files: &[FileCoverage]) -> (BTreeMap<ItemKind, Counts>, Counts) {
+    let mut by_type: BTreeMap<ItemKind, Counts> = BTreeMap::new();
+    let mut total = Counts::default();
+    for file in files {
+        for (item_type, counts) in &file.by_type {
+            by_type.entry(*item_type).or_default().add(*counts);
+            total.add(*counts);
+        }
+    }
+    (by_type, total)
+}
+
+/// Render a table with a row per file, a row per item type, and the project total
+pub fn table(files: &[FileCoverage]) -> String {
+    let (by_type, total) = project_totals(files);
+    let paths: Vec<String> = files.iter().map(|f| f.path.display().to_string()).collect();
+    let width = paths.iter().map(|p| p.len())
+        .chain(by_type.keys().map(|t| t.as_str().len()))
+        .chain(["FILE".len(), "TOTAL".len()])
+        .max()
+        .unwrap_or(0);
+    
+    let row = |label: &str, counts: Counts| format!("{:<width$}  {:>5}  {:>10}  {}\n",
+        label,
+        counts.items,
+        counts.documented,
+        coverage_label(counts.percent(), 8),
+        width = width);
+    
+    let header = format!("{:<width$}  {:>5}  {:>10}  {:>8}", "FILE", "ITEMS", "DOCUMENTED", "COVERAGE", width = width);
+    let mut table = format!("{}\n", header.bold());
+    for (file, path) in files.iter().zip(&paths) {
+        table.push_str(&row(path, file.total()));
+    }
+    
+    let header = format!("{:<width$}  {:>5}  {:>10}  {:>8}", "TYPE", "ITEMS", "DOCUMENTED", "COVERAGE", width = width);
+    table.push_str(&format!("\n{}\n", header.bold()));
+    for (item_type, counts) in &by_type {
+        table.push_str(&row(item_type.as_str(), *counts));
+    }
+    
+    let label = format!("{:<width$}", "TOTAL", width = width).bold().to_string();
+    table.push_str(&format!("\n{}", row(&label, total)));
+    table
+}
+
+/// Coverage of one file or item type in the JSON report
+#[derive(Serialize)]
+struct CoverageRecord<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<&'a Path>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_type: Option<ItemKind>,
+    items: usize,
+    documented: usize,
+    coverage: f64,
+}
+
+impl<'a> CoverageRecord<'a> {
+    fn new(file: Option<&'a Path>, item_type: Option<ItemKind>, counts: Counts) -> Self {
+        Self {
+            file,
+            item_type,
+            items: counts.items,
+            documented: counts.documented,
+            coverage: (counts.percent() * 10.0).round() / 10.0,
+        }
+    }
+}
+
+/// The coverage report printed by `--coverage --format json`
+#[derive(Serialize)]
+struct CoverageDocument<'a> {
+    files: Vec<CoverageRecord<'a>>,
+    types: Vec<CoverageRecord<'a>>,
+    total: CoverageRecord<'a>,
+}
+
+/// Render the coverage report as a JSON document
+pub fn json(files: &[FileCoverage]) -> String {
+    let (by_type, total) = project_totals(files);
+    let document = CoverageDocument {
+        files: files.iter()
+            .map(|file| CoverageRecord::new(Some(&file.path), None, file.total()))
+            .collect(),
+        types: by_type.iter()
+            .map(|(item_type, counts)| CoverageRecord::new(None, Some(*item_type), *counts))
+            .collect(),
+        total: CoverageRecord::new(None, None, total),
+    };
+    serde_json::to_string_pretty(&document).unwrap_or_default()
+}
+
+/// Render the coverage report as a standalone HTML page
+pub fn html(files: &[FileCoverage]) -> String {
+    let (by_type, total) = project_totals(files);
+    let row = |label: &str, counts: Counts| {
+        let percent = counts.percent();
+        let class = if percent >= 90.0 { "good" } else if percent >= 50.0 { "fair" } else { "poor" };
+        format!("<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"{}\">{:.1}%</td></tr>\n",
+            escape_html(label),
+            counts.items,
+            counts.documented,
+            class,
+            percent)
+    };
+    
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>Documentation coverage</title>\n<style>\n\
+        body { font-family: sans-serif; margin: 2em; }\n\
+        table { border-collapse: collapse; margin-bottom: 2em; }\n\
+        th, td { padding: 0.3em 1em; border-bottom: 1px solid #ddd; text-align: right; }\n\
+        th:first-child, td:first-child { text-align: left; }\n\
+        .good { color: #1a7f37; } .fair { color: #9a6700; } .poor { color: #cf222e; }\n\
+        </style>\n</head>\n<body>\n");
+    html.push_str(&format!("<h1>Documentation coverage: {:.1}%</h1>\n", total.percent()));
+    
+    html.push_str("<table>\n<tr><th>File</th><th>Items</th><th>Documented</th><th>Coverage</th></tr>\n");
+    for file in files {
+        html.push_str(&row(&file.path.display().to_string(), file.total()));
+    }
+    html.push_str("</table>\n");
+    
+    html.push_str("<table>\n<tr><th>Type</th><th>Items</th><th>Documented</th><th>Coverage</th></tr>\n");
+    for (item_type, counts) in &by_type {
+        html.push_str(&row(item_type.as_str(), *counts));
+    }
+    html.push_str(&row("Total", total));
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+/// Escape text for inclusion in HTML
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,44 @@
+use colored::Colorize;
+use similar::TextDiff;
+use std::path::Path;
+
+use crate::discover;
+
+/// Render the change from `old` to `new` as a unified diff with three lines of context
+/// around each hunk, which `git apply` and `patch -p1` can apply directly. Returns an
+/// empty string when nothing changed.
+pub fn unified(path: &Path, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    
+    // Paths are relative to where DocGen was run, so the patch applies from there, and
+    // `/`-separated on every system
+    let path = discover::normalize(path);
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string()
+}
+
+/// Color a unified diff for the terminal: file headers in bold, hunk headers in cyan,
+/// removed lines in red, and added lines in green
+pub fn colorize(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            let colored = if line.starts_with("--- ") || line.starts_with("+++ ") {
+                line.bold().to_string()
+            } else if line.starts_with("@@") {
+                line.cyan().to_string()
+            } else if line.starts_with('-') {
+                line.red().to_string()
+            } else if line.starts_with('+') {
+                line.green().to_string()
+            } else {
+                line.to_string()
+            };
+            colored + "\n"
+        })
+        .collect()
+}
@@ -0,0 +1,406 @@
+use regex::Regex;
+use std::path::{Component, Path, PathBuf, Prefix};
+use std::process::Command;
+
+use crate::error::{DocGenError, DocGenResult};
+
+/// Whether file names are matched regardless of case, as on the default filesystems of
+/// Windows and macOS
+pub const CASE_INSENSITIVE: bool = cfg!(any(windows, target_os = "macos"));
+
+/// A compiled glob pattern. `**` matches any number of directories, `*` and `?` match
+/// within one path component. Patterns without a `/` match the file name alone, unless
+/// built with `Glob::anchored`. On Windows, `\` separates directories too.
+#[derive(Clone)]
+pub struct Glob {
+    regex: Regex,
+    name_only: bool,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> DocGenResult<Self> {
+        let pattern = if cfg!(windows) { pattern.replace('\\', "/") } else { pattern.to_string() };
+        let pattern = pattern.trim_start_matches("./");
+        let flags = if CASE_INSENSITIVE { "(?i)" } else { "" };
+        let regex = Regex::new(&format!("{}^{}$", flags, glob_to_regex(pattern)))
+            .map_err(|e| DocGenError::ConfigError(format!("Invalid glob pattern '{}': {}", pattern, e)))?;
+        Ok(Self { regex, name_only: !pattern.contains('/') })
+    }
+    
+    /// A pattern matched against the whole path even without a `/`, as a glob given on
+    /// the command line is: `*.py` is the Python files in the current directory
+    pub fn anchored(pattern: &str) -> DocGenResult<Self> {
+        Ok(Self { name_only: false, ..Self::new(pattern)? })
+    }
+    
+    /// Check whether a path matches the pattern
+    pub fn matches(&self, path: &Path) -> bool {
+        if self.name_only {
+            return path.file_name()
+                .is_some_and(|name| self.regex.is_match(&name.to_string_lossy()));
+        }
+        self.regex.is_match(&normalize(path))
+    }
+}
+
+/// Translate a glob pattern into an (unanchored) regular expression
+fn glob_to_regex(pattern: &str) -> String {
+    let mut regex = String::new();
+    let mut chars = pattern.chars().peekable();
+    
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            },
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex
+}
+
+/// Path as a `/`-separated string without a leading `./`, as written in globs, patches,
+/// and the files DocGen keeps. Windows drive and UNC prefixes, including the `\\?\`
+/// forms of long paths, become `C:` and `//server/share`.
+pub fn normalize(path: &Path) -> String {
+    let path = path.strip_prefix(".").unwrap_or(path);
+    let mut parts: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => parts.push(match prefix.kind() {
+                Prefix::Disk(drive) | Prefix::VerbatimDisk(drive) => format!("{}:", drive as char),
+                Prefix::UNC(server, share) | Prefix::VerbatimUNC(server, share) => {
+                    format!("//{}/{}", server.to_string_lossy(), share.to_string_lossy())
+                },
+                _ => component.as_os_str().to_string_lossy().to_string(),
+            }),
+            Component::RootDir if parts.is_empty() => parts.push(String::new()),
+            Component::RootDir | Component::CurDir => {},
+            _ => parts.push(component.as_os_str().to_string_lossy().to_string()),
+        }
+    }
+    parts.join("/")
+}
+
+/// Canonical form of a path, or the path itself when it doesn't exist. The `\\?\`
+/// prefix Windows puts on canonical paths is dropped, so they can be compared with
+/// paths built from the current directory.
+pub fn canonical(path: &Path) -> PathBuf {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let simplified = {
+        let text = canonical.to_string_lossy();
+        text.strip_prefix(r"\\?\UNC\").map(|unc| PathBuf::from(format!(r"\\{}", unc)))
+            .or_else(|| text.strip_prefix(r"\\?\").map(PathBuf::from))
+    };
+    simplified.unwrap_or(canonical)
+}
+
+/// Whether a command-line argument is a glob pattern rather than a literal path
+fn is_pattern(arg: &Path) -> bool {
+    let text = arg.to_string_lossy();
+    text.contains('*') || text.contains('?')
+}
+
+/// One rule from a `.gitignore` file
+#[derive(Clone)]
+struct IgnoreRule {
+    base: PathBuf,
+    glob: Glob,
+    negated: bool,
+    dir_only: bool,
+}
+
+/// Rules from the `.gitignore` files between the repository root and a directory,
+/// keyed by the absolute directory each file lives in
+#[derive(Clone, Default)]
+struct Gitignore {
+    rules: Vec<IgnoreRule>,
+}
+
+impl Gitignore {
+    /// Add the rules of a directory's `.gitignore`, if it has one
+    fn load(&mut self, dir: &Path) {
+        let content = match std::fs::read_to_string(dir.join(".gitignore")) {
+            Ok(content) => content,
+            Err(_) => return,
+        };
+        
+        for line in content.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let dir_only = line.ends_with('/');
+            let pattern = line.trim_end_matches('/');
+            
+            // A leading slash anchors a pattern to the .gitignore's directory, as does a
+            // slash in the middle; other patterns match a name at any depth
+            if let Ok(mut glob) = Glob::new(pattern.trim_start_matches('/')) {
+                glob.name_only &= !pattern.starts_with('/');
+                self.rules.push(IgnoreRule { base: dir.to_path_buf(), glob, negated, dir_only });
+            }
+        }
+    }
+    
+    /// Check whether a path is ignored; the last matching rule wins
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let relative = match path.strip_prefix(&rule.base) {
+                Ok(relative) => relative,
+                Err(_) => continue,
+            };
+            if rule.glob.matches(relative) {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// Expand command-line paths into the files to process. Directories are walked
+/// recursively, skipping `.git`, DocGen's backups, and anything `.gitignore`
+/// excludes, and keep only the files `accept` recognizes. Arguments containing `*` or
+/// `?` are matched as globs against the whole path, from their literal prefix down.
+/// Files named explicitly are always kept. Every file must then match one of the
+/// `include` globs (when any are given) and none of the `exclude` globs.
+pub fn expand(
+    args: &[PathBuf],
+    root: &Path,
+    include: &[String],
+    exclude: &[String],
+    accept: impl Fn(&Path) -> bool,
+) -> DocGenResult<Vec<PathBuf>> {
+    let include = include.iter().map(|p| Glob::new(p)).collect::<DocGenResult<Vec<_>>>()?;
+    let exclude = exclude.iter().map(|p| Glob::new(p)).collect::<DocGenResult<Vec<_>>>()?;
+    
+    let mut files = Vec::new();
+    for arg in args {
+        if arg.is_dir() {
+            walk(arg, &gitignore_for(arg, root), &mut |path| {
+                if accept(path) {
+                    files.push(path.to_path_buf());
+                }
+            });
+        } else if is_pattern(arg) && !arg.exists() {
+            let pattern = Glob::anchored(&arg.to_string_lossy())?;
+            let base = literal_prefix(arg);
+            walk(&base, &gitignore_for(&base, root), &mut |path| {
+                if pattern.matches(path) {
+                    files.push(path.to_path_buf());
+                }
+            });
+        } else {
+            files.push(arg.clone());
+        }
+    }
+    
+    files.retain(|file| {
+        (include.is_empty() || include.iter().any(|g| g.matches(file)))
+            && !exclude.iter().any(|g| g.matches(file))
+    });
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Files staged for commit (added, copied, modified, or renamed), relative to the
+/// repository root
+pub fn staged_files(root: &Path) -> DocGenResult<Vec<PathBuf>> {
+    let output = git(root, &["diff", "--cached", "--name-only", "--diff-filter=ACMR", "-z"])?;
+    Ok(output.split('\0')
+        .filter(|name| !name.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Content of a file as staged for commit, rather than as it is in the working tree
+pub fn staged_content(file_path: &Path) -> DocGenResult<String> {
+    let dir = file_path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let name = file_path.file_name()
+        .ok_or_else(|| DocGenError::ConfigError(format!("{} is not a file", file_path.display())))?;
+    git(dir, &["show", &format!(":./{}", name.to_string_lossy())])
+}
+
+/// Content of a file at a git revision, or None if the file didn't exist there
+pub fn revision_content(file_path: &Path, revision: &str) -> DocGenResult<Option<String>> {
+    let dir = file_path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let name = file_path.file_name()
+        .ok_or_else(|| DocGenError::ConfigError(format!("{} is not a file", file_path.display())))?;
+    let object = format!("{}:./{}", revision, name.to_string_lossy());
+    if git(dir, &["cat-file", "-e", &object]).is_err() {
+        return Ok(None);
+    }
+    git(dir, &["show", &object]).map(Some)
+}
+
+/// Check that a revision names a commit in the repository, so a mistyped ref is
+/// reported once rather than read as every file being new
+pub fn verify_revision(root: &Path, revision: &str) -> DocGenResult<()> {
+    git(root, &["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", revision)])
+        .map_err(|_| DocGenError::ConfigError(format!("{} is not a revision in {}", revision, root.display())))?;
+    Ok(())
+}
+
+/// Run git in a directory, returning what it printed
+fn git(dir: &Path, args: &[&str]) -> DocGenResult<String> {
+    let output = Command::new("git")
+        .arg("-C").arg(dir)
+        .args(args)
+        .output()?;
+    if !output.status.success() {
+        return Err(DocGenError::ConfigError(format!("git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Directory part of a glob before its first wildcard component
+fn literal_prefix(pattern: &Path) -> PathBuf {
+    let mut base = PathBuf::new();
+    for component in pattern.components() {
+        if is_pattern(Path::new(component.as_os_str())) {
+            break;
+        }
+        base.push(component);
+    }
+    if base.as_os_str().is_empty() || !base.is_dir() {
+        PathBuf::from(".")
+    } else {
+        base
+    }
+}
+
+/// Rules from the `.gitignore` files of a directory's ancestors, up to the repository root
+fn gitignore_for(dir: &Path, root: &Path) -> Gitignore {
+    let mut gitignore = Gitignore::default();
+    let dir = canonical(dir);
+    let root = canonical(root);
+    
+    let mut ancestors: Vec<&Path> = dir.ancestors()
+        .skip(1)
+        .take_while(|a| a.starts_with(&root))
+        .collect();
+    ancestors.reverse();
+    for ancestor in ancestors {
+        gitignore.load(ancestor);
+    }
+    gitignore
+}
+
+/// Recursively visit the files under a directory in sorted order. Symlinked
+/// directories aren't followed, so a link back up the tree can't loop and a linked
+/// tree isn't visited a second time under another path; symlinked files are visited.
+fn walk(dir: &Path, parent_rules: &Gitignore, visit: &mut dyn FnMut(&Path)) {
+    let dir_absolute = canonical(dir);
+    let mut gitignore = parent_rules.clone();
+    gitignore.load(&dir_absolute);
+    
+    let mut names: Vec<(std::ffi::OsString, bool)> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(|e| e.ok())
+            .map(|e| (e.file_name(), e.file_type().is_ok_and(|t| t.is_symlink())))
+            .collect(),
+        Err(_) => return,
+    };
+    names.sort();
+    
+    for (name, is_symlink) in names {
+        let path = dir.join(&name);
+        let is_dir = path.is_dir();
+        if is_dir && is_symlink {
+            continue;
+        }
+        if name == ".git" || name == crate::backup::BACKUP_DIR || gitignore.is_ignored(&dir_absolute.join(&name), is_dir) {
+            continue;
+        }
+        if is_dir {
+            walk(&path, &gitignore, visit);
+        } else {
+            visit(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_directories_are_not_followed() {
+        let dir = std::env::temp_dir().join(format!("docgen-walk-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("d")).unwrap();
+        std::fs::write(dir.join("d/y.rs"), "fn y() {}\n").unwrap();
+        std::os::unix::fs::symlink("..", dir.join("d/loop")).unwrap();
+        std::os::unix::fs::symlink("y.rs", dir.join("d/z.rs")).unwrap();
+        
+        let files = expand(std::slice::from_ref(&dir), &dir, &[], &[], |_| true).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(files, vec![dir.join("d/y.rs"), dir.join("d/z.rs")]);
+    }
+    
+    #[test]
+    fn command_line_globs_match_the_whole_path() {
+        let name = Glob::new("*.py").unwrap();
+        let anchored = Glob::anchored("*.py").unwrap();
+        for path in ["a.py", "./a.py"] {
+            assert!(name.matches(Path::new(path)));
+            assert!(anchored.matches(Path::new(path)));
+        }
+        assert!(name.matches(Path::new("src/b.py")));
+        assert!(!anchored.matches(Path::new("src/b.py")));
+        assert!(!anchored.matches(Path::new("./src/b.py")));
+    }
+    
+    #[test]
+    fn command_line_globs_match_from_their_literal_prefix() {
+        let dir = std::env::temp_dir().join(format!("docgen-glob-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+        for file in ["a.py", "src/b.py", "src/c.rs", "src/nested/d.py"] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        
+        let expand_glob = |pattern: &str| expand(&[dir.join(pattern)], &dir, &[], &[], |_| true).unwrap();
+        let top = expand_glob("*.py");
+        let src = expand_glob("src/*.py");
+        let recursive = expand_glob("src/**/*.py");
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(top, vec![dir.join("a.py")]);
+        assert_eq!(src, vec![dir.join("src/b.py")]);
+        assert_eq!(recursive, vec![dir.join("src/b.py"), dir.join("src/nested/d.py")]);
+    }
+    
+    #[test]
+    fn include_and_exclude_match_names_at_any_depth() {
+        let dir = std::env::temp_dir().join(format!("docgen-filter-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src/tests")).unwrap();
+        for file in ["a.py", "src/b.py", "src/c.rs", "src/tests/d.py"] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        
+        let include = ["*.py".to_string()];
+        let exclude = ["**/tests/**".to_string()];
+        let files = expand(std::slice::from_ref(&dir), &dir, &include, &exclude, |_| true).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(files, vec![dir.join("a.py"), dir.join("src/b.py")]);
+    }
+}
@@ -1,43 +1,160 @@
+use clap::ValueEnum;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
 use crate::error::DocGenResult;
-use crate::parser::{ParsedCode, CodeItem};
+use crate::parser::{ParsedCode, CodeItem, ItemKind};
+use crate::rules::{Reason, Rule, Severity};
+use crate::sections;
+
+/// Section layout of Python docstrings (`--style`, or `style` in docsherpa.toml)
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocstringStyle {
+    /// `Args:`, `Returns:`, and `Raises:` sections with indented entries
+    Google,
+    /// `Parameters`, `Returns`, and `Raises` headings underlined with dashes
+    Numpy,
+    /// reStructuredText fields such as `:param name:`, `:returns:`, and `:raises Error:`
+    Sphinx,
+}
+
+impl DocstringStyle {
+    /// Prompt instruction describing the style's layout
+    pub fn instructions(self) -> &'static str {
+        match self {
+            DocstringStyle::Google => "Use the Google docstring style: a summary line, then `Args:`, `Returns:`, and \
+                `Raises:` sections as needed, with one indented `name (type): description` entry per parameter.",
+            DocstringStyle::Numpy => "Use the NumPy docstring style: a summary line, then `Parameters`, `Returns`, and \
+                `Raises` sections as needed, each heading underlined with dashes, with one `name : type` line per \
+                parameter followed by an indented description.",
+            DocstringStyle::Sphinx => "Use the Sphinx (reStructuredText) docstring style: a summary line, then one \
+                `:param name: description` and `:type name: type` field per parameter, and `:returns:`, \
+                `:rtype:`, and `:raises ErrorType:` fields as needed.",
+        }
+    }
+    
+    /// Style whose sections a docstring uses, if it has any
+    pub fn detect(docstring: &str) -> Option<Self> {
+        let numpy_re = Regex::new(r"(?m)^\s*(?:Parameters|Returns|Yields|Raises|Attributes|Examples|Notes)\s*\n\s*-{3,}\s*$").unwrap();
+        let sphinx_re = Regex::new(r"(?m)^\s*:(?:param|type|returns?|rtype|yields?|raises?)\b").unwrap();
+        let google_re = Regex::new(r"(?m)^\s*(?:Args|Arguments|Returns|Yields|Raises|Attributes|Examples|Note):\s*$").unwrap();
+        
+        if numpy_re.is_match(docstring) {
+            Some(DocstringStyle::Numpy)
+        } else if sphinx_re.is_match(docstring) {
+            Some(DocstringStyle::Sphinx)
+        } else if google_re.is_match(docstring) {
+            Some(DocstringStyle::Google)
+        } else {
+            None
+        }
+    }
+}
+
+/// What is wrong with an item's documentation, written in lowercase in reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueKind {
+    /// The item has no documentation
+    Missing,
+    /// The documentation doesn't describe the item's current signature
+    Outdated,
+    /// The signature changed since the item was last documented
+    Changed,
+    /// The documentation breaks a style rule (`--lint`)
+    Style,
+}
+
+impl IssueKind {
+    /// Name of the kind as written in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueKind::Missing => "missing",
+            IssueKind::Outdated => "outdated",
+            IssueKind::Changed => "changed",
+            IssueKind::Style => "style",
+        }
+    }
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
 
 /// Represents an issue with documentation
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DocstringIssue {
-    pub item_type: String,      // "function", "method", "class"
+    pub item_type: ItemKind,    // Function, method, class, etc.
     pub name: String,           // Name of the item
     pub line_number: usize,     // Line number in the file
-    pub issue_type: String,     // "missing" or "outdated"
+    pub issue_type: IssueKind,  // Missing, outdated, changed, or style
     pub item_index: usize,      // Index in the parsed items array
+    pub reasons: Vec<Reason>,   // Rules the documentation breaks, e.g. "undocumented-param: foo"
+}
+
+impl DocstringIssue {
+    /// Severity used when reporting: that of the most serious rule broken, or for
+    /// issues without one, error for missing documentation and info for style
+    pub fn severity(&self) -> &'static str {
+        let severity = self.reasons.iter().map(|r| r.severity).max().unwrap_or(match self.issue_type {
+            IssueKind::Missing => Severity::Error,
+            IssueKind::Style => Severity::Info,
+            IssueKind::Outdated | IssueKind::Changed => Severity::Warning,
+        });
+        severity.as_str()
+    }
 }
 
-/// Analyze parsed code for docstring issues
-pub fn analyze(parsed_code: &ParsedCode) -> DocGenResult<Vec<DocstringIssue>> {
+/// Analyze parsed code for docstring issues. With a style chosen, Python docstrings
+/// that don't follow it are also treated as outdated.
+pub fn analyze(parsed_code: &ParsedCode, style: Option<DocstringStyle>) -> DocGenResult<Vec<DocstringIssue>> {
     let mut issues = Vec::new();
+    let ignored = parsed_code.ignored_items();
     
     for (index, item) in parsed_code.items.iter().enumerate() {
+        // Items excluded with a `docsherpa: ignore` or `skip-file` comment
+        if ignored[index] {
+            continue;
+        }
+        
         // Check if docstring is missing
         if item.existing_docstring.is_none() {
             issues.push(DocstringIssue {
-                item_type: item.item_type.clone(),
+                item_type: item.item_type,
                 name: item.name.clone(),
                 line_number: item.line_number,
-                issue_type: "missing".to_string(),
+                issue_type: IssueKind::Missing,
                 item_index: index,
+                reasons: vec![Reason::new(Rule::MissingDocstring)],
             });
             continue;
         }
         
-        // Check if docstring might be outdated
-        // This is a simplistic check that can be enhanced
+        // Check whether the documentation still matches the signature, noting each way
+        // it doesn't
         if let Some(docstring) = &item.existing_docstring {
-            if is_likely_outdated(item, docstring) {
+            let mut reasons = sections::outdated_reasons(item, docstring);
+            let wrong_style = match style {
+                Some(style) if parsed_code.language == "python" && !item.is_script_statement() => {
+                    !follows_style(item, docstring, style)
+                },
+                _ => false,
+            };
+            if wrong_style {
+                reasons.push(Reason::new(Rule::WrongStyle));
+            }
+            if !reasons.is_empty() {
                 issues.push(DocstringIssue {
-                    item_type: item.item_type.clone(),
+                    item_type: item.item_type,
                     name: item.name.clone(),
                     line_number: item.line_number,
-                    issue_type: "outdated".to_string(),
+                    issue_type: IssueKind::Outdated,
                     item_index: index,
+                    reasons,
                 });
             }
         }
@@ -46,38 +163,160 @@ pub fn analyze(parsed_code: &ParsedCode) -> DocGenResult<Vec<DocstringIssue>> {
     Ok(issues)
 }
 
-/// Check if a docstring is likely outdated
-/// This is a simple heuristic and can be enhanced
-fn is_likely_outdated(item: &CodeItem, docstring: &str) -> bool {
-    // Check if all parameters are mentioned in the docstring
-    for param in &item.parameters {
-        // Skip self parameter for methods
-        if param == "self" {
+/// Whether a Python docstring is written in the given style: it must not use another
+/// style's sections, and must have sections at all when there are parameters to describe
+fn follows_style(item: &CodeItem, docstring: &str, style: DocstringStyle) -> bool {
+    match DocstringStyle::detect(docstring) {
+        Some(found) => found == style,
+        None => item.parameters.is_empty(),
+    }
+}
+
+/// Longest summary line allowed by PEP 8 / PEP 257 conventions
+pub const SUMMARY_MAX_CHARS: usize = 79;
+
+/// Languages whose docstrings follow the summary-line conventions below; CLI help
+/// strings and OpenAPI descriptions have their own
+const LINTED_LANGUAGES: &[&str] = &["python", "rust", "javascript", "typescript", "protobuf", "objc", "scala", "groovy", "java", "swift"];
+
+/// Check documented items that have no other issue against the summary line and
+/// blank-line conventions (PEP 257 / rustdoc), adding a "style" issue for each
+pub fn lint(parsed_code: &ParsedCode, issues: &mut Vec<DocstringIssue>) {
+    if !LINTED_LANGUAGES.contains(&parsed_code.language.as_str()) {
+        return;
+    }
+    
+    let ignored = parsed_code.ignored_items();
+    for (index, item) in parsed_code.items.iter().enumerate() {
+        if issues.iter().any(|i| i.item_index == index) || item.is_script_statement() || ignored[index] {
             continue;
         }
         
-        // Clean parameter name for comparison (remove * or = if present)
-        let clean_param = param.trim_matches(|c| c == '*' || c == '=');
-        
-        // Check if parameter is mentioned in docstring
-        if !docstring.contains(clean_param) {
-            return true;
+        if let Some(docstring) = &item.existing_docstring {
+            let reasons = style_violations(docstring);
+            if !reasons.is_empty() {
+                issues.push(DocstringIssue {
+                    item_type: item.item_type,
+                    name: item.name.clone(),
+                    line_number: item.line_number,
+                    issue_type: IssueKind::Style,
+                    item_index: index,
+                    reasons,
+                });
+            }
         }
     }
+}
+
+/// List the style rules a docstring violates
+pub fn style_violations(docstring: &str) -> Vec<Reason> {
+    let text = dedent(docstring);
+    let lines: Vec<&str> = text.lines().collect();
+    let summary = lines.first().map_or("", |l| l.trim());
+    let mut reasons = Vec::new();
     
-    // Check if return type is mentioned for functions/methods with return annotations
-    if let Some(_) = &item.returns {
-        if !docstring.to_lowercase().contains("return") {
-            return true;
+    if summary.is_empty() {
+        return reasons;
+    }
+    if summary.chars().next().is_some_and(|c| c.is_lowercase()) {
+        reasons.push(Reason::new(Rule::SummaryCapital));
+    }
+    if imperative_form(first_word(summary)).is_some() {
+        reasons.push(Reason::new(Rule::SummaryImperative));
+    }
+    if !summary.ends_with('.') {
+        reasons.push(Reason::new(Rule::SummaryPeriod));
+    }
+    if summary.chars().count() > SUMMARY_MAX_CHARS {
+        reasons.push(Reason::new(Rule::SummaryLength));
+    }
+    if lines.len() > 1 && !lines[1].trim().is_empty() {
+        reasons.push(Reason::new(Rule::BlankLine));
+    }
+    
+    reasons
+}
+
+/// Fix the given style violations with plain string transforms. Returns None when a
+/// violation (an overlong summary) needs rewriting rather than a mechanical fix.
+pub fn fix_style(docstring: &str, rules: &[Rule]) -> Option<String> {
+    let text = dedent(docstring);
+    let mut lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+    
+    let mut summary = lines.first()?.trim().to_string();
+    if rules.contains(&Rule::SummaryImperative) {
+        if let Some(imperative) = imperative_form(first_word(&summary)) {
+            summary = format!("{}{}", imperative, &summary[first_word(&summary).len()..]);
         }
     }
+    if rules.contains(&Rule::SummaryCapital) {
+        let mut chars = summary.chars();
+        if let Some(first) = chars.next() {
+            summary = first.to_uppercase().chain(chars).collect();
+        }
+    }
+    if rules.contains(&Rule::SummaryPeriod) && !summary.ends_with('.') {
+        summary = format!("{}.", summary.trim_end_matches([',', ';', ':']));
+    }
     
-    // Check if docstring is very short (likely a placeholder)
-    if docstring.trim().len() < 10 {
-        return true;
+    if rules.contains(&Rule::SummaryLength) && summary.chars().count() > SUMMARY_MAX_CHARS {
+        return None;
+    }
+    lines[0] = summary;
+    
+    if rules.contains(&Rule::BlankLine) && lines.len() > 1 && !lines[1].trim().is_empty() {
+        lines.insert(1, String::new());
+    }
+    
+    Some(lines.join("\n"))
+}
+
+/// First word of a summary line
+fn first_word(summary: &str) -> &str {
+    summary.split(|c: char| !c.is_alphanumeric()).next().unwrap_or("")
+}
+
+/// Imperative form of a third-person verb ("Returns" -> "Return", "Applies" -> "Apply"),
+/// or None if the word does not look like one
+fn imperative_form(word: &str) -> Option<String> {
+    let lower = word.to_lowercase();
+    if lower.len() <= 3 || !lower.ends_with('s') || ["ss", "us", "is", "ous"].iter().any(|e| lower.ends_with(e)) {
+        return None;
+    }
+    if ["always", "perhaps", "towards", "series", "news", "options", "settings", "details", "bytes"].contains(&lower.as_str()) {
+        return None;
     }
     
-    false
+    let stem = if lower == "does" || lower == "goes" {
+        word[..2].to_string()
+    } else if lower.ends_with("ies") {
+        format!("{}y", &word[..word.len() - 3])
+    } else if ["ches", "shes", "xes", "sses", "zes"].iter().any(|e| lower.ends_with(e)) {
+        word[..word.len() - 2].to_string()
+    } else {
+        word[..word.len() - 1].to_string()
+    };
+    Some(stem)
+}
+
+/// Strip the indentation that continuation lines of a docstring carry in the source
+fn dedent(docstring: &str) -> String {
+    let text = docstring.trim();
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or("");
+    let rest: Vec<&str> = lines.collect();
+    
+    let indent = rest.iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    
+    std::iter::once(first.trim())
+        .chain(rest.iter().map(|l| if l.len() >= indent { &l[indent..] } else { l.trim() }))
+        .map(|l| l.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Represents an updated docstring
@@ -87,3 +326,10 @@ pub struct UpdatedDocstring {
     pub new_docstring: String,
     pub indentation: String,
 }
+
+impl UpdatedDocstring {
+    /// Number of lines the new docstring occupies in the file
+    pub fn line_count(&self) -> usize {
+        self.new_docstring.trim().lines().count()
+    }
+}
@@ -0,0 +1,32 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Lines of failing `cargo test --doc` output shown in the report
+const OUTPUT_LINES: usize = 20;
+
+/// Run the doctests of the crate a Rust file belongs to (`--verify-doctests`), found
+/// from the nearest `Cargo.toml` above it. A crate without a library has no doctests
+/// to run, which passes. On failure, returns the end of the test output.
+pub fn verify(file_path: &Path) -> Result<(), String> {
+    let file_path = std::fs::canonicalize(file_path).unwrap_or_else(|_| file_path.to_path_buf());
+    let crate_dir = file_path.ancestors()
+        .skip(1)
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .ok_or_else(|| format!("no Cargo.toml found above {}", file_path.display()))?;
+    
+    let output = Command::new("cargo")
+        .args(["test", "--doc", "--quiet"])
+        .current_dir(crate_dir)
+        .output()
+        .map_err(|e| format!("could not run cargo: {}", e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    
+    let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    if text.contains("no library targets found") {
+        return Ok(());
+    }
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    Err(lines[lines.len().saturating_sub(OUTPUT_LINES)..].join("\n"))
+}
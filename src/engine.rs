@@ -0,0 +1,606 @@
+use colored::Colorize;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use anyhow::Result;
+
+use crate::{changes, config, coverage, discover, docstring, doctest, history, interactive, io, lang, llm, parser, priority, progress, provenance, report, review, rules, tracker, updater, Language};
+
+/// Content of a file to analyze: as staged for commit with `--staged`, the editor's
+/// unsaved buffer with `--overlay`, or else what is on disk, without a byte order mark
+pub fn read_source(file_path: &Path, config: &config::Config) -> Result<String> {
+    if config.staged {
+        return Ok(io::strip_bom(&discover::staged_content(file_path)?).to_string());
+    }
+    match &config.overlay {
+        Some(overlay) => Ok(overlay.read(file_path)?),
+        None => Ok(io::read_text(file_path)?),
+    }
+}
+
+/// Parser for the items a run documents: command-line arguments with `--cli-help`,
+/// otherwise code items, with script statements, constants, and private items when
+/// asked for. New comments are written in the styles `config` sets for C, C++, and
+/// TypeScript.
+pub fn mode_parser(
+    language: &Language,
+    file_path: &Path,
+    cli_help: bool,
+    script_comments: bool,
+    constants: bool,
+    include_private: bool,
+    config: &config::Config,
+) -> Result<Box<dyn lang::LanguageParser>> {
+    let parser = if cli_help {
+        lang::get_cli_help_parser(language)?
+    } else if script_comments {
+        lang::get_script_parser(language, constants)?
+    } else if matches!(language, Language::C | Language::Cpp) {
+        lang::get_doxygen_parser(language, config.cpp_comment_style)
+    } else if matches!(language, Language::TypeScript) {
+        lang::get_typescript_parser(constants, config.ts_tag_style)
+    } else if constants {
+        lang::get_constants_parser(language, file_path)
+    } else {
+        lang::get_file_parser(language, file_path)
+    };
+    Ok(if include_private { parser } else { lang::get_public_parser(parser) })
+}
+
+/// Analyze one file and report its documentation issues to `out`, then, unless only
+/// checking, document them and write the file, or show the change as a diff or
+/// propose it for review, as the configuration asks
+pub async fn process_file(
+    file_path: &PathBuf,
+    language: &Language,
+    config: &config::Config,
+    history: Option<(&Mutex<history::History>, &str)>,
+    provenance: Option<(&Mutex<provenance::Provenance>, &str)>,
+    out: &mut Vec<u8>,
+) -> Result<report::FileReport> {
+    if config.verbose {
+        writeln!(out, "Detected language: {:?}", language)?;
+        writeln!(out, "\n{} {}", "Processing:".blue(), file_path.display())?;
+    }
+    
+    // Files after the one where an interactive review was quit are left alone
+    if interactive::quit_requested() {
+        return Ok(report::FileReport::new(file_path));
+    }
+    
+    let content = read_source(file_path, config)?;
+    
+    // Parse code based on language
+    let parser = mode_parser(language, file_path, config.cli_help, config.script_comments, config.constants, config.include_private, config)?;
+    let parsed_code = parser.parse(&content)?;
+    
+    // Items inside syntax errors can't be analyzed, but the rest of the file can
+    for error in &parsed_code.syntax_errors {
+        writeln!(out, "{} syntax error in {} at line {}, skipping the code inside it: {}",
+            "DocGen:".yellow(),
+            file_path.display(),
+            error.line,
+            error.message)?;
+    }
+    
+    // Charts should also describe each of their templates in the README
+    if matches!(language, Language::Helm) {
+        for template in lang::helm::uncovered_templates(file_path) {
+            writeln!(out, "{} chart template {} is not mentioned in the chart's README",
+                "DocGen:".yellow(),
+                template.display())?;
+        }
+    }
+    
+    // Analyze docstrings
+    let mut docstring_issues = docstring::analyze(&parsed_code, config.docstring_style)?;
+    
+    // With --since, documentation is only outdated where the signature changed since
+    // the base revision; files that are new since then are judged as usual
+    if let Some(base) = &config.since {
+        if let Some(base_content) = discover::revision_content(file_path, base)? {
+            let base_code = parser.parse(io::strip_bom(&base_content))?;
+            changes::since_base(&base_code, &parsed_code, &mut docstring_issues);
+        }
+    }
+    
+    // Flag items whose signature changed since their documentation was last recorded
+    if let Some((history, file_key)) = history {
+        let mut history = history.lock().expect("history lock poisoned");
+        history.detect_changes(file_key, &parsed_code, &mut docstring_issues);
+        history.record(file_key, &parsed_code, &docstring_issues);
+    }
+    
+    if config.lint {
+        docstring::lint(&parsed_code, &mut docstring_issues);
+    }
+    config.rules.apply(&mut docstring_issues);
+    let mut file_report = report::FileReport::new(file_path);
+    let counts = coverage::FileCoverage::new(file_path, &parsed_code).total();
+    file_report.total_items = counts.items;
+    file_report.documented_items = counts.documented;
+    file_report.issues = docstring_issues.clone();
+    file_report.syntax_errors = parsed_code.syntax_errors.clone();
+    
+    if docstring_issues.is_empty() {
+        if config.verbose {
+            writeln!(out, "{} {}", "✓".green(), "All items are properly documented".green())?;
+        }
+        return Ok(file_report);
+    }
+    
+    // Report issues
+    writeln!(out, "{} found {} documentation issues in {}", 
+        "DocGen:".yellow(),
+        docstring_issues.len(),
+        file_path.display())?;
+    
+    if config.group_by != report::GroupBy::File {
+        write!(out, "{}", report::file_table(&file_report, config.group_by))?;
+    }
+    
+    // Individual issues are listed only when details or tracker links were asked for
+    if config.verbose || config.issue_url_template.is_some() {
+        for issue in &docstring_issues {
+            writeln!(out, "  {} {}: {}", "→".yellow(), issue.item_type, issue.name)?;
+            if config.verbose {
+                writeln!(out, "    Line {}: {} ({})", issue.line_number, issue.issue_type, issue.severity())?;
+                if !issue.reasons.is_empty() {
+                    let reasons: Vec<String> = issue.reasons.iter().map(|r| r.to_string()).collect();
+                    writeln!(out, "    Reasons: {}", reasons.join(", "))?;
+                }
+            }
+            if let Some(template) = &config.issue_url_template {
+                writeln!(out, "    Track: {}", tracker::issue_url(template, file_path, issue))?;
+            }
+        }
+    }
+    
+    // Exit if we're just checking or in test mode
+    if config.check_only || config.test_mode {
+        if config.test_mode && config.verbose {
+            writeln!(out, "{} Test mode - skipping LLM API calls", "DocGen:".blue())?;
+            
+            // Print parsed code items for verification
+            writeln!(out, "\n{} Parsed code items:", "DocGen:".blue())?;
+            for (index, item) in parsed_code.items.iter().enumerate() {
+                writeln!(out, "  Item {}: {} '{}'", index, item.item_type, item.name)?;
+                writeln!(out, "    Line: {}", item.line_number)?;
+                writeln!(out, "    Parameters: {:?}", item.parameters)?;
+                writeln!(out, "    Docstring: {}", item.existing_docstring.as_ref().map_or("None", |s| s))?;
+                writeln!(out)?;
+            }
+        }
+        return Ok(file_report);
+    }
+    
+    // A summary-only pass just fills gaps; outdated and style issues wait for a full run
+    let mut to_fix = docstring_issues;
+    if config.summary_only {
+        to_fix.retain(|issue| issue.issue_type == docstring::IssueKind::Missing);
+        if to_fix.is_empty() {
+            return Ok(file_report);
+        }
+    }
+    
+    // Documentation written or edited by hand is reported but never overwritten
+    if let Some((provenance, file_key)) = provenance {
+        let protected = provenance.lock().expect("provenance lock poisoned").protect(file_key, &parsed_code, &mut to_fix);
+        if protected > 0 {
+            writeln!(out, "{} Leaving {} docstrings written by hand as they are (--track-generated)", 
+                "DocGen:".yellow(),
+                protected)?;
+        }
+        if to_fix.is_empty() {
+            return Ok(file_report);
+        }
+    }
+    
+    // Put the items to document first at the front, keeping as many as `--max-fixes`
+    // still allows
+    priority::order(&mut to_fix, &parsed_code, config.order, file_path);
+    if let Some(budget) = &config.fix_budget {
+        let granted = priority::claim(budget, to_fix.len());
+        if granted < to_fix.len() {
+            writeln!(out, "{} Leaving {} items for a later run (--max-fixes reached)", 
+                "DocGen:".yellow(),
+                to_fix.len() - granted)?;
+            to_fix.truncate(granted);
+        }
+        if to_fix.is_empty() {
+            return Ok(file_report);
+        }
+    }
+    
+    let items: HashSet<usize> = to_fix.iter().map(|issue| issue.item_index).collect();
+    progress::items_planned(file_path, items.len());
+    
+    // Giant files are documented and written a chunk of items at a time, so finished
+    // chunks survive an interruption. The preview modes show every change at once.
+    let chunk_size = match config.chunk_size {
+        Some(size) if !config.diff_only && !config.dry_run && !config.interactive && config.review_file.is_none() => size,
+        _ => to_fix.len(),
+    };
+    let chunk_count = to_fix.len().div_ceil(chunk_size);
+    let first_parse = parsed_code;
+    let mut parsed_code = None;
+    let mut content = content;
+    let mut llm_client = None;
+    
+    for (chunk_index, chunk) in to_fix.chunks(chunk_size).enumerate() {
+        // Re-parse after each written chunk so the remaining items' line numbers are current,
+        // remembering which index each item had in the first parse for the report
+        let (current, chunk_issues, first_index) = if chunk_index == 0 {
+            let first_index: HashMap<usize, usize> = chunk.iter().map(|i| (i.item_index, i.item_index)).collect();
+            (&first_parse, chunk.to_vec(), first_index)
+        } else {
+            let current = parsed_code.insert(parser.parse(&content)?);
+            let mut chunk_issues = Vec::new();
+            let mut first_index = HashMap::new();
+            for issue in chunk {
+                if let Some(index) = current.find_item(&first_parse, issue.item_index) {
+                    first_index.insert(index, issue.item_index);
+                    chunk_issues.push(docstring::DocstringIssue {
+                        item_index: index,
+                        line_number: current.items[index].line_number,
+                        ..issue.clone()
+                    });
+                }
+            }
+            (&*current, chunk_issues, first_index)
+        };
+        
+        if chunk_count > 1 {
+            writeln!(out, "{} Chunk {}/{}: items {}-{} of {}", 
+                "DocGen:".blue(),
+                chunk_index + 1,
+                chunk_count,
+                chunk_index * chunk_size + 1,
+                chunk_index * chunk_size + chunk.len(),
+                to_fix.len())?;
+        }
+        
+        // Style issues with a mechanical fix don't need a model; the rest are generated
+        let (style_issues, model_issues): (Vec<docstring::DocstringIssue>, Vec<docstring::DocstringIssue>) = chunk_issues.iter()
+            .cloned()
+            .partition(|issue| issue.issue_type == docstring::IssueKind::Style);
+        let (mut updated_docstrings, rewrite_issues) = fix_style_issues(current, style_issues);
+        let style_fixed: Vec<usize> = updated_docstrings.iter().map(|updated| updated.item_index).collect();
+        
+        if !model_issues.is_empty() || !rewrite_issues.is_empty() {
+            // Use LLM to generate docstrings, with one client for every chunk
+            writeln!(out, "{} Generating documentation using {}...", 
+                "DocGen:".blue(),
+                if config.template_fix { "templates" } else if config.scheduler.is_some() { "the provider pool" } else { config.provider.as_str() })?;
+            
+            let llm_client: &dyn llm::LlmClient = match &llm_client {
+                Some(client) => client,
+                None => llm_client.insert(llm::get_client(config)?),
+            }.as_ref();
+            let mut generated = llm_client.generate_docstrings(current, &model_issues, config).await?;
+            
+            // Summaries too long to fix mechanically are rewritten under the style rules
+            if !rewrite_issues.is_empty() {
+                let mut style_config = config.clone();
+                style_config.extra_instructions.push(format!(
+                    "Start with a one-line summary of at most {} characters, written in the imperative mood \
+                    (\"Return\", not \"Returns\"), starting with a capital letter and ending with a period. \
+                    Separate any further description from the summary with a blank line.",
+                    docstring::SUMMARY_MAX_CHARS
+                ));
+                generated.extend(llm_client.generate_docstrings(current, &rewrite_issues, &style_config).await?);
+            }
+            
+            // Items whose requests were never sent are left for a later run
+            if config.budget.exhausted() {
+                file_report.unfinished += model_issues.iter()
+                    .chain(&rewrite_issues)
+                    .filter(|issue| !generated.iter().any(|g| g.item_index == issue.item_index))
+                    .count();
+            }
+            
+            if let Some(max_lines) = config.max_doc_lines {
+                generated = enforce_max_doc_lines(
+                    llm_client, current, &chunk_issues, generated, max_lines, config, out).await?;
+            }
+            // Templates are written by the project, so only provider output is held to the policy
+            if !config.policy.is_empty() && !config.template_fix {
+                generated = enforce_policy(
+                    llm_client, current, &chunk_issues, generated, config, out).await?;
+            }
+            file_report.tokens_used = llm_client.tokens_used();
+            updated_docstrings.extend(generated);
+        }
+        
+        // Report how many lines each proposed docstring adds
+        let mut total_lines = 0;
+        for updated in &updated_docstrings {
+            let item = &current.items[updated.item_index];
+            let line_count = updated.line_count();
+            total_lines += line_count;
+            writeln!(out, "  {} +{} lines: {} {}", "✎".blue(), line_count, item.item_type, item.name)?;
+            progress::emit(config, progress::ProgressEvent::ItemGenerated {
+                file: file_path,
+                item_type: item.item_type,
+                name: &item.name,
+                line: item.line_number,
+                lines: line_count,
+            });
+        }
+        writeln!(out, "{} {} docstrings, {} lines in total", 
+            "DocGen:".blue(),
+            updated_docstrings.len(),
+            total_lines)?;
+        
+        let mut edited = Vec::new();
+        if config.interactive {
+            let drafts: HashMap<usize, String> = updated_docstrings.iter()
+                .map(|updated| (updated.item_index, updated.new_docstring.clone()))
+                .collect();
+            updated_docstrings = interactive::review(file_path, current, updated_docstrings, out)?;
+            if updated_docstrings.is_empty() {
+                return Ok(file_report);
+            }
+            edited = updated_docstrings.iter()
+                .filter(|updated| drafts.get(&updated.item_index) != Some(&updated.new_docstring))
+                .map(|updated| updated.item_index)
+                .collect();
+        }
+        
+        // Update the file with new docstrings
+        let updated_content = parser.update_content(&content, &updated_docstrings)?;
+        
+        for updated in &updated_docstrings {
+            let text = llm::unformat_docstring(&updated.new_docstring, &current.language);
+            file_report.suggestions.insert(first_index[&updated.item_index], text.to_string());
+        }
+        
+        // In review mode the changes go to the review file rather than the source file
+        if config.review_file.is_some() {
+            file_report.proposed = updated_docstrings.iter()
+                .map(|updated| review::ReviewItem::new(file_path, language.name(), &current.items[updated.item_index], updated))
+                .collect();
+            return Ok(file_report);
+        }
+        
+        // Write back to file, or show the change in diff mode
+        match updater::write_update(file_path, &content, &updated_content, config, out)? {
+            Some(patch) => file_report.diff = patch,
+            None => {
+                // Generated doctests that don't pass aren't kept
+                if config.verify_doctests && current.language == "rust" {
+                    if let Err(output) = doctest::verify(file_path) {
+                        io::write_text(file_path, &content)?;
+                        writeln!(out, "{} Doctests failed, so {} was left as it was (--verify-doctests):\n{}", 
+                            "DocGen:".red(),
+                            file_path.display(),
+                            output)?;
+                        return Ok(file_report);
+                    }
+                }
+                progress::emit(config, progress::ProgressEvent::FileWritten { file: file_path, items: updated_docstrings.len() });
+                writeln!(out, "{} Updated documentation in {}", 
+                    "DocGen:".green(),
+                    file_path.display())?;
+                
+                // Docstrings edited during an interactive review count as written by hand
+                if let Some((provenance, file_key)) = provenance {
+                    let written = parser.parse(&updated_content)?;
+                    let generated: Vec<(usize, String)> = updated_docstrings.iter()
+                        .filter(|updated| !edited.contains(&updated.item_index))
+                        .filter_map(|updated| {
+                            let model = if style_fixed.contains(&updated.item_index) {
+                                "docgen".to_string()
+                            } else {
+                                llm_client.as_ref()?.model()
+                            };
+                            Some((written.find_item(current, updated.item_index)?, model))
+                        })
+                        .collect();
+                    provenance.lock().expect("provenance lock poisoned").record(file_key, &written, &generated);
+                }
+            },
+        }
+        
+        // Record what was documented for the run summary
+        for updated in &updated_docstrings {
+            if let Some(issue) = chunk_issues.iter().find(|i| i.item_index == updated.item_index) {
+                let item = &current.items[updated.item_index];
+                file_report.documented.push(report::DocumentedItem {
+                    item_type: item.item_type,
+                    name: item.name.clone(),
+                    parent: item.parent.clone(),
+                    issue_type: issue.issue_type,
+                });
+            }
+        }
+        content = updated_content;
+    }
+    
+    Ok(file_report)
+}
+
+/// Document source code that is already in memory, such as an editor's unsaved buffer,
+/// returning the updated source and the issues found in it. Nothing is read, written,
+/// or printed; `file_path` is only consulted by parsers whose items depend on where a
+/// file is (an Ansible role's name). With `check_only` or `test_mode` the source is
+/// returned unchanged.
+pub async fn process_source(
+    file_path: &Path,
+    source: &str,
+    language: &Language,
+    config: &config::Config,
+) -> Result<(String, Vec<docstring::DocstringIssue>)> {
+    let parser = mode_parser(language, file_path, config.cli_help, config.script_comments, config.constants, config.include_private, config)?;
+    let parsed_code = parser.parse(source)?;
+    
+    let mut issues = docstring::analyze(&parsed_code, config.docstring_style)?;
+    if config.lint {
+        docstring::lint(&parsed_code, &mut issues);
+    }
+    config.rules.apply(&mut issues);
+    
+    let mut to_fix = issues.clone();
+    if config.summary_only {
+        to_fix.retain(|issue| issue.issue_type == docstring::IssueKind::Missing);
+    }
+    if to_fix.is_empty() || config.check_only || config.test_mode {
+        return Ok((source.to_string(), issues));
+    }
+    
+    // Style issues with a mechanical fix don't need a model; the rest are generated
+    let (style_issues, model_issues): (Vec<docstring::DocstringIssue>, Vec<docstring::DocstringIssue>) = to_fix.iter()
+        .cloned()
+        .partition(|issue| issue.issue_type == docstring::IssueKind::Style);
+    let (mut updated_docstrings, rewrite_issues) = fix_style_issues(&parsed_code, style_issues);
+    let model_issues: Vec<docstring::DocstringIssue> = model_issues.into_iter().chain(rewrite_issues).collect();
+    
+    if !model_issues.is_empty() {
+        let llm_client = llm::get_client(config)?;
+        let mut generated = llm_client.generate_docstrings(&parsed_code, &model_issues, config).await?;
+        
+        // Messages about regenerated docstrings are for the command-line tool's output
+        let mut out = Vec::new();
+        if let Some(max_lines) = config.max_doc_lines {
+            generated = enforce_max_doc_lines(
+                llm_client.as_ref(), &parsed_code, &model_issues, generated, max_lines, config, &mut out).await?;
+        }
+        if !config.policy.is_empty() && !config.template_fix {
+            generated = enforce_policy(
+                llm_client.as_ref(), &parsed_code, &model_issues, generated, config, &mut out).await?;
+        }
+        updated_docstrings.extend(generated);
+    }
+    
+    Ok((parser.update_content(source, &updated_docstrings)?, issues))
+}
+
+/// Apply mechanical fixes to style issues, returning the fixed docstrings and the
+/// issues that need the summary rewritten by a model
+fn fix_style_issues(
+    parsed_code: &parser::ParsedCode,
+    style_issues: Vec<docstring::DocstringIssue>,
+) -> (Vec<docstring::UpdatedDocstring>, Vec<docstring::DocstringIssue>) {
+    let mut fixed = Vec::new();
+    let mut rewrite = Vec::new();
+    
+    for issue in style_issues {
+        let item = &parsed_code.items[issue.item_index];
+        let broken: Vec<rules::Rule> = issue.reasons.iter().map(|r| r.rule).collect();
+        match item.existing_docstring.as_deref().and_then(|d| docstring::fix_style(d, &broken)) {
+            Some(text) => fixed.push(docstring::UpdatedDocstring {
+                item_index: issue.item_index,
+                new_docstring: llm::format_docstring(&text, item, &parsed_code.language),
+                indentation: item.indentation.clone(),
+            }),
+            None => rewrite.push(issue),
+        }
+    }
+    
+    (fixed, rewrite)
+}
+
+/// Regenerate docstrings that break the documentation policy with an instruction
+/// describing its rules, dropping any that still break it
+async fn enforce_policy(
+    llm_client: &dyn llm::LlmClient,
+    parsed_code: &parser::ParsedCode,
+    issues: &[docstring::DocstringIssue],
+    updated_docstrings: Vec<docstring::UpdatedDocstring>,
+    config: &config::Config,
+    out: &mut Vec<u8>,
+) -> Result<Vec<docstring::UpdatedDocstring>> {
+    let violating: Vec<docstring::DocstringIssue> = issues.iter()
+        .filter(|issue| updated_docstrings.iter()
+            .any(|u| u.item_index == issue.item_index && !config.policy.violations(&u.new_docstring).is_empty()))
+        .cloned()
+        .collect();
+    
+    if violating.is_empty() {
+        return Ok(updated_docstrings);
+    }
+    
+    if config.verbose {
+        writeln!(out, "{} Regenerating {} docstrings that break the documentation policy", 
+            "DocGen:".blue(),
+            violating.len())?;
+    }
+    
+    let mut policy_config = config.clone();
+    policy_config.extra_instructions.push(config.policy.instructions());
+    let regenerated = llm_client.generate_docstrings(parsed_code, &violating, &policy_config).await?;
+    
+    let mut result = Vec::new();
+    for updated in updated_docstrings {
+        let candidate = if config.policy.violations(&updated.new_docstring).is_empty() {
+            updated
+        } else {
+            regenerated.iter().find(|r| r.item_index == updated.item_index).cloned().unwrap_or(updated)
+        };
+        
+        let violations = config.policy.violations(&candidate.new_docstring);
+        if !violations.is_empty() {
+            let item = &parsed_code.items[candidate.item_index];
+            log::warn!("Skipping {} '{}': docstring still {}", item.item_type, item.name, violations.join(", "));
+            continue;
+        }
+        result.push(candidate);
+    }
+    
+    Ok(result)
+}
+
+/// Regenerate docstrings longer than `max_lines` with an instruction to be more
+/// concise, dropping any that still exceed the limit
+async fn enforce_max_doc_lines(
+    llm_client: &dyn llm::LlmClient,
+    parsed_code: &parser::ParsedCode,
+    issues: &[docstring::DocstringIssue],
+    updated_docstrings: Vec<docstring::UpdatedDocstring>,
+    max_lines: usize,
+    config: &config::Config,
+    out: &mut Vec<u8>,
+) -> Result<Vec<docstring::UpdatedDocstring>> {
+    let overlong: Vec<docstring::DocstringIssue> = issues.iter()
+        .filter(|issue| updated_docstrings.iter()
+            .any(|u| u.item_index == issue.item_index && u.line_count() > max_lines))
+        .cloned()
+        .collect();
+    
+    if overlong.is_empty() {
+        return Ok(updated_docstrings);
+    }
+    
+    if config.verbose {
+        writeln!(out, "{} Regenerating {} docstrings longer than {} lines", 
+            "DocGen:".blue(),
+            overlong.len(),
+            max_lines)?;
+    }
+    
+    let mut concise_config = config.clone();
+    concise_config.extra_instructions.push(format!(
+        "Be more concise: the previous attempt was too long. The documentation MUST NOT exceed {} lines.",
+        max_lines
+    ));
+    let regenerated = llm_client.generate_docstrings(parsed_code, &overlong, &concise_config).await?;
+    
+    let mut result = Vec::new();
+    for updated in updated_docstrings {
+        let candidate = if updated.line_count() > max_lines {
+            regenerated.iter().find(|r| r.item_index == updated.item_index).cloned().unwrap_or(updated)
+        } else {
+            updated
+        };
+        
+        if candidate.line_count() > max_lines {
+            let item = &parsed_code.items[candidate.item_index];
+            log::warn!("Skipping {} '{}': docstring still exceeds {} lines", item.item_type, item.name, max_lines);
+            continue;
+        }
+        result.push(candidate);
+    }
+    
+    Ok(result)
+}
@@ -2,12 +2,24 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum DocGenError {
-    #[error("Failed to parse Python code: {0}")]
+    #[error("Failed to parse code: {0}")]
     ParsingError(String),
     
     #[error("LLM API error: {0}")]
     LlmApiError(String),
     
+    #[error("Invalid API key: {0}")]
+    InvalidApiKey(String),
+    
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+    
+    #[error("Model not found: {0}")]
+    ModelNotFound(String),
+    
+    #[error("Blocked by content filter: {0}")]
+    ContentFiltered(String),
+    
     #[error("File I/O error: {0}")]
     FileError(#[from] std::io::Error),
     
@@ -17,8 +29,39 @@ pub enum DocGenError {
     #[error("Failed to update file content: {0}")]
     UpdateError(String),
     
+    #[error("Lock error: {0}")]
+    LockError(String),
+    
+    #[error("The run's token or cost budget is used up")]
+    BudgetExhausted,
+    
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
 
+impl DocGenError {
+    /// Exit status of a run that fails with this error. Provider errors that need the
+    /// user to do something each have their own, so scripts can tell them apart;
+    /// everything else exits with 2.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            DocGenError::InvalidApiKey(_) => 3,
+            DocGenError::QuotaExceeded(_) => 4,
+            DocGenError::ModelNotFound(_) => 5,
+            DocGenError::ContentFiltered(_) => 6,
+            _ => 2,
+        }
+    }
+    
+    /// Whether the error came from a provider, which another provider might not give
+    pub fn is_provider_error(&self) -> bool {
+        matches!(self,
+            DocGenError::LlmApiError(_)
+            | DocGenError::InvalidApiKey(_)
+            | DocGenError::QuotaExceeded(_)
+            | DocGenError::ModelNotFound(_)
+            | DocGenError::ContentFiltered(_))
+    }
+}
+
 pub type DocGenResult<T> = Result<T, DocGenError>;
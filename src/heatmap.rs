@@ -0,0 +1,231 @@
+use serde::Serialize;
+use std::path::{Component, Path};
+
+use crate::coverage::{escape_html, Counts, FileCoverage};
+
+/// Size of the treemap drawn on the HTML page, in pixels
+const WIDTH: f64 = 1200.0;
+const HEIGHT: f64 = 800.0;
+
+/// Height of the label strip at the top of each directory's box, in pixels
+const LABEL_HEIGHT: f64 = 18.0;
+
+/// A directory or file in the heatmap, sized by its item count and colored by coverage
+#[derive(Serialize)]
+struct Node {
+    name: String,
+    path: String,
+    items: usize,
+    documented: usize,
+    coverage: f64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(name: &str, path: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            path: path.to_string(),
+            items: 0,
+            documented: 0,
+            coverage: 100.0,
+            children: Vec::new(),
+        }
+    }
+    
+    /// Add a file's counts under the path components still to be placed
+    fn add(&mut self, components: &[String], counts: Counts) {
+        self.items += counts.items;
+        self.documented += counts.documented;
+        let (first, rest) = match components.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        
+        let index = match self.children.iter().position(|child| &child.name == first) {
+            Some(index) => index,
+            None => {
+                let path = if self.path.is_empty() { first.clone() } else { format!("{}/{}", self.path, first) };
+                self.children.push(Node::new(first, &path));
+                self.children.len() - 1
+            },
+        };
+        self.children[index].add(rest, counts);
+    }
+    
+    /// Fill in coverage percentages, and order children largest first for the layout
+    fn finish(&mut self) {
+        let counts = Counts { documented: self.documented, items: self.items };
+        self.coverage = (counts.percent() * 10.0).round() / 10.0;
+        self.children.retain(|child| child.items > 0);
+        self.children.sort_by(|a, b| b.items.cmp(&a.items).then_with(|| a.name.cmp(&b.name)));
+        for child in &mut self.children {
+            child.finish();
+        }
+    }
+}
+
+/// Build the tree of directories and files, starting from the deepest directory that
+/// holds all of them
+fn tree(files: &[FileCoverage]) -> Node {
+    let mut root = Node::new(".", "");
+    for file in files {
+        root.add(&components(&file.path), file.total());
+    }
+    while root.children.len() == 1 && !root.children[0].children.is_empty() {
+        root = root.children.remove(0);
+    }
+    root.finish();
+    root
+}
+
+/// Names of the directories and file in a path, leaving out `.` and the root
+fn components(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            Component::ParentDir => Some("..".to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Render the heatmap as a JSON tree: each directory with its item count, coverage,
+/// and children
+pub fn json(files: &[FileCoverage]) -> String {
+    serde_json::to_string_pretty(&tree(files)).unwrap_or_default()
+}
+
+/// A box of the treemap
+#[derive(Clone, Copy)]
+struct Rect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+/// Split a box between sizes, largest first, keeping the pieces as close to square as
+/// possible (the squarified treemap layout)
+fn squarify(sizes: &[f64], rect: Rect) -> Vec<Rect> {
+    let total: f64 = sizes.iter().sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    let scale = rect.w * rect.h / total;
+    let areas: Vec<f64> = sizes.iter().map(|size| size * scale).collect();
+    
+    // Worst aspect ratio of a row of areas laid along a side of the given length
+    let worst = |row: &[f64], side: f64| {
+        let sum: f64 = row.iter().sum();
+        row.iter()
+            .map(|area| (side * side * area / (sum * sum)).max(sum * sum / (side * side * area)))
+            .fold(0.0, f64::max)
+    };
+    
+    let mut rects = Vec::new();
+    let mut free = rect;
+    let mut start = 0;
+    while start < areas.len() {
+        let side = free.w.min(free.h);
+        let mut end = start + 1;
+        while end < areas.len() && worst(&areas[start..=end], side) <= worst(&areas[start..end], side) {
+            end += 1;
+        }
+        
+        // Lay the row along the shorter side of the free space
+        let row_area: f64 = areas[start..end].iter().sum();
+        if free.w >= free.h {
+            let w = row_area / free.h;
+            let mut y = free.y;
+            for area in &areas[start..end] {
+                let h = area / w;
+                rects.push(Rect { x: free.x, y, w, h });
+                y += h;
+            }
+            free.x += w;
+            free.w -= w;
+        } else {
+            let h = row_area / free.w;
+            let mut x = free.x;
+            for area in &areas[start..end] {
+                let w = area / h;
+                rects.push(Rect { x, y: free.y, w, h });
+                x += w;
+            }
+            free.y += h;
+            free.h -= h;
+        }
+        start = end;
+    }
+    rects
+}
+
+/// Color for a coverage percentage, from red (none) through yellow to green (full)
+fn color(coverage: f64) -> String {
+    format!("hsl({:.0}, 65%, 45%)", coverage * 1.2)
+}
+
+/// Add the boxes of a node and of its descendants to the page
+fn render(node: &Node, rect: Rect, html: &mut String) {
+    let title = format!("{}: {} of {} items documented ({:.1}%)",
+        if node.path.is_empty() { "." } else { &node.path },
+        node.documented,
+        node.items,
+        node.coverage);
+    let is_directory = !node.children.is_empty();
+    let label = if rect.w > 40.0 && rect.h > 14.0 { escape_html(&node.name) } else { String::new() };
+    html.push_str(&format!(
+        "<div class=\"{}\" style=\"left:{:.1}px;top:{:.1}px;width:{:.1}px;height:{:.1}px;background:{}\" title=\"{}\">{}</div>\n",
+        if is_directory { "dir" } else { "file" },
+        rect.x,
+        rect.y,
+        rect.w,
+        rect.h,
+        color(node.coverage),
+        escape_html(&title),
+        label));
+    
+    // Children fill the directory's box below its label, when there is room for them
+    let inner = Rect {
+        x: rect.x + 2.0,
+        y: rect.y + LABEL_HEIGHT,
+        w: rect.w - 4.0,
+        h: rect.h - LABEL_HEIGHT - 2.0,
+    };
+    if !is_directory || inner.w < 4.0 || inner.h < 4.0 {
+        return;
+    }
+    let sizes: Vec<f64> = node.children.iter().map(|child| child.items as f64).collect();
+    for (child, child_rect) in node.children.iter().zip(squarify(&sizes, inner)) {
+        render(child, child_rect, html);
+    }
+}
+
+/// Render the heatmap as a standalone HTML page: a treemap where each directory's and
+/// file's area is its item count and its color its coverage
+pub fn html(files: &[FileCoverage]) -> String {
+    let root = tree(files);
+    let mut html = format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>Documentation debt</title>\n<style>\n\
+        body {{ font-family: sans-serif; margin: 2em; }}\n\
+        .map {{ position: relative; width: {}px; height: {}px; }}\n\
+        .map div {{ position: absolute; box-sizing: border-box; overflow: hidden; white-space: nowrap; \
+        font-size: 12px; color: #fff; padding: 1px 3px; }}\n\
+        .dir {{ border: 1px solid #333; }}\n\
+        .file {{ border: 1px solid rgba(255, 255, 255, 0.6); }}\n\
+        </style>\n</head>\n<body>\n",
+        WIDTH,
+        HEIGHT);
+    html.push_str(&format!("<h1>Documentation debt: {:.1}% of {} items documented</h1>\n\
+        <p>Each box is a directory or file, sized by its number of items and colored by coverage, \
+        from red (undocumented) to green (fully documented).</p>\n<div class=\"map\">\n",
+        root.coverage,
+        root.items));
+    if root.items > 0 {
+        render(&root, Rect { x: 0.0, y: 0.0, w: WIDTH, h: HEIGHT }, &mut html);
+    }
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
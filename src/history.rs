@@ -0,0 +1,168 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::docstring::{DocstringIssue, IssueKind};
+use crate::error::DocGenResult;
+use crate::parser::{CodeItem, ParsedCode};
+use crate::rules::{Reason, Rule};
+
+/// Name of the history file kept in the repository root
+const HISTORY_FILE: &str = ".docgen-history.json";
+
+/// What an item looked like the last time its documentation was judged up to date
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct ItemRecord {
+    signature: String,
+    docstring_hash: u64,
+}
+
+/// Signatures of documented items from previous runs, keyed by file then item
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct History {
+    files: BTreeMap<String, BTreeMap<String, ItemRecord>>,
+}
+
+impl History {
+    /// Path of the history file for a repository root
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(HISTORY_FILE)
+    }
+    
+    /// Load the history, starting fresh if the file is missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable history file {}: {}", path.display(), e);
+                History::default()
+            }),
+            Err(_) => History::default(),
+        }
+    }
+    
+    /// Write the history back to disk
+    pub fn save(&self, path: &Path) -> DocGenResult<()> {
+        let content = serde_json::to_string_pretty(self).unwrap_or_default();
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+    
+    /// Flag documented items whose signature changed since their documentation was last
+    /// recorded while the documentation itself stayed the same. These come first in
+    /// `issues` so they are fixed before anything else.
+    pub fn detect_changes(&self, file_key: &str, parsed_code: &ParsedCode, issues: &mut Vec<DocstringIssue>) {
+        let records = match self.files.get(file_key) {
+            Some(records) => records,
+            None => return,
+        };
+        
+        let ignored = parsed_code.ignored_items();
+        for (index, item) in parsed_code.items.iter().enumerate() {
+            if issues.iter().any(|i| i.item_index == index) || ignored[index] {
+                continue;
+            }
+            
+            if let (Some(docstring), Some(record)) = (&item.existing_docstring, records.get(&item_key(item))) {
+                if record.signature != signature(item) && record.docstring_hash == fnv1a(docstring) {
+                    issues.push(DocstringIssue {
+                        item_type: item.item_type,
+                        name: item.name.clone(),
+                        line_number: item.line_number,
+                        issue_type: IssueKind::Changed,
+                        item_index: index,
+                        reasons: vec![Reason::new(Rule::SignatureChanged)],
+                    });
+                }
+            }
+        }
+        
+        issues.sort_by_key(|issue| issue.issue_type != IssueKind::Changed);
+    }
+    
+    /// Record the current signature of every item whose documentation has no issues.
+    /// Items with open issues keep their previous record so they stay flagged until fixed.
+    pub fn record(&mut self, file_key: &str, parsed_code: &ParsedCode, issues: &[DocstringIssue]) {
+        let records = self.files.entry(file_key.to_string()).or_default();
+        
+        for (index, item) in parsed_code.items.iter().enumerate() {
+            if issues.iter().any(|i| i.item_index == index) {
+                continue;
+            }
+            if let Some(docstring) = &item.existing_docstring {
+                records.insert(item_key(item), ItemRecord {
+                    signature: signature(item),
+                    docstring_hash: fnv1a(docstring),
+                });
+            }
+        }
+    }
+    
+    /// Parameters an item had when its documentation was last recorded
+    pub fn previous_parameters(&self, file_key: &str, item: &CodeItem) -> Option<Vec<String>> {
+        let record = self.files.get(file_key)?.get(&item_key(item))?;
+        let (parameters, _) = record.signature.split_once(" | ")?;
+        Some(parameters.split(", ").filter(|p| !p.is_empty()).map(|p| p.to_string()).collect())
+    }
+    
+    /// Items that look renamed since their documentation was last recorded, as old and
+    /// new names: an item without a record whose signature, given back an old name,
+    /// matches exactly one record of an item that is gone from the file
+    pub fn renamed_items(&self, file_key: &str, parsed_code: &ParsedCode) -> Vec<(String, String)> {
+        let records = match self.files.get(file_key) {
+            Some(records) => records,
+            None => return Vec::new(),
+        };
+        let current: Vec<String> = parsed_code.items.iter().map(item_key).collect();
+        
+        let mut renames = Vec::new();
+        for (item, key) in parsed_code.items.iter().zip(&current) {
+            if records.contains_key(key) {
+                continue;
+            }
+            // Records of the same kind of item under the same parent, with another name
+            let prefix = &key[..key.len() - item.name.len()];
+            let name_re = match Regex::new(&format!(r"\b{}\b", regex::escape(&item.name))) {
+                Ok(name_re) => name_re,
+                Err(_) => continue,
+            };
+            let signature = signature(item);
+            let (parameters, declaration) = signature.split_once(" | ").unwrap_or_default();
+            let candidates: Vec<&str> = records.iter()
+                .filter(|(old_key, _)| !current.contains(old_key))
+                .filter_map(|(old_key, record)| {
+                    let old_name = old_key.strip_prefix(prefix).filter(|name| !name.contains('.'))?;
+                    let renamed = format!("{} | {}", parameters, name_re.replace(declaration, regex::NoExpand(old_name)));
+                    (renamed == record.signature).then_some(old_name)
+                })
+                .collect();
+            if let [old_name] = candidates[..] {
+                renames.push((old_name.to_string(), item.name.clone()));
+            }
+        }
+        renames
+    }
+}
+
+/// Key identifying an item within its file
+pub fn item_key(item: &CodeItem) -> String {
+    match &item.parent {
+        Some(parent) => format!("{}:{}.{}", item.item_type, parent, item.name),
+        None => format!("{}:{}", item.item_type, item.name),
+    }
+}
+
+/// Signature of an item as far as its documentation is concerned: its parameters and
+/// its declaration line (which carries return types), ignoring whitespace. Parsed return
+/// types are not used directly because some parsers include source positions in them.
+pub fn signature(item: &CodeItem) -> String {
+    let declaration = item.code.lines().next().unwrap_or("").split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{} | {}", item.parameters.join(", "), declaration)
+}
+
+/// FNV-1a hash, used because it is stable across Rust versions and platforms
+pub fn fnv1a(text: &str) -> u64 {
+    text.trim().bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::{DocGenError, DocGenResult};
+
+/// Command the hooks run: check the staged files, failing only on missing documentation
+const HOOK_COMMAND: &str = "docgen --check --staged --fail-on missing --no-history";
+
+/// Marks a git hook as written by `docgen install-hook`, so it may be replaced
+const HOOK_MARKER: &str = "# Installed by `docgen install-hook`";
+
+/// Hook manifest read by the pre-commit framework
+pub const PRE_COMMIT_HOOKS_FILE: &str = ".pre-commit-hooks.yaml";
+
+/// Write a git pre-commit hook that blocks commits whose staged files have items
+/// without documentation. A hook that `docgen` didn't write is only replaced with
+/// `force`. Returns the path of the hook.
+pub fn install_git_hook(root: &Path, force: bool) -> DocGenResult<PathBuf> {
+    let path = git_hook_path(root);
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        if !existing.contains(HOOK_MARKER) && !force {
+            return Err(DocGenError::ConfigError(format!(
+                "{} already exists; pass --force to replace it", path.display())));
+        }
+    }
+    
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, format!("#!/bin/sh\n{}: block commits that leave staged files without documentation\nexec {}\n",
+        HOOK_MARKER,
+        HOOK_COMMAND))?;
+    
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(path)
+}
+
+/// Add a `docgen` entry to the repository's `.pre-commit-hooks.yaml`, creating it if
+/// needed. Returns the path of the manifest, or None when it already has the entry.
+pub fn install_pre_commit_entry(root: &Path) -> DocGenResult<Option<PathBuf>> {
+    let path = root.join(PRE_COMMIT_HOOKS_FILE);
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    if existing.lines().any(|line| line.trim_start_matches(['-', ' ']) == "id: docgen") {
+        return Ok(None);
+    }
+    
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&format!("- id: docgen\n  \
+        name: docgen\n  \
+        description: Block commits that leave staged files without documentation\n  \
+        entry: {}\n  \
+        language: system\n  \
+        pass_filenames: false\n",
+        HOOK_COMMAND));
+    std::fs::write(&path, content)?;
+    Ok(Some(path))
+}
+
+/// Where git looks for the pre-commit hook, which `core.hooksPath` or a worktree can
+/// move away from `.git/hooks`
+fn git_hook_path(root: &Path) -> PathBuf {
+    let output = Command::new("git")
+        .arg("-C").arg(root)
+        .args(["rev-parse", "--git-path", "hooks/pre-commit"])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+            if path.is_absolute() { path } else { root.join(path) }
+        },
+        _ => root.join(".git").join("hooks").join("pre-commit"),
+    }
+}
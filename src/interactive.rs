@@ -0,0 +1,134 @@
+use colored::Colorize;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::docstring::UpdatedDocstring;
+use crate::error::{DocGenError, DocGenResult};
+use crate::llm;
+use crate::parser::ParsedCode;
+
+/// Set once the user quits, so the files after the current one are left alone
+static QUIT: AtomicBool = AtomicBool::new(false);
+
+/// Whether the user has quit the interactive review
+pub fn quit_requested() -> bool {
+    QUIT.load(Ordering::SeqCst)
+}
+
+/// Fail early when there is no terminal to prompt on
+pub fn ensure_terminal() -> DocGenResult<()> {
+    if std::io::stdin().is_terminal() {
+        Ok(())
+    } else {
+        Err(DocGenError::ConfigError(
+            "--interactive needs a terminal; use --review-file to review changes without one".into()))
+    }
+}
+
+/// Show each proposed docstring and ask whether to apply it, returning the accepted
+/// ones (with any edits made along the way). Output buffered for the file so far is
+/// printed first, so the prompts appear after it.
+pub fn review(
+    file_path: &Path,
+    parsed_code: &ParsedCode,
+    updated_docstrings: Vec<UpdatedDocstring>,
+    out: &mut Vec<u8>,
+) -> DocGenResult<Vec<UpdatedDocstring>> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(out)?;
+    out.clear();
+    
+    let total = updated_docstrings.len();
+    let mut accepted = Vec::new();
+    for (position, mut updated) in updated_docstrings.into_iter().enumerate() {
+        let item = &parsed_code.items[updated.item_index];
+        writeln!(stdout, "\n{} {} {} ({}:{}) [{}/{}]",
+            "Review:".blue().bold(),
+            item.item_type,
+            item.name.bold(),
+            file_path.display(),
+            item.line_number,
+            position + 1,
+            total)?;
+        if let Some(signature) = item.code.lines().next() {
+            writeln!(stdout, "  {}", signature.trim().dimmed())?;
+        }
+        for line in updated.new_docstring.trim().lines() {
+            writeln!(stdout, "  {}", format!("+ {}", line).green())?;
+        }
+        
+        loop {
+            match prompt(&mut stdout)?.as_str() {
+                "a" | "accept" => {
+                    accepted.push(updated);
+                    break;
+                },
+                "r" | "reject" => break,
+                "e" | "edit" => {
+                    let draft = llm::unformat_docstring(&updated.new_docstring, &parsed_code.language).to_string();
+                    match edit(&draft)? {
+                        Some(text) => {
+                            // Keep the whitespace before a closing delimiter, such as the
+                            // indentation of a Python docstring's closing quotes
+                            let text = format!("{}{}", text, &draft[draft.trim_end().len()..]);
+                            updated.new_docstring = llm::format_docstring(&text, item, &parsed_code.language);
+                            accepted.push(updated);
+                            break;
+                        },
+                        // An empty draft is taken as changing one's mind; ask again
+                        None => writeln!(stdout, "  Edited docstring was empty; nothing accepted")?,
+                    }
+                },
+                "s" | "skip" => return Ok(accepted),
+                "q" | "quit" => {
+                    QUIT.store(true, Ordering::SeqCst);
+                    return Ok(accepted);
+                },
+                _ => writeln!(stdout, "  Please answer a, r, e, s, or q")?,
+            }
+        }
+    }
+    
+    Ok(accepted)
+}
+
+/// Ask for a decision; end of input counts as quitting
+fn prompt(stdout: &mut std::io::Stdout) -> DocGenResult<String> {
+    write!(stdout, "{} ", "[a]ccept / [r]eject / [e]dit / [s]kip file / [q]uit?".yellow())?;
+    stdout.flush()?;
+    
+    let mut answer = String::new();
+    if std::io::stdin().lock().read_line(&mut answer)? == 0 {
+        return Ok("q".to_string());
+    }
+    Ok(answer.trim().to_lowercase())
+}
+
+/// Open the draft in `$VISUAL` or `$EDITOR` (falling back to vi) and return the edited
+/// text, or None if it was emptied
+fn edit(draft: &str) -> DocGenResult<Option<String>> {
+    let path = std::env::temp_dir().join(format!("docgen-draft-{}.txt", std::process::id()));
+    std::fs::write(&path, format!("{}\n", draft.trim_end()))?;
+    
+    // Editors are often configured with arguments, e.g. "code --wait"
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .map_err(|e| DocGenError::ConfigError(format!("Could not start editor '{}': {}", editor, e)))?;
+    
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    if !status.success() {
+        return Err(DocGenError::ConfigError(format!("Editor '{}' exited with {}", editor, status)));
+    }
+    
+    let text = edited?.trim_end().to_string();
+    Ok(if text.trim().is_empty() { None } else { Some(text) })
+}
@@ -0,0 +1,119 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::DocGenResult;
+
+/// The byte order mark some editors put at the start of UTF-8 files
+const BOM: char = '\u{feff}';
+
+/// How a text file is laid out apart from its content: whether it starts with a byte
+/// order mark, ends its lines with CRLF, and ends with a line ending
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextFormat {
+    pub bom: bool,
+    pub crlf: bool,
+    pub final_newline: bool,
+}
+
+impl TextFormat {
+    /// Format of some text. Line endings are judged by the first line, as edits are.
+    pub fn detect(content: &str) -> Self {
+        Self {
+            bom: content.starts_with(BOM),
+            crlf: content.find('\n').is_some_and(|end| content[..end].ends_with('\r')),
+            final_newline: content.ends_with('\n'),
+        }
+    }
+    
+    /// Format of the file at a path, or None if there is no file to match yet
+    pub fn of_file(path: &Path) -> DocGenResult<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(Some(Self::detect(&content))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+    
+    /// Lay text out in this format. Text for a CRLF file that has no CRLF line endings
+    /// left, having been put back together from its lines, gets them back; text that
+    /// still has some keeps its line endings as they are, mixed or not.
+    pub fn apply(&self, content: &str) -> String {
+        let content = strip_bom(content);
+        let mut text = if self.crlf && !content.contains("\r\n") {
+            content.replace('\n', "\r\n")
+        } else {
+            content.to_string()
+        };
+        
+        let newline = if self.crlf { "\r\n" } else { "\n" };
+        if self.final_newline && !text.is_empty() && !text.ends_with('\n') {
+            text.push_str(newline);
+        } else if !self.final_newline {
+            let trimmed = text.strip_suffix('\n').map(|t| t.strip_suffix('\r').unwrap_or(t));
+            if let Some(trimmed) = trimmed {
+                text.truncate(trimmed.len());
+            }
+        }
+        
+        if self.bom {
+            text.insert(0, BOM);
+        }
+        text
+    }
+}
+
+/// Text without the byte order mark it may start with
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix(BOM).unwrap_or(content)
+}
+
+/// Read a source file as text, without its byte order mark, which parsers and edits
+/// at the start of the file would otherwise treat as content
+pub fn read_text(path: &Path) -> DocGenResult<String> {
+    let content = std::fs::read_to_string(path)?;
+    match content.strip_prefix(BOM) {
+        Some(stripped) => Ok(stripped.to_string()),
+        None => Ok(content),
+    }
+}
+
+/// Write updated text over a file in the format it already has, keeping its byte
+/// order mark, line endings, and final newline. Returns the text as written.
+pub fn write_text(path: &Path, content: &str) -> DocGenResult<String> {
+    let text = match TextFormat::of_file(path)? {
+        Some(format) => format.apply(content),
+        None => content.to_string(),
+    };
+    write_atomic(path, text.as_bytes())?;
+    Ok(text)
+}
+
+/// Write a file by writing a temporary file beside it and renaming it into place, so
+/// an interrupted write leaves either the old contents or the new ones, never a
+/// truncated file. The file keeps its permissions, and a symlink keeps pointing at
+/// the file it did, which is the one written.
+pub fn write_atomic(path: &Path, content: &[u8]) -> DocGenResult<()> {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let temp_path = temp_path(&path);
+    
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        if let Ok(metadata) = std::fs::metadata(&path) {
+            std::fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        std::fs::rename(&temp_path, &path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    Ok(result?)
+}
+
+/// Temporary file in the same directory as a file, so renaming it over the file
+/// doesn't cross filesystems
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!(".{}.docgen-{}.tmp", name, std::process::id()))
+}
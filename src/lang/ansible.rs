@@ -0,0 +1,330 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+use std::path::Path;
+
+/// Ansible parser implementation. Tasks and handlers are documented through their
+/// `name:` field, and a role's `tasks/main.yml` through a `#` header comment.
+pub struct AnsibleParser {
+    role: Option<String>,
+    list_key_re: Regex,
+    play_re: Regex,
+}
+
+/// Task keywords that are not the module a task runs
+const TASK_KEYWORDS: &[&str] = &[
+    "name", "when", "register", "become", "become_user", "become_method", "tags", "vars",
+    "notify", "loop", "loop_control", "ignore_errors", "changed_when", "failed_when",
+    "delegate_to", "run_once", "environment", "no_log", "retries", "delay", "until",
+    "check_mode", "diff", "any_errors_fatal", "args", "listen", "rescue", "always",
+];
+
+/// Keys whose value is a list of tasks
+const TASK_LISTS: &[&str] = &["tasks", "pre_tasks", "post_tasks", "handlers", "block", "rescue", "always"];
+
+impl Default for AnsibleParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnsibleParser {
+    pub fn new() -> Self {
+        Self {
+            role: None,
+            list_key_re: Regex::new(r"^(?:-\s+)?([a-z_]+):\s*$").unwrap(),
+            play_re: Regex::new(r"^(?:-\s+|\s{2})hosts:").unwrap(),
+        }
+    }
+    
+    /// Document the file as the task list of a role, adding a header for the role
+    pub fn with_role(mut self, role: Option<String>) -> Self {
+        self.role = role;
+        self
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Whether a line carries no YAML content
+    fn is_blank(&self, line: &str) -> bool {
+        let code = line.trim();
+        code.is_empty() || code.starts_with('#')
+    }
+    
+    /// Line indices of every task list item, at any nesting depth. In a playbook,
+    /// tasks are listed under `tasks:`, `handlers:`, and the like; in a task file the
+    /// top-level list items are tasks. Items under `block:` are tasks as well.
+    fn task_starts(&self, lines: &[&str]) -> Vec<usize> {
+        let is_playbook = lines.iter().any(|l| self.play_re.is_match(l));
+        let mut starts = Vec::new();
+        
+        if !is_playbook {
+            starts.extend(lines.iter().enumerate()
+                .filter(|(_, l)| l.starts_with("- ") || **l == "-")
+                .map(|(i, _)| i));
+        }
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            let is_list_key = self.list_key_re.captures(line.trim())
+                .is_some_and(|caps| TASK_LISTS.contains(&&caps[1]));
+            if !is_list_key {
+                continue;
+            }
+            let key_indent = line.len() - line.trim_start().len() + if line.trim_start().starts_with('-') { 2 } else { 0 };
+            
+            // The list's items sit at the indentation of its first item
+            let mut item_indent = None;
+            for (i, item_line) in lines.iter().enumerate().skip(line_index + 1) {
+                if self.is_blank(item_line) {
+                    continue;
+                }
+                let indent = item_line.len() - item_line.trim_start().len();
+                let is_item = item_line.trim_start().starts_with('-');
+                match item_indent {
+                    None if is_item && indent >= key_indent => item_indent = Some(indent),
+                    None => break,
+                    Some(expected) if indent < expected || (indent == expected && !is_item) => break,
+                    Some(_) => {},
+                }
+                if Some(indent) == item_indent && is_item {
+                    starts.push(i);
+                }
+            }
+        }
+        
+        starts.sort();
+        starts.dedup();
+        starts
+    }
+    
+    /// Last line of the list item starting at `line_index`
+    fn item_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let indent = lines[line_index].len() - lines[line_index].trim_start().len();
+        let mut end = line_index;
+        for (i, line) in lines.iter().enumerate().skip(line_index + 1) {
+            if self.is_blank(line) {
+                continue;
+            }
+            if line.len() - line.trim_start().len() <= indent {
+                break;
+            }
+            end = i;
+        }
+        end
+    }
+    
+    /// The task's own keys, with the line each one is on. Keys are at the column
+    /// of the first key after the `- `.
+    fn task_keys(&self, lines: &[&str], start: usize, end: usize) -> Vec<(String, usize)> {
+        let first = lines[start];
+        let dash = first.len() - first.trim_start().len();
+        let key_column = dash + 1 + first[dash + 1..].len() - first[dash + 1..].trim_start().len();
+        
+        (start..=end)
+            .filter_map(|i| {
+                let line = lines[i];
+                let key_text = if i == start {
+                    &line[key_column.min(line.len())..]
+                } else if line.len() - line.trim_start().len() == key_column {
+                    line.trim_start()
+                } else {
+                    return None;
+                };
+                let key = key_text.split(':').next()?.trim();
+                if key.is_empty() || !key_text.contains(':') || !key.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+                    return None;
+                }
+                Some((key.to_string(), i))
+            })
+            .collect()
+    }
+    
+    /// Where a role's header comment is: the range of lines it occupies (empty when
+    /// there is none) and its text. It sits directly above the first line of YAML
+    /// content, or above the `---` document start.
+    fn role_header(&self, lines: &[&str]) -> (usize, usize, Option<String>) {
+        let anchor = lines.iter()
+            .position(|l| !self.is_blank(l) && l.trim() != "---")
+            .unwrap_or(lines.len());
+        if let Some((start, text)) = self.leading_comment(lines, anchor) {
+            return (start, anchor, Some(text));
+        }
+        if anchor > 0 && lines[anchor - 1].trim() == "---" {
+            if let Some((start, text)) = self.leading_comment(lines, anchor - 1) {
+                return (start, anchor - 1, Some(text));
+            }
+        }
+        (anchor, anchor, None)
+    }
+    
+    /// Collect the `#` comment block directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with('#') {
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| l.trim().trim_start_matches('#').trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+}
+
+impl LanguageParser for AnsibleParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // The role is the whole file, documented by its header comment
+        if let Some(role) = &self.role {
+            let (start, end, header) = self.role_header(&lines);
+            code_items.push(CodeItem {
+                item_type: ItemKind::Role,
+                name: role.clone(),
+                line_number: 1,
+                code: content.to_string(),
+                existing_docstring: header,
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: String::new(),
+                span: 0..content.len(),
+                docstring_span: (start < end).then(|| offsets.start(start)..offsets.start(end)),
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        for start in self.task_starts(&lines) {
+            let end = self.item_end(&lines, start);
+            let keys = self.task_keys(&lines, start, end);
+            
+            // A task is named after its module when it has no name of its own; the
+            // module's parameters in the task's code are the context for naming it
+            let module = keys.iter()
+                .map(|(key, _)| key.as_str())
+                .find(|key| !TASK_KEYWORDS.contains(key))
+                .unwrap_or("task")
+                .to_string();
+            let name_line = keys.iter().find(|(key, _)| key == "name").map(|(_, i)| *i);
+            let existing_name = name_line
+                .map(|i| lines[i].split_once("name:").map_or("", |(_, name)| name).trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+                .filter(|name| !name.is_empty());
+            
+            // The `name:` key and its value, up to the end of the line
+            let name_span = name_line.map(|i| {
+                let line_start = offsets.start(i);
+                line_start + lines[i].find("name:").unwrap_or(0)..line_start + lines[i].len()
+            });
+            
+            code_items.push(CodeItem {
+                item_type: if module == "block" { ItemKind::Block } else { ItemKind::Task },
+                name: module,
+                line_number: start + 1,
+                code: lines[start..=end].join("\n"),
+                existing_docstring: existing_name,
+                parent: self.role.clone(),
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: self.extract_indentation(lines[start]),
+                span: offsets.lines(start, end),
+                docstring_span: name_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "ansible".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            if item.item_type == ItemKind::Role {
+                let header: String = update.new_docstring
+                    .trim()
+                    .lines()
+                    .map(|line| if line.trim().is_empty() { "#\n".to_string() } else { format!("# {}\n", line.trim_end()) })
+                    .collect();
+                let range = item.docstring_span.clone().unwrap_or_else(|| {
+                    let at = offsets.start(self.role_header(&lines).0);
+                    at..at
+                });
+                edits.push(Edit::new(range, header));
+                continue;
+            }
+            
+            // Task names are a single line
+            let name = update.new_docstring.split_whitespace().collect::<Vec<_>>().join(" ");
+            let name = super::yaml_scalar(name.trim_end_matches('.'));
+            
+            match &item.docstring_span {
+                Some(span) => edits.push(Edit::new(span.clone(), format!("name: {}", name))),
+                None => {
+                    // `- module: ...` becomes `- name: ...` followed by the module line
+                    let start = item.line_number - 1;
+                    let first = lines[start];
+                    let dash = first.len() - first.trim_start().len();
+                    let rest = first[dash + 1..].trim_start();
+                    let key_indent = " ".repeat(first.len() - rest.len());
+                    let mut text = format!("{}name: {}", &first[..first.len() - rest.len()], name);
+                    if !rest.is_empty() {
+                        text.push_str(&format!("\n{}{}", key_indent, rest));
+                    }
+                    let line_start = offsets.start(start);
+                    edits.push(Edit::new(line_start..line_start + first.len(), text));
+                },
+            }
+        }
+        
+        apply_edits(content, edits)
+    }
+}
+
+/// Name of the role a task file belongs to, for the entry point `roles/<role>/tasks/main.yml`
+pub fn role_name(file_path: &Path) -> Option<String> {
+    let stem = file_path.file_stem()?.to_str()?;
+    let tasks_dir = file_path.parent()?;
+    let role_dir = tasks_dir.parent()?;
+    let is_entry_point = stem == "main" && tasks_dir.file_name()? == "tasks";
+    let in_roles = role_dir.parent().and_then(|p| p.file_name()).is_some_and(|n| n == "roles");
+    if is_entry_point && in_roles {
+        role_dir.file_name()?.to_str().map(|name| name.to_string())
+    } else {
+        None
+    }
+}
@@ -0,0 +1,226 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Line of `#` that opens and closes a function header comment
+const BANNER: &str = "#######################################";
+
+/// Shell script parser implementation for function definitions (`name() {` and
+/// `function name {`), documented with header comment blocks that list the globals,
+/// arguments, outputs, and return status of each function
+pub struct BashParser {
+    function_re: Regex,
+}
+
+impl Default for BashParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BashParser {
+    pub fn new() -> Self {
+        Self {
+            function_re: Regex::new(
+                r"^(?:function\s+(?P<keyword_name>[A-Za-z_][\w:.-]*)(?:\s*\(\s*\))?|(?P<name>[A-Za-z_][\w:.-]*)\s*\(\s*\))\s*(?P<rest>.*)$"
+            ).unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Collect the block of `#` comments directly above a line, without the banner
+    /// lines around a header or a script's `#!` line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 {
+            let previous = lines[start - 1].trim_start();
+            if !previous.starts_with('#') || previous.starts_with("#!") {
+                break;
+            }
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| l.trim())
+            .filter(|l| !l.chars().all(|c| c == '#'))
+            .map(|l| {
+                let l = l.trim_start_matches('#');
+                l.strip_prefix(' ').unwrap_or(l).trim_end()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the line index of the closing brace of a function body, or the line
+    /// itself for a body on one line or in parentheses
+    fn body_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut depth = 0i32;
+        let mut opened = false;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            opened |= line.contains('{');
+            depth += line.matches('{').count() as i32;
+            depth -= line.matches('}').count() as i32;
+            if opened && depth <= 0 {
+                return i;
+            }
+            // A body has to open on the definition's line or the one after it
+            if !opened && i > line_index {
+                return line_index;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+}
+
+impl LanguageParser for BashParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Functions defined inside another function's body are skipped
+        let mut inside_until: Option<usize> = None;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            if inside_until.is_some_and(|end| line_index <= end) {
+                continue;
+            }
+            
+            let caps = match self.function_re.captures(line.trim()) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let rest = caps["rest"].trim();
+            if !(rest.is_empty() || rest.starts_with('{') || rest.starts_with('(') || rest.starts_with('#')) {
+                continue;
+            }
+            let name = caps.name("keyword_name").or_else(|| caps.name("name")).map_or("", |m| m.as_str()).to_string();
+            
+            let end_line = self.body_end(&lines, line_index);
+            inside_until = Some(end_line);
+            
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, line_index)
+                .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: ItemKind::Function,
+                // Functions named with a leading underscore are internal by convention
+                visibility: if name.starts_with('_') { Visibility::Private } else { Visibility::Public },
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end_line].join("\n"),
+                existing_docstring,
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end_line),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "bash".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            // Section entries keep their own indentation under the `#`
+            let mut comment = format!("{}{}\n", item.indentation, BANNER);
+            for line in update.new_docstring.trim().lines() {
+                let trimmed = line.trim_end();
+                if trimmed.is_empty() {
+                    comment.push_str(&format!("{}#\n", item.indentation));
+                } else {
+                    comment.push_str(&format!("{}# {}\n", item.indentation, trimmed));
+                }
+            }
+            comment.push_str(&format!("{}{}\n", item.indentation, BANNER));
+            
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
+
+/// Positional arguments a function body reads, in order: `$1`, `$2`, ..., then `$@`
+/// or `$*` if it takes all of them
+pub fn positional_arguments(code: &str) -> Vec<String> {
+    let positional_re = Regex::new(r"\$\{?([1-9][0-9]*|@|\*)").unwrap();
+    let mut numbered: Vec<usize> = Vec::new();
+    let mut all = None;
+    for caps in positional_re.captures_iter(code) {
+        match caps[1].parse::<usize>() {
+            Ok(n) if !numbered.contains(&n) => numbered.push(n),
+            Ok(_) => {},
+            Err(_) => all = all.or(Some(format!("${}", &caps[1]))),
+        }
+    }
+    numbered.sort_unstable();
+    numbered.into_iter().map(|n| format!("${}", n)).chain(all).collect()
+}
+
+/// Global variables a function body reads or assigns: upper-case names that it
+/// doesn't declare `local`
+pub fn globals(code: &str) -> Vec<String> {
+    let local_re = Regex::new(r"\b(?:local|declare|typeset)\s+(?:-\w+\s+)*([A-Za-z_]\w*)").unwrap();
+    let variable_re = Regex::new(r"\$\{?([A-Z_][A-Z0-9_]*)\b|(?m)^\s*(?:export\s+)?([A-Z_][A-Z0-9_]*)=").unwrap();
+    let locals: Vec<&str> = local_re.captures_iter(code).map(|caps| caps.get(1).map_or("", |m| m.as_str())).collect();
+    
+    let mut names: Vec<String> = Vec::new();
+    for caps in variable_re.captures_iter(code) {
+        let name = caps.get(1).or_else(|| caps.get(2)).map_or("", |m| m.as_str());
+        if name != "_" && !locals.contains(&name) && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Prompt context for a shell function: the positional arguments and globals its body
+/// uses, which its header lists
+pub fn function_context(code: &str) -> String {
+    let mut context = String::new();
+    let arguments: Vec<String> = positional_arguments(code).iter().map(|a| format!("`{}`", a)).collect();
+    if !arguments.is_empty() {
+        context.push_str(&format!("\n\nIt reads the positional arguments {}.", arguments.join(", ")));
+    }
+    let globals: Vec<String> = globals(code).iter().map(|g| format!("`{}`", g)).collect();
+    if !globals.is_empty() {
+        context.push_str(&format!("\n\nIt uses the global variables {}.", globals.join(", ")));
+    }
+    context
+}
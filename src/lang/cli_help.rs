@@ -0,0 +1,315 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Parser for Python command-line argument definitions (argparse and click)
+/// that are missing a `help=` string
+pub struct PythonCliHelpParser {
+    call_re: Regex,
+    help_re: Regex,
+    flag_re: Regex,
+}
+
+impl Default for PythonCliHelpParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PythonCliHelpParser {
+    pub fn new() -> Self {
+        Self {
+            call_re: Regex::new(r"\.add_argument\(|@click\.option\(").unwrap(),
+            help_re: Regex::new(r"\bhelp\s*=").unwrap(),
+            flag_re: Regex::new(r#"["']([^"']+)["']"#).unwrap(),
+        }
+    }
+    
+    /// Find the byte offset of the parenthesis closing the call opened at `open`
+    fn find_closing_paren(&self, content: &str, open: usize) -> Option<usize> {
+        let mut depth = 0;
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        
+        for (offset, c) in content[open..].char_indices() {
+            if let Some(q) = quote {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+                continue;
+            }
+            
+            match c {
+                '"' | '\'' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(open + offset);
+                    }
+                },
+                _ => {}
+            }
+        }
+        None
+    }
+    
+    /// Locate every argument definition as (call start, closing paren) byte offsets
+    fn find_calls(&self, content: &str) -> Vec<(usize, usize)> {
+        self.call_re.find_iter(content)
+            .filter_map(|m| {
+                let open = m.end() - 1;
+                self.find_closing_paren(content, open).map(|close| (m.start(), close))
+            })
+            .collect()
+    }
+    
+    /// Escape text for use inside a double-quoted Python string
+    fn python_string(&self, text: &str) -> String {
+        let single_line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        format!("\"{}\"", single_line.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+impl LanguageParser for PythonCliHelpParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let mut code_items = Vec::new();
+        
+        for (start, close) in self.find_calls(content) {
+            let call = &content[start..=close];
+            let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+            let line_number = content[..start].matches('\n').count() + 1;
+            
+            let name = self.flag_re.captures(call)
+                .map(|caps| caps[1].to_string())
+                .unwrap_or_else(|| "argument".to_string());
+            
+            code_items.push(CodeItem {
+                item_type: ItemKind::CliArgument,
+                name,
+                line_number,
+                code: content[line_start..=close].to_string(),
+                existing_docstring: if self.help_re.is_match(call) { Some(call.to_string()) } else { None },
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: content[line_start..start].chars().take_while(|c| c.is_whitespace()).collect(),
+                span: line_start..close + 1,
+                docstring_span: if self.help_re.is_match(call) { Some(start..close + 1) } else { None },
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "cli".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let calls = self.find_calls(content);
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let (start, close) = *calls.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            if self.help_re.is_match(&content[start..=close]) {
+                // Help text written by hand is never replaced
+                continue;
+            }
+            
+            let help = format!("help={}", self.python_string(&update.new_docstring));
+            let before_close = &content[start..close];
+            let line_start = content[..close].rfind('\n').map_or(0, |i| i + 1);
+            
+            if content[line_start..close].trim().is_empty() {
+                // Closing paren sits on its own line: add a new argument line above it
+                let last_arg = before_close.trim_end();
+                let arg_line_start = content[..start + last_arg.len()].rfind('\n').map_or(0, |i| i + 1);
+                let arg_indent: String = content[arg_line_start..].chars().take_while(|c| c.is_whitespace()).collect();
+                let separator = if last_arg.ends_with(',') || last_arg.ends_with('(') { "" } else { "," };
+                let at = start + last_arg.len();
+                edits.push(Edit::new(at..at, format!("{}\n{}{},", separator, arg_indent, help)));
+            } else {
+                let trimmed = before_close.trim_end();
+                let insertion = if trimmed.ends_with('(') {
+                    help
+                } else if trimmed.ends_with(',') {
+                    format!(" {}", help)
+                } else {
+                    format!(", {}", help)
+                };
+                let at = start + trimmed.len();
+                edits.push(Edit::new(at..at, insertion));
+            }
+        }
+        
+        apply_edits(content, edits)
+    }
+}
+
+/// Parser for clap derive fields (`#[arg(...)]` / `#[clap(...)]`) that have
+/// no doc comment, which clap would otherwise use as the help text
+pub struct RustCliHelpParser {
+    field_re: Regex,
+}
+
+impl Default for RustCliHelpParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RustCliHelpParser {
+    pub fn new() -> Self {
+        Self {
+            field_re: Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?(\w+)\s*:[^:]").unwrap(),
+        }
+    }
+    
+    /// Check whether a line starts a clap argument attribute
+    fn is_arg_attribute(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        trimmed.starts_with("#[arg") || trimmed.starts_with("#[clap")
+    }
+    
+    /// Find clap-annotated fields as (first attribute line, field line) indices
+    fn find_fields(&self, lines: &[&str]) -> Vec<(usize, usize)> {
+        let mut fields = Vec::new();
+        let mut i = 0;
+        
+        while i < lines.len() {
+            if !self.is_arg_attribute(lines[i]) {
+                i += 1;
+                continue;
+            }
+            
+            // Walk back over any attributes stacked above this one
+            let mut attr_start = i;
+            while attr_start > 0 && lines[attr_start - 1].trim().starts_with("#[") {
+                attr_start -= 1;
+            }
+            
+            // Walk forward to the field declaration; attributes on the struct itself are skipped
+            let mut field = i;
+            let mut is_container = false;
+            while field < lines.len() && !self.field_re.is_match(lines[field]) {
+                let trimmed = lines[field].trim_start();
+                if trimmed.contains("struct ") || trimmed.contains("enum ") {
+                    is_container = true;
+                    break;
+                }
+                field += 1;
+            }
+            
+            if field < lines.len() && !is_container {
+                fields.push((attr_start, field));
+            }
+            i = field + 1;
+        }
+        
+        fields
+    }
+    
+    /// Collect the `///` doc comment directly above the attribute block
+    fn doc_comment(&self, lines: &[&str], attr_start: usize) -> Option<(usize, String)> {
+        let mut start = attr_start;
+        while start > 0 && lines[start - 1].trim().starts_with("///") {
+            start -= 1;
+        }
+        
+        if start == attr_start {
+            return None;
+        }
+        
+        let text = lines[start..attr_start]
+            .iter()
+            .map(|l| l.trim().trim_start_matches("///").trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some((start, text))
+    }
+}
+
+impl LanguageParser for RustCliHelpParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        for (attr_start, field) in self.find_fields(&lines) {
+            let attributes = lines[attr_start..field].join("\n");
+            let name = self.field_re.captures(lines[field])
+                .map(|caps| caps[1].to_string())
+                .unwrap_or_else(|| "argument".to_string());
+            
+            // An explicit `help = "..."` counts as documentation too, though a doc
+            // comment written for the field goes above the attributes holding it
+            let (docstring_span, docstring) = match self.doc_comment(&lines, attr_start) {
+                Some((start, text)) => (Some(offsets.lines(start, attr_start - 1)), Some(text)),
+                None => (None, if attributes.contains("help") { Some(attributes.clone()) } else { None }),
+            };
+            
+            code_items.push(CodeItem {
+                item_type: ItemKind::CliArgument,
+                name,
+                line_number: attr_start + 1,
+                code: lines[attr_start..=field].join("\n"),
+                existing_docstring: docstring,
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: lines[attr_start].chars().take_while(|c| c.is_whitespace()).collect(),
+                span: offsets.lines(attr_start, field),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "cli".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let doc_comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .map(|line| format!("{}\n", format!("{}/// {}", item.indentation, line.trim()).trim_end()))
+                .collect();
+            
+            edits.push(Edit::new(item.doc_range(), doc_comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,486 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use serde::Deserialize;
+use tree_sitter::{Language, Node, Parser};
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// How new Doxygen comments are written (`cpp_comment_style` in docsherpa.toml);
+/// existing comments keep the style they were written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStyle {
+    /// `/** ... */` blocks with a ` * ` gutter
+    #[default]
+    Block,
+    /// `///` lines
+    Line,
+}
+
+/// C and C++ parser implementation for free functions, classes, structs, enums, and
+/// methods (including those defined out of line as `Class::method`), documented with
+/// Doxygen comments. Both languages are parsed with the C++ grammar.
+pub struct CppParser {
+    language: Language,
+    /// Name reported for parsed files: "c" or "cpp"
+    name: &'static str,
+    comment_style: CommentStyle,
+}
+
+/// Where a declaration is: the types it's nested in, whether it's local to the file
+/// (in an anonymous namespace or a private type), and whether the members declared
+/// at this point of a class are private
+#[derive(Clone, Default)]
+struct Scope {
+    types: Vec<String>,
+    local: bool,
+    private: bool,
+}
+
+/// The items found so far, and the functions among them declared without a body, as
+/// (parent, name, parameter count), so that their definitions later in the file
+/// aren't reported a second time
+#[derive(Default)]
+struct Collected {
+    items: Vec<CodeItem>,
+    declared: HashSet<(Option<String>, String, usize)>,
+}
+
+impl Default for CppParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CppParser {
+    /// Parser for C++ source and headers
+    pub fn new() -> Self {
+        Self::with_name("cpp")
+    }
+    
+    /// Parser for C source and headers
+    pub fn c() -> Self {
+        Self::with_name("c")
+    }
+    
+    fn with_name(name: &'static str) -> Self {
+        // The grammar is compiled and linked by the tree-sitter-cpp crate
+        let language = tree_sitter_cpp::language();
+        Parser::new().set_language(language).expect("Failed to load C++ grammar");
+        Self { language, name, comment_style: CommentStyle::default() }
+    }
+    
+    /// Write new comments as `/** */` blocks or `///` lines
+    pub fn with_comment_style(mut self, comment_style: CommentStyle) -> Self {
+        self.comment_style = comment_style;
+        self
+    }
+    
+    /// Extract a substring from the source based on a byte range
+    fn get_node_text<'a>(&self, source: &'a str, range: Range<usize>) -> &'a str {
+        &source[range.start..range.end]
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// The node documentation goes above: the `template <...>` declaration, `extern "C"`
+    /// specification, `typedef`, or declaration wrapping a definition, or the
+    /// definition itself
+    fn anchor<'t>(&self, node: Node<'t>) -> Node<'t> {
+        match node.parent() {
+            Some(parent) if matches!(
+                parent.kind(),
+                "template_declaration" | "linkage_specification" | "declaration" | "field_declaration" | "type_definition"
+            ) => self.anchor(parent),
+            _ => node,
+        }
+    }
+    
+    /// A name without the template arguments of its own or any qualifier:
+    /// `Foo<T>::Bar` is `Foo::Bar`
+    fn strip_template_arguments(&self, name: &str) -> String {
+        let mut stripped = String::new();
+        let mut depth = 0;
+        for c in name.chars() {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ if depth == 0 && !c.is_whitespace() => stripped.push(c),
+                _ => {}
+            }
+        }
+        stripped
+    }
+    
+    /// Collect the Doxygen comment (`/** */`, `/*! */`, `///`, or `//!`) directly above
+    /// a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 {
+            return None;
+        }
+        
+        let mut start = line_index;
+        if lines[line_index - 1].trim().ends_with("*/") {
+            start = line_index - 1;
+            while start > 0 && !lines[start].contains("/*") {
+                start -= 1;
+            }
+            let opening = lines[start].trim_start();
+            if !opening.starts_with("/**") && !opening.starts_with("/*!") {
+                return None;
+            }
+        } else {
+            while start > 0 && ["///", "//!"].iter().any(|p| lines[start - 1].trim_start().starts_with(p)) {
+                start -= 1;
+            }
+            if start == line_index {
+                return None;
+            }
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("///")
+                    .trim_start_matches("//!")
+                    .trim_start_matches("/**")
+                    .trim_start_matches("/*!")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Name of one parameter, without its type, default value, or array bounds; None
+    /// for unnamed parameters, `void`, and `...`. A function pointer names itself
+    /// inside its first parentheses: `int (*cb)(int)`.
+    fn parameter_name(&self, param: Node, content: &str) -> Option<String> {
+        let mut declarator = param.child_by_field_name("declarator")?;
+        loop {
+            match declarator.kind() {
+                "identifier" => return Some(self.get_node_text(content, declarator.byte_range()).to_string()),
+                kind if kind.starts_with("abstract_") => return None,
+                _ => declarator = declarator.child_by_field_name("declarator").or_else(|| declarator.named_child(0))?,
+            }
+        }
+    }
+    
+    /// Build a code item for a declaration, documented above its anchor. Declarations
+    /// that don't start their own line, like the members of a class written on one
+    /// line, are left to the documentation of what they're declared in.
+    fn code_item(
+        &self,
+        node: Node,
+        item_type: ItemKind,
+        name: String,
+        lines: &[&str],
+        offsets: &LineOffsets,
+    ) -> Option<CodeItem> {
+        let anchor = self.anchor(node).start_position();
+        if !lines[anchor.row].get(..anchor.column).is_some_and(|before| before.trim().is_empty()) {
+            return None;
+        }
+        
+        let line_index = node.start_position().row;
+        let end_line = node.end_position().row;
+        let (docstring_span, existing_docstring) = self.leading_comment(lines, anchor.row)
+            .map(|(start, text)| (offsets.lines(start, anchor.row - 1), text))
+            .unzip();
+        
+        Some(CodeItem {
+            item_type,
+            name,
+            line_number: line_index + 1,
+            code: lines[line_index..=end_line].join("\n"),
+            existing_docstring,
+            parent: None,
+            parameters: Vec::new(),
+            parameter_details: Vec::new(),
+            returns: None,
+            indentation: self.extract_indentation(lines[line_index]),
+            span: offsets.lines(anchor.row, end_line),
+            docstring_span,
+            is_async: false,
+            is_generator: false,
+            decorators: Vec::new(),
+            visibility: Visibility::Public,
+            throws: Vec::new(),
+        })
+    }
+    
+    /// Collect documentable items from a list of declarations: a file, a namespace or
+    /// `extern "C"` body, a class body, or a preprocessor conditional
+    fn collect_items(&self, node: Node, scope: &Scope, content: &str, lines: &[&str], offsets: &LineOffsets, found: &mut Collected) {
+        let mut scope = scope.clone();
+        let mut cursor = node.walk();
+        
+        for child in node.named_children(&mut cursor) {
+            // `public:`, `protected:`, and `private:` change the access of the members below
+            if child.kind() == "access_specifier" {
+                scope.private = self.get_node_text(content, child.byte_range()) == "private";
+            } else {
+                self.collect_declaration(child, &scope, content, lines, offsets, found);
+            }
+        }
+    }
+    
+    /// Collect the items a declaration defines. Function bodies are not descended
+    /// into, so local classes and lambdas are skipped.
+    fn collect_declaration(&self, node: Node, scope: &Scope, content: &str, lines: &[&str], offsets: &LineOffsets, found: &mut Collected) {
+        match node.kind() {
+            "namespace_definition" => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    // Everything in an anonymous namespace is local to the file
+                    let anonymous = node.child_by_field_name("name").is_none();
+                    let scope = Scope { local: scope.local || anonymous, ..scope.clone() };
+                    self.collect_items(body, &scope, content, lines, offsets, found);
+                }
+            },
+            // An `extern "C"` block is transparent, like a namespace
+            "linkage_specification" => match node.child_by_field_name("body") {
+                Some(body) if body.kind() == "declaration_list" => self.collect_items(body, scope, content, lines, offsets, found),
+                Some(body) => self.collect_declaration(body, scope, content, lines, offsets, found),
+                None => {},
+            },
+            "template_declaration" | "preproc_if" | "preproc_ifdef" | "preproc_else" | "preproc_elif" => {
+                self.collect_items(node, scope, content, lines, offsets, found);
+            },
+            "class_specifier" | "struct_specifier" | "union_specifier" | "enum_specifier" => {
+                self.type_item(node, scope, content, lines, offsets, found);
+            },
+            "declaration" | "field_declaration" | "type_definition" => {
+                if let Some(specifier) = node.child_by_field_name("type") {
+                    self.collect_declaration(specifier, scope, content, lines, offsets, found);
+                }
+                if node.kind() != "type_definition" {
+                    self.function_item(node, scope, content, lines, offsets, found);
+                }
+            },
+            "function_definition" => self.function_item(node, scope, content, lines, offsets, found),
+            _ => {}
+        }
+    }
+    
+    /// Collect a class, struct, union, or enum definition, and the members of all but
+    /// the enum. Forward declarations aren't definitions; an anonymous struct is named
+    /// by the `typedef` it's declared in.
+    fn type_item(&self, node: Node, scope: &Scope, content: &str, lines: &[&str], offsets: &LineOffsets, found: &mut Collected) {
+        let Some(body) = node.child_by_field_name("body") else {
+            return;
+        };
+        let name_node = node.child_by_field_name("name").or_else(|| {
+            node.parent()
+                .filter(|parent| parent.kind() == "type_definition")
+                .and_then(|parent| parent.child_by_field_name("declarator"))
+        });
+        let Some(name_node) = name_node else {
+            return;
+        };
+        let name = self.get_node_text(content, name_node.byte_range());
+        let name = self.strip_template_arguments(name.rsplit("::").next().unwrap_or(name));
+        
+        let item_type = match node.kind() {
+            "class_specifier" => ItemKind::Class,
+            "enum_specifier" => ItemKind::Enum,
+            _ => ItemKind::Struct,
+        };
+        let private = scope.local || scope.private;
+        let parent = if scope.types.is_empty() { None } else { Some(scope.types.join("::")) };
+        if let Some(item) = self.code_item(node, item_type, name.clone(), lines, offsets) {
+            found.items.push(CodeItem {
+                parent,
+                visibility: if private { Visibility::Private } else { Visibility::Public },
+                ..item
+            });
+        }
+        
+        if item_type != ItemKind::Enum {
+            // Members of a class are private until a `public:`; those of a struct aren't
+            let mut types = scope.types.clone();
+            types.push(name);
+            let scope = Scope { types, local: private, private: item_type == ItemKind::Class };
+            self.collect_items(body, &scope, content, lines, offsets, found);
+        }
+    }
+    
+    /// Collect a function, method, or constructor from its definition or prototype.
+    /// Deleted and defaulted functions need no documentation, and declarations without
+    /// a return type that aren't constructors or destructors are macros.
+    fn function_item(&self, node: Node, scope: &Scope, content: &str, lines: &[&str], offsets: &LineOffsets, found: &mut Collected) {
+        // The function's declarator, under those of a returned pointer or reference
+        let Some(mut declarator) = node.child_by_field_name("declarator") else {
+            return;
+        };
+        let mut return_suffix = String::new();
+        while matches!(declarator.kind(), "pointer_declarator" | "reference_declarator") {
+            return_suffix.push_str(if declarator.kind() == "pointer_declarator" { "*" } else { "&" });
+            match declarator.child_by_field_name("declarator").or_else(|| declarator.named_child(0)) {
+                Some(inner) => declarator = inner,
+                None => return,
+            }
+        }
+        if declarator.kind() != "function_declarator" {
+            return;
+        }
+        let mut cursor = node.walk();
+        if node.children(&mut cursor).any(|child| matches!(child.kind(), "default_method_clause" | "delete_method_clause")) {
+            return;
+        }
+        
+        // The name, after any `Class::` qualifier; a function pointer variable has none
+        let Some(qualified) = declarator.child_by_field_name("declarator") else {
+            return;
+        };
+        let mut name_node = qualified;
+        while name_node.kind() == "qualified_identifier" {
+            match name_node.child_by_field_name("name") {
+                Some(inner) => name_node = inner,
+                None => return,
+            }
+        }
+        if !matches!(name_node.kind(), "identifier" | "field_identifier" | "destructor_name" | "operator_name" | "template_function") {
+            return;
+        }
+        let name = self.get_node_text(content, name_node.child_by_field_name("name").unwrap_or(name_node).byte_range()).to_string();
+        let qualifier = Some(self.get_node_text(content, qualified.start_byte()..name_node.start_byte()))
+            .map(|qualifier| self.strip_template_arguments(qualifier.trim_end().trim_end_matches("::")))
+            .filter(|qualifier| !qualifier.is_empty());
+        
+        // Only constructors and destructors, named after their class, have no return type
+        let class_name = qualifier.as_deref()
+            .map(|q| q.rsplit("::").next().unwrap_or(q))
+            .or(scope.types.last().map(String::as_str));
+        let constructor = class_name == Some(name.trim_start_matches('~'));
+        let return_type = match node.child_by_field_name("type") {
+            Some(node) => self.get_node_text(content, node.byte_range()).to_string() + &return_suffix,
+            None if constructor => String::new(),
+            None => return,
+        };
+        let trailing = declarator.named_children(&mut declarator.walk())
+            .find(|child| child.kind() == "trailing_return_type")
+            .and_then(|child| child.named_child(0))
+            .map(|node| self.get_node_text(content, node.byte_range()).to_string());
+        let return_type = match trailing {
+            Some(trailing) if return_type == "auto" => trailing,
+            _ => return_type,
+        };
+        let returns = Some(return_type).filter(|t| !t.is_empty() && t != "void");
+        
+        let parameters: Vec<String> = declarator.child_by_field_name("parameters")
+            .map(|list| {
+                let mut cursor = list.walk();
+                list.named_children(&mut cursor)
+                    .filter_map(|param| self.parameter_name(param, content))
+                    .collect()
+            })
+            .unwrap_or_default();
+        
+        let item_type = if constructor && !name.starts_with('~') {
+            ItemKind::Constructor
+        } else if qualifier.is_some() || !scope.types.is_empty() {
+            ItemKind::Method
+        } else {
+            ItemKind::Function
+        };
+        let parent = qualifier.clone().or_else(|| {
+            if scope.types.is_empty() { None } else { Some(scope.types.join("::")) }
+        });
+        
+        // A `static` free function is local to its file
+        let mut cursor = node.walk();
+        let is_static = node.children(&mut cursor)
+            .any(|child| child.kind() == "storage_class_specifier" && self.get_node_text(content, child.byte_range()) == "static");
+        let private = scope.local || scope.private || (is_static && scope.types.is_empty() && qualifier.is_none());
+        
+        let key = (parent.as_deref().map(|p| p.rsplit("::").next().unwrap_or(p).to_string()), name.clone(), parameters.len());
+        if node.kind() != "function_definition" {
+            found.declared.insert(key);
+        } else if found.declared.contains(&key) {
+            return;
+        }
+        
+        if let Some(item) = self.code_item(node, item_type, name, lines, offsets) {
+            found.items.push(CodeItem {
+                parent,
+                parameters,
+                returns,
+                visibility: if private { Visibility::Private } else { Visibility::Public },
+                ..item
+            });
+        }
+    }
+}
+
+impl LanguageParser for CppParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        // Parse the code using tree-sitter
+        // Since Parser doesn't implement Clone, we create a new one each time
+        let mut parser = Parser::new();
+        parser.set_language(self.language).expect("Failed to load C++ grammar");
+        
+        let tree = parser.parse(content, None)
+            .ok_or_else(|| DocGenError::ParsingError("Failed to parse C++ code".into()))?;
+        
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut found = Collected::default();
+        self.collect_items(tree.root_node(), &Scope::default(), content, &lines, &offsets, &mut found);
+        
+        Ok(ParsedCode {
+            items: found.items,
+            original_content: content.to_string(),
+            language: self.name.to_string(),
+            syntax_errors: super::syntax_errors(tree.root_node()),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            // An existing comment is rewritten in the style it was written in
+            let style = match &item.docstring_span {
+                Some(span) if content[span.clone()].trim_start().starts_with("//") => CommentStyle::Line,
+                Some(_) => CommentStyle::Block,
+                None => self.comment_style,
+            };
+            
+            let mut comment = String::new();
+            if style == CommentStyle::Block {
+                comment.push_str(&format!("{}/**\n", item.indentation));
+            }
+            for line in update.new_docstring.trim().lines() {
+                let trimmed = line.trim();
+                let gutter = if style == CommentStyle::Block { " *" } else { "///" };
+                if trimmed.is_empty() {
+                    comment.push_str(&format!("{}{}\n", item.indentation, gutter));
+                } else {
+                    comment.push_str(&format!("{}{} {}\n", item.indentation, gutter, trimmed));
+                }
+            }
+            if style == CommentStyle::Block {
+                comment.push_str(&format!("{} */\n", item.indentation));
+            }
+            
+            // The comment goes above any `template <...>` line of the declaration
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,362 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// C# parser implementation for classes, structs, interfaces, enums, records,
+/// methods, constructors, and properties, documented with `///` XML doc comments
+pub struct CSharpParser {
+    type_re: Regex,
+    method_re: Regex,
+    property_re: Regex,
+}
+
+impl Default for CSharpParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CSharpParser {
+    pub fn new() -> Self {
+        Self {
+            type_re: Regex::new(
+                r"^(?P<mods>(?:(?:public|private|protected|internal|static|sealed|abstract|partial|readonly|unsafe|new|file|ref)\s+)*)(?P<kind>record\s+(?:class|struct)|class|struct|interface|enum|record)\s+(?P<name>[A-Za-z_]\w*)"
+            ).unwrap(),
+            method_re: Regex::new(
+                r"^(?P<mods>(?:(?:public|private|protected|internal|static|virtual|override|abstract|sealed|async|extern|unsafe|new|partial|readonly)\s+)*)(?:(?P<type>[\w.]+(?:<[^()]*>)?(?:\[,*\])*\??)\s+)?(?P<name>[A-Za-z_][\w.]*)(?:<[^()]*>)?\s*\("
+            ).unwrap(),
+            property_re: Regex::new(
+                r"^(?P<mods>(?:(?:public|private|protected|internal|static|virtual|override|abstract|sealed|new|required|readonly|unsafe)\s+)*)(?P<type>[\w.]+(?:<[^()]*>)?(?:\[,*\])*\??)\s+(?P<name>[A-Za-z_][\w.]*)\s*(?:\{|=>|$)"
+            ).unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Index of the first line of the attributes (`[HttpGet]`, `[Obsolete]`) directly
+    /// above a declaration, or the declaration itself
+    fn attribute_start(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with('[') {
+            start -= 1;
+        }
+        start
+    }
+    
+    /// Collect the `///` comment block directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with("///") {
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| l.trim().trim_start_matches("///").trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the last line of a signature, which may span several lines while its
+    /// parameter list is open
+    fn signature_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            depth += code.matches('(').count() as i32;
+            depth -= code.matches(')').count() as i32;
+            if depth <= 0 {
+                return i;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+    
+    /// Find the line index of the closing brace of the body opened at or after a
+    /// declaration, which may be on a line of its own below it, or None if it has no
+    /// braced body
+    fn body_end(&self, lines: &[&str], signature_end: usize, line_index: usize) -> Option<usize> {
+        let opens_here = lines[line_index..=signature_end].iter()
+            .any(|l| l.split("//").next().unwrap_or("").contains('{'));
+        let opens_next = lines.get(signature_end + 1).is_some_and(|l| l.trim_start().starts_with('{'));
+        if !opens_here && !opens_next {
+            return None;
+        }
+        
+        let mut depth = 0i32;
+        let mut opened = false;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            opened |= code.contains('{');
+            depth += code.matches('{').count() as i32;
+            depth -= code.matches('}').count() as i32;
+            if opened && depth <= 0 {
+                return Some(i);
+            }
+        }
+        Some(lines.len().saturating_sub(1))
+    }
+    
+    /// Last line of an expression-bodied member (`=> ...;`), which may continue on
+    /// the lines below it
+    fn statement_end(&self, lines: &[&str], line_index: usize) -> usize {
+        (line_index..lines.len())
+            .find(|&i| lines[i].split("//").next().unwrap_or("").trim_end().ends_with(';'))
+            .unwrap_or(line_index)
+    }
+    
+    /// Split a signature (starting at its opening parenthesis) into parameter names
+    /// and whatever follows the parameter list
+    fn parse_parameters<'a>(&self, rest: &'a str) -> (Vec<String>, &'a str) {
+        let mut parameters = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 1;
+        
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' | '[' | '<' | '{' => depth += 1,
+                ')' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    return (parameters, rest[i + 1..].trim());
+                },
+                ')' | ']' | '>' | '}' => depth -= 1,
+                ',' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    start = i + 1;
+                },
+                _ => {}
+            }
+        }
+        (parameters, "")
+    }
+    
+    /// Name of one parameter, without attributes, modifiers (`ref`, `out`,
+    /// `params`, `this`), type, or default value
+    fn parameter_name(&self, param: &str) -> Option<String> {
+        let declaration = param.split('=').next().unwrap_or("").trim();
+        declaration.split_whitespace()
+            .last()
+            .map(|name| name.trim_start_matches('@').to_string())
+            .filter(|name| !name.is_empty())
+    }
+    
+    /// Visibility of a declaration. Members without an access modifier are private,
+    /// except in interfaces and explicit interface implementations (`IFoo.Bar`); types
+    /// without one are internal to the assembly, and counted as public.
+    fn visibility(&self, modifiers: &str, name: &str, member_of: Option<&str>) -> Visibility {
+        let modifiers: Vec<&str> = modifiers.split_whitespace().collect();
+        if modifiers.contains(&"private") {
+            return Visibility::Private;
+        }
+        let accessible = modifiers.iter().any(|m| matches!(*m, "public" | "protected" | "internal"));
+        match member_of {
+            Some(kind) if kind != "interface" && !accessible && !name.contains('.') => Visibility::Private,
+            _ => Visibility::Public,
+        }
+    }
+}
+
+/// Words that can look like a return type or member name in statements such as
+/// `return Foo(x)` or `await Bar()`
+const KEYWORDS: &[&str] = &[
+    "return", "new", "else", "throw", "await", "yield", "case", "if", "for", "foreach", "while", "switch",
+    "catch", "using", "lock", "fixed", "namespace", "event", "delegate", "operator", "get", "set", "init",
+];
+
+impl LanguageParser for CSharpParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Stack of enclosing bodies: (kind, name, closing line index)
+        let mut scopes: Vec<(String, String, usize)> = Vec::new();
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            while scopes.last().is_some_and(|(_, _, end)| *end < line_index) {
+                scopes.pop();
+            }
+            
+            // Declarations inside a member's body (local functions) are skipped
+            let member_of = scopes.last().map(|(kind, _, _)| kind.as_str());
+            if member_of.is_some_and(|kind| matches!(kind, "method" | "constructor" | "property")) {
+                continue;
+            }
+            
+            let code = line.split("//").next().unwrap_or("").trim();
+            let mut is_async = false;
+            let (kind, name, modifiers, parameters, returns, end_line, body_end) = if let Some(caps) = self.type_re.captures(code) {
+                let kind = caps["kind"].split_whitespace().next().unwrap_or_default().to_string();
+                let name = caps["name"].to_string();
+                
+                // A record's positional parameters come right after its name
+                let (signature_end, parameters) = match code[caps.get(0).map_or(0, |m| m.end())..].trim_start() {
+                    rest if kind == "record" && rest.starts_with('(') => {
+                        let signature_end = self.signature_end(&lines, line_index);
+                        let signature = lines[line_index..=signature_end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+                        let open = signature.find(&format!("{}(", name)).map_or(0, |p| p + name.len());
+                        (signature_end, self.parse_parameters(&signature[open..]).0)
+                    },
+                    _ => (line_index, Vec::new()),
+                };
+                let body_end = self.body_end(&lines, signature_end, line_index);
+                (kind, name, caps["mods"].to_string(), parameters, None, body_end.unwrap_or(signature_end), body_end)
+            } else if member_of.is_none() {
+                // Members are only declared inside a type
+                continue;
+            } else if let Some(caps) = self.method_re.captures(code) {
+                let name = caps["name"].to_string();
+                let return_type = caps.name("type").map(|t| t.as_str().to_string());
+                if KEYWORDS.contains(&name.as_str()) || return_type.as_deref().is_some_and(|t| KEYWORDS.contains(&t)) {
+                    continue;
+                }
+                
+                // Only a constructor, named after its type, has no return type
+                let constructor = return_type.is_none() && scopes.last().is_some_and(|(_, type_name, _)| *type_name == name);
+                if return_type.is_none() && !constructor {
+                    continue;
+                }
+                
+                let signature_end = self.signature_end(&lines, line_index);
+                let signature = lines[line_index..=signature_end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+                let open = caps.get(0).map_or(0, |m| m.end() - 1);
+                let (parameters, after) = self.parse_parameters(&signature[open..]);
+                
+                // A declaration continues with a body, `=>`, generic constraints, or a
+                // constructor initializer; only abstract, extern, partial, and interface
+                // members end with `;`. Anything else is a call.
+                let after = after.split("//").next().unwrap_or("").trim();
+                let modifiers = caps["mods"].to_string();
+                let bodiless = member_of == Some("interface")
+                    || modifiers.split_whitespace().any(|m| matches!(m, "abstract" | "extern" | "partial"));
+                let declared = after.is_empty() || after.starts_with('{') || after.starts_with("=>")
+                    || after.starts_with("where ") || (constructor && after.starts_with(':'))
+                    || (bodiless && after.starts_with(';'));
+                if !declared {
+                    continue;
+                }
+                
+                is_async = modifiers.split_whitespace().any(|m| m == "async");
+                let body_end = self.body_end(&lines, signature_end, line_index);
+                let end_line = match body_end {
+                    Some(end) => end,
+                    None if after.contains("=>") => self.statement_end(&lines, signature_end),
+                    None => signature_end,
+                };
+                
+                // An awaited Task returns nothing; a Task<T> returns its T
+                let returns = return_type.filter(|t| !matches!(t.as_str(), "void" | "Task" | "ValueTask"));
+                let kind = if constructor { "constructor" } else { "method" };
+                (kind.to_string(), name, modifiers, parameters, returns, end_line, body_end)
+            } else if let Some(caps) = self.property_re.captures(code) {
+                let name = caps["name"].to_string();
+                if KEYWORDS.contains(&name.as_str()) || KEYWORDS.contains(&&caps["type"]) {
+                    continue;
+                }
+                // A property's accessors may open on the line below its name
+                let accessors_below = lines.get(line_index + 1).is_some_and(|l| l.trim_start().starts_with('{'));
+                if caps[0].ends_with(|c: char| c.is_alphanumeric() || c == '_') && !accessors_below {
+                    continue;
+                }
+                
+                let body_end = self.body_end(&lines, line_index, line_index);
+                let end_line = match body_end {
+                    Some(end) => end,
+                    None if caps[0].ends_with("=>") => self.statement_end(&lines, line_index),
+                    None => line_index,
+                };
+                ("property".to_string(), name, caps["mods"].to_string(), Vec::new(), None, end_line, body_end)
+            } else {
+                continue;
+            };
+            
+            let parent_path: Vec<&str> = scopes.iter().map(|(_, name, _)| name.as_str()).collect();
+            let parent = if parent_path.is_empty() { None } else { Some(parent_path.join(".")) };
+            let visibility = self.visibility(&modifiers, &name, member_of);
+            
+            if let Some(end) = body_end {
+                scopes.push((kind.clone(), name.clone(), end));
+            }
+            
+            let doc_anchor = self.attribute_start(&lines, line_index);
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, doc_anchor)
+                .map(|(start, text)| (offsets.lines(start, doc_anchor - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: kind.parse::<ItemKind>().expect("declaration patterns only match item kinds"),
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end_line].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(doc_anchor, end_line),
+                docstring_span,
+                is_async,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "csharp".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            // Plain text from the model becomes the summary element
+            let docstring = update.new_docstring.trim();
+            let docstring = if docstring.starts_with('<') {
+                docstring.to_string()
+            } else {
+                format!("<summary>\n{}\n</summary>", docstring)
+            };
+            
+            // The comment takes the indentation of the declaration, which is nested one
+            // level per enclosing namespace and type block
+            let comment: String = docstring
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim_end();
+                    if trimmed.trim().is_empty() {
+                        format!("{}///\n", item.indentation)
+                    } else {
+                        format!("{}/// {}\n", item.indentation, trimmed)
+                    }
+                })
+                .collect();
+            
+            // Comments go above any attributes on the declaration
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,260 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Container configuration parser implementation for Dockerfile build stages, build
+/// arguments, and exposed ports, and for docker-compose services, documented with
+/// short `#` comments for infrastructure reviewers
+pub struct DockerParser {
+    language: &'static str,
+    instruction_re: Regex,
+    directive_re: Regex,
+}
+
+impl Default for DockerParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DockerParser {
+    /// Parser for Dockerfiles
+    pub fn new() -> Self {
+        Self::with_language("dockerfile")
+    }
+    
+    /// Parser for docker-compose files
+    pub fn compose() -> Self {
+        Self::with_language("compose")
+    }
+    
+    fn with_language(language: &'static str) -> Self {
+        Self {
+            language,
+            instruction_re: Regex::new(r"(?i)^(FROM|ARG|EXPOSE)\s+(.+)$").unwrap(),
+            directive_re: Regex::new(r"(?i)^#\s*(syntax|escape|check)\s*=").unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Collect the `#` comment block directly above a line. Parser directives such as
+    /// `# syntax=docker/dockerfile:1` are not documentation.
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 {
+            let previous = lines[start - 1].trim_start();
+            if !previous.starts_with('#') || self.directive_re.is_match(previous) {
+                break;
+            }
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| l.trim().trim_start_matches('#').trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Last line of an instruction continued with trailing backslashes
+    fn continuation_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut end = line_index;
+        while end + 1 < lines.len() && lines[end].trim_end().ends_with('\\') {
+            end += 1;
+        }
+        end
+    }
+    
+    /// Dockerfile stages, build arguments, and exposed ports
+    fn parse_dockerfile(&self, lines: &[&str], offsets: &LineOffsets) -> Vec<CodeItem> {
+        let mut code_items = Vec::new();
+        
+        // Line indices of the FROM instructions, so a stage's code runs to the next one
+        let stage_starts: Vec<usize> = lines.iter()
+            .enumerate()
+            .filter(|(_, l)| l.trim_start().to_uppercase().starts_with("FROM "))
+            .map(|(i, _)| i)
+            .collect();
+        let mut stage: Option<String> = None;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            let caps = match self.instruction_re.captures(line.trim()) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let instruction = caps[1].to_uppercase();
+            let arguments = caps[2].trim_end_matches('\\').trim();
+            let words: Vec<&str> = arguments.split_whitespace()
+                .filter(|w| !w.starts_with("--"))
+                .collect();
+            
+            // Comments are kept to a line or two, so items have no parameters that the
+            // documentation is expected to mention
+            let (item_type, name, parent, end) = match instruction.as_str() {
+                "FROM" => {
+                    // A stage is named by its `AS` alias, or else by its base image
+                    let image = words.first().copied().unwrap_or("").to_string();
+                    let name = match words.iter().position(|w| w.eq_ignore_ascii_case("as")) {
+                        Some(position) => words.get(position + 1).map_or(image.clone(), |w| w.to_string()),
+                        None => image.clone(),
+                    };
+                    stage = Some(name.clone());
+                    let end = stage_starts.iter()
+                        .find(|&&start| start > line_index)
+                        .map_or(lines.len() - 1, |&next| next - 1);
+                    (ItemKind::Stage, name, None, end)
+                },
+                "ARG" => {
+                    let name = words.first().map_or("", |w| w.split('=').next().unwrap_or(w)).to_string();
+                    (ItemKind::Arg, name, stage.clone(), self.continuation_end(lines, line_index))
+                },
+                _ => (ItemKind::Expose, words.join(" "), stage.clone(), self.continuation_end(lines, line_index)),
+            };
+            
+            let (docstring_span, existing_docstring) = self.leading_comment(lines, line_index)
+                .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type,
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end].join("\n").trim_end().to_string(),
+                existing_docstring,
+                parent,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        code_items
+    }
+    
+    /// Services under a compose file's top-level `services:` key
+    fn parse_compose(&self, lines: &[&str], offsets: &LineOffsets) -> Vec<CodeItem> {
+        let mut code_items = Vec::new();
+        let services = match lines.iter().position(|l| l.trim_end() == "services:") {
+            Some(services) => services,
+            None => return code_items,
+        };
+        
+        // Services are the keys at the indentation of the first entry under `services:`
+        let mut service_indent: Option<usize> = None;
+        for line_index in services + 1..lines.len() {
+            let line = lines[line_index];
+            let code = line.trim();
+            if code.is_empty() || code.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - line.trim_start().len();
+            if indent == 0 {
+                break;
+            }
+            if *service_indent.get_or_insert(indent) != indent || !code.ends_with(':') {
+                continue;
+            }
+            
+            // The service's block runs until the next line at its indentation or less
+            let end = (line_index + 1..lines.len())
+                .take_while(|&i| {
+                    let l = lines[i];
+                    l.trim().is_empty() || l.trim_start().starts_with('#') || l.len() - l.trim_start().len() > indent
+                })
+                .last()
+                .unwrap_or(line_index);
+            
+            let (docstring_span, existing_docstring) = self.leading_comment(lines, line_index)
+                .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: ItemKind::Service,
+                name: code.trim_end_matches(':').trim_matches('"').to_string(),
+                line_number: line_index + 1,
+                code: lines[line_index..=end].join("\n").trim_end().to_string(),
+                existing_docstring,
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        code_items
+    }
+}
+
+impl LanguageParser for DockerParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let items = if self.language == "compose" {
+            self.parse_compose(&lines, &offsets)
+        } else {
+            self.parse_dockerfile(&lines, &offsets)
+        };
+        
+        Ok(ParsedCode {
+            items,
+            original_content: content.to_string(),
+            language: self.language.to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim();
+                    if trimmed.is_empty() {
+                        format!("{}#\n", item.indentation)
+                    } else {
+                        format!("{}# {}\n", item.indentation, trimmed)
+                    }
+                })
+                .collect();
+            
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,266 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Fortran parser implementation for subroutines and functions in free-form and
+/// fixed-form (FORTRAN 77) source, documented with Doxygen `!>` header comments
+pub struct FortranParser {
+    procedure_re: Regex,
+    end_re: Regex,
+    module_re: Regex,
+    end_module_re: Regex,
+    result_re: Regex,
+}
+
+impl Default for FortranParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FortranParser {
+    pub fn new() -> Self {
+        Self {
+            procedure_re: Regex::new(
+                r"(?i)^(?:(?:pure|impure|elemental|recursive|module)\s+)*((?:integer|real|double\s+precision|complex|logical|character|type\s*\([^)]*\))(?:\s*\([^)]*\)|\s*\*\s*\d+)?\s+)?(?:(?:pure|impure|elemental|recursive)\s+)*(subroutine|function)\s+(\w+)"
+            ).unwrap(),
+            end_re: Regex::new(r"(?i)^end\s*(?:(?:subroutine|function)\b.*)?$").unwrap(),
+            module_re: Regex::new(r"(?i)^module\s+(\w+)\s*$").unwrap(),
+            end_module_re: Regex::new(r"(?i)^end\s*module\b").unwrap(),
+            result_re: Regex::new(r"(?i)\bresult\s*\(\s*(\w+)\s*\)").unwrap(),
+        }
+    }
+    
+    /// Whether the source is fixed-form, detected from comment markers in column 1
+    fn is_fixed_form(&self, lines: &[&str]) -> bool {
+        lines.iter().any(|line| {
+            let mut chars = line.chars();
+            matches!(chars.next(), Some('c') | Some('C') | Some('*'))
+                && chars.next().is_none_or(|c| c.is_whitespace() || c == '-' || c == '=' || c == '*')
+        })
+    }
+    
+    /// Whether a line is a comment in the given source form
+    fn is_comment(&self, line: &str, fixed_form: bool) -> bool {
+        if fixed_form && matches!(line.chars().next(), Some('c') | Some('C') | Some('*')) {
+            return true;
+        }
+        line.trim_start().starts_with('!')
+    }
+    
+    /// Statement text of a line, without trailing comments or fixed-form columns 1-6
+    fn statement<'a>(&self, line: &'a str, fixed_form: bool) -> &'a str {
+        let code = if fixed_form { line.get(6..).unwrap_or("") } else { line };
+        code.split('!').next().unwrap_or("").trim()
+    }
+    
+    /// Find the last line of a statement that continues with `&` (free form) or a
+    /// mark in column 6 of the following lines (fixed form)
+    fn statement_end(&self, lines: &[&str], line_index: usize, fixed_form: bool) -> usize {
+        let mut end = line_index;
+        if fixed_form {
+            while end + 1 < lines.len()
+                && !self.is_comment(lines[end + 1], true)
+                && lines[end + 1].chars().nth(5).is_some_and(|c| !c.is_whitespace() && c != '0')
+            {
+                end += 1;
+            }
+        } else {
+            while end + 1 < lines.len() && self.statement(lines[end], false).ends_with('&') {
+                end += 1;
+            }
+        }
+        end
+    }
+    
+    /// Find the line index of the `end` statement closing a procedure, skipping
+    /// procedures contained in it
+    fn procedure_end(&self, lines: &[&str], line_index: usize, fixed_form: bool) -> usize {
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            if self.is_comment(line, fixed_form) {
+                continue;
+            }
+            let code = self.statement(line, fixed_form);
+            if self.procedure_re.is_match(code) {
+                depth += 1;
+            } else if self.end_re.is_match(code) {
+                depth -= 1;
+                if depth <= 0 {
+                    return i;
+                }
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+    
+    /// Collect the comment block directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize, fixed_form: bool) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 && self.is_comment(lines[start - 1], fixed_form) {
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                let l = l.trim();
+                let l = if fixed_form { l.trim_start_matches(['c', 'C', '*']) } else { l };
+                l.trim_start_matches('!').trim_start_matches('>').trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Dummy argument names from a procedure statement
+    fn parse_arguments(&self, statement: &str, name_end: usize) -> Vec<String> {
+        let rest = &statement[name_end..];
+        let rest = rest.trim_start();
+        if !rest.starts_with('(') {
+            return Vec::new();
+        }
+        rest[1..].split(')').next().unwrap_or("")
+            .split(',')
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty() && a != "*")
+            .collect()
+    }
+}
+
+impl LanguageParser for FortranParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let fixed_form = self.is_fixed_form(&lines);
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        let mut module: Option<String> = None;
+        let mut in_interface = false;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            if self.is_comment(line, fixed_form) {
+                continue;
+            }
+            let code = self.statement(line, fixed_form);
+            let lower = code.to_lowercase();
+            
+            // Procedures declared in interface blocks are documented where they are defined
+            if lower.starts_with("interface") || lower.starts_with("abstract interface") {
+                in_interface = true;
+                continue;
+            }
+            if lower.starts_with("end interface") || lower.starts_with("endinterface") {
+                in_interface = false;
+                continue;
+            }
+            if let Some(caps) = self.module_re.captures(code) {
+                module = Some(caps[1].to_string());
+                continue;
+            }
+            if self.end_module_re.is_match(code) {
+                module = None;
+                continue;
+            }
+            if in_interface {
+                continue;
+            }
+            
+            let statement_end = self.statement_end(&lines, line_index, fixed_form);
+            let statement = lines[line_index..=statement_end]
+                .iter()
+                .map(|l| self.statement(l, fixed_form).trim_end_matches('&').trim_start_matches('&').trim())
+                .collect::<Vec<_>>()
+                .join(" ");
+            
+            let caps = match self.procedure_re.captures(&statement) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let kind = caps[2].to_lowercase();
+            let name = caps[3].to_string();
+            let parameters = self.parse_arguments(&statement, caps.get(3).map_or(0, |m| m.end()));
+            
+            // A function returns its declared type, or else its result variable
+            let returns = if kind == "function" {
+                caps.get(1)
+                    .map(|t| t.as_str().trim().to_string())
+                    .or_else(|| self.result_re.captures(&statement).map(|r| r[1].to_string()))
+                    .or_else(|| Some(name.clone()))
+            } else {
+                None
+            };
+            
+            let end = self.procedure_end(&lines, line_index, fixed_form);
+            
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, line_index, fixed_form)
+                .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: kind.parse().expect("procedure pattern only matches item kinds"),
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end].join("\n"),
+                existing_docstring,
+                parent: module.clone(),
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: if fixed_form { String::new() } else { line.chars().take_while(|c| c.is_whitespace()).collect() },
+                span: offsets.lines(line_index, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "fortran".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let fixed_form = self.is_fixed_form(&content.lines().collect::<Vec<_>>());
+        
+        // Fixed-form comments need their marker in column 1
+        let marker = if fixed_form { "C>" } else { "!>" };
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim_end();
+                    if trimmed.trim().is_empty() {
+                        format!("{}{}\n", item.indentation, marker)
+                    } else {
+                        format!("{}{} {}\n", item.indentation, marker, trimmed)
+                    }
+                })
+                .collect();
+            
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,299 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+use std::ops::Range;
+
+/// GitHub Actions parser implementation for workflows (`.github/workflows/*.yml`) and
+/// actions (`action.yml`). Workflows get a `#` header comment summarizing their
+/// triggers and jobs; inputs, outputs, and secrets get a `description` field, as
+/// does an action without one.
+pub struct GitHubActionsParser {
+    key_re: Regex,
+}
+
+/// Maps whose entries are documented with a `description` field, by path from the root
+const DESCRIBED_SECTIONS: &[&[&str]] = &[
+    &["inputs"],
+    &["outputs"],
+    &["on", "workflow_call", "inputs"],
+    &["on", "workflow_call", "outputs"],
+    &["on", "workflow_call", "secrets"],
+    &["on", "workflow_dispatch", "inputs"],
+];
+
+/// One `key:` line of the YAML document
+struct Key {
+    line_index: usize,
+    indent: usize,
+    key: String,
+    value: String,
+    /// Keys of the maps enclosing this one, from the root
+    path: Vec<String>,
+}
+
+impl Default for GitHubActionsParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitHubActionsParser {
+    pub fn new() -> Self {
+        Self {
+            key_re: Regex::new(r#"^(\s*)("[^"]+"|'[^']+'|[\w.\-]+):(?:\s+(.*))?$"#).unwrap(),
+        }
+    }
+    
+    /// Whether a line carries no YAML content
+    fn is_blank(&self, line: &str) -> bool {
+        let code = line.trim();
+        code.is_empty() || code.starts_with('#')
+    }
+    
+    /// Every mapping key in the document with its path. Keys inside lists (such as a
+    /// job's steps) are left out; nothing documented lives there.
+    fn keys(&self, lines: &[&str]) -> Vec<Key> {
+        let mut keys = Vec::new();
+        let mut parents: Vec<(usize, String)> = Vec::new();
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            if self.is_blank(line) {
+                continue;
+            }
+            let indent = line.len() - line.trim_start().len();
+            while parents.last().is_some_and(|(parent_indent, _)| *parent_indent >= indent) {
+                parents.pop();
+            }
+            let caps = match self.key_re.captures(line) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let key = caps[2].trim_matches(|c| c == '"' || c == '\'').to_string();
+            let value = caps.get(3).map_or("", |v| v.as_str().trim()).to_string();
+            let path = parents.iter().map(|(_, k)| k.clone()).collect();
+            
+            if value.is_empty() {
+                parents.push((indent, key.clone()));
+            }
+            keys.push(Key { line_index, indent, key, value, path });
+        }
+        
+        keys
+    }
+    
+    /// Where a workflow's header comment is: the range of lines it occupies (empty
+    /// when there is none) and its text. It sits directly above the first line of
+    /// YAML content, or above the `---` document start.
+    fn header(&self, lines: &[&str]) -> (usize, usize, Option<String>) {
+        let anchor = lines.iter()
+            .position(|l| !self.is_blank(l) && l.trim() != "---")
+            .unwrap_or(lines.len());
+        if let Some((start, text)) = self.leading_comment(lines, anchor) {
+            return (start, anchor, Some(text));
+        }
+        if anchor > 0 && lines[anchor - 1].trim() == "---" {
+            if let Some((start, text)) = self.leading_comment(lines, anchor - 1) {
+                return (start, anchor - 1, Some(text));
+            }
+        }
+        (anchor, anchor, None)
+    }
+    
+    /// Collect the `#` comment block directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with('#') {
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| l.trim().trim_start_matches('#').trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Last line of the block under the key on `line_index`
+    fn block_end(&self, lines: &[&str], line_index: usize, indent: usize) -> usize {
+        (line_index + 1..lines.len())
+            .take_while(|&i| self.is_blank(lines[i]) || lines[i].len() - lines[i].trim_start().len() > indent)
+            .filter(|&i| !self.is_blank(lines[i]))
+            .last()
+            .unwrap_or(line_index)
+    }
+    
+    /// Span of a key's line, without its newline
+    fn line_span(&self, lines: &[&str], offsets: &LineOffsets, line_index: usize) -> Range<usize> {
+        offsets.start(line_index)..offsets.start(line_index) + lines[line_index].len()
+    }
+    
+    /// Unquoted value of a `description:` key
+    fn unquote(&self, value: &str) -> String {
+        value.trim().trim_matches(|c| c == '"' || c == '\'').to_string()
+    }
+}
+
+impl LanguageParser for GitHubActionsParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let keys = self.keys(&lines);
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        let top_level = |name: &str| keys.iter().find(|k| k.path.is_empty() && k.key == name);
+        let name = top_level("name").map(|k| self.unquote(&k.value));
+        
+        if top_level("runs").is_some() {
+            // An action describes itself in its metadata, next to its name
+            let line_index = top_level("name").map_or(0, |k| k.line_index);
+            let description = top_level("description");
+            code_items.push(CodeItem {
+                item_type: ItemKind::Action,
+                name: name.unwrap_or_else(|| "action".to_string()),
+                line_number: line_index + 1,
+                code: content.to_string(),
+                existing_docstring: description.map(|k| self.unquote(&k.value)).filter(|d| !d.is_empty()),
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: String::new(),
+                span: 0..content.len(),
+                docstring_span: description.map(|k| self.line_span(&lines, &offsets, k.line_index)),
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        } else if top_level("jobs").is_some() {
+            let (start, end, header) = self.header(&lines);
+            code_items.push(CodeItem {
+                item_type: ItemKind::Workflow,
+                name: name.unwrap_or_else(|| "workflow".to_string()),
+                line_number: 1,
+                code: content.to_string(),
+                existing_docstring: header,
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: String::new(),
+                span: 0..content.len(),
+                docstring_span: (start < end).then(|| offsets.start(start)..offsets.start(end)),
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        for entry in &keys {
+            let section = match DESCRIBED_SECTIONS.iter().find(|s| s.iter().eq(entry.path.iter())) {
+                Some(section) => section,
+                None => continue,
+            };
+            // Entries written inline (`name: {}`) have no block to add a description to
+            if !entry.value.is_empty() {
+                continue;
+            }
+            
+            let end = self.block_end(&lines, entry.line_index, entry.indent);
+            let description_key = keys.iter()
+                .filter(|k| k.line_index > entry.line_index && k.line_index <= end)
+                .find(|k| k.key == "description" && k.path.len() == entry.path.len() + 1);
+            let description = description_key
+                .map(|k| self.unquote(&k.value))
+                .filter(|d| !d.is_empty());
+            let last = section.last().copied().unwrap_or("inputs");
+            
+            code_items.push(CodeItem {
+                item_type: last.trim_end_matches('s').parse().expect("described sections hold inputs, outputs, or secrets"),
+                name: entry.key.clone(),
+                line_number: entry.line_index + 1,
+                code: lines[entry.line_index..=end].join("\n"),
+                existing_docstring: description,
+                parent: section.get(1).map(|trigger| trigger.to_string()),
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: lines[entry.line_index][..entry.indent].to_string(),
+                span: offsets.lines(entry.line_index, end),
+                docstring_span: description_key.map(|k| self.line_span(&lines, &offsets, k.line_index)),
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "github-actions".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            if item.item_type == ItemKind::Workflow {
+                let header: String = update.new_docstring
+                    .trim()
+                    .lines()
+                    .map(|line| if line.trim().is_empty() { "#\n".to_string() } else { format!("# {}\n", line.trim_end()) })
+                    .collect();
+                let range = item.docstring_span.clone().unwrap_or_else(|| {
+                    let at = offsets.start(self.header(&lines).0);
+                    at..at
+                });
+                edits.push(Edit::new(range, header));
+                continue;
+            }
+            
+            // Descriptions are a single line
+            let description = super::yaml_scalar(&update.new_docstring.split_whitespace().collect::<Vec<_>>().join(" "));
+            let line_index = item.line_number - 1;
+            
+            let key_indent = if item.item_type == ItemKind::Action {
+                0
+            } else {
+                let indent = item.indentation.len();
+                let end = self.block_end(&lines, line_index, indent);
+                self.keys(&lines).iter()
+                    .find(|k| k.line_index > line_index && k.line_index <= end)
+                    .map_or(indent + 2, |k| k.indent)
+            };
+            let description_line = format!("{}description: {}", " ".repeat(key_indent), description);
+            
+            edits.push(match &item.docstring_span {
+                Some(span) => Edit::new(span.clone(), description_line),
+                // An action's description follows its name; an entry's comes first in its block
+                None if item.item_type == ItemKind::Action && !lines.get(line_index).is_some_and(|l| l.starts_with("name:")) => {
+                    Edit::new(0..0, format!("{}\n", description_line))
+                },
+                None => Edit::after_line(content, &offsets, line_index, format!("{}\n", description_line)),
+            });
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,325 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// CUDA and OpenCL parser implementation for kernels (`__global__`, `__kernel`) and
+/// the host and device functions around them, documented with Doxygen `/** ... */`
+/// comments
+pub struct GpuParser {
+    language: &'static str,
+    function_re: Regex,
+}
+
+/// Qualifiers that can precede a function's return type
+const QUALIFIERS: &[&str] = &[
+    "__global__", "__device__", "__host__", "__forceinline__", "__noinline__",
+    "__kernel", "kernel", "static", "inline", "extern", "constexpr",
+];
+
+/// Words that can look like a return type or function name in statements
+const KEYWORDS: &[&str] = &["if", "for", "while", "switch", "return", "else", "do", "sizeof", "case", "new", "delete"];
+
+impl Default for GpuParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GpuParser {
+    /// Parser for CUDA source
+    pub fn new() -> Self {
+        Self::with_language("cuda")
+    }
+    
+    /// Parser for OpenCL C kernel source
+    pub fn opencl() -> Self {
+        Self::with_language("opencl")
+    }
+    
+    fn with_language(language: &'static str) -> Self {
+        Self {
+            language,
+            function_re: Regex::new(r"^([A-Za-z_][\w\s\*&:<>,]*?)\b([A-Za-z_]\w*)\s*\(").unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Index of the `template <...>` line directly above a function, or the function itself
+    fn template_start(&self, lines: &[&str], line_index: usize) -> usize {
+        if line_index > 0 && lines[line_index - 1].trim_start().starts_with("template") {
+            line_index - 1
+        } else {
+            line_index
+        }
+    }
+    
+    /// Collect the doc comment (`/** */` or `///`) directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 {
+            return None;
+        }
+        
+        let mut start = line_index;
+        if lines[line_index - 1].trim().ends_with("*/") {
+            start = line_index - 1;
+            while start > 0 && !lines[start].contains("/*") {
+                start -= 1;
+            }
+            if !lines[start].trim_start().starts_with("/**") {
+                return None;
+            }
+        } else {
+            while start > 0 && lines[start - 1].trim_start().starts_with("///") {
+                start -= 1;
+            }
+            if start == line_index {
+                return None;
+            }
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("///")
+                    .trim_start_matches("/**")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the last line of a function signature, which may span several lines
+    /// while its parameter list is open
+    fn signature_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            depth += code.matches('(').count() as i32;
+            depth -= code.matches(')').count() as i32;
+            if depth <= 0 {
+                return i;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+    
+    /// Find the line index of the closing brace of a function body, or None for a
+    /// prototype without one
+    fn body_end(&self, lines: &[&str], signature_end: usize) -> Option<usize> {
+        let after_signature = lines[signature_end].split("//").next().unwrap_or("");
+        let opens_here = after_signature.contains('{');
+        let opens_next = lines.get(signature_end + 1).is_some_and(|l| l.trim_start().starts_with('{'));
+        if !opens_here && !opens_next {
+            return None;
+        }
+        
+        let mut depth = 0i32;
+        let mut opened = false;
+        for (i, line) in lines.iter().enumerate().skip(signature_end) {
+            let code = line.split("//").next().unwrap_or("");
+            opened |= code.contains('{');
+            depth += code.matches('{').count() as i32;
+            depth -= code.matches('}').count() as i32;
+            if opened && depth <= 0 {
+                return Some(i);
+            }
+        }
+        Some(lines.len().saturating_sub(1))
+    }
+    
+    /// Parameter names from a parameter list, without types or array bounds
+    fn parameter_names(&self, list: &str) -> Vec<String> {
+        list.split(',')
+            .filter_map(|param| {
+                let declaration = param.split('=').next().unwrap_or("").split('[').next().unwrap_or("").trim();
+                let words: Vec<&str> = declaration
+                    .split(|c: char| c.is_whitespace() || c == '*' || c == '&')
+                    .filter(|w| !w.is_empty())
+                    .collect();
+                if words.len() < 2 {
+                    return None;
+                }
+                words.last().map(|name| name.to_string())
+            })
+            .collect()
+    }
+}
+
+impl LanguageParser for GpuParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Closing line of the function being skipped over; statements inside bodies
+        // are not declarations
+        let mut body_end: Option<usize> = None;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            if body_end.is_some_and(|end| line_index <= end) {
+                continue;
+            }
+            // Only unindented lines start declarations at file scope
+            if line.starts_with(char::is_whitespace) {
+                continue;
+            }
+            
+            let code = line.trim();
+            if code.starts_with('#') || code.starts_with("//") || code.starts_with('*') {
+                continue;
+            }
+            let caps = match self.function_re.captures(code) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let name = caps[2].to_string();
+            let prefix: Vec<&str> = caps[1].split_whitespace().collect();
+            if prefix.is_empty() || KEYWORDS.contains(&name.as_str()) || prefix.iter().any(|w| KEYWORDS.contains(w)) {
+                continue;
+            }
+            
+            let signature_end = self.signature_end(&lines, line_index);
+            let end = match self.body_end(&lines, signature_end) {
+                Some(end) => end,
+                None => continue,
+            };
+            body_end = Some(end);
+            
+            let is_kernel = prefix.iter().any(|w| matches!(*w, "__global__" | "__kernel" | "kernel"));
+            let return_type = prefix.iter()
+                .filter(|w| !QUALIFIERS.contains(w))
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ");
+            
+            let signature = lines[line_index..=signature_end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+            let open = caps.get(0).map_or(0, |m| m.end());
+            let list = signature[open..].rsplit_once(')').map_or("", |(list, _)| list);
+            
+            let anchor = self.template_start(&lines, line_index);
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, anchor)
+                .map(|(start, text)| (offsets.lines(start, anchor - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: if is_kernel { ItemKind::Kernel } else { ItemKind::Function },
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end].join("\n"),
+                existing_docstring,
+                parent: None,
+                parameters: self.parameter_names(list),
+                parameter_details: Vec::new(),
+                returns: if return_type.is_empty() || return_type == "void" { None } else { Some(return_type) },
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(anchor, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: self.language.to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let mut comment = format!("{}/**\n", item.indentation);
+            for line in update.new_docstring.trim().lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    comment.push_str(&format!("{} *\n", item.indentation));
+                } else {
+                    comment.push_str(&format!("{} * {}\n", item.indentation, trimmed));
+                }
+            }
+            comment.push_str(&format!("{} */\n", item.indentation));
+            
+            // The comment goes above a `template <...>` line
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
+
+/// Prompt context for a kernel: the launch dimensions its thread indexing implies and
+/// the memory spaces it uses, so the documentation can describe thread/block semantics
+pub fn kernel_context(code: &str, language: &str) -> String {
+    let index_builtins: &[&str] = if language == "opencl" {
+        &["get_global_id", "get_local_id", "get_group_id", "get_local_size", "get_global_size", "get_num_groups"]
+    } else {
+        &["threadIdx", "blockIdx", "blockDim", "gridDim"]
+    };
+    
+    // Builtins used, each with the highest dimension it is indexed in
+    let mut used = Vec::new();
+    let mut dimensions = 0;
+    for builtin in index_builtins {
+        let mut highest = None;
+        for (dimension, suffixes) in [(1, [".x", "(0)"]), (2, [".y", "(1)"]), (3, [".z", "(2)"])] {
+            if suffixes.iter().any(|suffix| code.contains(&format!("{}{}", builtin, suffix))) {
+                highest = Some(dimension);
+            }
+        }
+        if let Some(dimension) = highest {
+            used.push(*builtin);
+            dimensions = dimensions.max(dimension);
+        }
+    }
+    
+    let memory_spaces: Vec<&str> = if language == "opencl" {
+        ["__global", "__local", "__constant", "__private"].into_iter()
+            .filter(|space| code.contains(space) || code.contains(&format!("{} ", &space[2..])))
+            .collect()
+    } else {
+        ["__shared__", "__constant__", "__managed__"].into_iter()
+            .filter(|space| code.contains(space))
+            .collect()
+    };
+    
+    let mut context = String::from("\n\nThis is a GPU kernel.");
+    if !used.is_empty() {
+        context.push_str(&format!(
+            " Its thread indexing ({}) implies a {}D launch.",
+            used.join(", "), dimensions
+        ));
+    }
+    if !memory_spaces.is_empty() {
+        context.push_str(&format!(" It uses {} memory.", memory_spaces.join(", ")));
+    }
+    if code.contains("__syncthreads") || code.contains("barrier(") {
+        context.push_str(" Threads synchronize within a block.");
+    }
+    context.push_str(
+        " Describe how work is divided among threads and blocks, the launch dimensions the kernel \
+        expects, any bounds checks, and the memory space of each pointer parameter."
+    );
+    context
+}
@@ -0,0 +1,287 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Groovy parser implementation for classes, interfaces, traits, enums, and methods
+/// (including Jenkins shared-library steps and Gradle plugins), documented with
+/// Groovydoc `/** ... */` comments
+pub struct GroovyParser {
+    type_re: Regex,
+    method_re: Regex,
+}
+
+impl Default for GroovyParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GroovyParser {
+    pub fn new() -> Self {
+        Self {
+            type_re: Regex::new(
+                r"^(?:(?:public|private|protected|static|final|abstract)\s+)*(class|interface|trait|enum|@interface)\s+([A-Za-z_]\w*)"
+            ).unwrap(),
+            method_re: Regex::new(
+                r"^(?P<mods>(?:(?:public|private|protected|static|final|abstract|synchronized|default)\s+)*)(?:(?P<def>def)\s+|(?:<[^>]+>\s+)?(?P<type>(?:(?:[a-z]\w*\.)*[A-Z]\w*|void|boolean|byte|char|short|int|long|float|double)(?:<[^()]*>)?(?:\[\])*)\s+)?(?P<name>[A-Za-z_]\w*)\s*\("
+            ).unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Index of the first line of the annotations (`@Override`, `@NonCPS`) directly
+    /// above a declaration, or the declaration itself
+    fn annotation_start(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with('@') {
+            start -= 1;
+        }
+        start
+    }
+    
+    /// Collect the `/** ... */` comment directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 || !lines[line_index - 1].trim().ends_with("*/") {
+            return None;
+        }
+        
+        let mut start = line_index - 1;
+        while start > 0 && !lines[start].contains("/*") {
+            start -= 1;
+        }
+        if !lines[start].trim_start().starts_with("/**") {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("/**")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the last line of a method signature, which may span several lines
+    /// while its parameter list is open
+    fn signature_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            depth += code.matches('(').count() as i32;
+            depth -= code.matches(')').count() as i32;
+            if depth <= 0 {
+                return i;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+    
+    /// Find the line index of the closing brace of the body opened at or after a
+    /// declaration, or None if it has no braced body
+    fn body_end(&self, lines: &[&str], signature_end: usize, line_index: usize) -> Option<usize> {
+        let opens_here = lines[line_index..=signature_end].iter()
+            .any(|l| l.split("//").next().unwrap_or("").contains('{'));
+        let opens_next = lines.get(signature_end + 1).is_some_and(|l| l.trim_start().starts_with('{'));
+        if !opens_here && !opens_next {
+            return None;
+        }
+        
+        let mut depth = 0i32;
+        let mut opened = false;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            opened |= code.contains('{');
+            depth += code.matches('{').count() as i32;
+            depth -= code.matches('}').count() as i32;
+            if opened && depth <= 0 {
+                return Some(i);
+            }
+        }
+        Some(lines.len().saturating_sub(1))
+    }
+    
+    /// Split a method signature (starting at its opening parenthesis) into parameter
+    /// names and whatever follows the parameter list
+    fn parse_parameters<'a>(&self, rest: &'a str) -> (Vec<String>, &'a str) {
+        let mut parameters = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 1;
+        
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' | '[' | '<' | '{' => depth += 1,
+                // The arrow of a closure default value is not a closing bracket
+                '>' if rest[..i].ends_with('-') => {},
+                ')' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    return (parameters, rest[i + 1..].trim());
+                },
+                ')' | ']' | '>' | '}' => depth -= 1,
+                ',' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    start = i + 1;
+                },
+                _ => {}
+            }
+        }
+        (parameters, "")
+    }
+    
+    /// Name of one parameter, without annotations, type, or default value
+    fn parameter_name(&self, param: &str) -> Option<String> {
+        let declaration = param.split('=').next().unwrap_or("").trim();
+        declaration.split_whitespace()
+            .last()
+            .map(|name| name.trim_start_matches("...").to_string())
+            .filter(|name| !name.is_empty())
+    }
+}
+
+/// Words that can look like a return type or method name in statements such as
+/// `return foo(x)` or `else if (x)`
+const KEYWORDS: &[&str] = &["return", "new", "else", "throw", "assert", "case", "if", "for", "while", "switch", "catch", "synchronized"];
+
+impl LanguageParser for GroovyParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Stack of enclosing bodies: (kind, name, closing line index)
+        let mut scopes: Vec<(String, String, usize)> = Vec::new();
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            while scopes.last().is_some_and(|(_, _, end)| *end < line_index) {
+                scopes.pop();
+            }
+            
+            let code = line.trim();
+            let (kind, name, parameters, returns, signature_end) = if let Some(caps) = self.type_re.captures(code) {
+                let kind = caps[1].trim_start_matches('@').to_string();
+                (kind, caps[2].to_string(), Vec::new(), None, line_index)
+            } else if let Some(caps) = self.method_re.captures(code) {
+                let name = caps["name"].to_string();
+                let return_type = caps.name("type").map(|t| t.as_str().to_string());
+                let declared = caps.name("def").is_some() || return_type.is_some() || !caps["mods"].is_empty();
+                if !declared || KEYWORDS.contains(&name.as_str()) {
+                    continue;
+                }
+                if return_type.as_deref().is_some_and(|t| KEYWORDS.contains(&t)) {
+                    continue;
+                }
+                
+                let signature_end = self.signature_end(&lines, line_index);
+                let signature = lines[line_index..=signature_end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+                let open = caps.get(0).map_or(0, |m| m.end() - 1);
+                let (parameters, after) = self.parse_parameters(&signature[open..]);
+                
+                // Anything other than a body, `throws`, or nothing (abstract and interface
+                // methods) after the parameters means this is a call, not a declaration
+                let after = after.split("//").next().unwrap_or("").trim();
+                if !(after.is_empty() || after.starts_with('{') || after.starts_with("throws") || after.starts_with(';')) {
+                    continue;
+                }
+                
+                let returns = return_type.filter(|t| t != "void");
+                ("method".to_string(), name, parameters, returns, signature_end)
+            } else {
+                continue;
+            };
+            
+            let body_end = self.body_end(&lines, signature_end, line_index);
+            let end_line = body_end.unwrap_or(signature_end);
+            
+            // Methods inside another method's body (closures, local classes) are skipped
+            let inside_method = scopes.iter().any(|(kind, _, _)| kind == "method");
+            let parent_path: Vec<&str> = scopes.iter().map(|(_, name, _)| name.as_str()).collect();
+            let parent = if parent_path.is_empty() { None } else { Some(parent_path.join(".")) };
+            
+            if let Some(end) = body_end {
+                scopes.push((kind.clone(), name.clone(), end));
+            }
+            if inside_method {
+                continue;
+            }
+            
+            // A top-level `call` method in a shared library's vars/ script defines a
+            // pipeline step named after the file
+            let item_type = if kind == "method" && parent.is_none() && name == "call" {
+                ItemKind::Step
+            } else {
+                kind.parse().expect("declaration patterns only match item kinds")
+            };
+            
+            let doc_anchor = self.annotation_start(&lines, line_index);
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, doc_anchor)
+                .map(|(start, text)| (offsets.lines(start, doc_anchor - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type,
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end_line].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(doc_anchor, end_line),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "groovy".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let mut comment = format!("{}/**\n", item.indentation);
+            for line in update.new_docstring.trim().lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    comment.push_str(&format!("{} *\n", item.indentation));
+                } else {
+                    comment.push_str(&format!("{} * {}\n", item.indentation, trimmed));
+                }
+            }
+            comment.push_str(&format!("{} */\n", item.indentation));
+            
+            // Groovydoc goes above any annotations on the declaration
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,204 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Helm chart `values.yaml` parser implementation. Every value helm-docs lists in a
+/// chart's README table is documented with a `# --` comment directly above its key.
+pub struct HelmParser {
+    key_re: Regex,
+}
+
+impl Default for HelmParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HelmParser {
+    pub fn new() -> Self {
+        Self {
+            key_re: Regex::new(r#"^(\s*)("[^"]+"|'[^']+'|[\w.\-/]+):(?:\s+(.*))?$"#).unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Whether a line carries no YAML content
+    fn is_blank(&self, line: &str) -> bool {
+        let code = line.trim();
+        code.is_empty() || code.starts_with('#')
+    }
+    
+    /// Index of the next line with YAML content after `line_index`
+    fn next_content(&self, lines: &[&str], line_index: usize) -> Option<usize> {
+        (line_index + 1..lines.len()).find(|&i| !self.is_blank(lines[i]))
+    }
+    
+    /// Collect the helm-docs comment above a key: the comment lines from the one
+    /// starting with `# --` down to the key. Other comments are not documentation.
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with('#') {
+            start -= 1;
+            if lines[start].trim_start().starts_with("# --") {
+                let text = lines[start..line_index]
+                    .iter()
+                    .map(|l| l.trim().trim_start_matches('#').trim_start().trim_start_matches("--").trim())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .trim()
+                    .to_string();
+                return Some((start, text));
+            }
+        }
+        None
+    }
+}
+
+impl LanguageParser for HelmParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Enclosing maps as (indentation, key), for each key's dotted path
+        let mut parents: Vec<(usize, String)> = Vec::new();
+        // Lines up to this index belong to a value already seen (a list or block scalar)
+        let mut skip_until: Option<usize> = None;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            if skip_until.is_some_and(|end| line_index <= end) || self.is_blank(line) {
+                continue;
+            }
+            let caps = match self.key_re.captures(line) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let indent = caps[1].len();
+            let key = caps[2].trim_matches(|c| c == '"' || c == '\'').to_string();
+            let value = caps.get(3).map_or("", |v| v.as_str().trim());
+            
+            while parents.last().is_some_and(|(parent_indent, _)| *parent_indent >= indent) {
+                parents.pop();
+            }
+            let parent = (!parents.is_empty())
+                .then(|| parents.iter().map(|(_, k)| k.as_str()).collect::<Vec<_>>().join("."));
+            let name = match &parent {
+                Some(parent) => format!("{}.{}", parent, key),
+                None => key.clone(),
+            };
+            
+            // A key with nothing after it is a map when indented keys follow; lists
+            // and block scalars are values, and the lines under them are skipped
+            let next = self.next_content(&lines, line_index);
+            let next_indent = next.map(|i| lines[i].len() - lines[i].trim_start().len());
+            let is_map = value.is_empty()
+                && next.is_some_and(|i| next_indent > Some(indent) && !lines[i].trim_start().starts_with('-'));
+            if is_map {
+                parents.push((indent, key));
+                continue;
+            }
+            
+            let end = (line_index + 1..lines.len())
+                .take_while(|&i| {
+                    let l = lines[i];
+                    let l_indent = l.len() - l.trim_start().len();
+                    self.is_blank(l) || l_indent > indent || (l_indent == indent && l.trim_start().starts_with('-'))
+                })
+                .filter(|&i| !self.is_blank(lines[i]))
+                .last()
+                .unwrap_or(line_index);
+            skip_until = Some(end);
+            
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, line_index)
+                .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: ItemKind::Value,
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end].join("\n"),
+                existing_docstring,
+                parent,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "helm".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            // The first line carries the `--` marker; helm-docs joins the rest onto it
+            let comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+                .map(|(i, line)| {
+                    let marker = if i == 0 { "# --" } else { "#" };
+                    format!("{}{} {}\n", item.indentation, marker, line.trim())
+                })
+                .collect();
+            
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
+
+/// Chart templates the chart's README (or its helm-docs `README.md.gotmpl`) never
+/// mentions by name, for the chart whose `values.yaml` is at `values_path`. Helpers
+/// (`_*.tpl`), `NOTES.txt`, and chart tests are not expected to be covered.
+pub fn uncovered_templates(values_path: &Path) -> Vec<PathBuf> {
+    let chart_dir = values_path.parent().unwrap_or_else(|| Path::new("."));
+    let readme = ["README.md.gotmpl", "README.md"].iter()
+        .filter_map(|name| std::fs::read_to_string(chart_dir.join(name)).ok())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+    
+    let mut templates: Vec<PathBuf> = match std::fs::read_dir(chart_dir.join("templates")) {
+        Ok(entries) => entries.filter_map(|e| e.ok().map(|e| e.path())).collect(),
+        Err(_) => return Vec::new(),
+    };
+    templates.sort();
+    templates.retain(|template| {
+        let file_name = template.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let stem = template.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
+        let is_manifest = template.is_file()
+            && !file_name.starts_with('_')
+            && (file_name.ends_with(".yaml") || file_name.ends_with(".yml"));
+        is_manifest && !readme.contains(&stem)
+    });
+    templates
+}
@@ -0,0 +1,404 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Java parser implementation for classes, interfaces, enums, methods, and
+/// constructors, documented with Javadoc `/** ... */` comments
+pub struct JavaParser {
+    type_re: Regex,
+    method_re: Regex,
+}
+
+impl Default for JavaParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JavaParser {
+    pub fn new() -> Self {
+        Self {
+            type_re: Regex::new(
+                r"^(?P<mods>(?:(?:public|private|protected|static|final|abstract|sealed|non-sealed|strictfp)\s+)*)(?P<kind>class|interface|enum|@interface)\s+(?P<name>[A-Za-z_$][\w$]*)"
+            ).unwrap(),
+            method_re: Regex::new(
+                r"^(?P<mods>(?:(?:public|private|protected|static|final|abstract|synchronized|native|strictfp|default)\s+)*)(?:<[^()]+?>\s+)?(?:(?P<type>(?:(?:[a-z]\w*\.)*[A-Z][\w$]*(?:\.[A-Z][\w$]*)*|void|boolean|byte|char|short|int|long|float|double)(?:<[^()]*>)?(?:\[\])*)\s+)?(?P<name>[A-Za-z_$][\w$]*)\s*\("
+            ).unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Index of the first line of the annotations (`@Override`, `@Deprecated`) directly
+    /// above a declaration, or the declaration itself
+    fn annotation_start(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with('@') {
+            start -= 1;
+        }
+        start
+    }
+    
+    /// Collect the `/** ... */` comment directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 || !lines[line_index - 1].trim().ends_with("*/") {
+            return None;
+        }
+        
+        let mut start = line_index - 1;
+        while start > 0 && !lines[start].contains("/*") {
+            start -= 1;
+        }
+        if !lines[start].trim_start().starts_with("/**") {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("/**")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// The code of each line, with comments and the contents of string, character, and
+    /// text block literals removed, so that the braces and parentheses left can be counted
+    fn code_lines(&self, lines: &[&str]) -> Vec<String> {
+        let mut in_comment = false;
+        let mut in_text_block = false;
+        
+        lines.iter().map(|line| {
+            let mut code = String::new();
+            let mut rest = *line;
+            while let Some(c) = rest.chars().next() {
+                if in_comment || in_text_block {
+                    let close = if in_comment { rest.find("*/").map(|i| i + 2) } else { self.literal_end(rest, "\"\"\"") };
+                    rest = close.map_or("", |end| &rest[end..]);
+                    in_comment &= close.is_none();
+                    in_text_block &= close.is_none();
+                } else if rest.starts_with("//") {
+                    break;
+                } else if rest.starts_with("/*") {
+                    in_comment = true;
+                    rest = &rest[2..];
+                } else if rest.starts_with("\"\"\"") {
+                    code.push_str("\"\"");
+                    in_text_block = true;
+                    rest = &rest[3..];
+                } else if c == '"' || c == '\'' {
+                    code.push_str("\"\"");
+                    rest = self.literal_end(&rest[1..], &rest[..1]).map_or("", |end| &rest[1 + end..]);
+                } else {
+                    code.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+            code
+        }).collect()
+    }
+    
+    /// Offset just past the quote that closes a literal, skipping escaped ones, or None
+    /// if the literal doesn't close on this line
+    fn literal_end(&self, text: &str, quote: &str) -> Option<usize> {
+        let mut escaped = false;
+        for (i, c) in text.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if text[i..].starts_with(quote) {
+                return Some(i + quote.len());
+            }
+        }
+        None
+    }
+    
+    /// Find the last line of a method signature, which may span several lines: those
+    /// of its parameter list, and up to the `{` or `;` that ends a `throws` clause on
+    /// the lines after it
+    fn signature_end(&self, code_lines: &[String], line_index: usize) -> usize {
+        let mut depth = 0i32;
+        let mut close = code_lines.len().saturating_sub(1);
+        for (i, code) in code_lines.iter().enumerate().skip(line_index) {
+            depth += code.matches('(').count() as i32;
+            depth -= code.matches(')').count() as i32;
+            if depth <= 0 {
+                close = i;
+                break;
+            }
+        }
+        
+        (close..code_lines.len().min(close + 4))
+            .take_while(|&i| i == close || code_lines[i].trim_start().starts_with("throws") || code_lines[i - 1].trim_end().ends_with(','))
+            .find(|&i| code_lines[i].contains(['{', ';']))
+            .unwrap_or(close)
+    }
+    
+    /// Find the line index of the closing brace of the body opened at or after a
+    /// declaration, or None if it has no braced body
+    fn body_end(&self, code_lines: &[String], signature_end: usize, line_index: usize) -> Option<usize> {
+        let opens_here = code_lines[line_index..=signature_end].iter().any(|code| code.contains('{'));
+        let opens_next = code_lines.get(signature_end + 1).is_some_and(|code| code.trim_start().starts_with('{'));
+        if !opens_here && !opens_next {
+            return None;
+        }
+        
+        let mut depth = 0i32;
+        let mut opened = false;
+        for (i, code) in code_lines.iter().enumerate().skip(line_index) {
+            opened |= code.contains('{');
+            depth += code.matches('{').count() as i32;
+            depth -= code.matches('}').count() as i32;
+            if opened && depth <= 0 {
+                return Some(i);
+            }
+        }
+        Some(code_lines.len().saturating_sub(1))
+    }
+    
+    /// Split a method signature (starting at its opening parenthesis) into parameter
+    /// names and whatever follows the parameter list
+    fn parse_parameters<'a>(&self, rest: &'a str) -> (Vec<String>, &'a str) {
+        let mut parameters = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 1;
+        
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' | '[' | '<' | '{' => depth += 1,
+                ')' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    return (parameters, rest[i + 1..].trim());
+                },
+                ')' | ']' | '>' | '}' => depth -= 1,
+                ',' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    start = i + 1;
+                },
+                _ => {}
+            }
+        }
+        (parameters, "")
+    }
+    
+    /// Name of one parameter, without annotations, modifiers, or type
+    fn parameter_name(&self, param: &str) -> Option<String> {
+        param.split_whitespace()
+            .last()
+            .map(|name| name.trim_start_matches("...").trim_end_matches("[]").to_string())
+            .filter(|name| !name.is_empty())
+    }
+    
+    /// Exception types named in a `throws` clause, from the text after a parameter list
+    fn throws_clause(&self, after: &str) -> Vec<String> {
+        let clause = match after.strip_prefix("throws") {
+            Some(clause) => clause,
+            None => return Vec::new(),
+        };
+        clause.split(['{', ';'])
+            .next()
+            .unwrap_or("")
+            .split(',')
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+    
+    /// Visibility of a declaration. Members without `public` are private: those of
+    /// classes and enums declared `private`, `protected`, or package-private without a
+    /// modifier, and interface members only when declared `private`. Top-level types
+    /// without one are package-private, and counted as public.
+    fn visibility(&self, modifiers: &str, member_of: Option<&str>) -> Visibility {
+        let modifiers: Vec<&str> = modifiers.split_whitespace().collect();
+        if modifiers.contains(&"private") {
+            return Visibility::Private;
+        }
+        match member_of {
+            Some(kind) if kind != "interface" && !modifiers.contains(&"public") => Visibility::Private,
+            _ => Visibility::Public,
+        }
+    }
+}
+
+/// Words that can look like a return type or method name in statements such as
+/// `return foo(x)` or `else if (x)`
+const KEYWORDS: &[&str] = &["return", "new", "else", "throw", "assert", "case", "if", "for", "while", "switch", "catch", "synchronized", "yield"];
+
+impl LanguageParser for JavaParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let code_lines = self.code_lines(&lines);
+        let mut code_items = Vec::new();
+        
+        // Stack of enclosing bodies: (kind, name, closing line index)
+        let mut scopes: Vec<(String, String, usize)> = Vec::new();
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            while scopes.last().is_some_and(|(_, _, end)| *end < line_index) {
+                scopes.pop();
+            }
+            
+            let code = line.trim();
+            let (kind, name, modifiers, parameters, returns, throws, signature_end) = if let Some(caps) = self.type_re.captures(code) {
+                let kind = caps["kind"].trim_start_matches('@').to_string();
+                (kind, caps["name"].to_string(), caps["mods"].to_string(), Vec::new(), None, Vec::new(), line_index)
+            } else if let Some(caps) = self.method_re.captures(code) {
+                let name = caps["name"].to_string();
+                let return_type = caps.name("type").map(|t| t.as_str().to_string());
+                if KEYWORDS.contains(&name.as_str()) || return_type.as_deref().is_some_and(|t| KEYWORDS.contains(&t)) {
+                    continue;
+                }
+                
+                // Only a constructor, named after its class, has no return type
+                let enclosing_type = scopes.last().filter(|(kind, _, _)| kind != "method").map(|(_, name, _)| name.as_str());
+                let constructor = return_type.is_none() && enclosing_type == Some(name.as_str());
+                if return_type.is_none() && !constructor {
+                    continue;
+                }
+                
+                let signature_end = self.signature_end(&code_lines, line_index);
+                let signature = lines[line_index..=signature_end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+                let open = caps.get(0).map_or(0, |m| m.end() - 1);
+                let (parameters, after) = self.parse_parameters(&signature[open..]);
+                
+                // Anything other than a body, `throws`, or nothing (abstract and interface
+                // methods, annotation elements) after the parameters means this is a call
+                // or a field, not a declaration
+                let after = after.split("//").next().unwrap_or("").trim();
+                if !(after.starts_with('{') || after.starts_with("throws") || after.starts_with(';') || after.starts_with("default")) {
+                    continue;
+                }
+                
+                let kind = if constructor { "constructor" } else { "method" };
+                let returns = return_type.filter(|t| t != "void");
+                (kind.to_string(), name, caps["mods"].to_string(), parameters, returns, self.throws_clause(after), signature_end)
+            } else {
+                continue;
+            };
+            
+            let body_end = self.body_end(&code_lines, signature_end, line_index);
+            let end_line = body_end.unwrap_or(signature_end);
+            
+            // Declarations inside a method's body (local and anonymous classes) are skipped
+            let inside_method = scopes.iter().any(|(kind, _, _)| kind == "method" || kind == "constructor");
+            let parent_path: Vec<&str> = scopes.iter().map(|(_, name, _)| name.as_str()).collect();
+            let parent = if parent_path.is_empty() { None } else { Some(parent_path.join(".")) };
+            let visibility = self.visibility(&modifiers, scopes.last().map(|(kind, _, _)| kind.as_str()));
+            
+            if let Some(end) = body_end {
+                scopes.push((kind.clone(), name.clone(), end));
+            }
+            if inside_method {
+                continue;
+            }
+            
+            let doc_anchor = self.annotation_start(&lines, line_index);
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, doc_anchor)
+                .map(|(start, text)| (offsets.lines(start, doc_anchor - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: kind.parse::<ItemKind>().expect("declaration patterns only match item kinds"),
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end_line].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(doc_anchor, end_line),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility,
+                throws,
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "java".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let mut comment = format!("{}/**\n", item.indentation);
+            for line in update.new_docstring.trim().lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    comment.push_str(&format!("{} *\n", item.indentation));
+                } else {
+                    comment.push_str(&format!("{} * {}\n", item.indentation, trimmed));
+                }
+            }
+            comment.push_str(&format!("{} */\n", item.indentation));
+            
+            // Javadoc goes above any annotations on the declaration
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn braces_in_literals_and_comments_are_not_counted() {
+        let content = "public class Braces {\n    /** Open. */\n    public String open() {\n        return \"{\" + '{'; /* } */\n    }\n    \n    public String link() {\n        if (ok(\"http://example.com\")) {\n            return \"\";\n        }\n        return null;\n    }\n}\n";
+        let parsed = JavaParser::new().parse(content).unwrap();
+        let items: Vec<(&str, Option<&str>, &str)> = parsed.items.iter()
+            .map(|item| (item.name.as_str(), item.parent.as_deref(), item.code.lines().last().unwrap_or("")))
+            .collect();
+        assert_eq!(items, vec![
+            ("Braces", None, "}"),
+            ("open", Some("Braces"), "    }"),
+            ("link", Some("Braces"), "    }"),
+        ]);
+    }
+    
+    #[test]
+    fn only_public_and_interface_members_are_public() {
+        let content = "class Shop {\n    public void a() {}\n    protected void b() {}\n    void c() {}\n    private void d() {}\n}\n\ninterface Api {\n    void e();\n    private void f() {}\n}\n";
+        let parsed = JavaParser::new().parse(content).unwrap();
+        let visibility: Vec<(&str, Visibility)> = parsed.items.iter().map(|item| (item.name.as_str(), item.visibility)).collect();
+        assert_eq!(visibility, vec![
+            ("Shop", Visibility::Public),
+            ("a", Visibility::Public),
+            ("b", Visibility::Private),
+            ("c", Visibility::Private),
+            ("d", Visibility::Private),
+            ("Api", Visibility::Public),
+            ("e", Visibility::Public),
+            ("f", Visibility::Private),
+        ]);
+    }
+}
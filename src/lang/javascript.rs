@@ -1,25 +1,27 @@
 use crate::error::{DocGenError, DocGenResult};
-use crate::parser::{CodeItem, ParsedCode};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, Parameter, ParsedCode, Visibility};
 use crate::docstring::UpdatedDocstring;
 use super::LanguageParser;
 use tree_sitter::{Parser, Language, Query, QueryCursor};
 use std::ops::Range;
 
-extern "C" {
-    fn tree_sitter_javascript() -> Language;
-}
-
 /// JavaScript language parser implementation
 pub struct JavaScriptParser {
-    parser: Parser,
+    language: Language,
+}
+
+impl Default for JavaScriptParser {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl JavaScriptParser {
     pub fn new() -> Self {
-        let mut parser = Parser::new();
-        let language = unsafe { tree_sitter_javascript() };
-        parser.set_language(language).expect("Failed to load JavaScript grammar");
-        Self { parser }
+        // The grammar is compiled and linked by the tree-sitter-javascript crate
+        let language = tree_sitter_javascript::language();
+        Parser::new().set_language(language).expect("Failed to load JavaScript grammar");
+        Self { language }
     }
     
     /// Extract a substring from the source based on a byte range
@@ -27,20 +29,6 @@ impl JavaScriptParser {
         &source[range.start..range.end]
     }
     
-    /// Get the line number for a given byte position
-    fn get_line_number(&self, source: &str, position: usize) -> usize {
-        let mut line_number = 1;
-        for (i, c) in source.char_indices() {
-            if i >= position {
-                break;
-            }
-            if c == '\n' {
-                line_number += 1;
-            }
-        }
-        line_number
-    }
-    
     /// Extract indentation from a line
     fn extract_indentation(&self, content: &str, line_number: usize) -> String {
         if let Some(line) = content.lines().nth(line_number - 1) {
@@ -59,9 +47,34 @@ impl JavaScriptParser {
             .join("\n")
     }
     
-    /// Check if a given node is a JSDoc comment
-    fn is_jsdoc_comment(&self, node_text: &str) -> bool {
-        node_text.trim().starts_with("/**") && node_text.trim().ends_with("*/")
+    /// Collect the JSDoc `/** ... */` comment directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 || !lines[line_index - 1].trim().ends_with("*/") {
+            return None;
+        }
+        
+        let mut start = line_index - 1;
+        while start > 0 && !lines[start].contains("/*") {
+            start -= 1;
+        }
+        if !lines[start].trim_start().starts_with("/**") {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("/**")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
     }
     
     /// Extract parameters from a function declaration
@@ -98,71 +111,185 @@ impl JavaScriptParser {
         params
     }
     
-    /// Extract JSDoc comment and check if it's outdated
-    fn extract_jsdoc(&self, node: tree_sitter::Node, source: &str) -> Option<String> {
-        let mut cursor = node.walk();
-        let mut comment_node = None;
-        
-        // Check for comments directly before the function declaration
-        if cursor.goto_first_child() {
-            if cursor.node().kind() == "comment" && self.is_jsdoc_comment(self.get_node_text(source, cursor.node().byte_range())) {
-                comment_node = Some(cursor.node());
-            }
+    /// The declaration a node belongs to: the `const`, `let`, or `var` statement of a
+    /// variable's declarator, or the node itself
+    fn declaration<'t>(&self, node: tree_sitter::Node<'t>) -> tree_sitter::Node<'t> {
+        match node.parent() {
+            Some(parent) if node.kind() == "variable_declarator" => parent,
+            _ => node,
         }
-        
-        // There's no goto_previous_sibling in tree-sitter, so we need to use a different approach
-        // Look for comments in the source code before the node's start position
-        if comment_node.is_none() {
-            let node_start_position = node.start_position(); // We use this variable later in the function
-            let node_start_byte = node.start_byte();
-            
-            // Use a substring of the source code up to the node start position
-            let preceding_text = &source[..node_start_byte];
-            
-            // Look for the closest JSDoc comment
-            if let Some(last_jsdoc_start) = preceding_text.rfind("/**") {
-                if let Some(last_jsdoc_end) = preceding_text[last_jsdoc_start..].find("*/") {
-                    let full_comment = &preceding_text[last_jsdoc_start..(last_jsdoc_start + last_jsdoc_end + 2)];
-                    
-                    // Check if it's close enough to be related to this node
-                    let comment_lines_count = full_comment.matches('\n').count();
-                    let comment_end_pos = preceding_text[..last_jsdoc_start].matches('\n').count() + comment_lines_count;
-                    
-                    // Check if the comment is immediately before the node (accounting for blank lines)
-                    if node_start_position.row as usize - comment_end_pos <= 2 {
-                        return Some(
-                            full_comment.trim()
-                                .trim_start_matches("/**")
-                                .trim_end_matches("*/")
-                                .lines()
-                                .map(|line| line.trim().trim_start_matches("*").trim())
-                                .collect::<Vec<_>>()
-                                .join("\n")
-                                .trim()
-                                .to_string()
-                        );
-                    }
-                }
-            }
-            
-            return None;
+    }
+    
+    /// The node documentation goes above: an `export` statement wrapping the
+    /// declaration, or the declaration itself
+    fn anchor<'t>(&self, node: tree_sitter::Node<'t>) -> tree_sitter::Node<'t> {
+        let declaration = self.declaration(node);
+        match declaration.parent() {
+            Some(parent) if parent.kind() == "export_statement" => parent,
+            _ => declaration,
+        }
+    }
+    
+    /// Span of a declaration, starting at the line of its anchor
+    fn item_span(&self, node: tree_sitter::Node, offsets: &LineOffsets) -> Range<usize> {
+        offsets.start(self.anchor(node).start_position().row)..self.declaration(node).end_byte()
+    }
+    
+    /// Parameters of a function expression or arrow function, including the single
+    /// unparenthesized parameter of `x => ...`
+    fn function_parameters(&self, function_node: tree_sitter::Node, source: &str) -> Vec<String> {
+        if let Some(parameter) = function_node.child_by_field_name("parameter") {
+            return vec![self.get_node_text(source, parameter.byte_range()).to_string()];
         }
+        let params_node = match function_node.child_by_field_name("parameters") {
+            Some(params_node) => params_node,
+            None => return Vec::new(),
+        };
+        let mut cursor = params_node.walk();
+        let param_nodes: Vec<tree_sitter::Node> = params_node.named_children(&mut cursor).collect();
+        self.extract_parameters(&param_nodes, source)
+    }
+    
+    /// Build a code item for a function value bound to a name: a variable's declarator,
+    /// the value of an `export default` statement, or a field of the class `parent`,
+    /// which is a method
+    fn function_value_item(
+        &self,
+        node: tree_sitter::Node,
+        value: tree_sitter::Node,
+        name: String,
+        parent: Option<String>,
+        content: &str,
+        offsets: &LineOffsets,
+    ) -> CodeItem {
+        let declaration = self.declaration(node);
+        let line_number = declaration.start_position().row + 1; // 1-indexed
+        let end_line = declaration.end_position().row + 1;
+        let (docstring_span, docstring) = self.extract_jsdoc(node, content, offsets).unzip();
         
-        // Extract the comment text if found
-        comment_node.map(|node| {
-            let comment_text = self.get_node_text(source, node.byte_range());
-            
-            // Clean up the comment (remove the /** and */ and trim)
-            comment_text.trim()
-                .trim_start_matches("/**")
-                .trim_end_matches("*/")
-                .lines()
-                .map(|line| line.trim().trim_start_matches("*").trim())
-                .collect::<Vec<_>>()
-                .join("\n")
-                .trim()
-                .to_string()
+        let visibility = match node.child_by_field_name("property") {
+            Some(property) => self.member_visibility(property),
+            None => Visibility::Public,
+        };
+        
+        let mut item = CodeItem {
+            item_type: if parent.is_some() { ItemKind::Method } else { ItemKind::Function },
+            name,
+            line_number,
+            code: self.extract_code_block(content, line_number, end_line),
+            existing_docstring: docstring,
+            parent,
+            parameters: self.function_parameters(value, content),
+            parameter_details: Vec::new(),
+            returns: None,
+            indentation: self.extract_indentation(content, line_number),
+            span: self.item_span(node, offsets),
+            docstring_span,
+            is_async: false,
+            is_generator: value.kind() == "generator_function",
+            decorators: Vec::new(),
+            visibility,
+            throws: Vec::new(),
+        };
+        mark_react_item(&mut item, value, content);
+        item
+    }
+    
+    /// Visibility of a class member from its name: private for `#private` members
+    fn member_visibility(&self, name_node: tree_sitter::Node) -> Visibility {
+        if name_node.kind() == "private_property_identifier" {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        }
+    }
+    
+    /// Extract the JSDoc comment above a declaration, with the span of its lines
+    fn extract_jsdoc(&self, node: tree_sitter::Node, source: &str, offsets: &LineOffsets) -> Option<(Range<usize>, String)> {
+        let line_index = self.anchor(node).start_position().row;
+        let lines: Vec<&str> = source.lines().collect();
+        self.leading_comment(&lines, line_index)
+            .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+    }
+}
+
+/// Whether an expression is a function: an arrow function, function expression, or
+/// generator function
+fn is_function_value(node: tree_sitter::Node) -> bool {
+    matches!(node.kind(), "arrow_function" | "function_expression" | "function" | "generator_function")
+}
+
+/// React kind of a function bound to `name`: a hook if it's named `useX`, a component
+/// if its name is capitalized and its body renders JSX, or None for a plain function
+pub fn react_kind(name: &str, function: tree_sitter::Node) -> Option<ItemKind> {
+    let hook = name.strip_prefix("use")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    if hook {
+        return Some(ItemKind::Hook);
+    }
+    let capitalized = name.chars().next().is_some_and(|c| c.is_ascii_uppercase());
+    let renders = function.child_by_field_name("body").is_some_and(contains_jsx);
+    (capitalized && renders).then_some(ItemKind::Component)
+}
+
+/// Whether a node is, or contains, a JSX element or fragment
+fn contains_jsx(node: tree_sitter::Node) -> bool {
+    if matches!(node.kind(), "jsx_element" | "jsx_self_closing_element" | "jsx_fragment") {
+        return true;
+    }
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).any(contains_jsx);
+    found
+}
+
+/// Names of the props a component destructures from its first parameter
+/// (`{ title, onClick = noop }`), written `props.title`, or None if it takes the
+/// props object whole. TypeScript parameters are looked into for their pattern.
+fn destructured_props(function: tree_sitter::Node, source: &str) -> Option<Vec<String>> {
+    let mut pattern = function.child_by_field_name("parameters")?.named_child(0)?;
+    if let Some(inner) = pattern.child_by_field_name("pattern").or_else(|| pattern.child_by_field_name("left")) {
+        pattern = inner;
+    }
+    if pattern.kind() != "object_pattern" {
+        return None;
+    }
+    
+    let mut cursor = pattern.walk();
+    let props = pattern.named_children(&mut cursor)
+        .filter_map(|property| match property.kind() {
+            "shorthand_property_identifier_pattern" => Some(property),
+            "pair_pattern" => property.child_by_field_name("key"),
+            "object_assignment_pattern" => property.child_by_field_name("left"),
+            _ => None,
         })
+        .map(|name| format!("props.{}", &source[name.byte_range()]))
+        .collect();
+    Some(props)
+}
+
+/// Mark a function item as a React component or hook. A component that destructures
+/// its props is documented as taking one `props` parameter, with each prop listed in
+/// its parameter details.
+pub fn mark_react_item(item: &mut CodeItem, function: tree_sitter::Node, source: &str) {
+    if item.item_type != ItemKind::Function {
+        return;
+    }
+    let kind = match react_kind(&item.name, function) {
+        Some(kind) => kind,
+        None => return,
+    };
+    item.item_type = kind;
+    
+    if kind == ItemKind::Component {
+        if let Some(props) = destructured_props(function, source) {
+            // A TypeScript component's props keep the type it declares for them
+            let annotation = item.parameter_details.first().and_then(|p| p.annotation.clone());
+            item.parameters = vec!["props".to_string()];
+            item.parameter_details = std::iter::once(Parameter { name: "props".to_string(), annotation, default: None })
+                .chain(props.into_iter().map(|name| Parameter { name, annotation: None, default: None }))
+                .collect();
+        }
     }
 }
 
@@ -173,28 +300,27 @@ impl LanguageParser for JavaScriptParser {
         // Parse the JavaScript code using tree-sitter
         // Since Parser doesn't implement Clone, we create a new one each time
         let mut parser = Parser::new();
-        let language = unsafe { tree_sitter_javascript() };
-        parser.set_language(language).expect("Failed to load JavaScript grammar");
+        parser.set_language(self.language).expect("Failed to load JavaScript grammar");
         
         let tree = parser.parse(content, None)
             .ok_or_else(|| DocGenError::ParsingError("Failed to parse JavaScript code".into()))?;
         
         let root_node = tree.root_node();
-        // We don't need cursor here, removing it
+        let offsets = LineOffsets::new(content);
         
         // Query to find function and class declarations
         let function_query = Query::new(
-            unsafe { tree_sitter_javascript() },
+            self.language,
             "(function_declaration name: (identifier) @function_name) @function"
         ).map_err(|e| DocGenError::ParsingError(format!("Failed to create function query: {}", e)))?;
         
         let method_query = Query::new(
-            unsafe { tree_sitter_javascript() },
-            "(method_definition name: (property_identifier) @method_name) @method"
+            self.language,
+            "(method_definition name: [(property_identifier) (private_property_identifier)] @method_name) @method"
         ).map_err(|e| DocGenError::ParsingError(format!("Failed to create method query: {}", e)))?;
         
         let class_query = Query::new(
-            unsafe { tree_sitter_javascript() },
+            self.language,
             "(class_declaration name: (identifier) @class_name) @class"
         ).map_err(|e| DocGenError::ParsingError(format!("Failed to create class query: {}", e)))?;
         
@@ -204,11 +330,11 @@ impl LanguageParser for JavaScriptParser {
         
         for function_match in function_matches {
             for capture in function_match.captures {
-                if capture.index == 0 { // @function capture
+                if function_query.capture_names()[capture.index as usize] == "function" {
                     let function_node = capture.node;
-                    let name_node = &function_query.capture_names()[1]; // @function_name - borrow instead of moving
+                    let name_node = "function_name";
                     
-                    if let Some(name_capture) = function_match.captures.iter().find(|c| &function_query.capture_names()[c.index as usize] == name_node) {
+                    if let Some(name_capture) = function_match.captures.iter().find(|c| function_query.capture_names()[c.index as usize] == name_node) {
                         let function_name = self.get_node_text(content, name_capture.node.byte_range()).to_string();
                         let start_position = function_node.start_position();
                         let end_position = function_node.end_position();
@@ -237,19 +363,29 @@ impl LanguageParser for JavaScriptParser {
                         };
                         
                         // Extract JSDoc comment
-                        let docstring = self.extract_jsdoc(function_node, content);
+                        let (docstring_span, docstring) = self.extract_jsdoc(function_node, content, &offsets).unzip();
                         
-                        code_items.push(CodeItem {
-                            item_type: "function".to_string(),
+                        let mut item = CodeItem {
+                            item_type: ItemKind::Function,
                             name: function_name,
                             line_number,
                             code: self.extract_code_block(content, line_number, end_line),
                             existing_docstring: docstring,
                             parent: None,
                             parameters: params,
+                            parameter_details: Vec::new(),
                             returns: None,
                             indentation: self.extract_indentation(content, line_number),
-                        });
+                            span: self.item_span(function_node, &offsets),
+                            docstring_span,
+                            is_async: false,
+                            is_generator: false,
+                            decorators: Vec::new(),
+                            visibility: Visibility::Public,
+                            throws: Vec::new(),
+                        };
+                        mark_react_item(&mut item, function_node, content);
+                        code_items.push(item);
                     }
                 }
             }
@@ -261,11 +397,11 @@ impl LanguageParser for JavaScriptParser {
         
         for class_match in class_matches {
             for capture in class_match.captures {
-                if capture.index == 0 { // @class capture
+                if class_query.capture_names()[capture.index as usize] == "class" {
                     let class_node = capture.node;
-                    let name_node = &class_query.capture_names()[1]; // @class_name - borrow instead of moving
+                    let name_node = "class_name";
                     
-                    if let Some(name_capture) = class_match.captures.iter().find(|c| &class_query.capture_names()[c.index as usize] == name_node) {
+                    if let Some(name_capture) = class_match.captures.iter().find(|c| class_query.capture_names()[c.index as usize] == name_node) {
                         let class_name = self.get_node_text(content, name_capture.node.byte_range()).to_string();
                         let start_position = class_node.start_position();
                         let end_position = class_node.end_position();
@@ -273,18 +409,26 @@ impl LanguageParser for JavaScriptParser {
                         let end_line = end_position.row + 1;
                         
                         // Extract JSDoc comment
-                        let docstring = self.extract_jsdoc(class_node, content);
+                        let (docstring_span, docstring) = self.extract_jsdoc(class_node, content, &offsets).unzip();
                         
                         code_items.push(CodeItem {
-                            item_type: "class".to_string(),
+                            item_type: ItemKind::Class,
                             name: class_name.clone(),
                             line_number,
                             code: self.extract_code_block(content, line_number, end_line),
                             existing_docstring: docstring,
                             parent: None,
                             parameters: Vec::new(),
+                            parameter_details: Vec::new(),
                             returns: None,
                             indentation: self.extract_indentation(content, line_number),
+                            span: self.item_span(class_node, &offsets),
+                            docstring_span,
+                            is_async: false,
+                            is_generator: false,
+                            decorators: Vec::new(),
+                            visibility: Visibility::Public,
+                            throws: Vec::new(),
                         });
                         
                         // Now process methods within the class
@@ -294,11 +438,11 @@ impl LanguageParser for JavaScriptParser {
                             
                             for method_match in method_matches {
                                 for method_capture in method_match.captures {
-                                    if method_capture.index == 0 { // @method capture
+                                    if method_query.capture_names()[method_capture.index as usize] == "method" {
                                         let method_node = method_capture.node;
-                                        let method_name_node = &method_query.capture_names()[1]; // @method_name - borrow instead of moving
+                                        let method_name_node = "method_name";
                                         
-                                        if let Some(method_name_capture) = method_match.captures.iter().find(|c| &method_query.capture_names()[c.index as usize] == method_name_node) {
+                                        if let Some(method_name_capture) = method_match.captures.iter().find(|c| method_query.capture_names()[c.index as usize] == method_name_node) {
                                             let method_name = self.get_node_text(content, method_name_capture.node.byte_range()).to_string();
                                             let method_start = method_node.start_position();
                                             let method_end = method_node.end_position();
@@ -327,144 +471,115 @@ impl LanguageParser for JavaScriptParser {
                                             };
                                             
                                             // Extract JSDoc comment
-                                            let docstring = self.extract_jsdoc(method_node, content);
+                                            let (docstring_span, docstring) = self.extract_jsdoc(method_node, content, &offsets).unzip();
                                             
                                             code_items.push(CodeItem {
-                                                item_type: "method".to_string(),
+                                                item_type: ItemKind::Method,
                                                 name: method_name,
                                                 line_number: method_line,
                                                 code: self.extract_code_block(content, method_line, method_end_line),
                                                 existing_docstring: docstring,
                                                 parent: Some(class_name.clone()),
                                                 parameters: params,
+                                                parameter_details: Vec::new(),
                                                 returns: None,
                                                 indentation: self.extract_indentation(content, method_line),
+                                                span: self.item_span(method_node, &offsets),
+                                                docstring_span,
+                                                is_async: false,
+                                                is_generator: false,
+                                                decorators: Vec::new(),
+                                                visibility: self.member_visibility(method_name_capture.node),
+                                                throws: Vec::new(),
                                             });
                                         }
                                     }
                                 }
                             }
+                            
+                            // Class fields holding functions (`handle = (event) => {...}`)
+                            // are documented as methods
+                            let mut field_cursor = class_body.walk();
+                            for field in class_body.named_children(&mut field_cursor).filter(|n| n.kind() == "field_definition") {
+                                if let (Some(property), Some(value)) = (field.child_by_field_name("property"), field.child_by_field_name("value")) {
+                                    if is_function_value(value) {
+                                        let name = self.get_node_text(content, property.byte_range()).to_string();
+                                        code_items.push(self.function_value_item(field, value, name, Some(class_name.clone()), content, &offsets));
+                                    }
+                                }
+                            }
                         }
                     }
                 }
             }
         }
         
+        // Functions bound to top-level variables (`const add = (a, b) => a + b`), and
+        // anonymous `export default` functions
+        let mut cursor = root_node.walk();
+        for child in root_node.named_children(&mut cursor) {
+            let declaration = match child.kind() {
+                "export_statement" => match (child.child_by_field_name("declaration"), child.child_by_field_name("value")) {
+                    (Some(declaration), _) => declaration,
+                    (None, Some(value)) if is_function_value(value) => {
+                        let name = value.child_by_field_name("name")
+                            .map_or_else(|| "default".to_string(), |n| self.get_node_text(content, n.byte_range()).to_string());
+                        code_items.push(self.function_value_item(value, value, name, None, content, &offsets));
+                        continue;
+                    },
+                    _ => continue,
+                },
+                _ => child,
+            };
+            
+            // A declaration of several names has no one place for a comment per function
+            if !matches!(declaration.kind(), "lexical_declaration" | "variable_declaration") || declaration.named_child_count() != 1 {
+                continue;
+            }
+            let declarator = match declaration.named_child(0) {
+                Some(declarator) => declarator,
+                None => continue,
+            };
+            if let (Some(name), Some(value)) = (declarator.child_by_field_name("name"), declarator.child_by_field_name("value")) {
+                if name.kind() == "identifier" && is_function_value(value) {
+                    let name = self.get_node_text(content, name.byte_range()).to_string();
+                    code_items.push(self.function_value_item(declarator, value, name, None, content, &offsets));
+                }
+            }
+        }
+        
         Ok(ParsedCode {
             items: code_items,
             original_content: content.to_string(),
+            language: "javascript".to_string(),
+            syntax_errors: super::syntax_errors(root_node),
         })
     }
     
     fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
-        let mut new_content = content.to_string();
-        
-        // Get access to the parsed code items for more accurate updates
-        let parsed_code = self.parse(&new_content)?;
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
         
-        // Sort updates in reverse order by line number to avoid line number shifts
-        let mut sorted_updates = updated_docstrings.to_vec();
-        sorted_updates.sort_by(|a, b| {
-            let a_line = parsed_code.items[a.item_index].line_number;
-            let b_line = parsed_code.items[b.item_index].line_number;
-            b_line.cmp(&a_line)
-        });
-        
-        for update in sorted_updates {
-            let item = &parsed_code.items[update.item_index];
-            let lines: Vec<&str> = new_content.lines().collect();
-            
-            // Get the line that defines the function/class/method
-            let line_index = item.line_number - 1; // Convert to 0-based index
-            
-            if line_index >= lines.len() {
-                return Err(DocGenError::UpdateError(
-                    format!("Line number {} is out of bounds", item.line_number)));
-            }
-            
-            // Unused variable - just remove the prefix and add underscore to avoid warning
-            let _def_line = lines[line_index];
-            
-            // Get indentation level from the definition line
-            let indentation = item.indentation.clone();
-            
-            // Check if there's an existing docstring to replace
-            let mut has_existing_docstring = false;
-            let mut docstring_start_line = line_index;
-            let mut docstring_end_line = line_index;
-            
-            // Look for existing JSDoc comment
-            for i in (0..line_index).rev() {
-                let line = lines[i].trim();
-                if line.starts_with("/**") {
-                    has_existing_docstring = true;
-                    docstring_start_line = i;
-                    
-                    // Find the end of the JSDoc comment
-                    for j in i..line_index {
-                        if lines[j].trim().contains("*/") {
-                            docstring_end_line = j;
-                            break;
-                        }
-                    }
-                    break;
-                } else if !line.is_empty() && !line.starts_with("//") {
-                    // We found a non-comment, non-empty line, so there's no preceding docstring
-                    break;
-                }
-            }
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
             
             // Format the JSDoc comment
-            let mut jsdoc_lines = Vec::new();
-            jsdoc_lines.push(format!("{}/**", indentation));
-            
-            // Add docstring lines with proper indentation
-            for line in update.new_docstring.lines() {
+            let mut jsdoc = format!("{}/**\n", item.indentation);
+            for line in update.new_docstring.trim().lines() {
                 let trimmed = line.trim();
                 if !trimmed.is_empty() {
-                    jsdoc_lines.push(format!("{} * {}", indentation, trimmed));
+                    jsdoc.push_str(&format!("{} * {}\n", item.indentation, trimmed));
                 } else {
-                    jsdoc_lines.push(format!("{} *", indentation));
+                    jsdoc.push_str(&format!("{} *\n", item.indentation));
                 }
             }
+            jsdoc.push_str(&format!("{} */\n", item.indentation));
             
-            jsdoc_lines.push(format!("{} */", indentation));
-            let formatted_jsdoc = jsdoc_lines.join("\n");
-            
-            // Update the content
-            if has_existing_docstring {
-                // Replace existing JSDoc comment
-                let before = if docstring_start_line > 0 {
-                    lines[..docstring_start_line].join("\n")
-                } else {
-                    String::new()
-                };
-                
-                let after = if docstring_end_line + 1 < lines.len() {
-                    format!("\n{}", lines[(docstring_end_line + 1)..].join("\n"))
-                } else {
-                    String::new()
-                };
-                
-                new_content = format!("{}\n{}{}", before, formatted_jsdoc, after);
-            } else {
-                // Insert new JSDoc comment before the definition
-                let before = if line_index > 0 {
-                    lines[..line_index].join("\n")
-                } else {
-                    String::new()
-                };
-                
-                let after = if line_index < lines.len() {
-                    format!("\n{}", lines[line_index..].join("\n"))
-                } else {
-                    String::new()
-                };
-                
-                new_content = format!("{}\n{}{}", before, formatted_jsdoc, after);
-            }
+            // Replace an existing JSDoc comment, or insert above the definition
+            edits.push(Edit::new(item.doc_range(), jsdoc));
         }
         
-        Ok(new_content)
+        apply_edits(content, edits)
     }
-}
\ No newline at end of file
+}
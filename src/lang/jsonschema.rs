@@ -0,0 +1,389 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+
+/// JSON Schema parser implementation, also covering the schemas of Swagger and
+/// OpenAPI documents written in JSON. Schemas and their properties are documented
+/// with a `description` key.
+///
+/// The document is scanned for byte offsets rather than deserialized so that updates
+/// can be spliced into the file without reformatting it or reordering its keys.
+pub struct JsonSchemaParser;
+
+/// A JSON value, keeping only the structure of objects
+enum JsonValue {
+    Object(JsonObject),
+    Other,
+}
+
+/// A JSON object with the byte offsets of its braces and members
+struct JsonObject {
+    open: usize,
+    close: usize,
+    members: Vec<JsonMember>,
+}
+
+/// One `"key": value` member of an object
+struct JsonMember {
+    key: String,
+    key_start: usize,
+    key_end: usize,
+    value_start: usize,
+    value_end: usize,
+    value: JsonValue,
+}
+
+/// Where to insert a description into a schema that has none
+struct Placement {
+    /// Byte offset a new member is inserted at
+    insert_at: usize,
+    /// Whether the new member goes after an existing one rather than before it
+    after_member: bool,
+    /// Indentation of the object's members, when they are on separate lines
+    member_indent: Option<String>,
+    /// Text between a key and its value, such as `": "`
+    separator: String,
+    /// Text between members on the same line, such as `", "`
+    delimiter: String,
+    /// Whether the object has no members yet
+    empty: bool,
+}
+
+/// Recursive-descent scanner over the document's bytes
+struct Scanner<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn error(&self, message: &str) -> DocGenError {
+        let line = self.text[..self.pos.min(self.text.len())].matches('\n').count() + 1;
+        DocGenError::ParsingError(format!("Invalid JSON at line {}: {}", line, message))
+    }
+    
+    fn skip_whitespace(&mut self) {
+        while self.text[self.pos..].starts_with(|c: char| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+    
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+    
+    fn value(&mut self) -> DocGenResult<JsonValue> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.object().map(JsonValue::Object),
+            Some(b'[') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.peek() != Some(b']') {
+                    loop {
+                        self.value()?;
+                        self.skip_whitespace();
+                        match self.peek() {
+                            Some(b',') => self.pos += 1,
+                            Some(b']') => break,
+                            _ => return Err(self.error("expected ',' or ']'")),
+                        }
+                    }
+                }
+                self.pos += 1;
+                Ok(JsonValue::Other)
+            },
+            Some(b'"') => self.string().map(|_| JsonValue::Other),
+            Some(_) => {
+                // Numbers, true, false, and null run until a delimiter
+                let length = self.text[self.pos..]
+                    .find(|c: char| c == ',' || c == '}' || c == ']' || c.is_ascii_whitespace())
+                    .unwrap_or(self.text.len() - self.pos);
+                if length == 0 {
+                    return Err(self.error("expected a value"));
+                }
+                self.pos += length;
+                Ok(JsonValue::Other)
+            },
+            None => Err(self.error("unexpected end of document")),
+        }
+    }
+    
+    fn object(&mut self) -> DocGenResult<JsonObject> {
+        let open = self.pos;
+        self.pos += 1;
+        let mut members = Vec::new();
+        
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonObject { open, close: self.pos - 1, members });
+        }
+        
+        loop {
+            self.skip_whitespace();
+            let key_start = self.pos;
+            let key = self.string()?;
+            let key_end = self.pos;
+            self.skip_whitespace();
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected ':'"));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let value_start = self.pos;
+            let value = self.value()?;
+            members.push(JsonMember { key, key_start, key_end, value_start, value_end: self.pos, value });
+            
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => break,
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+        
+        let close = self.pos;
+        self.pos += 1;
+        Ok(JsonObject { open, close, members })
+    }
+    
+    fn string(&mut self) -> DocGenResult<String> {
+        if self.peek() != Some(b'"') {
+            return Err(self.error("expected a string"));
+        }
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                Some(b'\\') => self.pos += 2,
+                Some(b'"') => break,
+                Some(_) => self.pos += 1,
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+        self.pos += 1;
+        serde_json::from_str(&self.text[start..self.pos]).map_err(|e| self.error(&e.to_string()))
+    }
+}
+
+impl JsonObject {
+    fn get(&self, key: &str) -> Option<&JsonMember> {
+        self.members.iter().find(|m| m.key == key)
+    }
+    
+    fn get_object(&self, key: &str) -> Option<&JsonObject> {
+        match self.get(key).map(|m| &m.value) {
+            Some(JsonValue::Object(object)) => Some(object),
+            _ => None,
+        }
+    }
+    
+    /// Members whose values are objects
+    fn object_members(&self) -> impl Iterator<Item = (&JsonMember, &JsonObject)> {
+        self.members.iter().filter_map(|m| match &m.value {
+            JsonValue::Object(object) => Some((m, object)),
+            _ => None,
+        })
+    }
+}
+
+/// Collects the documentable schemas of a document as it walks them
+struct SchemaWalker<'a> {
+    content: &'a str,
+    schemas: Vec<(CodeItem, Placement)>,
+}
+
+impl<'a> SchemaWalker<'a> {
+    /// Decoded value of a string member
+    fn string_value(&self, member: &JsonMember) -> Option<String> {
+        let value = &self.content[member.value_start..member.value_end];
+        serde_json::from_str::<String>(value).ok()
+    }
+    
+    /// Record a schema and descend into the schemas nested in it. Array items and
+    /// additional properties (no `item_type`) are not documented themselves, but
+    /// their properties are.
+    fn walk(&mut self, schema: &JsonObject, member: Option<&JsonMember>, name: &str, parent: Option<&str>, item_type: Option<ItemKind>) {
+        // A description next to `$ref` is ignored by most tools
+        if schema.get("$ref").is_some() {
+            return;
+        }
+        
+        if let Some(item_type) = item_type {
+            let start = member.map_or(schema.open, |m| m.key_start);
+            let line_index = self.content[..start].matches('\n').count();
+            let line = self.content.lines().nth(line_index).unwrap_or("");
+            let description = schema.get("description");
+            let existing_docstring = description
+                .and_then(|m| self.string_value(m))
+                .filter(|d| !d.trim().is_empty());
+            // Only a string description is replaced; anything else is left alone
+            let docstring_span = description
+                .filter(|m| self.content[m.value_start..].starts_with('"'))
+                .map(|m| m.value_start..m.value_end);
+            
+            self.schemas.push((
+                CodeItem {
+                    item_type,
+                    name: member.map_or(name, |m| m.key.as_str()).to_string(),
+                    line_number: line_index + 1,
+                    code: self.content[start..=schema.close].to_string(),
+                    span: start..schema.close + 1,
+                    docstring_span,
+                    is_async: false,
+                    is_generator: false,
+                    decorators: Vec::new(),
+                    visibility: Visibility::Public,
+                    throws: Vec::new(),
+                    existing_docstring,
+                    parent: parent.map(|p| p.to_string()),
+                    parameters: Vec::new(),
+                    parameter_details: Vec::new(),
+                    returns: None,
+                    indentation: line.chars().take_while(|c| c.is_whitespace()).collect(),
+                },
+                self.placement(schema),
+            ));
+        }
+        
+        for (key, nested) in schema.object_members() {
+            match key.key.as_str() {
+                "properties" | "patternProperties" => {
+                    for (property, property_schema) in nested.object_members() {
+                        let full_name = format!("{}.{}", name, property.key);
+                        self.walk(property_schema, Some(property), &full_name, Some(name), Some(ItemKind::Property));
+                    }
+                },
+                "definitions" | "$defs" => {
+                    for (definition, definition_schema) in nested.object_members() {
+                        self.walk(definition_schema, Some(definition), &definition.key, None, Some(ItemKind::Schema));
+                    }
+                },
+                "items" | "additionalProperties" => self.walk(nested, None, name, parent, None),
+                _ => {},
+            }
+        }
+    }
+    
+    /// Where a schema object's description is, or goes: after its `title` if it has
+    /// one, and otherwise first, matching the layout of its other members
+    fn placement(&self, schema: &JsonObject) -> Placement {
+        let content = self.content;
+        let separator = schema.members.first()
+            .map_or(": ".to_string(), |m| content[m.key_end..m.value_start].to_string());
+        // With a single member, compact `"key":value` documents are assumed to be
+        // compact throughout
+        let delimiter = match schema.members.get(1) {
+            Some(second) => content[schema.members[0].value_end..second.key_start].to_string(),
+            None if separator == ":" => ",".to_string(),
+            None => ", ".to_string(),
+        };
+        let member_indent = schema.members.first()
+            .filter(|m| content[schema.open..m.key_start].contains('\n'))
+            .map(|m| {
+                let line_start = content[..m.key_start].rfind('\n').map_or(0, |i| i + 1);
+                content[line_start..m.key_start].to_string()
+            });
+        
+        let (insert_at, after_member) = match (schema.get("title"), schema.members.first()) {
+            (Some(title), _) => (title.value_end, true),
+            (None, Some(first)) => (first.key_start, false),
+            (None, None) => (schema.close, false),
+        };
+        
+        Placement {
+            insert_at,
+            after_member,
+            member_indent,
+            separator,
+            delimiter,
+            empty: schema.members.is_empty(),
+        }
+    }
+}
+
+impl Default for JsonSchemaParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonSchemaParser {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    /// Every documentable schema in the document, with where its description goes
+    fn schemas(&self, content: &str) -> DocGenResult<Vec<(CodeItem, Placement)>> {
+        let mut scanner = Scanner { text: content, pos: 0 };
+        let root = match scanner.value()? {
+            JsonValue::Object(root) => root,
+            JsonValue::Other => return Ok(Vec::new()),
+        };
+        let mut walker = SchemaWalker { content, schemas: Vec::new() };
+        
+        // Swagger and OpenAPI documents keep their schemas in a named collection; a
+        // JSON Schema document is a schema itself
+        if root.get("swagger").is_some() || root.get("openapi").is_some() {
+            let collection = root.get_object("definitions")
+                .or_else(|| root.get_object("components").and_then(|c| c.get_object("schemas")));
+            for (member, schema) in collection.into_iter().flat_map(|c| c.object_members()) {
+                walker.walk(schema, Some(member), &member.key, None, Some(ItemKind::Schema));
+            }
+        } else if ["$schema", "type", "properties"].iter().any(|key| root.get(key).is_some()) {
+            let title = root.get("title")
+                .and_then(|m| walker.string_value(m))
+                .unwrap_or_else(|| "schema".to_string());
+            walker.walk(&root, None, &title, None, Some(ItemKind::Schema));
+        }
+        
+        Ok(walker.schemas)
+    }
+}
+
+impl LanguageParser for JsonSchemaParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let items = self.schemas(content)?.into_iter().map(|(item, _)| item).collect();
+        
+        Ok(ParsedCode {
+            items,
+            original_content: content.to_string(),
+            language: "json-schema".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let schemas = self.schemas(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let (item, placement) = schemas.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            // Descriptions are a single line of JSON string
+            let text = update.new_docstring.split_whitespace().collect::<Vec<_>>().join(" ");
+            let value = serde_json::to_string(&text)
+                .map_err(|e| DocGenError::UpdateError(e.to_string()))?;
+            
+            let edit = match &item.docstring_span {
+                Some(span) => Edit::new(span.clone(), value),
+                None => {
+                    let member = format!("\"description\"{}{}", placement.separator, value);
+                    let inserted = match (&placement.member_indent, placement.after_member) {
+                        (Some(indent), true) => format!(",\n{}{}", indent, member),
+                        (Some(indent), false) => format!("{},\n{}", member, indent),
+                        (None, true) => format!("{}{}", placement.delimiter, member),
+                        (None, false) if placement.empty => member,
+                        (None, false) => format!("{}{}", member, placement.delimiter),
+                    };
+                    Edit::new(placement.insert_at..placement.insert_at, inserted)
+                },
+            };
+            edits.push(edit);
+        }
+        
+        apply_edits(content, edits)
+    }
+}
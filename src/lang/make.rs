@@ -0,0 +1,341 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Build script parser implementation for Makefile targets and CMake functions and
+/// macros, documented with `#` comment headers describing purpose, arguments or
+/// variables, and usage
+pub struct MakeParser {
+    language: &'static str,
+    target_re: Regex,
+    command_re: Regex,
+    variable_re: Regex,
+}
+
+/// Variables set by make itself rather than by the user
+const BUILTIN_VARIABLES: &[&str] = &["MAKE", "MAKEFLAGS", "MAKECMDGOALS", "MAKEFILE_LIST", "CURDIR", "SHELL", "MAKELEVEL"];
+
+impl Default for MakeParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MakeParser {
+    /// Parser for Makefiles
+    pub fn new() -> Self {
+        Self::with_language("makefile")
+    }
+    
+    /// Parser for CMake scripts (`CMakeLists.txt`, `*.cmake`)
+    pub fn cmake() -> Self {
+        Self::with_language("cmake")
+    }
+    
+    fn with_language(language: &'static str) -> Self {
+        Self {
+            language,
+            // A rule line: targets, then a single or double colon
+            target_re: Regex::new(r"^([^\s:=#][^:=#]*?)\s*::?(.*)$").unwrap(),
+            command_re: Regex::new(r"(?i)^(function|macro)\s*\(\s*([\w.-]+)").unwrap(),
+            variable_re: Regex::new(r"\$[({]([A-Z_][A-Z0-9_]*)[)}]").unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Index of a `.PHONY:` line declaring a target directly above its rule, or the
+    /// rule itself. The header goes above the declaration so the two stay together.
+    fn phony_start(&self, lines: &[&str], line_index: usize, name: &str) -> usize {
+        if line_index == 0 {
+            return line_index;
+        }
+        let previous = lines[line_index - 1].trim();
+        let declares = previous.strip_prefix(".PHONY")
+            .and_then(|rest| rest.trim_start().strip_prefix(':'))
+            .is_some_and(|targets| name.split_whitespace().any(|n| targets.split_whitespace().any(|t| t == n)));
+        if declares { line_index - 1 } else { line_index }
+    }
+    
+    /// Collect the `#` comment block directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 {
+            let previous = lines[start - 1].trim_start();
+            if !previous.starts_with('#') || previous.starts_with("#!") {
+                break;
+            }
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| l.trim().trim_start_matches('#').trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Last line of a logical line continued with trailing backslashes
+    fn continuation_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut end = line_index;
+        while end + 1 < lines.len() && lines[end].trim_end().ends_with('\\') {
+            end += 1;
+        }
+        end
+    }
+    
+    /// Makefile targets with their recipes
+    fn parse_makefile(&self, lines: &[&str], offsets: &LineOffsets) -> Vec<CodeItem> {
+        let mut code_items = Vec::new();
+        let mut in_define = false;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            // Multi-line variable definitions can contain anything
+            let code = line.trim();
+            if in_define {
+                in_define = !code.starts_with("endef");
+                continue;
+            }
+            if code.starts_with("define ") {
+                in_define = true;
+                continue;
+            }
+            
+            // Recipes are indented with tabs; rules start in column one
+            if line.starts_with(char::is_whitespace) || code.starts_with('#') {
+                continue;
+            }
+            let caps = match self.target_re.captures(line) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            // `VAR := value` and target-specific `target: VAR = value` are assignments
+            let rest = caps[2].split('#').next().unwrap_or("");
+            if rest.contains('=') {
+                continue;
+            }
+            let name = caps[1].trim().to_string();
+            // Special targets (.PHONY, .SUFFIXES) and pattern rules are not user-facing
+            if name.starts_with('.') || name.contains('%') || name.starts_with("$(") {
+                continue;
+            }
+            
+            let mut end = self.continuation_end(lines, line_index);
+            while end + 1 < lines.len() && (lines[end + 1].starts_with('\t') || lines[end + 1].trim().is_empty()) {
+                if lines[end + 1].trim().is_empty() && !lines.get(end + 2).is_some_and(|l| l.starts_with('\t')) {
+                    break;
+                }
+                end += 1;
+            }
+            let code = lines[line_index..=end].join("\n");
+            
+            // Variables the rule reads are what a user can override on the command line
+            let mut variables: Vec<String> = Vec::new();
+            for caps in self.variable_re.captures_iter(&code) {
+                let variable = caps[1].to_string();
+                if !BUILTIN_VARIABLES.contains(&variable.as_str()) && !variables.contains(&variable) {
+                    variables.push(variable);
+                }
+            }
+            
+            // A `target: ## description` comment documents the target for `make help`
+            let inline_doc = line.split_once("##").map(|(_, text)| text.trim().to_string()).filter(|t| !t.is_empty());
+            let anchor = self.phony_start(lines, line_index, &name);
+            let (docstring_span, existing_docstring) = match self.leading_comment(lines, anchor) {
+                Some((start, text)) => (Some(offsets.lines(start, anchor - 1)), Some(text)),
+                None => match (line.find("##"), inline_doc) {
+                    (Some(pos), Some(text)) => {
+                        let line_start = offsets.start(line_index);
+                        (Some(line_start + pos..line_start + line.trim_end().len()), Some(text))
+                    },
+                    _ => (None, None),
+                },
+            };
+            
+            code_items.push(CodeItem {
+                item_type: ItemKind::Target,
+                name,
+                line_number: line_index + 1,
+                code,
+                existing_docstring,
+                parent: None,
+                parameters: variables,
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: String::new(),
+                span: offsets.lines(anchor, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        code_items
+    }
+    
+    /// CMake functions and macros with their bodies
+    fn parse_cmake(&self, lines: &[&str], offsets: &LineOffsets) -> Vec<CodeItem> {
+        let mut code_items = Vec::new();
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            let caps = match self.command_re.captures(line.trim()) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let kind = caps[1].to_lowercase();
+            let name = caps[2].to_string();
+            
+            // The end command closes the nearest matching definition
+            let end_command = format!("end{}", kind);
+            let end = (line_index + 1..lines.len())
+                .find(|&i| lines[i].trim().to_lowercase().starts_with(&end_command))
+                .unwrap_or(lines.len() - 1);
+            let code = lines[line_index..=end].join("\n");
+            
+            // Named arguments, then the keywords parsed out of ARGN
+            let signature = code.split(')').next().unwrap_or("");
+            let mut parameters: Vec<String> = signature.split_whitespace()
+                .skip_while(|w| !w.ends_with(&name))
+                .skip(1)
+                .map(|w| w.trim_matches(|c: char| c == '(' || c == ')').to_string())
+                .filter(|w| !w.is_empty())
+                .collect();
+            for keyword in self.parsed_keywords(&code) {
+                if !parameters.contains(&keyword) {
+                    parameters.push(keyword);
+                }
+            }
+            
+            let (docstring_span, existing_docstring) = self.leading_comment(lines, line_index)
+                .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: kind.parse().expect("command pattern only matches item kinds"),
+                name,
+                line_number: line_index + 1,
+                code,
+                existing_docstring,
+                parent: None,
+                parameters,
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        code_items
+    }
+    
+    /// Option and keyword names declared by a `cmake_parse_arguments` call
+    fn parsed_keywords(&self, code: &str) -> Vec<String> {
+        let lower = code.to_lowercase();
+        let start = match lower.find("cmake_parse_arguments(") {
+            Some(start) => start + "cmake_parse_arguments(".len(),
+            None => return Vec::new(),
+        };
+        let call = code[start..].split(')').next().unwrap_or("");
+        
+        // Split into arguments, keeping quoted lists together
+        let mut arguments = Vec::new();
+        let mut current = String::new();
+        let mut quoted = false;
+        for c in call.chars() {
+            match c {
+                '"' => quoted = !quoted,
+                c if c.is_whitespace() && !quoted => {
+                    if !current.is_empty() {
+                        arguments.push(std::mem::take(&mut current));
+                    }
+                },
+                c => current.push(c),
+            }
+        }
+        if !current.is_empty() {
+            arguments.push(current);
+        }
+        
+        // cmake_parse_arguments(<prefix> <options> <one_value> <multi_value> ...) or
+        // cmake_parse_arguments(PARSE_ARGV <N> <prefix> <options> ...)
+        let skip = if arguments.first().is_some_and(|a| a == "PARSE_ARGV") { 3 } else { 1 };
+        arguments.iter()
+            .skip(skip)
+            .take(3)
+            .flat_map(|list| list.split(';'))
+            .filter(|keyword| !keyword.is_empty() && !keyword.starts_with("${"))
+            .map(|keyword| keyword.to_string())
+            .collect()
+    }
+}
+
+impl LanguageParser for MakeParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let items = if self.language == "cmake" {
+            self.parse_cmake(&lines, &offsets)
+        } else {
+            self.parse_makefile(&lines, &offsets)
+        };
+        
+        Ok(ParsedCode {
+            items,
+            original_content: content.to_string(),
+            language: self.language.to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim_end();
+                    if trimmed.trim().is_empty() {
+                        format!("{}#\n", item.indentation)
+                    } else {
+                        format!("{}# {}\n", item.indentation, trimmed)
+                    }
+                })
+                .collect();
+            
+            // The comment goes above a `.PHONY` line declaring the target, replacing a
+            // comment block there; a `## description` on the rule line is left alone
+            let leading = item.docstring_span.clone()
+                .filter(|span| span.end <= item.span.start)
+                .unwrap_or(item.span.start..item.span.start);
+            edits.push(Edit::new(leading, comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,177 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// MATLAB parser implementation for functions, documented with help text: the
+/// `%` comment block directly below the function line, starting with an H1 line
+pub struct MatlabParser {
+    function_re: Regex,
+}
+
+impl Default for MatlabParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MatlabParser {
+    pub fn new() -> Self {
+        Self {
+            function_re: Regex::new(
+                r"^function\s+(?:(\[[^\]]*\]|\w+)\s*=\s*)?([\w.]+)\s*(?:\(([^)]*)\))?"
+            ).unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Find the last line of a declaration continued with `...`
+    fn declaration_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut end = line_index;
+        while end + 1 < lines.len() && lines[end].split('%').next().unwrap_or("").trim_end().ends_with("...") {
+            end += 1;
+        }
+        end
+    }
+    
+    /// Collect the help text directly below a function declaration, returning the
+    /// index just past its last line. Both `%` lines and `%{ ... %}` blocks count.
+    fn help_text(&self, lines: &[&str], first: usize) -> Option<(usize, String)> {
+        let opening = lines.get(first)?.trim();
+        let (end, body): (usize, Vec<&str>) = if opening == "%{" {
+            let close = (first + 1..lines.len()).find(|&i| lines[i].trim() == "%}")?;
+            (close + 1, lines[first + 1..close].iter().map(|l| l.trim()).collect())
+        } else if opening.starts_with('%') {
+            let mut end = first;
+            while end < lines.len() && lines[end].trim_start().starts_with('%') {
+                end += 1;
+            }
+            (end, lines[first..end].iter().map(|l| l.trim().trim_start_matches('%').trim()).collect())
+        } else {
+            return None;
+        };
+        
+        Some((end, body.join("\n").trim().to_string()))
+    }
+    
+    /// Split an argument or output list into names, skipping ignored `~` inputs
+    fn split_names(&self, list: &str) -> Vec<String> {
+        list.trim_matches(|c| c == '[' || c == ']')
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(|n| n.trim_end_matches("...").trim())
+            .filter(|n| !n.is_empty() && *n != "~")
+            .map(|n| n.to_string())
+            .collect()
+    }
+}
+
+impl LanguageParser for MatlabParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        let declarations: Vec<usize> = lines.iter()
+            .enumerate()
+            .filter(|(_, l)| self.function_re.is_match(l.trim()))
+            .map(|(i, _)| i)
+            .collect();
+        
+        for (n, &line_index) in declarations.iter().enumerate() {
+            let declaration_end = self.declaration_end(&lines, line_index);
+            let declaration = lines[line_index..=declaration_end]
+                .iter()
+                .map(|l| l.split('%').next().unwrap_or("").trim().trim_end_matches("..."))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let caps = match self.function_re.captures(&declaration) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            
+            let outputs = caps.get(1).map(|o| self.split_names(o.as_str())).unwrap_or_default();
+            let parameters = caps.get(3).map(|p| self.split_names(p.as_str())).unwrap_or_default();
+            
+            // A function runs until the next one in the file (MATLAB does not require `end`)
+            let end = declarations.get(n + 1).map_or(lines.len(), |next| *next);
+            let end = (line_index + 1..end).rev()
+                .find(|&i| !lines[i].trim().is_empty())
+                .unwrap_or(declaration_end);
+            let (docstring_span, existing_docstring) = self.help_text(&lines, declaration_end + 1)
+                .map(|(help_end, text)| (offsets.start(declaration_end + 1)..offsets.start(help_end), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: ItemKind::Function,
+                name: caps[2].to_string(),
+                line_number: line_index + 1,
+                code: lines[line_index..=end].join("\n"),
+                existing_docstring,
+                parent: None,
+                parameters,
+                parameter_details: Vec::new(),
+                returns: if outputs.is_empty() { None } else { Some(outputs.join(", ")) },
+                indentation: self.extract_indentation(lines[line_index]),
+                span: offsets.lines(line_index, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "matlab".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            // The H1 line follows `%` directly; the rest of the help is indented under it
+            let help: String = update.new_docstring
+                .trim()
+                .lines()
+                .enumerate()
+                .map(|(i, line)| {
+                    let trimmed = line.trim();
+                    if i == 0 {
+                        format!("{}%{}\n", item.indentation, trimmed)
+                    } else if trimmed.is_empty() {
+                        format!("{}%\n", item.indentation)
+                    } else {
+                        format!("{}%   {}\n", item.indentation, trimmed)
+                    }
+                })
+                .collect();
+            
+            // Help text goes directly below the declaration, replacing any already there
+            edits.push(match &item.docstring_span {
+                Some(span) => Edit::new(span.clone(), help),
+                None => {
+                    let declaration_end = self.declaration_end(&lines, item.line_number - 1);
+                    Edit::after_line(content, &offsets, declaration_end, help)
+                },
+            });
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -1,11 +1,35 @@
 pub mod python;
-// Temporarily disabled until tree-sitter linking issues are resolved
-// pub mod rust;
-// pub mod javascript;
-// pub mod typescript;
+pub mod openapi;
+pub mod protobuf;
+pub mod cli_help;
+pub mod objc;
+pub mod scala;
+pub mod groovy;
+pub mod powershell;
+pub mod fortran;
+pub mod matlab;
+pub mod gpu;
+pub mod solidity;
+pub mod vb;
+pub mod make;
+pub mod docker;
+pub mod ansible;
+pub mod helm;
+pub mod github;
+pub mod jsonschema;
+pub mod java;
+pub mod csharp;
+pub mod cpp;
+pub mod swift;
+pub mod bash;
+pub mod sql;
+pub mod rust;
+pub mod javascript;
+pub mod typescript;
 
-/// Trait for language-specific code structure parsers
-pub trait LanguageParser {
+/// Trait for language-specific code structure parsers; parsers are shared with the
+/// tasks that process files concurrently
+pub trait LanguageParser: Send + Sync {
     /// Parse code content into a structured representation
     fn parse(&self, content: &str) -> crate::error::DocGenResult<crate::parser::ParsedCode>;
     
@@ -17,19 +41,236 @@ pub trait LanguageParser {
     ) -> crate::error::DocGenResult<String>;
 }
 
+/// Quote a plain-text value for a YAML file when YAML would otherwise read it as
+/// something else
+pub fn yaml_scalar(text: &str) -> String {
+    let text = text.trim();
+    let needs_quotes = text.contains(": ") || text.contains(" #") || text.ends_with(':')
+        || text.starts_with(|c: char| "{[]}*&!|>'\"%@`,?-#".contains(c));
+    if needs_quotes {
+        format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Syntax errors in a tree-sitter tree: the tokens it had to assume were MISSING, and
+/// the innermost ERROR nodes. Tree-sitter parses around them, so the items elsewhere in
+/// the file are still found. An ERROR node often spans valid code before the error, so
+/// errors inside it are reported in its place.
+pub fn syntax_errors(node: tree_sitter::Node) -> Vec<crate::parser::SyntaxError> {
+    let line = node.start_position().row + 1;
+    if node.is_missing() {
+        return vec![crate::parser::SyntaxError { line, message: format!("missing `{}`", node.kind()) }];
+    }
+    if !node.has_error() {
+        return Vec::new();
+    }
+    
+    let mut cursor = node.walk();
+    let errors: Vec<crate::parser::SyntaxError> = node.children(&mut cursor)
+        .flat_map(syntax_errors)
+        .collect();
+    if errors.is_empty() && node.is_error() {
+        return vec![crate::parser::SyntaxError { line, message: "unexpected syntax".to_string() }];
+    }
+    errors
+}
+
 /// Factory function to get a language parser implementation
 pub fn get_parser(language: &super::Language) -> Box<dyn LanguageParser> {
     match language {
-        // For now, only Python is fully implemented
         super::Language::Python => Box::new(python::PythonParser::new()),
-        // Other languages temporarily return Python parser until tree-sitter is fixed
-        _ => {
-            println!("Warning: Requested language not fully implemented. Using Python parser instead.");
-            Box::new(python::PythonParser::new())
-        }
-        // Uncomment these when tree-sitter linking issues are resolved
-        // super::Language::Rust => Box::new(rust::RustParser::new()),
-        // super::Language::JavaScript => Box::new(javascript::JavaScriptParser::new()),
-        // super::Language::TypeScript => Box::new(typescript::TypeScriptParser::new()),
+        super::Language::Rust => Box::new(rust::RustParser::new()),
+        super::Language::JavaScript => Box::new(javascript::JavaScriptParser::new()),
+        super::Language::TypeScript => Box::new(typescript::TypeScriptParser::new()),
+        super::Language::OpenApi => Box::new(openapi::OpenApiParser::new()),
+        super::Language::Protobuf => Box::new(protobuf::ProtobufParser::new()),
+        super::Language::ObjectiveC => Box::new(objc::ObjcParser::new()),
+        super::Language::Scala => Box::new(scala::ScalaParser::new()),
+        super::Language::Groovy => Box::new(groovy::GroovyParser::new()),
+        super::Language::PowerShell => Box::new(powershell::PowerShellParser::new()),
+        super::Language::Fortran => Box::new(fortran::FortranParser::new()),
+        super::Language::Matlab => Box::new(matlab::MatlabParser::new()),
+        super::Language::Cuda => Box::new(gpu::GpuParser::new()),
+        super::Language::OpenCl => Box::new(gpu::GpuParser::opencl()),
+        super::Language::Solidity => Box::new(solidity::SolidityParser::new()),
+        super::Language::VbNet => Box::new(vb::VbParser::new()),
+        super::Language::VbScript => Box::new(vb::VbParser::vbscript()),
+        super::Language::CMake => Box::new(make::MakeParser::cmake()),
+        super::Language::Makefile => Box::new(make::MakeParser::new()),
+        super::Language::Dockerfile => Box::new(docker::DockerParser::new()),
+        super::Language::Compose => Box::new(docker::DockerParser::compose()),
+        super::Language::Ansible => Box::new(ansible::AnsibleParser::new()),
+        super::Language::Helm => Box::new(helm::HelmParser::new()),
+        super::Language::GitHubActions => Box::new(github::GitHubActionsParser::new()),
+        super::Language::JsonSchema => Box::new(jsonschema::JsonSchemaParser::new()),
+        super::Language::Java => Box::new(java::JavaParser::new()),
+        super::Language::CSharp => Box::new(csharp::CSharpParser::new()),
+        super::Language::C => Box::new(cpp::CppParser::c()),
+        super::Language::Cpp => Box::new(cpp::CppParser::new()),
+        super::Language::Swift => Box::new(swift::SwiftParser::new()),
+        super::Language::Bash => Box::new(bash::BashParser::new()),
+        super::Language::Sql => Box::new(sql::SqlParser::new()),
+        super::Language::Registered(extension) => Box::new(RegisteredParser { extension: extension.clone() }),
+        // Auto is resolved from the file extension before a parser is requested
+        super::Language::Auto => Box::new(python::PythonParser::new()),
+    }
+}
+
+/// Factory function to get a parser for a particular file, for languages where the
+/// file's location matters (an Ansible role's name comes from its directory, and a
+/// Rust crate's `lib.rs` documents the crate)
+pub fn get_file_parser(language: &super::Language, file_path: &std::path::Path) -> Box<dyn LanguageParser> {
+    match language {
+        super::Language::Ansible => Box::new(ansible::AnsibleParser::new().with_role(ansible::role_name(file_path))),
+        super::Language::Rust => Box::new(rust::RustParser::new().with_module(rust::module_name(file_path))),
+        _ => get_parser(language),
+    }
+}
+
+/// Factory function to get a C or C++ parser that writes new comments in the
+/// configured style (`cpp_comment_style`)
+pub fn get_doxygen_parser(language: &super::Language, comment_style: cpp::CommentStyle) -> Box<dyn LanguageParser> {
+    match language {
+        super::Language::C => Box::new(cpp::CppParser::c().with_comment_style(comment_style)),
+        _ => Box::new(cpp::CppParser::new().with_comment_style(comment_style)),
+    }
+}
+
+/// Factory function to get a TypeScript parser that writes tags in the configured
+/// style (`ts_tag_style`), documenting exported constants if asked to
+pub fn get_typescript_parser(constants: bool, tag_style: typescript::TagStyle) -> Box<dyn LanguageParser> {
+    Box::new(typescript::TypeScriptParser::new().with_constants(constants).with_tag_style(tag_style))
+}
+
+/// Factory function to get a parser that also documents module-level constants, for
+/// the languages whose parsers find them (Python, Rust, and TypeScript); other
+/// languages get their usual parser
+pub fn get_constants_parser(language: &super::Language, file_path: &std::path::Path) -> Box<dyn LanguageParser> {
+    match language {
+        super::Language::Python => Box::new(python::PythonParser::new().with_constants(true)),
+        super::Language::Rust => Box::new(rust::RustParser::new().with_constants(true).with_module(rust::module_name(file_path))),
+        super::Language::TypeScript => Box::new(typescript::TypeScriptParser::new().with_constants(true)),
+        _ => get_file_parser(language, file_path),
+    }
+}
+
+/// Factory function to get a parser that also documents top-level script statements,
+/// and module-level constants when `constants` is set
+pub fn get_script_parser(language: &super::Language, constants: bool) -> crate::error::DocGenResult<Box<dyn LanguageParser>> {
+    match language {
+        super::Language::Python => Ok(Box::new(python::PythonParser::new().with_script_comments(true).with_constants(constants))),
+        _ => Err(crate::error::DocGenError::ConfigError(
+            "Script comment blocks are only supported for Python".into())),
+    }
+}
+
+/// Parser that leaves out the private items another parser finds. Item indexes in
+/// updates refer to the public items, and are translated back for the other parser.
+pub struct PublicOnlyParser {
+    inner: Box<dyn LanguageParser>,
+}
+
+impl PublicOnlyParser {
+    /// Indexes of the public items among all the items the other parser finds
+    fn public_indexes(&self, content: &str) -> crate::error::DocGenResult<Vec<usize>> {
+        Ok(self.inner.parse(content)?.items.iter()
+            .enumerate()
+            .filter(|(_, item)| item.visibility == crate::parser::Visibility::Public)
+            .map(|(index, _)| index)
+            .collect())
+    }
+}
+
+impl LanguageParser for PublicOnlyParser {
+    fn parse(&self, content: &str) -> crate::error::DocGenResult<crate::parser::ParsedCode> {
+        let mut parsed_code = self.inner.parse(content)?;
+        parsed_code.items.retain(|item| item.visibility == crate::parser::Visibility::Public);
+        Ok(parsed_code)
+    }
+    
+    fn update_content(
+        &self,
+        content: &str,
+        updated_docstrings: &[crate::docstring::UpdatedDocstring]
+    ) -> crate::error::DocGenResult<String> {
+        let public = self.public_indexes(content)?;
+        let updated_docstrings = updated_docstrings.iter()
+            .map(|update| {
+                let item_index = *public.get(update.item_index).ok_or_else(|| crate::error::DocGenError::UpdateError(
+                    format!("Item index {} is out of bounds", update.item_index)))?;
+                Ok(crate::docstring::UpdatedDocstring { item_index, ..update.clone() })
+            })
+            .collect::<crate::error::DocGenResult<Vec<_>>>()?;
+        self.inner.update_content(content, &updated_docstrings)
+    }
+}
+
+/// Wrap a parser so that only public items are documented (`--public-only`)
+pub fn get_public_parser(parser: Box<dyn LanguageParser>) -> Box<dyn LanguageParser> {
+    Box::new(PublicOnlyParser { inner: parser })
+}
+
+/// Parsers registered for file extensions by crates that embed DocGen, which take
+/// precedence over the built-in languages for those extensions
+static REGISTERED_PARSERS: std::sync::RwLock<std::collections::BTreeMap<String, std::sync::Arc<dyn LanguageParser>>> =
+    std::sync::RwLock::new(std::collections::BTreeMap::new());
+
+/// Normalize an extension as a registry key: without a leading dot, in lowercase
+fn registry_key(extension: &str) -> String {
+    extension.trim_start_matches('.').to_lowercase()
+}
+
+/// Register a parser for files with an extension (`"kt"` or `".kt"`, in any case), so
+/// a language DocGen doesn't support can be documented, or a built-in language's
+/// parser replaced. A later registration for the same extension replaces an earlier one.
+pub fn register_parser(extension: &str, parser: Box<dyn LanguageParser>) {
+    let mut parsers = REGISTERED_PARSERS.write().unwrap_or_else(|e| e.into_inner());
+    parsers.insert(registry_key(extension), std::sync::Arc::from(parser));
+}
+
+/// Check whether a parser has been registered for an extension
+pub fn is_registered(extension: &str) -> bool {
+    let parsers = REGISTERED_PARSERS.read().unwrap_or_else(|e| e.into_inner());
+    parsers.contains_key(&registry_key(extension))
+}
+
+/// Parser that hands files to the parser registered for an extension, looked up when
+/// it's used, which is an error if there is none
+pub struct RegisteredParser {
+    extension: String,
+}
+
+impl RegisteredParser {
+    /// The parser registered for the extension
+    fn registered(&self) -> crate::error::DocGenResult<std::sync::Arc<dyn LanguageParser>> {
+        let parsers = REGISTERED_PARSERS.read().unwrap_or_else(|e| e.into_inner());
+        parsers.get(&registry_key(&self.extension)).cloned().ok_or_else(|| crate::error::DocGenError::ConfigError(
+            format!("No parser is registered for '.{}' files", self.extension)))
+    }
+}
+
+impl LanguageParser for RegisteredParser {
+    fn parse(&self, content: &str) -> crate::error::DocGenResult<crate::parser::ParsedCode> {
+        self.registered()?.parse(content)
+    }
+    
+    fn update_content(
+        &self,
+        content: &str,
+        updated_docstrings: &[crate::docstring::UpdatedDocstring]
+    ) -> crate::error::DocGenResult<String> {
+        self.registered()?.update_content(content, updated_docstrings)
+    }
+}
+
+/// Factory function to get a parser for command-line argument help text
+pub fn get_cli_help_parser(language: &super::Language) -> crate::error::DocGenResult<Box<dyn LanguageParser>> {
+    match language {
+        super::Language::Python => Ok(Box::new(cli_help::PythonCliHelpParser::new())),
+        super::Language::Rust => Ok(Box::new(cli_help::RustCliHelpParser::new())),
+        _ => Err(crate::error::DocGenError::ConfigError(
+            "CLI help generation is only supported for Python (argparse/click) and Rust (clap)".into())),
     }
 }
\ No newline at end of file
@@ -0,0 +1,245 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Objective-C parser implementation for interfaces, protocols, properties, and
+/// methods, documented with HeaderDoc/AppleDoc `/** ... */` comments
+pub struct ObjcParser {
+    container_re: Regex,
+    property_re: Regex,
+    selector_part_re: Regex,
+    return_type_re: Regex,
+}
+
+impl Default for ObjcParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ObjcParser {
+    pub fn new() -> Self {
+        Self {
+            container_re: Regex::new(r"^@(interface|protocol|implementation)\s+(\w+)(?:\s*\(\s*(\w*)\s*\))?").unwrap(),
+            property_re: Regex::new(r"^@property\b[^;]*?(\w+)\s*;").unwrap(),
+            selector_part_re: Regex::new(r"(\w+)\s*:\s*\([^)]*\)\s*(\w+)").unwrap(),
+            return_type_re: Regex::new(r"^[-+]\s*\(([^)]*)\)\s*(\w+)").unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Collect the doc comment (`/** */`, `/*! */`, or `///`) directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 {
+            return None;
+        }
+        let previous = lines[line_index - 1].trim();
+        
+        let start = if previous.ends_with("*/") {
+            // Walk back to the opening of the block comment
+            let mut start = line_index - 1;
+            while start > 0 && !lines[start].contains("/*") {
+                start -= 1;
+            }
+            let opening = lines[start].trim_start();
+            if !opening.starts_with("/**") && !opening.starts_with("/*!") {
+                return None;
+            }
+            start
+        } else if previous.starts_with("///") {
+            let mut start = line_index - 1;
+            while start > 0 && lines[start - 1].trim().starts_with("///") {
+                start -= 1;
+            }
+            start
+        } else {
+            return None;
+        };
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("///")
+                    .trim_start_matches("/**")
+                    .trim_start_matches("/*!")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the line index where a method declaration ends (at `;` or `{`)
+    fn declaration_end(&self, lines: &[&str], line_index: usize) -> usize {
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            if code.contains(';') || code.contains('{') {
+                return i;
+            }
+        }
+        line_index
+    }
+    
+    /// Parse a method declaration into its selector, parameter names, and return type
+    fn parse_method(&self, declaration: &str) -> Option<(String, Vec<String>, Option<String>)> {
+        let declaration = declaration.split([';', '{']).next().unwrap_or("");
+        let caps = self.return_type_re.captures(declaration)?;
+        let return_type = caps[1].trim().to_string();
+        
+        let parts: Vec<(String, String)> = self.selector_part_re.captures_iter(declaration)
+            .map(|c| (c[1].to_string(), c[2].to_string()))
+            .collect();
+        
+        let (selector, parameters) = if parts.is_empty() {
+            (caps[2].to_string(), Vec::new())
+        } else {
+            let selector = parts.iter().map(|(part, _)| format!("{}:", part)).collect::<String>();
+            (selector, parts.into_iter().map(|(_, name)| name).collect())
+        };
+        
+        let returns = match return_type.as_str() {
+            "void" | "IBAction" => None,
+            _ => Some(return_type),
+        };
+        Some((selector, parameters, returns))
+    }
+}
+
+impl LanguageParser for ObjcParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Name of the enclosing @interface/@protocol/@implementation, if any
+        let mut container: Option<String> = None;
+        let mut line_index = 0;
+        
+        while line_index < lines.len() {
+            let line = lines[line_index];
+            let code = line.trim();
+            
+            if code.starts_with("@end") {
+                container = None;
+                line_index += 1;
+                continue;
+            }
+            
+            let (item_type, name, parameters, returns, end_line) = if let Some(caps) = self.container_re.captures(code) {
+                let kind = caps[1].to_string();
+                let name = caps[2].to_string();
+                
+                // Forward declarations (`@protocol Foo;`) have no body to document
+                if code.ends_with(';') {
+                    line_index += 1;
+                    continue;
+                }
+                container = Some(name.clone());
+                
+                // A class is documented at its @interface; only the methods of an
+                // @implementation (which may be private) are picked up
+                if kind == "implementation" {
+                    line_index += 1;
+                    continue;
+                }
+                
+                let item_name = match caps.get(3) {
+                    Some(category) if !category.as_str().is_empty() => format!("{} ({})", name, category.as_str()),
+                    _ => name,
+                };
+                let item_type = if kind == "protocol" { ItemKind::Protocol } else { ItemKind::Interface };
+                (item_type, item_name, Vec::new(), None, line_index)
+            } else if container.is_some() && (code.starts_with('-') || code.starts_with('+')) {
+                let end = self.declaration_end(&lines, line_index);
+                let declaration = lines[line_index..=end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+                match self.parse_method(&declaration) {
+                    Some((selector, parameters, returns)) => (ItemKind::Method, selector, parameters, returns, end),
+                    None => {
+                        line_index += 1;
+                        continue;
+                    },
+                }
+            } else if container.is_some() && code.starts_with("@property") {
+                match self.property_re.captures(code) {
+                    Some(caps) => (ItemKind::Property, caps[1].to_string(), Vec::new(), None, line_index),
+                    None => {
+                        line_index += 1;
+                        continue;
+                    },
+                }
+            } else {
+                line_index += 1;
+                continue;
+            };
+            
+            let parent = if item_type == ItemKind::Interface || item_type == ItemKind::Protocol { None } else { container.clone() };
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, line_index)
+                .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type,
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end_line].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end_line),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+            line_index = end_line + 1;
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "objc".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let mut comment = format!("{}/**\n", item.indentation);
+            for line in update.new_docstring.trim().lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    comment.push_str(&format!("{} *\n", item.indentation));
+                } else {
+                    comment.push_str(&format!("{} * {}\n", item.indentation, trimmed));
+                }
+            }
+            comment.push_str(&format!("{} */\n", item.indentation));
+            
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
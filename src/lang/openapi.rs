@@ -0,0 +1,267 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+
+/// HTTP methods that introduce an operation under a path item
+const HTTP_METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// OpenAPI (YAML) specification parser implementation
+///
+/// The parser works on indentation rather than a full YAML model so that
+/// updates can be spliced into the file without reformatting it.
+pub struct OpenApiParser;
+
+/// A mapping key found while scanning the YAML document
+struct YamlKey {
+    line_index: usize,
+    indent: usize,
+    key: String,
+}
+
+impl Default for OpenApiParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenApiParser {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    /// Extract the mapping key from a line, if the line starts one
+    fn parse_key(&self, line: &str) -> Option<(usize, String)> {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+            return None;
+        }
+        
+        let colon = trimmed.find(':')?;
+        let rest = &trimmed[colon + 1..];
+        if !rest.is_empty() && !rest.starts_with(' ') {
+            return None;
+        }
+        
+        let key = trimmed[..colon].trim_matches(|c| c == '"' || c == '\'');
+        Some((indent, key.to_string()))
+    }
+    
+    /// Collect every mapping key together with its path of ancestor keys
+    fn collect_keys(&self, content: &str) -> Vec<(YamlKey, Vec<String>)> {
+        let mut keys = Vec::new();
+        let mut stack: Vec<(usize, String)> = Vec::new();
+        
+        for (line_index, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            
+            let indent = line.len() - line.trim_start().len();
+            while stack.last().is_some_and(|(i, _)| *i >= indent) {
+                stack.pop();
+            }
+            
+            if let Some((indent, key)) = self.parse_key(line) {
+                let path: Vec<String> = stack.iter().map(|(_, k)| k.clone()).collect();
+                keys.push((YamlKey { line_index, indent, key: key.clone() }, path));
+                stack.push((indent, key));
+            }
+        }
+        
+        keys
+    }
+    
+    /// Find the last line index of the block that belongs to the key on `line_index`
+    fn block_end(&self, lines: &[&str], line_index: usize, indent: usize) -> usize {
+        let mut end = line_index;
+        for (i, line) in lines.iter().enumerate().skip(line_index + 1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_indent = line.len() - line.trim_start().len();
+            if line_indent <= indent {
+                break;
+            }
+            end = i;
+        }
+        end
+    }
+    
+    /// Indentation of the direct children of a block, or a two-space step if it has none
+    fn child_indent(&self, lines: &[&str], line_index: usize, indent: usize) -> usize {
+        lines.iter()
+            .skip(line_index + 1)
+            .find(|line| !line.trim().is_empty() && !line.trim().starts_with('#'))
+            .map(|line| line.len() - line.trim_start().len())
+            .filter(|child| *child > indent)
+            .unwrap_or(indent + 2)
+    }
+    
+    /// Find the `description` key among the direct children of a block.
+    /// Returns the line range it occupies and its text.
+    fn find_description(&self, lines: &[&str], line_index: usize, indent: usize) -> Option<(usize, usize, String)> {
+        let end = self.block_end(lines, line_index, indent);
+        let child = self.child_indent(lines, line_index, indent);
+        
+        for i in (line_index + 1)..=end {
+            match self.parse_key(lines[i]) {
+                Some((key_indent, key)) if key_indent == child && key == "description" => {
+                    let value = lines[i].trim()["description:".len()..].trim();
+                    let value_end = self.block_end(lines, i, key_indent).min(end);
+                    
+                    let text = if value.starts_with('|') || value.starts_with('>') || value.is_empty() {
+                        lines[(i + 1)..(value_end + 1)]
+                            .iter()
+                            .map(|l| l.trim())
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    } else {
+                        value.trim_matches(|c| c == '"' || c == '\'').to_string()
+                    };
+                    return Some((i, value_end, text));
+                },
+                _ => {}
+            }
+        }
+        None
+    }
+    
+    /// Check whether a block is only a `$ref`, which cannot carry its own description
+    fn is_reference(&self, lines: &[&str], line_index: usize, indent: usize) -> bool {
+        let end = self.block_end(lines, line_index, indent);
+        let child = self.child_indent(lines, line_index, indent);
+        (line_index + 1..=end).any(|i| {
+            matches!(self.parse_key(lines[i]), Some((key_indent, key)) if key_indent == child && key == "$ref")
+        })
+    }
+    
+    /// Collect the `name` of every entry in an operation's `parameters` list
+    fn extract_parameters(&self, lines: &[&str], line_index: usize, end: usize) -> Vec<String> {
+        lines[line_index..=end]
+            .iter()
+            .filter_map(|line| {
+                let trimmed = line.trim().trim_start_matches('-').trim();
+                trimmed.strip_prefix("name:").map(|name| name.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+            })
+            .collect()
+    }
+    
+    /// Render a description as YAML lines at the given indentation
+    fn format_description(&self, text: &str, indent: &str) -> Vec<String> {
+        let text = text.trim();
+        if !text.contains('\n') {
+            let needs_quotes = text.contains(": ")
+                || text.contains(" #")
+                || text.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`".contains(c));
+            let value = if needs_quotes {
+                format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+            } else {
+                text.to_string()
+            };
+            return vec![format!("{}description: {}", indent, value)];
+        }
+        
+        let mut result = vec![format!("{}description: |", indent)];
+        for line in text.lines() {
+            if line.trim().is_empty() {
+                result.push(String::new());
+            } else {
+                result.push(format!("{}  {}", indent, line.trim_end()));
+            }
+        }
+        result
+    }
+}
+
+impl LanguageParser for OpenApiParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        for (key, path) in self.collect_keys(content) {
+            let path: Vec<&str> = path.iter().map(|s| s.as_str()).collect();
+            
+            let (item_type, name, parent) = match path.as_slice() {
+                ["paths", route] if HTTP_METHODS.contains(&key.key.as_str()) => {
+                    (ItemKind::Operation, format!("{} {}", key.key.to_uppercase(), route), None)
+                },
+                ["components", "schemas"] | ["definitions"] => {
+                    (ItemKind::Schema, key.key.clone(), None)
+                },
+                ["components", "schemas", schema, "properties"] | ["definitions", schema, "properties"] => {
+                    (ItemKind::Property, key.key.clone(), Some(schema.to_string()))
+                },
+                _ => continue,
+            };
+            
+            if self.is_reference(&lines, key.line_index, key.indent) {
+                continue;
+            }
+            
+            let end = self.block_end(&lines, key.line_index, key.indent);
+            let parameters = if item_type == ItemKind::Operation {
+                self.extract_parameters(&lines, key.line_index, end)
+            } else {
+                Vec::new()
+            };
+            
+            let (docstring_span, existing_docstring) = self.find_description(&lines, key.line_index, key.indent)
+                .map(|(start, end, text)| (offsets.lines(start, end), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type,
+                name,
+                line_number: key.line_index + 1,
+                code: lines[key.line_index..=end].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: " ".repeat(key.indent),
+                span: offsets.lines(key.line_index, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "openapi".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            let line_index = item.line_number - 1;
+            
+            // The description goes first among the key's children
+            let child = " ".repeat(self.child_indent(&lines, line_index, item.indentation.len()));
+            let text = format!("{}\n", self.format_description(&update.new_docstring, &child).join("\n"));
+            edits.push(match &item.docstring_span {
+                Some(span) => Edit::new(span.clone(), text),
+                None => Edit::after_line(content, &offsets, line_index, text),
+            });
+        }
+        
+        apply_edits(content, edits)
+    }
+}
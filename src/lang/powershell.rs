@@ -0,0 +1,270 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// PowerShell parser implementation for functions and filters (including advanced
+/// functions written as cmdlets), documented with comment-based help blocks
+pub struct PowerShellParser {
+    function_re: Regex,
+    param_re: Regex,
+    output_type_re: Regex,
+}
+
+impl Default for PowerShellParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PowerShellParser {
+    pub fn new() -> Self {
+        Self {
+            function_re: Regex::new(r"(?i)^(function|filter)\s+(?:(?:global|script|private):)?([\w-]+)").unwrap(),
+            param_re: Regex::new(r"(?i)^param\s*\(").unwrap(),
+            output_type_re: Regex::new(r"(?i)^\[OutputType\(\s*\[?([^\])]+)\]?\s*\)\]").unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Find the line index of the function's opening brace, on the declaration
+    /// line or the one after it
+    fn body_start(&self, lines: &[&str], line_index: usize) -> Option<usize> {
+        (line_index..lines.len().min(line_index + 2))
+            .find(|&i| lines[i].split('#').next().unwrap_or("").contains('{'))
+    }
+    
+    /// Find the line index of the closing brace of a body opened at `start`
+    fn body_end(&self, lines: &[&str], start: usize) -> usize {
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(start) {
+            let code = line.split('#').next().unwrap_or("");
+            depth += code.matches('{').count() as i32;
+            depth -= code.matches('}').count() as i32;
+            if depth <= 0 {
+                return i;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+    
+    /// Find a `<# ... #>` block starting at `start`, returning its last line index and text
+    fn help_block(&self, lines: &[&str], start: usize) -> Option<(usize, String)> {
+        if !lines.get(start)?.trim_start().starts_with("<#") {
+            return None;
+        }
+        let end = (start..lines.len()).find(|&i| lines[i].trim_end().ends_with("#>"))?;
+        
+        let text = lines[start..=end]
+            .iter()
+            .map(|l| l.trim().trim_start_matches("<#").trim_end_matches("#>").trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((end, text))
+    }
+    
+    /// Find a `<# ... #>` block ending directly above a line, returning its first line index and text
+    fn help_block_above(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 || !lines[line_index - 1].trim_end().ends_with("#>") {
+            return None;
+        }
+        let start = (0..line_index).rev().find(|&i| lines[i].trim_start().starts_with("<#"))?;
+        self.help_block(lines, start).map(|(_, text)| (start, text))
+    }
+    
+    /// Parameter names (without `$`) declared in a parameter list
+    fn split_parameters(&self, list: &str) -> Vec<String> {
+        let mut parameters = Vec::new();
+        let mut depth = 0i32;
+        let mut found = false;
+        let chars: Vec<char> = list.chars().collect();
+        
+        for (i, &c) in chars.iter().enumerate() {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                // Attribute arguments like `$true` sit inside brackets; default values
+                // come after the name, so only the first top-level variable counts
+                '$' if depth == 0 && !found => {
+                    let name: String = chars[i + 1..].iter().take_while(|c| c.is_alphanumeric() || **c == '_').collect();
+                    if !name.is_empty() {
+                        parameters.push(name);
+                        found = true;
+                    }
+                },
+                ',' if depth == 0 => found = false,
+                _ => {}
+            }
+        }
+        parameters
+    }
+    
+    /// Text between the parenthesis opening at `open` in `text` and its matching close
+    fn parenthesized<'a>(&self, text: &'a str, open: usize) -> &'a str {
+        let mut depth = 0i32;
+        for (i, c) in text[open..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return &text[open + 1..open + i];
+                    }
+                },
+                _ => {}
+            }
+        }
+        &text[(open + 1).min(text.len())..]
+    }
+    
+    /// Parameters and output type of a function, from an inline parameter list or
+    /// the `param(...)` block and `[OutputType()]` attribute at the top of its body
+    fn signature(&self, lines: &[&str], line_index: usize, body_start: usize, end: usize) -> (Vec<String>, Option<String>) {
+        let declaration = lines[line_index];
+        if let Some(open) = declaration.find('(') {
+            let inline = lines[line_index..=end].join("\n");
+            return (self.split_parameters(self.parenthesized(&inline, open)), None);
+        }
+        
+        let mut returns = None;
+        let mut i = body_start + 1;
+        if let Some((help_end, _)) = self.help_block(lines, i) {
+            i = help_end + 1;
+        }
+        
+        while i < end {
+            let code = lines[i].trim();
+            if let Some(caps) = self.output_type_re.captures(code) {
+                returns = Some(caps[1].trim().trim_matches('\'').trim_matches('"').to_string());
+            } else if self.param_re.is_match(code) {
+                let rest = lines[i..end].join("\n");
+                let open = rest.find('(').unwrap_or(0);
+                return (self.split_parameters(self.parenthesized(&rest, open)), returns);
+            } else if !code.is_empty() && !code.starts_with('[') && !code.starts_with('#') {
+                break;
+            }
+            i += 1;
+        }
+        (Vec::new(), returns)
+    }
+}
+
+impl LanguageParser for PowerShellParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Closing line of the function being skipped over, so nested functions are ignored
+        let mut enclosing_end: Option<usize> = None;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            if enclosing_end.is_some_and(|end| line_index <= end) {
+                continue;
+            }
+            
+            let caps = match self.function_re.captures(line.trim()) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let body_start = match self.body_start(&lines, line_index) {
+                Some(start) => start,
+                None => continue,
+            };
+            let end = self.body_end(&lines, body_start);
+            enclosing_end = Some(end);
+            
+            let (parameters, returns) = self.signature(&lines, line_index, body_start, end);
+            let (docstring_span, existing_docstring) = match self.help_block(&lines, body_start + 1) {
+                Some((help_end, text)) => (Some(offsets.lines(body_start + 1, help_end)), Some(text)),
+                None => self.help_block_above(&lines, line_index)
+                    .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                    .unzip(),
+            };
+            
+            code_items.push(CodeItem {
+                item_type: caps[1].to_lowercase().parse().expect("function pattern only matches item kinds"),
+                name: caps[2].to_string(),
+                line_number: line_index + 1,
+                code: lines[line_index..=end].join("\n"),
+                existing_docstring,
+                parent: None,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "powershell".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            let line_index = item.line_number - 1;
+            
+            let body_start = self.body_start(&lines, line_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("No body found for function {}", item.name)))?;
+            
+            // Help goes at the top of the function body, replacing any existing block
+            // there or above the function. One-line functions keep it above instead.
+            let one_line = self.body_end(&lines, body_start) == body_start;
+            let (range, indentation) = match &item.docstring_span {
+                Some(span) if span.start >= item.span.start => {
+                    (span.clone(), self.extract_indentation(lines[offsets.line_of(span.start)]))
+                },
+                Some(span) => (span.clone(), item.indentation.clone()),
+                None if one_line => (item.span.start..item.span.start, item.indentation.clone()),
+                None => {
+                    let body_indentation = lines.get(body_start + 1)
+                        .filter(|l| !l.trim().is_empty())
+                        .map(|l| self.extract_indentation(l))
+                        .unwrap_or_else(|| format!("{}    ", item.indentation));
+                    let at = offsets.end(body_start);
+                    (at..at, body_indentation)
+                },
+            };
+            
+            let mut help = format!("{}<#\n", indentation);
+            for line in update.new_docstring.trim().lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    help.push('\n');
+                } else {
+                    help.push_str(&format!("{}{}\n", indentation, trimmed));
+                }
+            }
+            help.push_str(&format!("{}#>\n", indentation));
+            
+            edits.push(Edit::new(range, help));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
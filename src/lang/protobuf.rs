@@ -0,0 +1,211 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+use std::ops::Range;
+
+/// Protocol Buffers (.proto) parser implementation
+pub struct ProtobufParser {
+    block_re: Regex,
+    rpc_re: Regex,
+    field_re: Regex,
+    enum_value_re: Regex,
+}
+
+impl Default for ProtobufParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProtobufParser {
+    pub fn new() -> Self {
+        Self {
+            block_re: Regex::new(r"^(message|enum|service|oneof)\s+(\w+)\s*\{").unwrap(),
+            rpc_re: Regex::new(r"^rpc\s+(\w+)\s*\(\s*(?:stream\s+)?([\w.]+)\s*\)\s*returns\s*\(\s*(?:stream\s+)?([\w.]+)\s*\)").unwrap(),
+            field_re: Regex::new(r"^(?:(?:optional|required|repeated)\s+)?(map\s*<[^>]+>|[\w.]+)\s+(\w+)\s*=\s*\d+").unwrap(),
+            enum_value_re: Regex::new(r"^([A-Z][A-Z0-9_]*)\s*=\s*-?\d+").unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Collect the contiguous `//` comment block directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim().starts_with("//") {
+            start -= 1;
+        }
+        
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| l.trim().trim_start_matches('/').trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some((start, text))
+    }
+    
+    /// Extract a trailing `// comment` from a declaration line
+    fn trailing_comment(&self, line: &str) -> Option<String> {
+        line.find("//").map(|pos| line[pos + 2..].trim().to_string()).filter(|c| !c.is_empty())
+    }
+    
+    /// Span of a trailing comment on a line, with the whitespace before it
+    fn trailing_comment_span(&self, line: &str, line_start: usize) -> Option<Range<usize>> {
+        let pos = line.find("//")?;
+        Some(line_start + line[..pos].trim_end().len()..line_start + line.len())
+    }
+    
+    /// Find the line index of the closing brace for a block opened on `line_index`
+    fn block_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut depth = 0i32;
+        let mut opened = false;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            opened |= code.contains('{');
+            depth += code.matches('{').count() as i32;
+            depth -= code.matches('}').count() as i32;
+            if opened && depth <= 0 {
+                return i;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+}
+
+impl LanguageParser for ProtobufParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Stack of enclosing blocks: (kind, name, closing line index)
+        let mut scopes: Vec<(String, String, usize)> = Vec::new();
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            while scopes.last().is_some_and(|(_, _, end)| *end < line_index) {
+                scopes.pop();
+            }
+            
+            let code = line.split("//").next().unwrap_or("").trim();
+            if code.is_empty() {
+                continue;
+            }
+            
+            // Parent path excludes oneof groups, whose fields belong to the message
+            let parent_path: Vec<&str> = scopes.iter()
+                .filter(|(kind, _, _)| kind != "oneof")
+                .map(|(_, name, _)| name.as_str())
+                .collect();
+            let parent = if parent_path.is_empty() { None } else { Some(parent_path.join(".")) };
+            let scope_kind = scopes.last().map(|(kind, _, _)| kind.as_str());
+            
+            let (item_type, name, parameters, returns, end_line) = if let Some(caps) = self.block_re.captures(code) {
+                let kind = caps[1].to_string();
+                let name = caps[2].to_string();
+                let end = self.block_end(&lines, line_index);
+                scopes.push((kind.clone(), name.clone(), end));
+                if kind == "oneof" {
+                    continue;
+                }
+                (kind.parse().expect("block pattern only matches item kinds"), name, Vec::new(), None, end)
+            } else if let Some(caps) = self.rpc_re.captures(code) {
+                (ItemKind::Rpc, caps[1].to_string(), vec![caps[2].to_string()], Some(caps[3].to_string()), line_index)
+            } else if scope_kind == Some("enum") {
+                match self.enum_value_re.captures(code) {
+                    Some(caps) => (ItemKind::EnumValue, caps[1].to_string(), Vec::new(), None, line_index),
+                    None => continue,
+                }
+            } else if matches!(scope_kind, Some("message") | Some("oneof")) {
+                match self.field_re.captures(code) {
+                    Some(caps) if !matches!(&caps[1], "option" | "reserved" | "extensions") => {
+                        (ItemKind::Field, caps[2].to_string(), Vec::new(), None, line_index)
+                    },
+                    _ => continue,
+                }
+            } else {
+                continue;
+            };
+            
+            let (docstring_span, docstring) = match self.leading_comment(&lines, line_index) {
+                Some((start, text)) => (Some(offsets.lines(start, line_index - 1)), Some(text)),
+                None => match self.trailing_comment(line) {
+                    Some(text) => (self.trailing_comment_span(line, offsets.start(line_index)), Some(text)),
+                    None => (None, None),
+                },
+            };
+            
+            code_items.push(CodeItem {
+                item_type,
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end_line].join("\n"),
+                existing_docstring: docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end_line),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "protobuf".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            let line_index = item.line_number - 1;
+            
+            let comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim();
+                    if trimmed.is_empty() {
+                        format!("{}//\n", item.indentation)
+                    } else {
+                        format!("{}// {}\n", item.indentation, trimmed)
+                    }
+                })
+                .collect();
+            
+            // A trailing comment is superseded by the new leading comment
+            let leading = item.docstring_span.clone()
+                .filter(|span| span.end <= item.span.start)
+                .unwrap_or(item.span.start..item.span.start);
+            edits.push(Edit::new(leading, comment));
+            if let Some(trailing) = self.trailing_comment_span(lines[line_index], offsets.start(line_index)) {
+                edits.push(Edit::new(trailing, ""));
+            }
+        }
+        
+        apply_edits(content, edits)
+    }
+}
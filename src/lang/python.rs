@@ -1,15 +1,60 @@
-use rustpython_parser::{parser, ast::{self, Mod}};
+use rustpython_parser::{parser, ast};
 use crate::error::{DocGenError, DocGenResult};
-use crate::parser::{CodeItem, ParsedCode};
+use crate::parser::{apply_edits, is_obvious_value, CodeItem, Edit, ItemKind, LineOffsets, Parameter, ParsedCode, SyntaxError, Visibility};
 use crate::docstring::UpdatedDocstring;
 use super::LanguageParser;
+use std::ops::Range;
+
+/// Where a new docstring goes for an item without one
+struct Placement {
+    /// Bytes the new docstring replaces: nothing at the start of the line it goes on
+    /// (or at the end of a constant's line), or the space after a definition's `:`
+    /// when the body follows on the same line
+    range: Range<usize>,
+    
+    /// Whether the body follows the `:` on the definition's line, and so moves to a
+    /// line of its own under the new docstring
+    inline_body: bool,
+    
+    /// Indentation of the body, which the docstring's lines are written at
+    indentation: String,
+}
+
+/// An item, with where a new docstring for it would go
+type PlacedItem = (CodeItem, Placement);
 
 /// Python language parser implementation
-pub struct PythonParser;
+pub struct PythonParser {
+    /// Also document top-level script statements with `#` comment blocks
+    script_comments: bool,
+    
+    /// Also document module-level constants
+    constants: bool,
+}
+
+impl Default for PythonParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl PythonParser {
     pub fn new() -> Self {
-        Self
+        Self { script_comments: false, constants: false }
+    }
+    
+    /// Enable `#` comment documentation for top-level script statements
+    /// (argument parser setup, `if __name__ == "__main__":` guards)
+    pub fn with_script_comments(mut self, enabled: bool) -> Self {
+        self.script_comments = enabled;
+        self
+    }
+    
+    /// Enable documentation for module-level UPPER_CASE constants whose values aren't
+    /// self-explanatory
+    pub fn with_constants(mut self, enabled: bool) -> Self {
+        self.constants = enabled;
+        self
     }
     
     /// Extract docstring from an AST node
@@ -24,6 +69,69 @@ impl PythonParser {
         None
     }
     
+    /// Span of a docstring's string literal, from its prefix to its closing quotes, so
+    /// that replacing it leaves the code around it on the same lines untouched
+    fn docstring_span(&self, content: &str, body: &[ast::Located<ast::StmtKind>], offsets: &LineOffsets) -> Option<Range<usize>> {
+        self.extract_docstring(body)?;
+        let stmt = body.first()?;
+        let start = byte_offset(content, offsets, stmt.location.row(), stmt.location.column());
+        let end = match stmt.end_location {
+            Some(end) => byte_offset(content, offsets, end.row(), end.column()),
+            None => content[..offsets.lines(stmt.location.row() - 1, stmt.location.row() - 1).end].trim_end().len(),
+        };
+        Some(start..end.max(start))
+    }
+    
+    /// Span of a definition, starting at its first decorator
+    fn definition_span(
+        &self,
+        stmt: &ast::Located<ast::StmtKind>,
+        decorator_list: &[ast::Located<ast::ExprKind>],
+        offsets: &LineOffsets,
+    ) -> Range<usize> {
+        let start = decorator_list.iter()
+            .map(|decorator| decorator.location.row())
+            .chain([stmt.location.row()])
+            .min()
+            .unwrap_or(1);
+        let end = stmt.end_location.map(|loc| loc.row()).unwrap_or(start);
+        offsets.lines(start - 1, end - 1)
+    }
+    
+    /// Where a new docstring for a definition goes: on a line of its own ahead of the
+    /// body's first statement, at the body's indentation. A body written after the `:`
+    /// on the definition's line (`def f(): return 1`) moves to the next line instead.
+    fn docstring_placement(
+        &self,
+        content: &str,
+        stmt: &ast::Located<ast::StmtKind>,
+        body: &[ast::Located<ast::StmtKind>],
+        offsets: &LineOffsets,
+    ) -> Placement {
+        let first = match body.first() {
+            Some(first) => first,
+            None => {
+                let start = offsets.start(stmt.location.row());
+                return Placement { range: start..start, inline_body: false, indentation: self.body_indentation(content, stmt) };
+            },
+        };
+        let start = byte_offset(content, offsets, first.location.row(), first.location.column());
+        let line_start = offsets.start(first.location.row() - 1);
+        let before = &content[line_start..start];
+        if before.trim().is_empty() {
+            Placement { range: line_start..line_start, inline_body: false, indentation: before.to_string() }
+        } else {
+            let colon = content[..start].trim_end_matches([' ', '\t']).len();
+            Placement { range: colon..start, inline_body: true, indentation: self.body_indentation(content, stmt) }
+        }
+    }
+    
+    /// Indentation for a body that isn't on lines of its own: one level past the
+    /// definition's
+    fn body_indentation(&self, content: &str, stmt: &ast::Located<ast::StmtKind>) -> String {
+        format!("{}    ", self.extract_indentation(content, stmt.location.row()))
+    }
+    
     /// Extract parameters from a function definition
     fn extract_parameters(&self, args: &ast::Arguments) -> Vec<String> {
         let mut params = Vec::new();
@@ -74,9 +182,37 @@ impl PythonParser {
         params
     }
     
-    /// Extract return type annotation if available
-    fn extract_return_type(&self, returns: &Option<Box<ast::Located<ast::ExprKind>>>) -> Option<String> {
-        returns.as_ref().map(|expr| format!("{:?}", expr))
+    /// Parameters with their annotations and defaults as written, in the order they are
+    /// declared. The AST lists defaults apart from the parameters, so each parameter
+    /// gets the default found between it and the next parameter.
+    fn extract_parameter_details(&self, args: &ast::Arguments, content: &str, offsets: &LineOffsets) -> Vec<Parameter> {
+        let mut declared: Vec<(&ast::Arg, &str)> = Vec::new();
+        declared.extend(args.posonlyargs.iter().chain(&args.args).map(|arg| (arg, "")));
+        declared.extend(args.vararg.iter().map(|arg| (&**arg, "*")));
+        declared.extend(args.kwonlyargs.iter().map(|arg| (arg, "")));
+        declared.extend(args.kwarg.iter().map(|arg| (&**arg, "**")));
+        
+        let defaults: Vec<&ast::Expr> = args.defaults.iter().chain(&args.kw_defaults).collect();
+        declared.iter()
+            .enumerate()
+            .map(|(index, &(arg, prefix))| {
+                let next = declared.get(index + 1).map_or((usize::MAX, usize::MAX), |&(next, _)| position(next));
+                let default = defaults.iter()
+                    .copied()
+                    .find(|expr| (position(arg)..next).contains(&position(*expr)))
+                    .map(|expr| source_text(content, offsets, expr));
+                Parameter {
+                    name: format!("{}{}", prefix, arg.node.arg),
+                    annotation: arg.node.annotation.as_deref().map(|expr| source_text(content, offsets, expr)),
+                    default,
+                }
+            })
+            .collect()
+    }
+    
+    /// Extract the return type annotation as written, if there is one
+    fn extract_return_type(&self, returns: &Option<Box<ast::Located<ast::ExprKind>>>, content: &str, offsets: &LineOffsets) -> Option<String> {
+        returns.as_deref().map(|expr| source_text(content, offsets, expr))
     }
     
     /// Extract a code block from the source content
@@ -96,178 +232,549 @@ impl PythonParser {
             "".to_string()
         }
     }
-}
-
-impl LanguageParser for PythonParser {
-    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
-        // Parse Python code using rustpython-parser
-        let statements = parser::parse_program(content, "<string>")
-            .map_err(|e| DocGenError::ParsingError(format!("Failed to parse Python code: {}", e)))?;
+    
+    /// Check whether a line is part of a documentation comment (shebangs and
+    /// encoding declarations are not)
+    fn is_doc_comment_line(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        trimmed.starts_with('#') && !trimmed.starts_with("#!") && !trimmed.contains("-*- coding")
+    }
+    
+    /// Check whether a line must stay at the top of the file: a shebang, or an encoding
+    /// declaration
+    fn is_header_line(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        trimmed.starts_with("#!") || (trimmed.starts_with('#') && (trimmed.contains("coding:") || trimmed.contains("coding=")))
+    }
+    
+    /// Where a new module docstring goes: after a shebang and an encoding declaration,
+    /// which must stay on the first two lines, and so ahead of any `from __future__`
+    /// imports, which must come after the docstring
+    fn module_docstring_placement(&self, content: &str, offsets: &LineOffsets) -> Placement {
+        let header_lines = content.lines()
+            .take(2)
+            .take_while(|line| self.is_header_line(line))
+            .count();
+        let start = offsets.start(header_lines);
+        Placement { range: start..start, inline_body: false, indentation: String::new() }
+    }
+    
+    /// Collect the contiguous `#` comment block directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 && self.is_doc_comment_line(lines[start - 1]) {
+            start -= 1;
+        }
         
-        // Process each statement in the module
-        let mut code_items = Vec::new();
+        if start == line_index {
+            return None;
+        }
         
-        for stmt in &statements {
-            match &stmt.node {
-                ast::StmtKind::FunctionDef { name, args, body, decorator_list: _, returns, type_comment: _ } => {
-                    let docstring = self.extract_docstring(body);
-                    let lineno = stmt.location.row();
-                    let end_lineno = stmt.end_location.map(|loc| loc.row()).unwrap_or(lineno);
-                    
-                    code_items.push(CodeItem {
-                        item_type: "function".to_string(),
-                        name: name.to_string(),
-                        line_number: lineno,
-                        code: self.extract_code_block(content, lineno, end_lineno),
-                        existing_docstring: docstring,
-                        parent: None,
-                        parameters: self.extract_parameters(args),
-                        returns: self.extract_return_type(returns),
-                        indentation: self.extract_indentation(content, lineno),
-                    });
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| l.trim().trim_start_matches('#').trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some((start, text))
+    }
+    
+    /// Find top-level script statements worth a comment block: argument parser
+    /// setup (the `ArgumentParser(...)` assignment and the calls configuring it)
+    /// and `if __name__ == "__main__":` guards
+    fn extract_script_items(&self, content: &str, statements: &[ast::Located<ast::StmtKind>], offsets: &LineOffsets) -> Vec<CodeItem> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut items = Vec::new();
+        let mut index = 0;
+        
+        while index < statements.len() {
+            let stmt = &statements[index];
+            let lineno = stmt.location.row();
+            let mut end_lineno = stmt.end_location.map(|loc| loc.row()).unwrap_or(lineno);
+            let first_line = lines.get(lineno - 1).map_or("", |l| l.trim());
+            index += 1;
+            
+            let (item_type, name) = match &stmt.node {
+                ast::StmtKind::If { .. } if first_line.starts_with("if __name__") && first_line.contains("__main__") => {
+                    (ItemKind::MainGuard, "__main__".to_string())
                 },
-                ast::StmtKind::ClassDef { name, body, decorator_list: _, bases: _, keywords: _ } => {
-                    let class_docstring = self.extract_docstring(body);
-                    let class_lineno = stmt.location.row();
-                    let class_end_lineno = stmt.end_location.map(|loc| loc.row()).unwrap_or(class_lineno);
-                    
-                    // Add the class itself
-                    code_items.push(CodeItem {
-                        item_type: "class".to_string(),
-                        name: name.to_string(),
-                        line_number: class_lineno,
-                        code: self.extract_code_block(content, class_lineno, class_end_lineno),
-                        existing_docstring: class_docstring,
-                        parent: None,
-                        parameters: Vec::new(),
-                        returns: None,
-                        indentation: self.extract_indentation(content, class_lineno),
-                    });
+                ast::StmtKind::Assign { .. } if first_line.contains("ArgumentParser(") => {
+                    let target = first_line.split('=').next().unwrap_or("parser").trim().to_string();
                     
-                    // Process class methods
-                    for class_stmt in body {
-                        if let ast::StmtKind::FunctionDef { name: method_name, args, body: method_body, decorator_list: _, returns, type_comment: _ } = &class_stmt.node {
-                            let docstring = self.extract_docstring(method_body);
-                            let method_lineno = class_stmt.location.row();
-                            let method_end_lineno = class_stmt.end_location.map(|loc| loc.row()).unwrap_or(method_lineno);
-                            
-                            code_items.push(CodeItem {
-                                item_type: "method".to_string(),
-                                name: method_name.to_string(),
-                                line_number: method_lineno,
-                                code: self.extract_code_block(content, method_lineno, method_end_lineno),
-                                existing_docstring: docstring,
-                                parent: Some(name.to_string()),
-                                parameters: self.extract_parameters(args),
-                                returns: self.extract_return_type(returns),
-                                indentation: self.extract_indentation(content, method_lineno),
-                            });
+                    // The setup continues while following statements configure the same parser
+                    while index < statements.len() {
+                        let next = &statements[index];
+                        let next_line = lines.get(next.location.row() - 1).map_or("", |l| l.trim());
+                        if !next_line.starts_with(&format!("{}.", target)) {
+                            break;
                         }
+                        end_lineno = next.end_location.map(|loc| loc.row()).unwrap_or(next.location.row());
+                        index += 1;
                     }
+                    (ItemKind::ArgparseSetup, target)
+                },
+                _ => continue,
+            };
+            
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, lineno - 1)
+                .map(|(start, text)| (offsets.lines(start, lineno - 2), text))
+                .unzip();
+            
+            items.push(CodeItem {
+                item_type,
+                name,
+                line_number: lineno,
+                code: self.extract_code_block(content, lineno, end_lineno),
+                existing_docstring,
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: self.extract_indentation(content, lineno),
+                span: offsets.lines(lineno - 1, end_lineno - 1),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        items
+    }
+    
+    /// Find the module-level constants worth documenting: assignments to an UPPER_CASE
+    /// name of a value that doesn't explain itself. Each is documented by a string
+    /// literal on the line after it, the attribute docstring read by Sphinx and other
+    /// documentation tools, which goes at the end of the line so a trailing comment
+    /// stays with the assignment.
+    fn extract_constants(&self, content: &str, statements: &[ast::Located<ast::StmtKind>], offsets: &LineOffsets) -> Vec<(CodeItem, Placement)> {
+        let mut items = Vec::new();
+        
+        for (index, stmt) in statements.iter().enumerate() {
+            let (target, value) = match &stmt.node {
+                ast::StmtKind::Assign { targets, value, .. } if targets.len() == 1 => (&targets[0], value.as_ref()),
+                ast::StmtKind::AnnAssign { target, value: Some(value), .. } => (target.as_ref(), value.as_ref()),
+                _ => continue,
+            };
+            let name = match &target.node {
+                ast::ExprKind::Name { id, .. } if is_constant_name(id) => id,
+                _ => continue,
+            };
+            if is_obvious_value(&source_text(content, offsets, value)) {
+                continue;
+            }
+            
+            let lineno = stmt.location.row();
+            let end_lineno = stmt.end_location.map(|loc| loc.row()).unwrap_or(lineno);
+            let following = &statements[index + 1..];
+            let line_end = content[..offsets.end(end_lineno - 1)].trim_end_matches(['\n', '\r']).len();
+            items.push((CodeItem {
+                item_type: ItemKind::Constant,
+                name: name.to_string(),
+                line_number: lineno,
+                code: self.extract_code_block(content, lineno, end_lineno),
+                existing_docstring: self.extract_docstring(following),
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: String::new(),
+                span: offsets.lines(lineno - 1, end_lineno - 1),
+                docstring_span: self.docstring_span(content, following, offsets),
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::of_python_name(name),
+                throws: Vec::new(),
+            }, Placement { range: line_end..line_end, inline_body: false, indentation: String::new() }));
+        }
+        
+        items
+    }
+    
+    /// Add the functions and classes among some statements, and those nested in their
+    /// bodies, to `code_items`. `parent` is the dotted path of the enclosing definitions
+    /// (e.g. `Outer.Inner` for the methods of a nested class), and functions directly
+    /// in a class body are its methods.
+    fn parse_definitions(
+        &self,
+        content: &str,
+        statements: &[ast::Located<ast::StmtKind>],
+        parent: Option<&str>,
+        in_class: bool,
+        offsets: &LineOffsets,
+        code_items: &mut Vec<PlacedItem>,
+    ) {
+        for stmt in statements {
+            let (mut item_type, name, body, decorator_list, mut parameters, mut parameter_details, returns, first_parameter) = match &stmt.node {
+                ast::StmtKind::FunctionDef { name, args, body, decorator_list, returns, type_comment: _ }
+                | ast::StmtKind::AsyncFunctionDef { name, args, body, decorator_list, returns, type_comment: _ } => (
+                    if in_class { ItemKind::Method } else { ItemKind::Function },
+                    name,
+                    body,
+                    decorator_list,
+                    self.extract_parameters(args),
+                    self.extract_parameter_details(args, content, offsets),
+                    self.extract_return_type(returns, content, offsets),
+                    args.posonlyargs.iter().chain(&args.args).next().map(|arg| arg.node.arg.clone()),
+                ),
+                ast::StmtKind::ClassDef { name, body, decorator_list, bases: _, keywords: _ } => {
+                    (ItemKind::Class, name, body, decorator_list, Vec::new(), Vec::new(), None, None)
+                },
+                _ => continue, // Ignore other statement types
+            };
+            let decorators: Vec<String> = decorator_list.iter()
+                .map(|decorator| source_text(content, offsets, decorator))
+                .collect();
+            let is_decorated = |names: &[&str]| decorators.iter().any(|decorator| names.contains(&decorator_name(decorator)));
+            
+            if item_type == ItemKind::Method {
+                // A property's setter and deleter share the documentation on its getter
+                if is_decorated(&["setter", "deleter"]) {
+                    continue;
+                }
+                // The instance, or the class for a classmethod, is passed implicitly and
+                // isn't documented; a staticmethod has neither
+                if let Some(receiver) = first_parameter.filter(|_| !is_decorated(&["staticmethod"])) {
+                    parameters.retain(|p| p != &receiver);
+                    parameter_details.retain(|p| p.name != receiver);
+                }
+                // Properties are read like attributes, and documented as the value they hold
+                if is_decorated(&["property", "cached_property", "abstractproperty"]) {
+                    item_type = ItemKind::Property;
+                }
+            }
+            let lineno = stmt.location.row();
+            let end_lineno = stmt.end_location.map(|loc| loc.row()).unwrap_or(lineno);
+            let is_function = item_type != ItemKind::Class;
+            let path = match parent {
+                Some(parent) => format!("{}.{}", parent, name),
+                None => name.to_string(),
+            };
+            
+            code_items.push((CodeItem {
+                item_type,
+                name: name.to_string(),
+                line_number: lineno,
+                code: self.extract_code_block(content, lineno, end_lineno),
+                existing_docstring: self.extract_docstring(body),
+                parent: parent.map(str::to_string),
+                parameters,
+                parameter_details,
+                returns,
+                indentation: self.extract_indentation(content, lineno),
+                span: self.definition_span(stmt, decorator_list, offsets),
+                docstring_span: self.docstring_span(content, body, offsets),
+                is_async: matches!(stmt.node, ast::StmtKind::AsyncFunctionDef { .. }),
+                is_generator: is_function && yields(body),
+                decorators,
+                visibility: Visibility::of_python_name(&path),
+                throws: Vec::new(),
+            }, self.docstring_placement(content, stmt, body, offsets)));
+            
+            // Definitions nested in this one come right after it
+            self.parse_definitions(content, body, Some(&path), item_type == ItemKind::Class, offsets, code_items);
+        }
+    }
+    
+    /// Parse a module's statements. When the module has syntax errors, the top-level
+    /// statements that don't parse on their own are blanked out and the rest parsed
+    /// again, so their items can still be analyzed; blanking keeps every line where it
+    /// was. Returns the statements and the errors recovered from.
+    fn parse_statements(&self, content: &str) -> DocGenResult<(Vec<ast::Located<ast::StmtKind>>, Vec<SyntaxError>)> {
+        // Parse Python code using rustpython-parser
+        let error = match parser::parse_program(content, "<string>") {
+            Ok(statements) => return Ok((statements, Vec::new())),
+            Err(e) => e,
+        };
+        
+        let lines: Vec<&str> = content.lines().collect();
+        let starts = top_level_starts(&lines);
+        let mut recovered = Vec::with_capacity(lines.len());
+        let mut errors = Vec::new();
+        for (index, &start) in starts.iter().enumerate() {
+            let end = starts.get(index + 1).copied().unwrap_or(lines.len());
+            // Leading newlines keep the statement on its own line numbers
+            let source = "\n".repeat(start) + &lines[start..end].join("\n");
+            match parser::parse_program(&source, "<string>") {
+                Ok(_) => recovered.extend_from_slice(&lines[start..end]),
+                Err(e) => {
+                    errors.push(SyntaxError { line: start + 1, message: e.to_string() });
+                    recovered.resize(end, "");
                 },
-                _ => {} // Ignore other statement types
             }
         }
         
+        match parser::parse_program(&recovered.join("\n"), "<string>") {
+            Ok(statements) if !errors.is_empty() => Ok((statements, errors)),
+            _ => Err(DocGenError::ParsingError(format!("Failed to parse Python code: {}", error))),
+        }
+    }
+    
+    /// Parse the items of a module, each with where a new docstring for it would go,
+    /// and the syntax errors parsed around
+    fn parse_items(&self, content: &str) -> DocGenResult<(Vec<PlacedItem>, Vec<SyntaxError>)> {
+        let (statements, syntax_errors) = self.parse_statements(content)?;
+        let offsets = LineOffsets::new(content);
+        
+        // Process each statement in the module
+        let mut code_items = Vec::new();
+        
+        // The module's docstring is its first statement; a file without statements
+        // (such as an empty `__init__.py`) needs none
+        if let Some(first) = statements.first() {
+            code_items.push((CodeItem {
+                item_type: ItemKind::Module,
+                name: "module".to_string(),
+                line_number: first.location.row(),
+                code: content.to_string(),
+                existing_docstring: self.extract_docstring(&statements),
+                parent: None,
+                parameters: Vec::new(),
+                parameter_details: Vec::new(),
+                returns: None,
+                indentation: String::new(),
+                span: 0..content.len(),
+                docstring_span: self.docstring_span(content, &statements, &offsets),
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            }, self.module_docstring_placement(content, &offsets)));
+        }
+        
+        self.parse_definitions(content, &statements, None, false, &offsets, &mut code_items);
+        
+        if self.constants {
+            code_items.extend(self.extract_constants(content, &statements, &offsets));
+        }
+        
+        // Script statements are documented with a comment block above them
+        if self.script_comments {
+            code_items.extend(self.extract_script_items(content, &statements, &offsets)
+                .into_iter()
+                .map(|item| {
+                    let start = item.span.start;
+                    let indentation = item.indentation.clone();
+                    (item, Placement { range: start..start, inline_body: false, indentation })
+                }));
+        }
+        
+        Ok((code_items, syntax_errors))
+    }
+}
+
+impl LanguageParser for PythonParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let (code_items, syntax_errors) = self.parse_items(content)?;
+        
         // Return the parsed code
         Ok(ParsedCode {
-            items: code_items,
+            items: code_items.into_iter().map(|(item, _)| item).collect(),
             original_content: content.to_string(),
+            language: "python".to_string(),
+            syntax_errors,
         })
     }
     
     fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
-        let mut new_content = content.to_string();
-        
-        // Get access to the parsed code items for more accurate updates
-        let parsed_code = self.parse(&new_content)?;
+        let (items, _) = self.parse_items(content)?;
+        let mut edits = Vec::new();
         
-        // Sort updates in reverse order by line number to avoid line number shifts
-        let mut sorted_updates = updated_docstrings.to_vec();
-        sorted_updates.sort_by(|a, b| {
-            let a_line = parsed_code.items[a.item_index].line_number;
-            let b_line = parsed_code.items[b.item_index].line_number;
-            b_line.cmp(&a_line)
-        });
-        
-        for update in sorted_updates {
-            let item = &parsed_code.items[update.item_index];
-            let lines: Vec<&str> = new_content.lines().collect();
-            
-            // Get the line that defines the function/class/method
-            let line_index = item.line_number - 1; // Convert to 0-based index
+        for update in updated_docstrings {
+            let (item, placement) = items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
             
-            if line_index >= lines.len() {
-                return Err(DocGenError::UpdateError(
-                    format!("Line number {} is out of bounds", item.line_number)));
+            if item.is_script_statement() {
+                let text: String = update.new_docstring
+                    .trim()
+                    .lines()
+                    .map(|line| format!("{}# {}", item.indentation, line.trim()).trim_end().to_string() + "\n")
+                    .collect();
+                edits.push(Edit::new(item.docstring_span.clone().unwrap_or(placement.range.clone()), text));
+                continue;
             }
             
-            let def_line = lines[line_index];
-            
-            // Get indentation level from the definition line
-            let indentation = item.indentation.clone();
-            
-            // Check if there's an existing docstring to replace
-            let mut has_existing_docstring = false;
-            let mut docstring_end_line = line_index;
-            
-            // If the next line starts with triple quotes, we have a docstring to replace
-            if line_index + 1 < lines.len() {
-                let next_line = lines[line_index + 1].trim();
-                if next_line.starts_with("\"\"\"") || next_line.starts_with("'''") {
-                    has_existing_docstring = true;
-                    
-                    // Find the end of the docstring
-                    for i in (line_index + 1)..lines.len() {
-                        if i == line_index + 1 && lines[i].trim().ends_with("\"\"\"") && lines[i].trim().starts_with("\"\"\"") {
-                            // Single line docstring
-                            docstring_end_line = i;
-                            break;
-                        } else if i > line_index + 1 && (lines[i].trim().ends_with("\"\"\"") || lines[i].trim().ends_with("'''")) {
-                            // Multi-line docstring
-                            docstring_end_line = i;
-                            break;
-                        }
+            // Only the bytes of the old literal, or of the insertion point, change, so
+            // the formatting and any code around them stay exactly as they were
+            let indentation = &placement.indentation;
+            let literal = docstring_literal(&update.new_docstring, indentation);
+            let (range, text) = match &item.docstring_span {
+                Some(span) => (span.clone(), literal),
+                None if item.item_type == ItemKind::Module => {
+                    // A new module docstring is set apart from the code after it
+                    let mut text = format!("{}\n", literal);
+                    if !content[placement.range.start..].starts_with(['\n', '\r']) {
+                        text.push('\n');
                     }
-                }
-            }
-            
-            // Add indentation to docstring lines
-            let indented_docstring = update.new_docstring
-                .lines()
-                .enumerate()
-                .map(|(_i, line)| {
-                    // Indent all lines properly - add 4 spaces to align with Python code indentation
-                    format!("{}    {}", indentation, line)
-                })
-                .collect::<Vec<_>>()
-                .join("\n");
-            
-            // Split content for insertion or replacement
-            if has_existing_docstring {
-                // Replace existing docstring
-                let start_line = lines[..=line_index].join("\n");
-                let end_line = if docstring_end_line + 1 < lines.len() {
-                    lines[(docstring_end_line + 1)..].join("\n")
-                } else {
-                    "".to_string()
-                };
-                
-                new_content = format!("{}\n{}\n{}", start_line, indented_docstring, end_line);
+                    (placement.range.clone(), text)
+                },
+                // A constant's docstring follows the line it is assigned on
+                None if item.item_type == ItemKind::Constant => (placement.range.clone(), format!("\n{}", literal)),
+                None if placement.inline_body => {
+                    (placement.range.clone(), format!("\n{}{}\n{}", indentation, literal, indentation))
+                },
+                None => (placement.range.clone(), format!("{}{}\n", indentation, literal)),
+            };
+            edits.push(Edit::new(range, text));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
+
+/// Whether a name is written as a constant, in UPPER_CASE with at least one letter
+fn is_constant_name(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Row and column of a node, for comparing where nodes are in the source
+fn position<T>(node: &ast::Located<T>) -> (usize, usize) {
+    (node.location.row(), node.location.column())
+}
+
+/// Name of the function a decorator applies, without its module or arguments: `route`
+/// for `@app.route("/")`, `setter` for `@name.setter`
+fn decorator_name(decorator: &str) -> &str {
+    let callee = decorator.split('(').next().unwrap_or(decorator);
+    callee.rsplit('.').next().unwrap_or(callee).trim()
+}
+
+/// Byte offset of a row and column of the AST; columns count characters from the
+/// start of the line
+fn byte_offset(content: &str, offsets: &LineOffsets, row: usize, column: usize) -> usize {
+    let line_start = offsets.start(row - 1);
+    content[line_start..].char_indices()
+        .nth(column)
+        .map_or(content.len(), |(index, _)| line_start + index)
+}
+
+/// Source text of an expression, with runs of whitespace (such as line breaks in a
+/// long annotation) collapsed to single spaces
+fn source_text(content: &str, offsets: &LineOffsets, expr: &ast::Expr) -> String {
+    let start = byte_offset(content, offsets, expr.location.row(), expr.location.column());
+    let end = expr.end_location.map_or(start, |end| byte_offset(content, offsets, end.row(), end.column()));
+    content[start..end.max(start)].split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A generated docstring as the string literal written into the file: the lines after
+/// the first are indented like the body, blank lines are left empty, and a docstring
+/// with backslashes becomes a raw string so they are kept as written (PEP 257)
+fn docstring_literal(docstring: &str, indentation: &str) -> String {
+    let docstring = docstring.trim();
+    let raw = docstring.starts_with("\"\"\"")
+        && docstring.contains('\\')
+        && !docstring.trim_end_matches('"').ends_with('\\');
+    let lines: Vec<String> = docstring.lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 || line.trim().is_empty() {
+                line.trim_end().to_string()
             } else {
-                // Insert new docstring after definition line
-                let split_index = if line_index > 0 {
-                    // Add 1 for the newline character
-                    lines[..line_index].join("\n").len() + def_line.len() + 1
-                } else {
-                    def_line.len()
-                };
-                
-                let (before, after) = new_content.split_at(split_index);
-                new_content = format!("{}\n{}{}", before, indented_docstring, after);
+                format!("{}{}", indentation, line.trim_end())
             }
+        })
+        .collect();
+    format!("{}{}", if raw { "r" } else { "" }, lines.join("\n"))
+}
+
+/// Lines starting the top-level statements of a module, for recovering from syntax
+/// errors a statement at a time. A decorator's statement starts at the decorator, and
+/// `else`, `except`, and the like continue the statement before them.
+fn top_level_starts(lines: &[&str]) -> Vec<usize> {
+    let mut starts = vec![0];
+    let mut decorated = false;
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() || line.starts_with(char::is_whitespace) || line.starts_with(['#', ')', ']', '}']) {
+            continue;
+        }
+        let keyword = line.split(|c: char| !c.is_alphanumeric() && c != '_').next().unwrap_or("");
+        if index > 0 && !decorated && !matches!(keyword, "else" | "elif" | "except" | "finally") {
+            starts.push(index);
+        }
+        decorated = line.starts_with('@');
+    }
+    starts
+}
+
+/// Whether a function body yields, making the function a generator. Yields in nested
+/// functions and classes belong to them; a yield is found where it is a statement of
+/// its own or the value assigned or returned, e.g. `yield x`, `y = yield`, or
+/// `return (yield x)`.
+fn yields(body: &[ast::Located<ast::StmtKind>]) -> bool {
+    fn is_yield(expr: &ast::Located<ast::ExprKind>) -> bool {
+        matches!(expr.node, ast::ExprKind::Yield { .. } | ast::ExprKind::YieldFrom { .. })
+    }
+    body.iter().any(|stmt| match &stmt.node {
+        ast::StmtKind::Expr { value }
+        | ast::StmtKind::Assign { value, .. }
+        | ast::StmtKind::AugAssign { value, .. } => is_yield(value),
+        ast::StmtKind::AnnAssign { value, .. } | ast::StmtKind::Return { value } => value.as_deref().is_some_and(is_yield),
+        ast::StmtKind::If { body, orelse, .. }
+        | ast::StmtKind::For { body, orelse, .. }
+        | ast::StmtKind::AsyncFor { body, orelse, .. }
+        | ast::StmtKind::While { body, orelse, .. } => yields(body) || yields(orelse),
+        ast::StmtKind::With { body, .. } | ast::StmtKind::AsyncWith { body, .. } => yields(body),
+        ast::StmtKind::Try { body, handlers, orelse, finalbody } => {
+            yields(body) || yields(orelse) || yields(finalbody)
+                || handlers.iter().any(|handler| match &handler.node {
+                    ast::ExcepthandlerKind::ExceptHandler { body, .. } => yields(body),
+                })
+        },
+        _ => false,
+    })
+}
+
+/// Outline of a module's public API for the prompt asking for its docstring: the
+/// signature and summary line of each public function and class, and of the public
+/// methods of the classes. The whole module is used when it doesn't parse.
+pub fn module_outline(content: &str) -> String {
+    let statements = match PythonParser::new().parse_statements(content) {
+        Ok((statements, _)) => statements,
+        Err(_) => return content.to_string(),
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let mut outline = Vec::new();
+    outline_definitions(&statements, &lines, "", &mut outline);
+    outline.join("\n")
+}
+
+/// Add the public functions and classes among some statements to an outline
+fn outline_definitions(statements: &[ast::Located<ast::StmtKind>], lines: &[&str], indent: &str, outline: &mut Vec<String>) {
+    for stmt in statements {
+        let (name, body, is_class) = match &stmt.node {
+            ast::StmtKind::FunctionDef { name, body, .. } | ast::StmtKind::AsyncFunctionDef { name, body, .. } => (name, body, false),
+            ast::StmtKind::ClassDef { name, body, .. } => (name, body, true),
+            _ => continue,
+        };
+        if name.starts_with('_') && name != "__init__" {
+            continue;
         }
         
-        Ok(new_content)
+        let signature = lines.get(stmt.location.row() - 1).map_or("", |line| line.trim());
+        outline.push(format!("{}{}", indent, signature));
+        let summary = PythonParser::new().extract_docstring(body)
+            .and_then(|doc| doc.lines().map(str::trim).find(|line| !line.is_empty()).map(str::to_string));
+        if let Some(summary) = summary {
+            outline.push(format!("{}    \"\"\"{}\"\"\"", indent, summary));
+        }
+        if is_class {
+            outline_definitions(body, lines, &format!("{}    ", indent), outline);
+        }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn return_annotations_are_kept_as_written() {
+        let parsed = PythonParser::new().parse("def f() -> list[int]:\n    return []\n").unwrap();
+        let function = parsed.items.iter().find(|item| item.name == "f").unwrap();
+        assert_eq!(function.returns.as_deref(), Some("list[int]"));
+    }
+}
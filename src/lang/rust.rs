@@ -1,44 +1,70 @@
 use crate::error::{DocGenError, DocGenResult};
-use crate::parser::{CodeItem, ParsedCode};
+use crate::parser::{apply_edits, is_obvious_value, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
 use crate::docstring::UpdatedDocstring;
 use super::LanguageParser;
-use tree_sitter::{Parser, Language, Query, QueryCursor};
+use tree_sitter::{Language, Node, Parser};
 use std::ops::Range;
-
-extern "C" {
-    fn tree_sitter_rust() -> Language;
-}
+use std::path::Path;
 
 /// Rust language parser implementation
 pub struct RustParser {
-    parser: Parser,
+    language: Language,
+    /// Also document public constants and statics
+    constants: bool,
+    /// Name of the crate or module the file is the root of, whose `//!` documentation
+    /// is checked as the file's first item
+    module: Option<String>,
+}
+
+impl Default for RustParser {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl RustParser {
     pub fn new() -> Self {
-        let mut parser = Parser::new();
-        let language = unsafe { tree_sitter_rust() };
-        parser.set_language(language).expect("Failed to load Rust grammar");
-        Self { parser }
+        // The grammar is compiled and linked by the tree-sitter-rust crate
+        let language = tree_sitter_rust::language();
+        Parser::new().set_language(language).expect("Failed to load Rust grammar");
+        Self { language, constants: false, module: None }
     }
     
-    /// Extract a substring from the source based on a byte range
-    fn get_node_text<'a>(&self, source: &'a str, range: Range<usize>) -> &'a str {
-        &source[range.start..range.end]
+    /// Document the file as the root of a crate or directory module with `//!` comments
+    pub fn with_module(mut self, module: Option<String>) -> Self {
+        self.module = module;
+        self
     }
     
-    /// Get the line number for a given byte position
-    fn get_line_number(&self, source: &str, position: usize) -> usize {
-        let mut line_number = 1;
-        for (i, c) in source.char_indices() {
-            if i >= position {
-                break;
-            }
-            if c == '\n' {
-                line_number += 1;
-            }
+    /// Enable documentation for public `const` and `static` items whose values aren't
+    /// self-explanatory
+    pub fn with_constants(mut self, enabled: bool) -> Self {
+        self.constants = enabled;
+        self
+    }
+    
+    /// Whether a `const` or `static` item is worth documenting: public, with a value
+    /// that doesn't explain itself
+    fn is_documented_constant(&self, node: Node, source: &str) -> bool {
+        let public = self.visibility(node) == Visibility::Public;
+        let value = node.child_by_field_name("value")
+            .map(|n| self.get_node_text(source, n.byte_range()))
+            .unwrap_or_default();
+        self.constants && public && !is_obvious_value(value)
+    }
+    
+    /// Visibility of a declaration: public with any `pub` modifier, including
+    /// `pub(crate)`, and private without one
+    fn visibility(&self, node: Node) -> Visibility {
+        match node.named_child(0) {
+            Some(n) if n.kind() == "visibility_modifier" => Visibility::Public,
+            _ => Visibility::Private,
         }
-        line_number
+    }
+    
+    /// Extract a substring from the source based on a byte range
+    fn get_node_text<'a>(&self, source: &'a str, range: Range<usize>) -> &'a str {
+        &source[range.start..range.end]
     }
     
     /// Extract indentation from a line
@@ -59,40 +85,17 @@ impl RustParser {
             .join("\n")
     }
     
-    /// Check if a given node is a documentation comment
-    fn is_doc_comment(&self, node_kind: &str) -> bool {
-        node_kind == "line_comment" && node_kind.starts_with("///")
-    }
-    
-    /// Extract parameters from a function declaration
-    fn extract_parameters(&self, params_node: tree_sitter::Node, source: &str) -> Vec<String> {
+    /// Extract parameter names from a function's parameter list, skipping `self`
+    fn extract_parameters(&self, params_node: Node, source: &str) -> Vec<String> {
         let mut params = Vec::new();
         let mut cursor = params_node.walk();
         
-        // Navigate to the parameters
-        if cursor.goto_first_child() {
-            // Skip the opening parenthesis
-            if cursor.goto_next_sibling() {
-                while cursor.node().kind() != ")" {
-                    if cursor.node().kind() == "parameter" {
-                        // Extract the parameter name
-                        let mut param_cursor = cursor.node().walk();
-                        if param_cursor.goto_first_child() {
-                            // Find the pattern node (which contains the parameter name)
-                            if param_cursor.node().kind() == "pattern" {
-                                // Get the identifier inside the pattern
-                                let mut pattern_cursor = param_cursor.node().walk();
-                                if pattern_cursor.goto_first_child() && pattern_cursor.node().kind() == "identifier" {
-                                    let param_name = self.get_node_text(source, pattern_cursor.node().byte_range());
-                                    params.push(param_name.to_string());
-                                }
-                            }
-                        }
-                    }
-                    if !cursor.goto_next_sibling() {
-                        break;
-                    }
-                }
+        for param in params_node.named_children(&mut cursor) {
+            if param.kind() != "parameter" {
+                continue;
+            }
+            if let Some(pattern) = param.child_by_field_name("pattern") {
+                params.push(self.get_node_text(source, pattern.byte_range()).to_string());
             }
         }
         
@@ -100,63 +103,330 @@ impl RustParser {
     }
     
     /// Extract return type from a function declaration
-    fn extract_return_type(&self, return_type_node: Option<tree_sitter::Node>, source: &str) -> Option<String> {
+    fn extract_return_type(&self, return_type_node: Option<Node>, source: &str) -> Option<String> {
         return_type_node.map(|node| self.get_node_text(source, node.byte_range()).to_string())
     }
     
-    /// Extract Rust doc comment blocks
-    fn extract_doc_comment(&self, node: tree_sitter::Node, source: &str) -> Option<String> {
-        let mut comments = Vec::new();
+    /// First of the attributes (`#[derive(...)]`, including ones that span several
+    /// lines) directly before an item, or the item itself. Doc comments go above the
+    /// attributes; `///` comments among them still belong to the item.
+    fn attribute_start<'a>(&self, node: Node<'a>, source: &str) -> Node<'a> {
+        let mut start = node;
+        let mut sibling = node.prev_sibling();
+        while let Some(previous) = sibling {
+            match previous.kind() {
+                "attribute_item" => start = previous,
+                "line_comment" if is_doc_line(self.get_node_text(source, previous.byte_range())) => {},
+                _ => break,
+            }
+            sibling = previous.prev_sibling();
+        }
+        start
+    }
+    
+    /// Text of the `///` lines among an item's attributes, between `anchor` and the
+    /// item's line
+    fn attribute_doc_comment(&self, lines: &[&str], anchor: usize, line_index: usize) -> Option<String> {
+        let text = lines[anchor..line_index]
+            .iter()
+            .filter(|l| is_doc_line(l))
+            .map(|l| {
+                let doc = l.trim_start().trim_start_matches("///");
+                doc.strip_prefix(' ').unwrap_or(doc).trim_end()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        (!text.is_empty()).then_some(text)
+    }
+    
+    /// Collect the `///` doc comment block directly above a line
+    fn leading_doc_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 && is_doc_line(lines[start - 1]) {
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
         
-        // Since tree-sitter doesn't have goto_previous_sibling, we need to use a different approach
-        // Look for doc comments in the source code before the node's start position
-        let _node_start_position = node.start_position(); // Prefix with underscore to indicate unused
-        let node_start_byte = node.start_byte();
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                let doc = l.trim_start().trim_start_matches("///");
+                doc.strip_prefix(' ').unwrap_or(doc).trim_end()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some((start, text))
+    }
+    
+    /// Collect the `//!` inner doc comment block at the top of an inline module's
+    /// body, which starts on the line after `line_index`
+    fn inner_doc_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, usize, String)> {
+        let start = line_index + 1;
+        let mut end = start;
+        while end < lines.len() && lines[end].trim_start().starts_with("//!") {
+            end += 1;
+        }
+        if end == start {
+            return None;
+        }
         
-        // Use a substring of the source code up to the node start position
-        let preceding_text = &source[..node_start_byte];
+        let text = lines[start..end]
+            .iter()
+            .map(|l| {
+                let doc = l.trim_start().trim_start_matches("//!");
+                doc.strip_prefix(' ').unwrap_or(doc).trim_end()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some((start, end - 1, text))
+    }
+    
+    /// Build the item for the crate or module a file is the root of. Its documentation
+    /// is the `//!` comment at the top of the file, which goes after any `#!` line and
+    /// inner attributes (`#![...]`).
+    fn file_module_item(&self, name: &str, root: Node, content: &str, lines: &[&str], offsets: &LineOffsets) -> CodeItem {
+        let mut insert_at = 0;
+        let mut doc_lines: Option<(usize, usize)> = None;
         
-        // Count the newlines to determine line number
-        let node_line_number = preceding_text.matches('\n').count() + 1;
+        let mut cursor = root.walk();
+        for child in root.named_children(&mut cursor) {
+            let row = child.start_position().row;
+            match child.kind() {
+                // The grammar reads a first `#![...]` line as a `#!` line
+                "shebang" | "inner_attribute_item" => insert_at = insert_at.max(child.end_position().row + 1),
+                "line_comment" if self.get_node_text(content, child.byte_range()).starts_with("//!") => {
+                    match &mut doc_lines {
+                        None => doc_lines = Some((row, row)),
+                        Some((_, end)) if *end + 1 == row => *end = row,
+                        Some(_) => {},
+                    }
+                },
+                "line_comment" | "block_comment" => {},
+                _ => break,
+            }
+        }
         
-        // Look back through the source code to find doc comments
-        if node_line_number > 1 {
-            let lines: Vec<&str> = source.lines().collect();
-            let mut current_line = node_line_number - 1;
-            let mut collecting_comments = false;
-            
-            while current_line > 0 {
-                let line = lines[current_line - 1].trim();
-                
-                if line.starts_with("///") {
-                    collecting_comments = true;
-                    // Strip the /// and any leading space
-                    let doc_text = line.trim_start_matches("///").trim();
-                    comments.push(doc_text.to_string());
-                } else if line.is_empty() {
-                    // Skip empty lines
-                } else if collecting_comments {
-                    // Found a non-comment, non-empty line after we started collecting comments
-                    break;
-                } else {
-                    // Found a non-comment, non-empty line before we started collecting comments
-                    break;
-                }
-                
-                current_line -= 1;
+        let existing_docstring = doc_lines.map(|(start, end)| {
+            lines[start..=end]
+                .iter()
+                .map(|l| {
+                    let doc = l.trim_start().trim_start_matches("//!");
+                    doc.strip_prefix(' ').unwrap_or(doc).trim_end()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        });
+        let start = doc_lines.map_or(insert_at, |(start, _)| start);
+        
+        CodeItem {
+            item_type: ItemKind::Module,
+            name: name.to_string(),
+            line_number: start + 1,
+            code: content.to_string(),
+            existing_docstring,
+            parent: None,
+            parameters: Vec::new(),
+            parameter_details: Vec::new(),
+            returns: None,
+            indentation: String::new(),
+            span: offsets.start(start)..content.len(),
+            docstring_span: doc_lines.map(|(start, end)| offsets.lines(start, end)),
+            is_async: false,
+            is_generator: false,
+            decorators: Vec::new(),
+            visibility: Visibility::Public,
+            throws: Vec::new(),
+        }
+    }
+    
+    /// Build a code item for a declaration node
+    fn code_item(
+        &self,
+        node: Node,
+        item_type: ItemKind,
+        parent: Option<String>,
+        content: &str,
+        lines: &[&str],
+        offsets: &LineOffsets,
+    ) -> Option<CodeItem> {
+        let name_node = node.child_by_field_name("name")?;
+        let name = self.get_node_text(content, name_node.byte_range()).to_string();
+        let line_number = node.start_position().row + 1; // 1-indexed
+        let end_line = node.end_position().row + 1;
+        
+        let parameters = node.child_by_field_name("parameters")
+            .map(|params_node| self.extract_parameters(params_node, content))
+            .unwrap_or_default();
+        let returns = self.extract_return_type(node.child_by_field_name("return_type"), content);
+        
+        let anchor = self.attribute_start(node, content).start_position().row;
+        let doc_comment = self.leading_doc_comment(lines, anchor);
+        // Documentation written among the attributes is replaced along with them, so
+        // that it moves above them
+        let (docstring_span, existing_docstring) = match self.attribute_doc_comment(lines, anchor, line_number - 1) {
+            Some(text) => {
+                let start = doc_comment.as_ref().map_or(anchor, |(start, _)| *start);
+                let existing = doc_comment.map_or(text.clone(), |(_, above)| format!("{}\n{}", above, text));
+                (Some(offsets.lines(start, line_number - 2)), Some(existing))
+            },
+            None => doc_comment
+                .map(|(start, text)| (offsets.lines(start, anchor - 1), text))
+                .unzip(),
+        };
+        
+        Some(CodeItem {
+            item_type,
+            name,
+            line_number,
+            code: self.extract_code_block(content, line_number, end_line),
+            existing_docstring,
+            parent,
+            parameters,
+            parameter_details: Vec::new(),
+            returns,
+            indentation: self.extract_indentation(content, line_number),
+            span: offsets.start(anchor)..node.end_byte(),
+            docstring_span,
+            is_async: false,
+            is_generator: false,
+            decorators: Vec::new(),
+            visibility: self.visibility(node),
+            throws: Vec::new(),
+        })
+    }
+    
+    /// Collect documentable items from a module-level declaration list. Function
+    /// bodies are not descended into, so nested helper functions are skipped.
+    fn collect_items(&self, node: Node, content: &str, lines: &[&str], offsets: &LineOffsets, code_items: &mut Vec<CodeItem>) {
+        let mut cursor = node.walk();
+        
+        for child in node.named_children(&mut cursor) {
+            match child.kind() {
+                "function_item" => code_items.extend(self.code_item(child, ItemKind::Function, None, content, lines, offsets)),
+                "struct_item" => code_items.extend(self.code_item(child, ItemKind::Struct, None, content, lines, offsets)),
+                "enum_item" => {
+                    if let Some(item) = self.code_item(child, ItemKind::Enum, None, content, lines, offsets) {
+                        let variants = child.child_by_field_name("body")
+                            .map(|body| self.collect_variants(body, &item, content, lines, offsets))
+                            .unwrap_or_default();
+                        code_items.push(item);
+                        code_items.extend(variants);
+                    }
+                },
+                "type_item" => code_items.extend(self.code_item(child, ItemKind::Type, None, content, lines, offsets)),
+                "macro_definition" => {
+                    let mut item = self.code_item(child, ItemKind::Macro, None, content, lines, offsets);
+                    // `macro_rules!` macros have no `pub`; they're exported with an attribute
+                    let line_index = child.start_position().row;
+                    let exported = lines[self.attribute_start(child, content).start_position().row..line_index]
+                        .iter()
+                        .any(|l| l.contains("macro_export"));
+                    item.iter_mut().for_each(|item| {
+                        item.visibility = if exported { Visibility::Public } else { Visibility::Private };
+                    });
+                    code_items.extend(item);
+                },
+                "const_item" | "static_item" if self.is_documented_constant(child, content) => {
+                    code_items.extend(self.code_item(child, ItemKind::Constant, None, content, lines, offsets));
+                },
+                "trait_item" => {
+                    code_items.extend(self.code_item(child, ItemKind::Trait, None, content, lines, offsets));
+                    let trait_name = child.child_by_field_name("name")
+                        .map(|n| self.get_node_text(content, n.byte_range()).to_string());
+                    if let Some(body) = child.child_by_field_name("body") {
+                        self.collect_methods(body, trait_name, content, lines, offsets, code_items);
+                    }
+                },
+                "impl_item" => {
+                    let type_name = child.child_by_field_name("type")
+                        .map(|n| self.get_node_text(content, n.byte_range()).to_string());
+                    if let Some(body) = child.child_by_field_name("body") {
+                        self.collect_methods(body, type_name, content, lines, offsets, code_items);
+                    }
+                },
+                "mod_item" => {
+                    // Modules declared with `mod name;` are documented in their own files
+                    if let Some(body) = child.child_by_field_name("body") {
+                        code_items.extend(self.module_item(child, content, lines, offsets));
+                        self.collect_items(body, content, lines, offsets, code_items);
+                    }
+                },
+                _ => {}
             }
         }
+    }
+    
+    /// Build a code item for an inline module, whose documentation may be a `//!`
+    /// comment at the top of its body rather than a `///` comment above it
+    fn module_item(&self, node: Node, content: &str, lines: &[&str], offsets: &LineOffsets) -> Option<CodeItem> {
+        let mut item = self.code_item(node, ItemKind::Module, None, content, lines, offsets)?;
+        if item.existing_docstring.is_none() {
+            if let Some((start, end, text)) = self.inner_doc_comment(lines, node.start_position().row) {
+                item.docstring_span = Some(offsets.lines(start, end));
+                item.existing_docstring = Some(text);
+            }
+        }
+        Some(item)
+    }
+    
+    /// Collect the variants of an enum that start their own lines, as items of the
+    /// enum that are as visible as it is. Variants of an enum written on one line
+    /// are left to the enum's documentation.
+    fn collect_variants(
+        &self,
+        body: Node,
+        parent: &CodeItem,
+        content: &str,
+        lines: &[&str],
+        offsets: &LineOffsets,
+    ) -> Vec<CodeItem> {
+        let mut cursor = body.walk();
+        body.named_children(&mut cursor)
+            .filter(|child| child.kind() == "enum_variant")
+            .filter(|child| {
+                let position = self.attribute_start(*child, content).start_position();
+                lines[position.row][..position.column].trim().is_empty()
+            })
+            .filter_map(|child| self.code_item(child, ItemKind::EnumValue, Some(parent.name.clone()), content, lines, offsets))
+            .map(|variant| CodeItem { visibility: parent.visibility, ..variant })
+            .collect()
+    }
+    
+    /// Collect the methods declared in an impl or trait body. The methods of a trait,
+    /// and of its impls, are as visible as the trait, whatever their modifiers.
+    fn collect_methods(
+        &self,
+        body: Node,
+        parent: Option<String>,
+        content: &str,
+        lines: &[&str],
+        offsets: &LineOffsets,
+        code_items: &mut Vec<CodeItem>,
+    ) {
+        let mut cursor = body.walk();
+        let of_trait = body.parent()
+            .is_some_and(|node| node.kind() == "trait_item" || node.child_by_field_name("trait").is_some());
         
-        if comments.is_empty() {
-            None
-        } else {
-            // Reverse comments to get them in the right order
-            comments.reverse();
-            Some(comments.join("\n"))
+        for child in body.named_children(&mut cursor) {
+            if child.kind() == "function_item" || child.kind() == "function_signature_item" {
+                let mut method = self.code_item(child, ItemKind::Method, parent.clone(), content, lines, offsets);
+                if of_trait {
+                    method.iter_mut().for_each(|method| method.visibility = Visibility::Public);
+                }
+                code_items.extend(method);
+            }
         }
     }
 }
 
+/// Whether a line is an outer doc comment: `///`, but not a `////` separator
+fn is_doc_line(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("///") && !line.starts_with("////")
+}
+
 impl LanguageParser for RustParser {
     fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
         let mut code_items = Vec::new();
@@ -164,295 +434,151 @@ impl LanguageParser for RustParser {
         // Parse the Rust code using tree-sitter
         // Since Parser doesn't implement Clone, we create a new one each time
         let mut parser = Parser::new();
-        let language = unsafe { tree_sitter_rust() };
-        parser.set_language(language).expect("Failed to load Rust grammar");
+        parser.set_language(self.language).expect("Failed to load Rust grammar");
         
         let tree = parser.parse(content, None)
             .ok_or_else(|| DocGenError::ParsingError("Failed to parse Rust code".into()))?;
         
-        let root_node = tree.root_node();
-        
-        // Query to find function, struct, and impl declarations
-        let function_query = Query::new(
-            unsafe { tree_sitter_rust() },
-            "(function_item name: (identifier) @function_name) @function"
-        ).map_err(|e| DocGenError::ParsingError(format!("Failed to create function query: {}", e)))?;
-        
-        let struct_query = Query::new(
-            unsafe { tree_sitter_rust() },
-            "(struct_item name: (type_identifier) @struct_name) @struct"
-        ).map_err(|e| DocGenError::ParsingError(format!("Failed to create struct query: {}", e)))?;
-        
-        let impl_query = Query::new(
-            unsafe { tree_sitter_rust() },
-            "(impl_item type: (type_identifier) @impl_type) @impl"
-        ).map_err(|e| DocGenError::ParsingError(format!("Failed to create impl query: {}", e)))?;
-        
-        let method_query = Query::new(
-            unsafe { tree_sitter_rust() },
-            "(impl_item (block (function_item name: (identifier) @method_name) @method))"
-        ).map_err(|e| DocGenError::ParsingError(format!("Failed to create method query: {}", e)))?;
-        
-        // Process function declarations
-        let mut query_cursor = QueryCursor::new();
-        let function_matches = query_cursor.matches(&function_query, root_node, content.as_bytes());
-        
-        for function_match in function_matches {
-            for capture in function_match.captures {
-                if capture.index == 0 { // @function capture
-                    let function_node = capture.node;
-                    let name_node = &function_query.capture_names()[1]; // @function_name - borrow instead of moving
-                    
-                    if let Some(name_capture) = function_match.captures.iter().find(|c| &function_query.capture_names()[c.index as usize] == name_node) {
-                        let function_name = self.get_node_text(content, name_capture.node.byte_range()).to_string();
-                        let start_position = function_node.start_position();
-                        let end_position = function_node.end_position();
-                        let line_number = start_position.row + 1; // 1-indexed
-                        let end_line = end_position.row + 1;
-                        
-                        // Find parameters
-                        let params = if let Some(params_node) = function_node.child_by_field_name("parameters") {
-                            self.extract_parameters(params_node, content)
-                        } else {
-                            Vec::new()
-                        };
-                        
-                        // Find return type
-                        let return_type = self.extract_return_type(function_node.child_by_field_name("return_type"), content);
-                        
-                        // Extract doc comment
-                        let docstring = self.extract_doc_comment(function_node, content);
-                        
-                        code_items.push(CodeItem {
-                            item_type: "function".to_string(),
-                            name: function_name,
-                            line_number,
-                            code: self.extract_code_block(content, line_number, end_line),
-                            existing_docstring: docstring,
-                            parent: None,
-                            parameters: params,
-                            returns: return_type,
-                            indentation: self.extract_indentation(content, line_number),
-                        });
-                    }
-                }
-            }
-        }
-        
-        // Process struct declarations
-        query_cursor = QueryCursor::new();
-        let struct_matches = query_cursor.matches(&struct_query, root_node, content.as_bytes());
-        
-        for struct_match in struct_matches {
-            for capture in struct_match.captures {
-                if capture.index == 0 { // @struct capture
-                    let struct_node = capture.node;
-                    let name_node = &struct_query.capture_names()[1]; // @struct_name - borrow instead of moving
-                    
-                    if let Some(name_capture) = struct_match.captures.iter().find(|c| &struct_query.capture_names()[c.index as usize] == name_node) {
-                        let struct_name = self.get_node_text(content, name_capture.node.byte_range()).to_string();
-                        let start_position = struct_node.start_position();
-                        let end_position = struct_node.end_position();
-                        let line_number = start_position.row + 1; // 1-indexed
-                        let end_line = end_position.row + 1;
-                        
-                        // Extract doc comment
-                        let docstring = self.extract_doc_comment(struct_node, content);
-                        
-                        code_items.push(CodeItem {
-                            item_type: "struct".to_string(),
-                            name: struct_name,
-                            line_number,
-                            code: self.extract_code_block(content, line_number, end_line),
-                            existing_docstring: docstring,
-                            parent: None,
-                            parameters: Vec::new(),
-                            returns: None,
-                            indentation: self.extract_indentation(content, line_number),
-                        });
-                    }
-                }
-            }
-        }
-        
-        // Process impl blocks and their methods
-        query_cursor = QueryCursor::new();
-        let impl_matches = query_cursor.matches(&impl_query, root_node, content.as_bytes());
-        
-        for impl_match in impl_matches {
-            for capture in impl_match.captures {
-                if capture.index == 0 { // @impl capture
-                    let impl_node = capture.node;
-                    let type_node = &impl_query.capture_names()[1]; // @impl_type - borrow instead of moving
-                    
-                    if let Some(type_capture) = impl_match.captures.iter().find(|c| &impl_query.capture_names()[c.index as usize] == type_node) {
-                        let type_name = self.get_node_text(content, type_capture.node.byte_range()).to_string();
-                        
-                        // Process methods within the impl block
-                        let mut method_cursor = QueryCursor::new();
-                        
-                        if let Some(block_node) = impl_node.child_by_field_name("body") {
-                            let method_matches = method_cursor.matches(&method_query, block_node, content.as_bytes());
-                            
-                            for method_match in method_matches {
-                                for method_capture in method_match.captures {
-                                    if method_query.capture_names()[method_capture.index as usize] == "method" {
-                                        let method_node = method_capture.node;
-                                        
-                                        // Find the method name
-                                        if let Some(name_capture) = method_match.captures.iter()
-                                            .find(|c| method_query.capture_names()[c.index as usize] == "method_name") {
-                                            let method_name = self.get_node_text(content, name_capture.node.byte_range()).to_string();
-                                            let start_position = method_node.start_position();
-                                            let end_position = method_node.end_position();
-                                            let line_number = start_position.row + 1; // 1-indexed
-                                            let end_line = end_position.row + 1;
-                                            
-                                            // Find parameters
-                                            let params = if let Some(params_node) = method_node.child_by_field_name("parameters") {
-                                                self.extract_parameters(params_node, content)
-                                            } else {
-                                                Vec::new()
-                                            };
-                                            
-                                            // Find return type
-                                            let return_type = self.extract_return_type(method_node.child_by_field_name("return_type"), content);
-                                            
-                                            // Extract doc comment
-                                            let docstring = self.extract_doc_comment(method_node, content);
-                                            
-                                            code_items.push(CodeItem {
-                                                item_type: "method".to_string(),
-                                                name: method_name,
-                                                line_number,
-                                                code: self.extract_code_block(content, line_number, end_line),
-                                                existing_docstring: docstring,
-                                                parent: Some(type_name.clone()),
-                                                parameters: params,
-                                                returns: return_type,
-                                                indentation: self.extract_indentation(content, line_number),
-                                            });
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        if let Some(module) = &self.module {
+            code_items.push(self.file_module_item(module, tree.root_node(), content, &lines, &offsets));
         }
+        self.collect_items(tree.root_node(), content, &lines, &offsets, &mut code_items);
         
         Ok(ParsedCode {
             items: code_items,
             original_content: content.to_string(),
+            language: "rust".to_string(),
+            syntax_errors: super::syntax_errors(tree.root_node()),
         })
     }
     
     fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
-        let mut new_content = content.to_string();
-        
-        // Get access to the parsed code items for more accurate updates
-        let parsed_code = self.parse(&new_content)?;
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
         
-        // Sort updates in reverse order by line number to avoid line number shifts
-        let mut sorted_updates = updated_docstrings.to_vec();
-        sorted_updates.sort_by(|a, b| {
-            let a_line = parsed_code.items[a.item_index].line_number;
-            let b_line = parsed_code.items[b.item_index].line_number;
-            b_line.cmp(&a_line)
-        });
-        
-        for update in sorted_updates {
-            let item = &parsed_code.items[update.item_index];
-            let lines: Vec<&str> = new_content.lines().collect();
-            
-            // Get the line that defines the item
-            let line_index = item.line_number - 1; // Convert to 0-based index
-            
-            if line_index >= lines.len() {
-                return Err(DocGenError::UpdateError(
-                    format!("Line number {} is out of bounds", item.line_number)));
-            }
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
             
-            // Get indentation level from the definition line
-            let indentation = item.indentation.clone();
-            
-            // Check if there's an existing doc comment to replace
-            let mut has_existing_docstring = false;
-            let mut doc_start_line = line_index;
-            let mut doc_end_line = line_index;
-            
-            // Look for existing doc comments
-            for i in (0..line_index).rev() {
-                let line = lines[i].trim();
-                if line.starts_with("///") {
-                    has_existing_docstring = true;
-                    doc_start_line = i;
-                } else if !line.is_empty() {
-                    // We found a non-comment, non-empty line, so stop looking
-                    break;
-                }
-            }
-            
-            if has_existing_docstring {
-                // Find the end of the doc comment block
-                for i in (doc_start_line..line_index).rev() {
-                    if lines[i].trim().starts_with("///") {
-                        doc_end_line = i;
-                        break;
-                    }
-                }
-            }
-            
-            // Format the new docstring as Rust doc comments
-            let new_doc_lines: Vec<String> = update.new_docstring
+            // Format the new docstring as Rust doc comments, which replace an existing
+            // block or go above any attributes. The file's own module, and a module
+            // documented inside its body, get `//!` comments there.
+            let file_module = self.module.is_some() && update.item_index == 0;
+            let (marker, indentation) = match &item.docstring_span {
+                _ if file_module => ("//!", String::new()),
+                Some(span) if span.start > item.span.start => {
+                    let line = &content[span.clone()];
+                    ("//!", line[..line.len() - line.trim_start().len()].to_string())
+                },
+                _ => ("///", item.indentation.clone()),
+            };
+            let doc_comment: String = update.new_docstring
+                .trim()
                 .lines()
                 .map(|line| {
-                    let trimmed = line.trim();
-                    if !trimmed.is_empty() {
-                        format!("{}/// {}", indentation, trimmed)
+                    let trimmed = line.trim_end();
+                    if !trimmed.trim().is_empty() {
+                        format!("{}{} {}\n", indentation, marker, trimmed)
                     } else {
-                        format!("{}///", indentation)
+                        format!("{}{}\n", indentation, marker)
                     }
                 })
                 .collect();
             
-            let formatted_doc = new_doc_lines.join("\n");
+            // Attributes in the replaced range, which had documentation among them,
+            // follow the new comment
+            let attributes: String = match &item.docstring_span {
+                Some(span) if marker == "///" => content[span.clone()]
+                    .split_inclusive('\n')
+                    .filter(|l| !is_doc_line(l))
+                    .collect(),
+                // A new crate or module comment is set apart from the code after it
+                None if file_module && !content[item.span.start..].starts_with(['\n', '\r']) => "\n".to_string(),
+                _ => String::new(),
+            };
             
-            // Update the content
-            if has_existing_docstring {
-                // Replace existing doc comment
-                let before = if doc_start_line > 0 {
-                    lines[..doc_start_line].join("\n")
-                } else {
-                    String::new()
-                };
-                
-                let after = if doc_end_line + 1 < lines.len() {
-                    format!("\n{}", lines[(doc_end_line + 1)..].join("\n"))
-                } else {
-                    String::new()
-                };
-                
-                new_content = format!("{}\n{}{}", before, formatted_doc, after);
-            } else {
-                // Insert new doc comment before the definition
-                let before = if line_index > 0 {
-                    lines[..line_index].join("\n")
-                } else {
-                    String::new()
-                };
-                
-                let after = if line_index < lines.len() {
-                    format!("\n{}", lines[line_index..].join("\n"))
-                } else {
-                    String::new()
-                };
-                
-                new_content = format!("{}\n{}{}", before, formatted_doc, after);
-            }
+            edits.push(Edit::new(item.doc_range(), doc_comment + &attributes));
         }
         
-        Ok(new_content)
+        apply_edits(content, edits)
     }
-}
\ No newline at end of file
+}
+
+/// Whether an item's documentation gets a `# Examples` section (`--with-examples`):
+/// public items that can be used from a doctest, which only sees the crate's public API
+pub fn takes_example(item: &CodeItem) -> bool {
+    // `pub(crate)` items count as public, but a doctest can't reach them
+    let restricted = item.code.trim_start().starts_with("pub(");
+    item.visibility == Visibility::Public && !restricted && matches!(item.item_type,
+        ItemKind::Function | ItemKind::Method | ItemKind::Struct | ItemKind::Enum | ItemKind::Trait | ItemKind::Type | ItemKind::Macro)
+}
+
+/// Name of the crate or module a Rust file is the root of, for the files whose `//!`
+/// documentation describes more than the file: a crate's `lib.rs` or `main.rs`, named
+/// for its package directory, and a directory module's `mod.rs`
+pub fn module_name(file_path: &Path) -> Option<String> {
+    let file_path = std::fs::canonicalize(file_path).unwrap_or_else(|_| file_path.to_path_buf());
+    let dir = file_path.parent()?;
+    let dir_name = |dir: &Path| dir.file_name().and_then(|n| n.to_str()).map(str::to_string);
+    match file_path.file_name()?.to_str()? {
+        "lib.rs" | "main.rs" if dir.file_name().is_some_and(|n| n == "src") => dir.parent().and_then(dir_name),
+        "lib.rs" | "main.rs" => dir_name(dir),
+        "mod.rs" => dir_name(dir),
+        _ => None,
+    }
+}
+
+/// Outline of a module's public items for its `//!` documentation prompt: each item's
+/// declaration line and the first line of its documentation, with the items of inline
+/// modules under them
+pub fn module_outline(content: &str) -> String {
+    let mut parser = Parser::new();
+    if parser.set_language(tree_sitter_rust::language()).is_err() {
+        return content.to_string();
+    }
+    let tree = match parser.parse(content, None) {
+        Some(tree) => tree,
+        None => return content.to_string(),
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let mut outline = Vec::new();
+    outline_items(tree.root_node(), &lines, "", &mut outline);
+    outline.join("\n")
+}
+
+/// Add the public items among a declaration list's children to an outline
+fn outline_items(node: Node, lines: &[&str], indent: &str, outline: &mut Vec<String>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let public = child.named_child(0).is_some_and(|n| n.kind() == "visibility_modifier");
+        let documented_kind = matches!(child.kind(),
+            "function_item" | "struct_item" | "enum_item" | "trait_item" | "type_item" | "const_item" | "static_item" | "mod_item");
+        let exported_macro = child.kind() == "macro_definition"
+            && child.prev_named_sibling().is_some_and(|n| n.kind() == "attribute_item"
+                && lines.get(n.start_position().row).is_some_and(|l| l.contains("macro_export")));
+        if !(exported_macro || public && documented_kind) {
+            continue;
+        }
+        
+        let row = child.start_position().row;
+        let mut summary_start = row;
+        while summary_start > 0 && lines[summary_start - 1].trim_start().starts_with("#[") {
+            summary_start -= 1;
+        }
+        let summary = lines[..summary_start].iter().rev()
+            .take_while(|l| is_doc_line(l))
+            .last()
+            .map(|l| l.trim());
+        if let Some(summary) = summary {
+            outline.push(format!("{}{}", indent, summary));
+        }
+        outline.push(format!("{}{}", indent, lines[row].trim()));
+        if child.kind() == "mod_item" {
+            if let Some(body) = child.child_by_field_name("body") {
+                outline_items(body, lines, &format!("{}    ", indent), outline);
+            }
+        }
+    }
+}
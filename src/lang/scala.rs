@@ -0,0 +1,295 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Scala parser implementation for classes, objects, traits, and defs,
+/// documented with Scaladoc `/** ... */` comments
+pub struct ScalaParser {
+    decl_re: Regex,
+}
+
+impl Default for ScalaParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScalaParser {
+    pub fn new() -> Self {
+        Self {
+            decl_re: Regex::new(
+                r"^(?:(?:private|protected)(?:\[\w+\])?\s+|override\s+|final\s+|implicit\s+|inline\s+|abstract\s+|sealed\s+|case\s+|lazy\s+)*(class|object|trait|def)\s+(`[^`]+`|[A-Za-z_$][\w$]*|[^\s\w\[(:=]+)"
+            ).unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Index of the first line of the annotations (`@tailrec`, `@deprecated(...)`)
+    /// directly above a declaration, or the declaration itself
+    fn annotation_start(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with('@') {
+            start -= 1;
+        }
+        start
+    }
+    
+    /// Collect the `/** ... */` comment directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 || !lines[line_index - 1].trim().ends_with("*/") {
+            return None;
+        }
+        
+        let mut start = line_index - 1;
+        while start > 0 && !lines[start].contains("/*") {
+            start -= 1;
+        }
+        if !lines[start].trim_start().starts_with("/**") {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("/**")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the last line of a declaration's signature, which may span several
+    /// lines while parameter lists are open or continue on the next line
+    fn signature_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            for c in code.chars() {
+                match c {
+                    '(' | '[' => depth += 1,
+                    ')' | ']' => depth -= 1,
+                    _ => {}
+                }
+            }
+            
+            let continues = lines.get(i + 1)
+                .map(|next| {
+                    let next = next.trim_start();
+                    next.starts_with('(') || next.starts_with(':') || next.starts_with("extends") || next.starts_with("with")
+                })
+                .unwrap_or(false);
+            if depth <= 0 && !continues {
+                return i;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+    
+    /// Find the line index of the closing brace for a body opened in the signature,
+    /// or None if the declaration has no braced body
+    fn body_end(&self, lines: &[&str], signature_end: usize, line_index: usize) -> Option<usize> {
+        let signature = lines[line_index..=signature_end].join("\n");
+        if !signature.split("//").next().unwrap_or("").contains('{') && !signature.trim_end().ends_with('{') {
+            return None;
+        }
+        
+        let mut depth = 0i32;
+        let mut opened = false;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            opened |= code.contains('{');
+            depth += code.matches('{').count() as i32;
+            depth -= code.matches('}').count() as i32;
+            if opened && depth <= 0 {
+                return Some(i);
+            }
+        }
+        Some(lines.len().saturating_sub(1))
+    }
+    
+    /// Split a signature (starting after the name) into its parameter lists and return type.
+    /// Curried lists (`(a: Int)(b: Int)`) and implicit/using lists are flattened in order.
+    fn parse_signature(&self, rest: &str) -> (Vec<String>, Option<String>) {
+        let mut parameters = Vec::new();
+        // Parents and traits after `extends` are not part of the parameter model
+        let rest = rest.split(" extends ").next().unwrap_or(rest);
+        let mut depth = 0i32;
+        let mut group_start = 0;
+        let mut return_start = None;
+        
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' | '[' => {
+                    if depth == 0 {
+                        group_start = i + 1;
+                    }
+                    depth += 1;
+                },
+                ')' | ']' => {
+                    depth -= 1;
+                    if depth == 0 && c == ')' {
+                        parameters.extend(self.split_parameters(&rest[group_start..i]));
+                    }
+                },
+                ':' if depth == 0 => {
+                    return_start = Some(i + 1);
+                    break;
+                },
+                '=' | '{' if depth == 0 => break,
+                _ => {}
+            }
+        }
+        
+        let returns = return_start.map(|start| {
+            rest[start..]
+                .split(['=', '{'])
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_string()
+        }).filter(|r| !r.is_empty() && r != "Unit");
+        
+        (parameters, returns)
+    }
+    
+    /// Parameter names from one parameter list, without modifiers or types
+    fn split_parameters(&self, list: &str) -> Vec<String> {
+        let mut parameters = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 0;
+        
+        for (i, c) in list.char_indices().chain(std::iter::once((list.len(), ','))) {
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth -= 1,
+                ',' if depth == 0 => {
+                    let param = list[start..i].trim();
+                    let name = param.split(':').next().unwrap_or("").split_whitespace()
+                        .rfind(|w| !matches!(*w, "implicit" | "using" | "val" | "var" | "override" | "private" | "protected"))
+                        .unwrap_or("");
+                    if !name.is_empty() && param.contains(':') {
+                        parameters.push(name.to_string());
+                    }
+                    start = i + 1;
+                },
+                _ => {}
+            }
+        }
+        parameters
+    }
+}
+
+impl LanguageParser for ScalaParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Stack of enclosing bodies: (kind, name, closing line index)
+        let mut scopes: Vec<(String, String, usize)> = Vec::new();
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            while scopes.last().is_some_and(|(_, _, end)| *end < line_index) {
+                scopes.pop();
+            }
+            
+            let code = line.trim();
+            let caps = match self.decl_re.captures(code) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let kind = caps[1].to_string();
+            let name = caps[2].trim_matches('`').to_string();
+            
+            let signature_end = self.signature_end(&lines, line_index);
+            let body_end = self.body_end(&lines, signature_end, line_index);
+            let end_line = body_end.unwrap_or(signature_end);
+            
+            // Defs local to another def's body are implementation details
+            let inside_def = scopes.iter().any(|(kind, _, _)| kind == "def");
+            let parent_path: Vec<&str> = scopes.iter().map(|(_, name, _)| name.as_str()).collect();
+            let parent = if parent_path.is_empty() { None } else { Some(parent_path.join(".")) };
+            
+            if let Some(end) = body_end {
+                scopes.push((kind.clone(), name.clone(), end));
+            }
+            if inside_def {
+                continue;
+            }
+            
+            let signature = lines[line_index..=signature_end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+            let rest = &signature[caps.get(2).map_or(0, |m| m.end())..];
+            let (parameters, returns) = self.parse_signature(rest);
+            
+            let doc_anchor = self.annotation_start(&lines, line_index);
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, doc_anchor)
+                .map(|(start, text)| (offsets.lines(start, doc_anchor - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: kind.parse().expect("declaration pattern only matches item kinds"),
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end_line].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(doc_anchor, end_line),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "scala".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let mut comment = format!("{}/**\n", item.indentation);
+            for line in update.new_docstring.trim().lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    comment.push_str(&format!("{}  *\n", item.indentation));
+                } else {
+                    comment.push_str(&format!("{}  * {}\n", item.indentation, trimmed));
+                }
+            }
+            comment.push_str(&format!("{}  */\n", item.indentation));
+            
+            // Scaladoc goes above any annotations on the declaration
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,278 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Solidity parser implementation for contracts, interfaces, libraries, functions,
+/// modifiers, and events, documented with NatSpec `///` comments
+pub struct SolidityParser {
+    contract_re: Regex,
+    member_re: Regex,
+}
+
+impl Default for SolidityParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SolidityParser {
+    pub fn new() -> Self {
+        Self {
+            contract_re: Regex::new(r"^(?:abstract\s+)?(contract|interface|library)\s+(\w+)").unwrap(),
+            member_re: Regex::new(r"^(?:(function|modifier|event)\s+(\w+)|(constructor|fallback|receive))\s*\(").unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Collect the NatSpec comment directly above a line, written either as `///`
+    /// lines or as a `/** ... */` block
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 {
+            return None;
+        }
+        
+        let mut start = line_index;
+        if lines[line_index - 1].trim().ends_with("*/") {
+            start = line_index - 1;
+            while start > 0 && !lines[start].contains("/*") {
+                start -= 1;
+            }
+            if !lines[start].trim_start().starts_with("/**") {
+                return None;
+            }
+        } else {
+            while start > 0 && lines[start - 1].trim_start().starts_with("///") {
+                start -= 1;
+            }
+            if start == line_index {
+                return None;
+            }
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("///")
+                    .trim_start_matches("/**")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the line index where a declaration's header ends: the line with its body's
+    /// opening brace, or with the `;` ending a body-less declaration
+    fn header_end(&self, lines: &[&str], line_index: usize) -> usize {
+        (line_index..lines.len())
+            .find(|&i| {
+                let code = lines[i].split("//").next().unwrap_or("");
+                code.contains('{') || code.contains(';')
+            })
+            .unwrap_or(line_index)
+    }
+    
+    /// Find the line index of the closing brace of the body opened on `header_end`
+    fn body_end(&self, lines: &[&str], header_end: usize) -> usize {
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(header_end) {
+            let code = line.split("//").next().unwrap_or("");
+            depth += code.matches('{').count() as i32;
+            depth -= code.matches('}').count() as i32;
+            if depth <= 0 {
+                return i;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+    
+    /// Text inside the parenthesis opening at `open`, and the rest after it
+    fn parenthesized<'a>(&self, text: &'a str, open: usize) -> (&'a str, &'a str) {
+        let mut depth = 0i32;
+        for (i, c) in text[open..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return (&text[open + 1..open + i], &text[open + i + 1..]);
+                    }
+                },
+                _ => {}
+            }
+        }
+        (&text[(open + 1).min(text.len())..], "")
+    }
+    
+    /// Parameter names from a parameter list; unnamed parameters are skipped
+    fn parameter_names(&self, list: &str) -> Vec<String> {
+        self.split_top_level(list)
+            .iter()
+            .filter_map(|param| {
+                let words: Vec<&str> = param.split_whitespace().collect();
+                let name = *words.last()?;
+                let is_name = words.len() > 1
+                    && !matches!(name, "memory" | "storage" | "calldata" | "indexed" | "payable")
+                    && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+                if is_name { Some(name.to_string()) } else { None }
+            })
+            .collect()
+    }
+    
+    /// Split a list on commas outside of parentheses
+    fn split_top_level(&self, list: &str) -> Vec<String> {
+        let mut parts = Vec::new();
+        let mut depth = 0i32;
+        let mut current = String::new();
+        for c in list.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(current.trim().to_string());
+                    current.clear();
+                    continue;
+                },
+                _ => {}
+            }
+            current.push(c);
+        }
+        if !current.trim().is_empty() {
+            parts.push(current.trim().to_string());
+        }
+        parts
+    }
+}
+
+impl LanguageParser for SolidityParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Enclosing contract: (name, closing line index)
+        let mut contract: Option<(String, usize)> = None;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            if contract.as_ref().is_some_and(|(_, end)| *end < line_index) {
+                contract = None;
+            }
+            
+            let code = line.trim();
+            let contract_caps = self.contract_re.captures(code);
+            let member_caps = self.member_re.captures(code);
+            if contract_caps.is_none() && member_caps.is_none() {
+                continue;
+            }
+            
+            let header_end = self.header_end(&lines, line_index);
+            let header = lines[line_index..=header_end]
+                .iter()
+                .map(|l| l.split("//").next().unwrap_or("").trim())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let header = header.split('{').next().unwrap_or("").trim_end_matches(';').trim();
+            let has_body = lines[header_end].split("//").next().unwrap_or("").contains('{');
+            let end = if has_body { self.body_end(&lines, header_end) } else { header_end };
+            
+            let (item_type, name, parameters, returns, parent) = if let Some(caps) = contract_caps {
+                if has_body {
+                    contract = Some((caps[2].to_string(), end));
+                }
+                (caps[1].to_string(), caps[2].to_string(), Vec::new(), None, None)
+            } else if let Some(caps) = member_caps {
+                let (kind, name) = match caps.get(3) {
+                    Some(special) => (special.as_str(), special.as_str().to_string()),
+                    None => (caps.get(1).map_or("", |k| k.as_str()), caps[2].to_string()),
+                };
+                let open = header.find('(').unwrap_or(0);
+                let (params, rest) = self.parenthesized(header, open);
+                
+                // Named return values are documented by name, others by type
+                let returns = rest.find("returns").map(|pos| {
+                    let open = rest[pos..].find('(').map_or(rest.len(), |p| pos + p);
+                    self.parenthesized(rest, open).0.trim().to_string()
+                }).filter(|r| !r.is_empty());
+                
+                // Fallback and receive functions are functions without a name of their own
+                let item_type = match kind {
+                    "fallback" | "receive" => "function",
+                    kind => kind,
+                };
+                let parent = contract.as_ref().map(|(contract_name, _)| contract_name.clone());
+                (item_type.to_string(), name, self.parameter_names(params), returns, parent)
+            } else {
+                continue;
+            };
+            
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, line_index)
+                .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: item_type.parse().expect("declaration patterns only match item kinds"),
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "solidity".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim();
+                    if trimmed.is_empty() {
+                        format!("{}///\n", item.indentation)
+                    } else {
+                        format!("{}/// {}\n", item.indentation, trimmed)
+                    }
+                })
+                .collect();
+            
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,254 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// SQL parser implementation for `CREATE FUNCTION` and `CREATE PROCEDURE` statements
+/// (PostgreSQL dialect, with `$$`-quoted or `BEGIN ATOMIC` bodies), documented with
+/// leading `--` comment blocks
+pub struct SqlParser {
+    create_re: Regex,
+    returns_re: Regex,
+    dollar_re: Regex,
+}
+
+/// Parameter modes that come before a parameter's name
+const MODES: &[&str] = &["in", "out", "inout", "variadic"];
+
+impl Default for SqlParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SqlParser {
+    pub fn new() -> Self {
+        Self {
+            create_re: Regex::new(
+                r#"(?i)^\s*CREATE\s+(?:OR\s+REPLACE\s+)?(?P<kind>FUNCTION|PROCEDURE)\s+(?P<name>(?:"[^"]+"|[\w$]+)(?:\.(?:"[^"]+"|[\w$]+))?)\s*(?:\(|$)"#
+            ).unwrap(),
+            returns_re: Regex::new(
+                r"(?is)\bRETURNS\s+(?P<type>.+?)(?:\s+(?:LANGUAGE|AS|IMMUTABLE|STABLE|VOLATILE|STRICT|SECURITY|PARALLEL|COST|ROWS|CALLED|SET|WINDOW|LEAKPROOF|NOT|BEGIN)\b|\s*;|\s*$)"
+            ).unwrap(),
+            dollar_re: Regex::new(r"\$(?:[A-Za-z_]\w*)?\$").unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Collect the block of `--` comments directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with("--") {
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                let l = l.trim().trim_start_matches('-');
+                l.strip_prefix(' ').unwrap_or(l).trim_end()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the line index that ends a statement: the first `;` outside its
+    /// dollar-quoted body, or after the `END` of a `BEGIN ATOMIC` body. Also returns
+    /// the index of the line the body starts on, which ends the statement's header.
+    fn statement_end(&self, lines: &[&str], line_index: usize) -> (usize, usize) {
+        let mut open_tag: Option<String> = None;
+        let mut body_start = None;
+        let mut atomic = false;
+        
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let mut outside = String::new();
+            let mut last = 0;
+            for tag in self.dollar_re.find_iter(line) {
+                match &open_tag {
+                    None => {
+                        outside.push_str(&line[last..tag.start()]);
+                        open_tag = Some(tag.as_str().to_string());
+                        body_start.get_or_insert(i);
+                    },
+                    Some(open) if open == tag.as_str() => open_tag = None,
+                    Some(_) => {},
+                }
+                last = tag.end();
+            }
+            if open_tag.is_some() {
+                continue;
+            }
+            outside.push_str(&line[last..]);
+            let code = outside.split("--").next().unwrap_or("").to_uppercase();
+            
+            if code.contains("BEGIN ATOMIC") {
+                atomic = true;
+                body_start.get_or_insert(i);
+            }
+            let ends = if atomic {
+                code.trim_start().starts_with("END")
+            } else {
+                code.contains(';')
+            };
+            if ends {
+                return (body_start.unwrap_or(i), i);
+            }
+        }
+        let last = lines.len().saturating_sub(1);
+        (body_start.unwrap_or(last), last)
+    }
+    
+    /// Parameter names from the header of a statement, starting at the parenthesis
+    /// after the routine's name, without modes, types, or defaults
+    fn parameter_names(&self, header: &str) -> Vec<String> {
+        let mut parameters = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 0;
+        
+        for (i, c) in header.char_indices() {
+            match c {
+                '(' => {
+                    depth += 1;
+                    if depth == 1 {
+                        start = i + 1;
+                    }
+                },
+                ')' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&header[start..i]));
+                    return parameters;
+                },
+                ')' => depth -= 1,
+                ',' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&header[start..i]));
+                    start = i + 1;
+                },
+                _ => {}
+            }
+        }
+        parameters
+    }
+    
+    /// Name of one parameter, or None for a parameter given only by its type
+    fn parameter_name(&self, param: &str) -> Option<String> {
+        let mut words = param.split_whitespace().peekable();
+        if words.peek().is_some_and(|w| MODES.contains(&w.to_lowercase().as_str())) {
+            words.next();
+        }
+        let name = words.next()?;
+        let rest = words.next()?;
+        // `integer DEFAULT 0` names no parameter; `total integer` does
+        if rest.eq_ignore_ascii_case("default") || rest.starts_with('=') {
+            return None;
+        }
+        Some(name.trim_matches('"').to_string())
+    }
+}
+
+impl LanguageParser for SqlParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Statements are found at the start of a line outside other statements' bodies
+        let mut inside_until: Option<usize> = None;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            if inside_until.is_some_and(|end| line_index <= end) {
+                continue;
+            }
+            let caps = match self.create_re.captures(line) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            
+            let (body_start, end_line) = self.statement_end(&lines, line_index);
+            inside_until = Some(end_line);
+            
+            // The header runs from the name up to the body: parameters, then RETURNS
+            let header = lines[line_index..=body_start.max(line_index)].join("\n");
+            let name_end = caps.name("name").map_or(0, |m| m.end());
+            let after_name = &header[name_end..];
+            let parameters = self.parameter_names(after_name);
+            let returns = self.returns_re.captures(after_name)
+                .map(|r| r["type"].split('$').next().unwrap_or("").split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|r| !r.is_empty() && !r.eq_ignore_ascii_case("void"));
+            
+            let full_name = caps["name"].to_string();
+            let (parent, name) = match full_name.rsplit_once('.') {
+                Some((schema, name)) => (Some(schema.trim_matches('"').to_string()), name.trim_matches('"').to_string()),
+                None => (None, full_name.trim_matches('"').to_string()),
+            };
+            let item_type = if caps["kind"].eq_ignore_ascii_case("procedure") { ItemKind::Procedure } else { ItemKind::Function };
+            
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, line_index)
+                .map(|(start, text)| (offsets.lines(start, line_index - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type,
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end_line].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(line_index, end_line),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "sql".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim_end();
+                    if trimmed.is_empty() {
+                        format!("{}--\n", item.indentation)
+                    } else {
+                        format!("{}-- {}\n", item.indentation, trimmed)
+                    }
+                })
+                .collect();
+            
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,325 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, ItemKind, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Swift parser implementation for functions, classes, structs, enums, protocols,
+/// actors, and initializers, including those in extensions, documented with `///`
+/// markup comments
+pub struct SwiftParser {
+    type_re: Regex,
+    function_re: Regex,
+}
+
+/// Modifiers and attributes that can precede a declaration
+const MODIFIERS: &str = r"(?P<mods>(?:(?:public|private|fileprivate|internal|open|package|final|static|class|override|mutating|nonmutating|convenience|required|dynamic|nonisolated|optional|indirect|@\w+(?:\([^)]*\))?)(?:\(set\))?\s+)*)";
+
+/// Words that follow `class` when it is a modifier rather than a declaration
+const MEMBER_KEYWORDS: &[&str] = &["func", "var", "let", "subscript", "init", "deinit", "override", "final"];
+
+impl Default for SwiftParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SwiftParser {
+    pub fn new() -> Self {
+        Self {
+            type_re: Regex::new(&format!(
+                r"^{}(?P<kind>class|struct|enum|protocol|actor|extension)\s+(?P<name>[A-Za-z_][\w.]*)", MODIFIERS
+            )).unwrap(),
+            function_re: Regex::new(&format!(
+                r"^{}(?:func\s+(?P<name>[A-Za-z_]\w*|`[^`]+`|[^\s\w(<`]+)|(?P<init>init)[?!]?)\s*(?:<[^()]*?>)?\s*\(", MODIFIERS
+            )).unwrap(),
+        }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Index of the first line of the attributes (`@discardableResult`, `@available(...)`)
+    /// directly above a declaration, or the declaration itself
+    fn attribute_start(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with('@') {
+            start -= 1;
+        }
+        start
+    }
+    
+    /// Collect the `///` or `/** ... */` comment directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 {
+            return None;
+        }
+        
+        let mut start = line_index;
+        if lines[line_index - 1].trim().ends_with("*/") {
+            start = line_index - 1;
+            while start > 0 && !lines[start].contains("/*") {
+                start -= 1;
+            }
+            if !lines[start].trim_start().starts_with("/**") {
+                return None;
+            }
+        } else {
+            while start > 0 && lines[start - 1].trim_start().starts_with("///") {
+                start -= 1;
+            }
+            if start == line_index {
+                return None;
+            }
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("///")
+                    .trim_start_matches("/**")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim_end()
+            })
+            .map(|l| l.strip_prefix(' ').unwrap_or(l))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the last line of a function signature, which may span several lines while
+    /// its parameter list is open
+    fn signature_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            depth += code.matches('(').count() as i32;
+            depth -= code.matches(')').count() as i32;
+            if depth <= 0 {
+                return i;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+    
+    /// Find the line index of the closing brace of the body opened at or after a
+    /// declaration, or None if it has no braced body
+    fn body_end(&self, lines: &[&str], signature_end: usize, line_index: usize) -> Option<usize> {
+        let opens_here = lines[line_index..=signature_end].iter()
+            .any(|l| l.split("//").next().unwrap_or("").contains('{'));
+        let opens_next = lines.get(signature_end + 1).is_some_and(|l| l.trim_start().starts_with('{'));
+        if !opens_here && !opens_next {
+            return None;
+        }
+        
+        let mut depth = 0i32;
+        let mut opened = false;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = line.split("//").next().unwrap_or("");
+            opened |= code.contains('{');
+            depth += code.matches('{').count() as i32;
+            depth -= code.matches('}').count() as i32;
+            if opened && depth <= 0 {
+                return Some(i);
+            }
+        }
+        Some(lines.len().saturating_sub(1))
+    }
+    
+    /// Split a function signature (starting at its opening parenthesis) into parameter
+    /// names and whatever follows the parameter list. The `>` of a closure type's `->`
+    /// doesn't close a generic argument list.
+    fn parse_parameters<'a>(&self, rest: &'a str) -> (Vec<String>, &'a str) {
+        let mut parameters = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 1;
+        let mut previous = ' ';
+        
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' | '[' | '<' | '{' => depth += 1,
+                ')' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    return (parameters, rest[i + 1..].trim());
+                },
+                '>' if previous == '-' => {},
+                ')' | ']' | '>' | '}' => depth -= 1,
+                ',' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    start = i + 1;
+                },
+                _ => {}
+            }
+            previous = c;
+        }
+        (parameters, "")
+    }
+    
+    /// Local name of one parameter, which documentation refers to, without its
+    /// argument label, type, or default value
+    fn parameter_name(&self, param: &str) -> Option<String> {
+        param.split(':')
+            .next()
+            .and_then(|names| names.split_whitespace().last())
+            .map(|name| name.trim_matches('`').to_string())
+            .filter(|name| !name.is_empty() && name != "_")
+    }
+    
+    /// Return type, whether the function is `async`, and what it throws, from the text
+    /// after a parameter list: `async throws -> Int`
+    fn effects(&self, after: &str) -> (Option<String>, bool, Vec<String>) {
+        let after = after.split("//").next().unwrap_or("");
+        let (effects, returns) = match after.split_once("->") {
+            Some((effects, returns)) => (effects, Some(returns)),
+            None => (after.split(['{', '=']).next().unwrap_or(""), None),
+        };
+        let returns = returns
+            .map(|r| r.split('{').next().unwrap_or("").split(" where ").next().unwrap_or("").trim().to_string())
+            .filter(|r| !r.is_empty() && r != "Void" && r != "()");
+        
+        let is_async = effects.split_whitespace().any(|word| word == "async");
+        let throws = effects.split_whitespace()
+            .find(|word| word.starts_with("throws") || *word == "rethrows")
+            .map(|word| {
+                // Typed throws name the error: `throws(ParseError)`
+                let error = word.trim_start_matches("throws").trim_matches(|c| c == '(' || c == ')');
+                vec![if error.is_empty() { "Error".to_string() } else { error.to_string() }]
+            })
+            .unwrap_or_default();
+        (returns, is_async, throws)
+    }
+}
+
+impl LanguageParser for SwiftParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let mut code_items = Vec::new();
+        
+        // Stack of enclosing bodies: (kind, name, closing line index)
+        let mut scopes: Vec<(String, String, usize)> = Vec::new();
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            while scopes.last().is_some_and(|(_, _, end)| *end < line_index) {
+                scopes.pop();
+            }
+            
+            let code = line.trim();
+            let type_caps = self.type_re.captures(code)
+                .filter(|caps| !MEMBER_KEYWORDS.contains(&&caps["name"]));
+            
+            let (kind, name, modifiers, parameters, returns, is_async, throws, signature_end) = if let Some(caps) = type_caps {
+                let kind = match &caps["kind"] {
+                    "actor" => "class",
+                    kind => kind,
+                };
+                (kind.to_string(), caps["name"].to_string(), caps["mods"].to_string(), Vec::new(), None, false, Vec::new(), line_index)
+            } else if let Some(caps) = self.function_re.captures(code) {
+                let signature_end = self.signature_end(&lines, line_index);
+                let signature = lines[line_index..=signature_end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+                let open = caps.get(0).map_or(0, |m| m.end() - 1);
+                let (parameters, after) = self.parse_parameters(&signature[open..]);
+                let (returns, is_async, throws) = self.effects(after);
+                
+                let in_type = scopes.last().is_some_and(|(kind, _, _)| !matches!(kind.as_str(), "method" | "function" | "init"));
+                let (kind, name) = match caps.name("name") {
+                    Some(name) => (if in_type { "method" } else { "function" }, name.as_str().trim_matches('`').to_string()),
+                    None => ("init", "init".to_string()),
+                };
+                (kind.to_string(), name, caps["mods"].to_string(), parameters, returns, is_async, throws, signature_end)
+            } else {
+                continue;
+            };
+            
+            let body_end = self.body_end(&lines, signature_end, line_index);
+            let end_line = body_end.unwrap_or(signature_end);
+            
+            // Declarations inside a function's body (nested functions and types) are skipped
+            let inside_function = scopes.iter().any(|(kind, _, _)| matches!(kind.as_str(), "method" | "function" | "init"));
+            let parent_path: Vec<&str> = scopes.iter().map(|(_, name, _)| name.as_str()).collect();
+            let parent = if parent_path.is_empty() { None } else { Some(parent_path.join(".")) };
+            
+            if let Some(end) = body_end {
+                scopes.push((kind.clone(), name.clone(), end));
+            }
+            // An extension isn't an item of its own; its members belong to the type it extends
+            if inside_function || kind == "extension" {
+                continue;
+            }
+            
+            let item_type = match kind.as_str() {
+                "init" => ItemKind::Constructor,
+                kind => kind.parse::<ItemKind>().expect("declaration patterns only match item kinds"),
+            };
+            let private = modifiers.split_whitespace().any(|m| m == "private" || m == "fileprivate");
+            
+            let doc_anchor = self.attribute_start(&lines, line_index);
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, doc_anchor)
+                .map(|(start, text)| (offsets.lines(start, doc_anchor - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type,
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end_line].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(doc_anchor, end_line),
+                docstring_span,
+                is_async,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: if private { Visibility::Private } else { Visibility::Public },
+                throws,
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "swift".to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            // Markup list items keep their own indentation, so only trailing whitespace
+            // is trimmed from each line
+            let comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim_end();
+                    if trimmed.is_empty() {
+                        format!("{}///\n", item.indentation)
+                    } else {
+                        format!("{}/// {}\n", item.indentation, trimmed)
+                    }
+                })
+                .collect();
+            
+            // Comments go above any attributes on the declaration
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
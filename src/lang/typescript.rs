@@ -1,26 +1,570 @@
 use crate::error::{DocGenError, DocGenResult};
-use crate::parser::{CodeItem, ParsedCode};
+use crate::parser::{apply_edits, is_obvious_value, CodeItem, Edit, ItemKind, LineOffsets, Parameter, ParsedCode, Visibility};
 use crate::docstring::UpdatedDocstring;
 use super::LanguageParser;
-use tree_sitter::{Parser, Language, Query, QueryCursor};
+use regex::Regex;
+use serde::Deserialize;
+use tree_sitter::{Language, Node, Parser, Tree};
+use std::ops::Range;
 
-/// TypeScript language parser implementation
-pub struct TypeScriptParser;
+/// How parameter and return tags are written in TypeScript comments (`ts_tag_style`
+/// in docsherpa.toml)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagStyle {
+    /// TSDoc: `@param name - description`, leaving types to the signature
+    #[default]
+    Tsdoc,
+    /// JSDoc: `@param {type} name - description`, with the types from the signature
+    Jsdoc,
+}
+
+impl TagStyle {
+    /// Prompt instruction describing how tags are written
+    pub fn instructions(self) -> &'static str {
+        match self {
+            TagStyle::Tsdoc => "Write TSDoc tags without types, which the signature already gives: \
+                `@param name - description`, `@typeParam T - description` for type parameters, and \
+                `@returns description`.",
+            TagStyle::Jsdoc => "Write JSDoc tags with the types from the signature in braces: \
+                `@param {type} name - description`, `@template T` for type parameters, and \
+                `@returns {type} description`.",
+        }
+    }
+}
+
+/// TypeScript language parser implementation for functions, classes, methods,
+/// interfaces, type aliases, and enums, documented with TSDoc comments
+pub struct TypeScriptParser {
+    language: Language,
+    tsx_language: Language,
+    /// Also document exported constants
+    constants: bool,
+    tag_style: TagStyle,
+}
+
+impl Default for TypeScriptParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl TypeScriptParser {
     pub fn new() -> Self {
-        Self
+        // The grammars are compiled and linked by the tree-sitter-typescript crate
+        let language = tree_sitter_typescript::language_typescript();
+        let tsx_language = tree_sitter_typescript::language_tsx();
+        Parser::new().set_language(language).expect("Failed to load TypeScript grammar");
+        Self { language, tsx_language, constants: false, tag_style: TagStyle::default() }
+    }
+    
+    /// Write `@param` and `@returns` tags in another style than TSDoc's
+    pub fn with_tag_style(mut self, tag_style: TagStyle) -> Self {
+        self.tag_style = tag_style;
+        self
+    }
+    
+    /// Enable documentation for exported `const` declarations whose values aren't
+    /// self-explanatory
+    pub fn with_constants(mut self, enabled: bool) -> Self {
+        self.constants = enabled;
+        self
+    }
+    
+    /// The declarator of an exported `const` declaration worth documenting: one that
+    /// declares a single name, to a value that is neither a function or class (those
+    /// are documented as what they are) nor self-explanatory
+    fn documented_constant<'t>(&self, declaration: Node<'t>, source: &str) -> Option<Node<'t>> {
+        if !self.constants || !self.get_node_text(source, declaration.byte_range()).starts_with("const") {
+            return None;
+        }
+        let declarator = declaration.named_child(0).filter(|_| declaration.named_child_count() == 1)?;
+        if declarator.child_by_field_name("name")?.kind() != "identifier" {
+            return None;
+        }
+        let value = declarator.child_by_field_name("value")?;
+        let function = matches!(value.kind(),
+            "arrow_function" | "function" | "function_expression" | "generator_function" | "class");
+        (!function && !is_obvious_value(self.get_node_text(source, value.byte_range()))).then_some(declarator)
+    }
+    
+    /// The declarator of a declaration that binds a single name to a function. A
+    /// declaration of several names has no one place for a comment per function.
+    fn function_declarator<'t>(&self, declaration: Node<'t>) -> Option<Node<'t>> {
+        let declarator = declaration.named_child(0).filter(|_| declaration.named_child_count() == 1)?;
+        let bound = declarator.child_by_field_name("name")?.kind() == "identifier";
+        (bound && declarator.child_by_field_name("value").is_some_and(is_function_value)).then_some(declarator)
+    }
+    
+    /// Parse source with the TypeScript grammar, retrying with the TSX grammar when
+    /// the source contains JSX that plain TypeScript cannot parse
+    fn parse_tree(&self, content: &str) -> DocGenResult<Tree> {
+        let mut parser = Parser::new();
+        parser.set_language(self.language).expect("Failed to load TypeScript grammar");
+        let tree = parser.parse(content, None)
+            .ok_or_else(|| DocGenError::ParsingError("Failed to parse TypeScript code".into()))?;
+        if !tree.root_node().has_error() {
+            return Ok(tree);
+        }
+        
+        parser.set_language(self.tsx_language).expect("Failed to load TSX grammar");
+        match parser.parse(content, None) {
+            Some(tsx_tree) if !tsx_tree.root_node().has_error() => Ok(tsx_tree),
+            _ => Ok(tree),
+        }
+    }
+    
+    /// Extract a substring from the source based on a byte range
+    fn get_node_text<'a>(&self, source: &'a str, range: Range<usize>) -> &'a str {
+        &source[range.start..range.end]
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, content: &str, line_number: usize) -> String {
+        if let Some(line) = content.lines().nth(line_number - 1) {
+            line.chars().take_while(|c| c.is_whitespace()).collect()
+        } else {
+            "".to_string()
+        }
+    }
+    
+    /// Extract a code block from the source content
+    fn extract_code_block(&self, content: &str, start_line: usize, end_line: usize) -> String {
+        content.lines()
+            .skip(start_line - 1)
+            .take(end_line - start_line + 1)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    
+    /// Collect the TSDoc `/** ... */` comment directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        if line_index == 0 || !lines[line_index - 1].trim().ends_with("*/") {
+            return None;
+        }
+        
+        let mut start = line_index - 1;
+        while start > 0 && !lines[start].contains("/*") {
+            start -= 1;
+        }
+        if !lines[start].trim_start().starts_with("/**") {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| {
+                l.trim()
+                    .trim_start_matches("/**")
+                    .trim_end_matches("*/")
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Extract the parameters of a formal parameter list with their declared types and
+    /// default values, leaving out the `this` parameter
+    fn extract_parameters(&self, params_node: Node, source: &str) -> Vec<Parameter> {
+        let mut params = Vec::new();
+        let mut cursor = params_node.walk();
+        
+        for param in params_node.named_children(&mut cursor) {
+            if param.kind() != "required_parameter" && param.kind() != "optional_parameter" {
+                continue;
+            }
+            if let Some(pattern) = param.child_by_field_name("pattern") {
+                let name = self.get_node_text(source, pattern.byte_range()).trim_start_matches("...");
+                if name != "this" {
+                    params.push(Parameter {
+                        name: name.to_string(),
+                        annotation: param.child_by_field_name("type")
+                            .map(|n| self.get_node_text(source, n.byte_range()).trim_start_matches(':').trim().to_string()),
+                        default: param.child_by_field_name("value")
+                            .map(|n| self.get_node_text(source, n.byte_range()).to_string()),
+                    });
+                }
+            }
+        }
+        
+        params
+    }
+    
+    /// Extract the declared return type, leaving out `void`
+    fn extract_return_type(&self, node: Node, source: &str) -> Option<String> {
+        node.child_by_field_name("return_type")
+            .map(|n| self.get_node_text(source, n.byte_range()).trim_start_matches(':').trim().to_string())
+            .filter(|t| t != "void")
+    }
+    
+    /// Write a `@param` or `@returns` tag in the configured style. TSDoc tags lose any
+    /// type in braces and separate a parameter's name from its description with ` - `;
+    /// JSDoc tags without a type take it from the signature. Other lines are unchanged.
+    fn styled_tag(&self, line: &str, item: &CodeItem) -> String {
+        let (tag, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let (type_name, rest) = braced_type(rest.trim_start());
+        
+        match tag {
+            "@param" => {
+                // JSDoc writes an optional parameter as `[name]` or `[name=default]`
+                let split = if rest.starts_with('[') { rest.find(']').map(|i| i + 1) } else { rest.find(char::is_whitespace) };
+                let (name, description) = rest.split_at(split.unwrap_or(rest.len()));
+                let description = description.trim_start();
+                let description = match description.strip_prefix('-') {
+                    Some(after) if after.is_empty() || after.starts_with(' ') => after.trim_start(),
+                    _ => description,
+                };
+                let bare = name.trim_start_matches('[').trim_end_matches(']').split('=').next().unwrap_or("").trim();
+                let separator = if description.is_empty() { "" } else { " - " };
+                
+                match self.tag_style {
+                    TagStyle::Tsdoc => format!("@param {}{}{}", bare, separator, description),
+                    TagStyle::Jsdoc => {
+                        let annotation = item.parameter_details.iter()
+                            .find(|p| p.name == bare)
+                            .and_then(|p| p.annotation.as_deref());
+                        match type_name.or(annotation) {
+                            Some(type_name) => format!("@param {{{}}} {}{}{}", type_name, name, separator, description),
+                            None => format!("@param {}{}{}", name, separator, description),
+                        }
+                    },
+                }
+            },
+            "@returns" | "@return" => {
+                let type_name = match self.tag_style {
+                    TagStyle::Tsdoc => None,
+                    TagStyle::Jsdoc => type_name.or(item.returns.as_deref()),
+                };
+                let parts: Vec<String> = [Some(tag.to_string()), type_name.map(|t| format!("{{{}}}", t)), Some(rest.to_string())]
+                    .into_iter()
+                    .flatten()
+                    .filter(|part| !part.is_empty())
+                    .collect();
+                parts.join(" ")
+            },
+            _ => line.to_string(),
+        }
+    }
+    
+    /// Visibility of a declaration: private for `#private` class members and members
+    /// declared `private`
+    fn visibility(&self, node: Node, source: &str) -> Visibility {
+        let mut cursor = node.walk();
+        let declared_private = node.children(&mut cursor)
+            .any(|n| n.kind() == "accessibility_modifier" && self.get_node_text(source, n.byte_range()) == "private");
+        let name = node.child_by_field_name("name").map(|n| n.kind());
+        if declared_private || name == Some("private_property_identifier") {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        }
+    }
+    
+    /// Build a code item for a declaration node, or a constant's declarator. The comment
+    /// goes above an `export` statement wrapping the declaration and above any
+    /// decorators on a method.
+    fn code_item(
+        &self,
+        node: Node,
+        item_type: ItemKind,
+        parent: Option<String>,
+        content: &str,
+        lines: &[&str],
+        offsets: &LineOffsets,
+    ) -> Option<CodeItem> {
+        // An anonymous `export default` function is known by the name it's imported as
+        let name = match node.child_by_field_name("name") {
+            Some(name_node) => self.get_node_text(content, name_node.byte_range()).to_string(),
+            None if node.parent().is_some_and(|p| p.kind() == "export_statement") => "default".to_string(),
+            None => return None,
+        };
+        
+        // A constant's declarator sits inside its `const` declaration
+        let declaration = match node.parent() {
+            Some(p) if node.kind() == "variable_declarator" => p,
+            _ => node,
+        };
+        // The signature of a function bound to a variable or field is its value's
+        let function = node.child_by_field_name("value").filter(|value| is_function_value(*value)).unwrap_or(node);
+        let mut anchor = match declaration.parent() {
+            Some(p) if p.kind() == "export_statement" => p,
+            _ => declaration,
+        };
+        while let Some(decorator) = anchor.prev_named_sibling().filter(|n| n.kind() == "decorator") {
+            anchor = decorator;
+        }
+        let line_number = anchor.start_position().row + 1; // 1-indexed
+        let end_line = declaration.end_position().row + 1;
+        
+        let parameter_details = match function.child_by_field_name("parameter") {
+            Some(parameter) => vec![Parameter {
+                name: self.get_node_text(content, parameter.byte_range()).to_string(),
+                annotation: None,
+                default: None,
+            }],
+            None => function.child_by_field_name("parameters")
+                .map(|params_node| self.extract_parameters(params_node, content))
+                .unwrap_or_default(),
+        };
+        let (docstring_span, existing_docstring) = self.leading_comment(lines, line_number - 1)
+            .map(|(start, text)| (offsets.lines(start, line_number - 2), text))
+            .unzip();
+        
+        let mut item = CodeItem {
+            item_type,
+            name,
+            line_number,
+            code: self.extract_code_block(content, line_number, end_line),
+            existing_docstring,
+            parent,
+            parameters: parameter_details.iter().map(|p| p.name.clone()).collect(),
+            parameter_details,
+            returns: self.extract_return_type(function, content),
+            indentation: self.extract_indentation(content, line_number),
+            span: offsets.start(line_number - 1)..declaration.end_byte(),
+            docstring_span,
+            is_async: false,
+            is_generator: function.kind() == "generator_function",
+            decorators: Vec::new(),
+            visibility: self.visibility(node, content),
+            throws: Vec::new(),
+        };
+        crate::lang::javascript::mark_react_item(&mut item, function, content);
+        Some(item)
+    }
+    
+    /// Collect documentable items from a list of statements. Function bodies are not
+    /// descended into, so nested helper functions are skipped.
+    fn collect_items(&self, node: Node, content: &str, lines: &[&str], offsets: &LineOffsets, code_items: &mut Vec<CodeItem>) {
+        let mut cursor = node.walk();
+        
+        for child in node.named_children(&mut cursor) {
+            let declaration = if child.kind() == "export_statement" {
+                match (child.child_by_field_name("declaration"), child.child_by_field_name("value")) {
+                    (Some(declaration), _) => declaration,
+                    (None, Some(value)) if is_function_value(value) => {
+                        code_items.extend(self.code_item(value, ItemKind::Function, None, content, lines, offsets));
+                        continue;
+                    },
+                    _ => continue,
+                }
+            } else {
+                child
+            };
+            
+            match declaration.kind() {
+                "function_declaration" | "generator_function_declaration" => {
+                    code_items.extend(self.code_item(declaration, ItemKind::Function, None, content, lines, offsets));
+                },
+                "class_declaration" | "abstract_class_declaration" => {
+                    code_items.extend(self.code_item(declaration, ItemKind::Class, None, content, lines, offsets));
+                    self.collect_members(declaration, content, lines, offsets, code_items);
+                },
+                "interface_declaration" => {
+                    code_items.extend(self.code_item(declaration, ItemKind::Interface, None, content, lines, offsets));
+                    self.collect_members(declaration, content, lines, offsets, code_items);
+                },
+                "type_alias_declaration" => code_items.extend(self.code_item(declaration, ItemKind::Type, None, content, lines, offsets)),
+                "enum_declaration" => code_items.extend(self.code_item(declaration, ItemKind::Enum, None, content, lines, offsets)),
+                "lexical_declaration" | "variable_declaration" => {
+                    // Functions bound to variables (`const add = (a: number) => ...`) are
+                    // documented as functions. Only exported constants are part of a
+                    // module's interface.
+                    if let Some(declarator) = self.function_declarator(declaration) {
+                        code_items.extend(self.code_item(declarator, ItemKind::Function, None, content, lines, offsets));
+                    } else if child.kind() == "export_statement" {
+                        if let Some(declarator) = self.documented_constant(declaration, content) {
+                            code_items.extend(self.code_item(declarator, ItemKind::Constant, None, content, lines, offsets));
+                        }
+                    }
+                },
+                // Namespaces hold declarations of their own
+                "internal_module" | "module" => {
+                    if let Some(body) = declaration.child_by_field_name("body") {
+                        self.collect_items(body, content, lines, offsets, code_items);
+                    }
+                },
+                "expression_statement" => {
+                    if let Some(inner) = declaration.named_child(0).filter(|n| n.kind() == "internal_module") {
+                        if let Some(body) = inner.child_by_field_name("body") {
+                            self.collect_items(body, content, lines, offsets, code_items);
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+    
+    /// Collect the methods of a class body or the method signatures of an interface body.
+    /// Members on the line the body opens on (`interface Shape { area(): number; }`)
+    /// are left to the container's documentation, which would go above the same line.
+    fn collect_members(&self, declaration: Node, content: &str, lines: &[&str], offsets: &LineOffsets, code_items: &mut Vec<CodeItem>) {
+        let parent = declaration.child_by_field_name("name")
+            .map(|n| self.get_node_text(content, n.byte_range()).to_string());
+        let body = match declaration.child_by_field_name("body") {
+            Some(body) => body,
+            None => return,
+        };
+        
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            // Class fields holding functions (`handle = (event: Event) => {...}`) are methods too
+            let function_field = member.kind() == "public_field_definition"
+                && member.child_by_field_name("value").is_some_and(is_function_value);
+            if member.start_position().row == body.start_position().row {
+                continue;
+            }
+            if function_field || matches!(member.kind(), "method_definition" | "abstract_method_signature" | "method_signature") {
+                code_items.extend(self.code_item(member, ItemKind::Method, parent.clone(), content, lines, offsets));
+            }
+        }
     }
 }
 
+/// Whether an expression is a function: an arrow function, function expression, or
+/// generator function
+fn is_function_value(node: Node) -> bool {
+    matches!(node.kind(), "arrow_function" | "function_expression" | "function" | "generator_function")
+}
+
 impl LanguageParser for TypeScriptParser {
-    fn parse(&self, _content: &str) -> DocGenResult<ParsedCode> {
-        // For now, return a stub implementation that will be expanded later
-        Err(DocGenError::ParsingError("TypeScript parsing not yet implemented".into()))
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let mut code_items = Vec::new();
+        
+        let tree = self.parse_tree(content)?;
+        let lines: Vec<&str> = content.lines().collect();
+        self.collect_items(tree.root_node(), content, &lines, &LineOffsets::new(content), &mut code_items);
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: "typescript".to_string(),
+            syntax_errors: super::syntax_errors(tree.root_node()),
+        })
     }
     
-    fn update_content(&self, _content: &str, _updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
-        // For now, return a stub implementation that will be expanded later
-        Err(DocGenError::UpdateError("TypeScript TSDoc updating not yet implemented".into()))
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            // Format the TSDoc comment
+            let mut tsdoc = format!("{}/**\n", item.indentation);
+            for line in update.new_docstring.trim().lines() {
+                let trimmed = line.trim();
+                if !trimmed.is_empty() {
+                    tsdoc.push_str(&format!("{} * {}\n", item.indentation, self.styled_tag(trimmed, item)));
+                } else {
+                    tsdoc.push_str(&format!("{} *\n", item.indentation));
+                }
+            }
+            tsdoc.push_str(&format!("{} */\n", item.indentation));
+            
+            // Replace an existing TSDoc comment, or insert above the declaration
+            edits.push(Edit::new(item.doc_range(), tsdoc));
+        }
+        
+        apply_edits(content, edits)
     }
-}
\ No newline at end of file
+}
+
+/// Split a type in braces (`{Map<string, {id: number}>}`) from the front of a tag's
+/// text, returning the type without its braces and the text after it
+fn braced_type(text: &str) -> (Option<&str>, &str) {
+    if !text.starts_with('{') {
+        return (None, text);
+    }
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return (Some(text[1..i].trim()), text[i + 1..].trim_start());
+                }
+            },
+            _ => {}
+        }
+    }
+    (None, text)
+}
+
+/// Names of the type parameters declared right after an item's name in its code
+/// (`function pick<T, K extends keyof T>`, `const wrap = <T,>(...)`), without their
+/// constraints or defaults
+pub fn type_parameters(name: &str, code: &str) -> Vec<String> {
+    let name_re = Regex::new(&format!(r"\b{}\b", regex::escape(name))).unwrap();
+    let after = match name_re.find(code) {
+        Some(found) => code[found.end()..].trim_start(),
+        None => return Vec::new(),
+    };
+    let after = after.strip_prefix('=').map_or(after, |rest| rest.trim_start());
+    let after = after.strip_prefix("async ").map_or(after, |rest| rest.trim_start());
+    if !after.starts_with('<') {
+        return Vec::new();
+    }
+    
+    let mut names = Vec::new();
+    let mut depth = 0;
+    let mut start = 1;
+    let mut previous = ' ';
+    for (i, c) in after.char_indices() {
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            // The `>` of an arrow function type doesn't close anything
+            '>' if previous == '=' => {},
+            '>' | ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    names.extend(type_parameter_name(&after[start..i]));
+                    break;
+                }
+            },
+            ',' if depth == 1 => {
+                names.extend(type_parameter_name(&after[start..i]));
+                start = i + 1;
+            },
+            _ => {}
+        }
+        previous = c;
+    }
+    names
+}
+
+/// Name of one type parameter, without `const`, `in`, or `out` modifiers
+fn type_parameter_name(parameter: &str) -> Option<String> {
+    parameter.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .filter(|word| !word.is_empty())
+        .find(|word| !matches!(*word, "const" | "in" | "out"))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn members_on_the_container_line_are_not_documented_separately() {
+        let parser = TypeScriptParser::new();
+        let content = "export interface Shape { area(): number; }\n";
+        let parsed = parser.parse(content).unwrap();
+        let names: Vec<&str> = parsed.items.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, vec!["Shape"]);
+        
+        let updated = parser.update_content(content, &[UpdatedDocstring {
+            item_index: 0,
+            new_docstring: "A shape with an area.".to_string(),
+            indentation: String::new(),
+        }]).unwrap();
+        assert_eq!(updated, "/**\n * A shape with an area.\n */\nexport interface Shape { area(): number; }\n");
+    }
+}
@@ -0,0 +1,350 @@
+use crate::error::{DocGenError, DocGenResult};
+use crate::parser::{apply_edits, CodeItem, Edit, LineOffsets, ParsedCode, Visibility};
+use crate::docstring::UpdatedDocstring;
+use super::LanguageParser;
+use regex::Regex;
+
+/// Visual Basic parser implementation for classes, modules, structures, interfaces,
+/// enums, Subs, Functions, and Properties. VB.NET code is documented with `'''` XML
+/// doc comments; VBScript (including classic ASP pages) with plain `'` comment blocks.
+pub struct VbParser {
+    language: &'static str,
+    type_re: Regex,
+    end_type_re: Regex,
+    member_re: Regex,
+    end_member_re: Regex,
+}
+
+impl Default for VbParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VbParser {
+    /// Parser for VB.NET source
+    pub fn new() -> Self {
+        Self::with_language("vbnet")
+    }
+    
+    /// Parser for VBScript source and classic ASP pages
+    pub fn vbscript() -> Self {
+        Self::with_language("vbscript")
+    }
+    
+    fn with_language(language: &'static str) -> Self {
+        Self {
+            language,
+            type_re: Regex::new(
+                r"(?i)^(?:(?:Public|Private|Protected|Friend|Shared|Partial|MustInherit|NotInheritable|Shadows)\s+)*(Class|Module|Structure|Interface|Enum)\s+(\w+)"
+            ).unwrap(),
+            end_type_re: Regex::new(r"(?i)^End\s+(Class|Module|Structure|Interface|Enum)\b").unwrap(),
+            member_re: Regex::new(
+                r"(?i)^(?P<mods>(?:(?:Public|Private|Protected|Friend|Shared|Overridable|Overrides|MustOverride|NotOverridable|Overloads|Shadows|Async|Iterator|Default|ReadOnly|WriteOnly|Partial)\s+)*)(?P<kind>Sub|Function|Property)\s+(?:(?:Get|Let|Set)\s+)?(?P<name>\w+)"
+            ).unwrap(),
+            end_member_re: Regex::new(r"(?i)(?:^|:\s*)End\s+(Sub|Function|Property)\b").unwrap(),
+        }
+    }
+    
+    /// Comment marker for documentation comments
+    fn marker(&self) -> &'static str {
+        if self.language == "vbnet" { "'''" } else { "'" }
+    }
+    
+    /// Extract indentation from a line
+    fn extract_indentation(&self, line: &str) -> String {
+        line.chars().take_while(|c| c.is_whitespace()).collect()
+    }
+    
+    /// Code of a line without its trailing `'` or `REM` comment
+    fn strip_comment<'a>(&self, line: &'a str) -> &'a str {
+        let mut in_string = false;
+        for (i, c) in line.char_indices() {
+            match c {
+                '"' => in_string = !in_string,
+                '\'' if !in_string => return &line[..i],
+                _ => {}
+            }
+        }
+        let trimmed = line.trim_start();
+        if trimmed.get(..4).is_some_and(|p| p.eq_ignore_ascii_case("rem ")) {
+            return "";
+        }
+        line
+    }
+    
+    /// Which lines are server-side script. In an ASP page only the text inside
+    /// `<% ... %>` and `<script runat="server">` blocks is code; anything else is markup.
+    fn script_lines(&self, lines: &[&str]) -> Vec<bool> {
+        let is_page = lines.iter().any(|l| l.contains("<%") || l.to_lowercase().contains("runat="));
+        if !is_page {
+            return vec![true; lines.len()];
+        }
+        
+        let mut in_code = false;
+        lines.iter()
+            .map(|line| {
+                let starts_in_code = in_code;
+                let lower = line.to_lowercase();
+                let mut position = 0;
+                loop {
+                    let rest = &lower[position..];
+                    let next = if in_code {
+                        [rest.find("%>"), rest.find("</script")].into_iter().flatten().min()
+                    } else {
+                        let server_script = rest.find("<script").filter(|&p| rest[p..].contains("runat"));
+                        [rest.find("<%"), server_script].into_iter().flatten().min()
+                    };
+                    match next {
+                        Some(offset) => {
+                            in_code = !in_code;
+                            position += offset + 2;
+                        },
+                        None => break,
+                    }
+                }
+                starts_in_code
+            })
+            .collect()
+    }
+    
+    /// Index of the first line of the attributes (`<Obsolete()>`) directly above
+    /// a declaration, or the declaration itself. VBScript has no attributes, and
+    /// a line starting with `<` there is page markup.
+    fn attribute_start(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut start = line_index;
+        while start > 0 && self.language == "vbnet" {
+            let previous = lines[start - 1].trim();
+            if !(previous.starts_with('<') && previous.trim_end_matches('_').trim_end().ends_with('>')) {
+                break;
+            }
+            start -= 1;
+        }
+        start
+    }
+    
+    /// Collect the documentation comment block directly above a line
+    fn leading_comment(&self, lines: &[&str], line_index: usize) -> Option<(usize, String)> {
+        let marker = self.marker();
+        let mut start = line_index;
+        while start > 0 && lines[start - 1].trim_start().starts_with(marker) {
+            start -= 1;
+        }
+        if start == line_index {
+            return None;
+        }
+        
+        let text = lines[start..line_index]
+            .iter()
+            .map(|l| l.trim().trim_start_matches('\'').trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Some((start, text))
+    }
+    
+    /// Find the last line of a declaration, which continues while it ends with ` _`
+    /// or its parameter list is still open
+    fn signature_end(&self, lines: &[&str], line_index: usize) -> usize {
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(line_index) {
+            let code = self.strip_comment(line).trim_end();
+            depth += code.matches('(').count() as i32;
+            depth -= code.matches(')').count() as i32;
+            if depth <= 0 && !code.ends_with(" _") {
+                return i;
+            }
+        }
+        lines.len().saturating_sub(1)
+    }
+    
+    /// Find the `End Sub`/`End Function`/`End Property` closing a member
+    fn member_end(&self, lines: &[&str], signature_end: usize, kind: &str) -> usize {
+        let closes = |i: usize| {
+            self.end_member_re.captures(self.strip_comment(lines[i]).trim())
+                .is_some_and(|caps| caps[1].eq_ignore_ascii_case(kind))
+        };
+        (signature_end..lines.len())
+            .find(|&i| closes(i))
+            .unwrap_or(signature_end)
+    }
+    
+    /// Split a declaration (starting at its opening parenthesis) into parameter
+    /// names and whatever follows the parameter list
+    fn parse_parameters<'a>(&self, rest: &'a str) -> (Vec<String>, &'a str) {
+        let mut parameters = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 1;
+        
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' | '{' => depth += 1,
+                ')' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    return (parameters, rest[i + 1..].trim());
+                },
+                ')' | '}' => depth -= 1,
+                ',' if depth == 1 => {
+                    parameters.extend(self.parameter_name(&rest[start..i]));
+                    start = i + 1;
+                },
+                _ => {}
+            }
+        }
+        (parameters, "")
+    }
+    
+    /// Name of one parameter, without modifiers, type, or default value
+    fn parameter_name(&self, param: &str) -> Option<String> {
+        param.split_whitespace()
+            .find(|word| !matches!(word.to_lowercase().as_str(), "byval" | "byref" | "optional" | "paramarray") && !word.starts_with('<'))
+            .map(|name| name.trim_end_matches("()").trim_end_matches('?').to_string())
+            .filter(|name| !name.is_empty())
+    }
+}
+
+impl LanguageParser for VbParser {
+    fn parse(&self, content: &str) -> DocGenResult<ParsedCode> {
+        let lines: Vec<&str> = content.lines().collect();
+        let offsets = LineOffsets::new(content);
+        let script_lines = self.script_lines(&lines);
+        let mut code_items = Vec::new();
+        
+        // Enclosing types: (kind, name)
+        let mut scopes: Vec<(String, String)> = Vec::new();
+        // Closing line of the member being skipped over
+        let mut member_end: Option<usize> = None;
+        
+        for (line_index, line) in lines.iter().enumerate() {
+            if !script_lines[line_index] || member_end.is_some_and(|end| line_index <= end) {
+                continue;
+            }
+            
+            let code = self.strip_comment(line).trim();
+            if self.end_type_re.is_match(code) {
+                scopes.pop();
+                continue;
+            }
+            
+            let parent = if scopes.is_empty() {
+                None
+            } else {
+                Some(scopes.iter().map(|(_, name)| name.as_str()).collect::<Vec<_>>().join("."))
+            };
+            
+            let (item_type, name, parameters, returns, end) = if let Some(caps) = self.type_re.captures(code) {
+                let kind = caps[1].to_lowercase();
+                scopes.push((kind.clone(), caps[2].to_string()));
+                (kind, caps[2].to_string(), Vec::new(), None, line_index)
+            } else if let Some(caps) = self.member_re.captures(code) {
+                let kind = caps["kind"].to_lowercase();
+                let signature_end = self.signature_end(&lines, line_index);
+                let signature = lines[line_index..=signature_end]
+                    .iter()
+                    .map(|l| self.strip_comment(l).trim().trim_end_matches(" _"))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                
+                let name_end = caps.name("name").map_or(0, |m| m.end());
+                let after_name = signature[name_end..].trim_start();
+                // Skip a generic type parameter list such as `(Of T)`
+                let after_name = if after_name.get(..4).is_some_and(|p| p.eq_ignore_ascii_case("(of ")) {
+                    after_name.split_once(')').map_or("", |(_, rest)| rest.trim_start())
+                } else {
+                    after_name
+                };
+                let (parameters, after) = if after_name.starts_with('(') {
+                    self.parse_parameters(after_name)
+                } else {
+                    (Vec::new(), after_name)
+                };
+                
+                // A Function's return type follows `As` after the parameter list
+                let returns = if kind == "function" {
+                    after.get(..3)
+                        .filter(|p| p.eq_ignore_ascii_case("as "))
+                        .and_then(|_| after[3..].split_whitespace().next())
+                        .map(|t| t.to_string())
+                } else {
+                    None
+                };
+                
+                // Interface members and MustOverride members have no body
+                let in_interface = scopes.last().is_some_and(|(kind, _)| kind == "interface");
+                let abstract_member = caps["mods"].to_lowercase().contains("mustoverride");
+                let end = if in_interface || abstract_member {
+                    signature_end
+                } else {
+                    self.member_end(&lines, signature_end, &kind)
+                };
+                member_end = Some(end);
+                
+                (kind, caps["name"].to_string(), parameters, returns, end)
+            } else {
+                continue;
+            };
+            
+            let doc_anchor = self.attribute_start(&lines, line_index);
+            let (docstring_span, existing_docstring) = self.leading_comment(&lines, doc_anchor)
+                .map(|(start, text)| (offsets.lines(start, doc_anchor - 1), text))
+                .unzip();
+            
+            code_items.push(CodeItem {
+                item_type: item_type.parse().expect("declaration patterns only match item kinds"),
+                name,
+                line_number: line_index + 1,
+                code: lines[line_index..=end].join("\n"),
+                existing_docstring,
+                parent,
+                parameters,
+                parameter_details: Vec::new(),
+                returns,
+                indentation: self.extract_indentation(line),
+                span: offsets.lines(doc_anchor, end),
+                docstring_span,
+                is_async: false,
+                is_generator: false,
+                decorators: Vec::new(),
+                visibility: Visibility::Public,
+                throws: Vec::new(),
+            });
+        }
+        
+        Ok(ParsedCode {
+            items: code_items,
+            original_content: content.to_string(),
+            language: self.language.to_string(),
+            syntax_errors: Vec::new(),
+        })
+    }
+    
+    fn update_content(&self, content: &str, updated_docstrings: &[UpdatedDocstring]) -> DocGenResult<String> {
+        let parsed_code = self.parse(content)?;
+        let marker = self.marker();
+        let mut edits = Vec::new();
+        
+        for update in updated_docstrings {
+            let item = parsed_code.items.get(update.item_index)
+                .ok_or_else(|| DocGenError::UpdateError(format!("Item index {} is out of bounds", update.item_index)))?;
+            
+            let comment: String = update.new_docstring
+                .trim()
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim_end();
+                    if trimmed.trim().is_empty() {
+                        format!("{}{}\n", item.indentation, marker)
+                    } else {
+                        format!("{}{} {}\n", item.indentation, marker, trimmed)
+                    }
+                })
+                .collect();
+            
+            // Comments go above any attributes on the declaration
+            edits.push(Edit::new(item.doc_range(), comment));
+        }
+        
+        apply_edits(content, edits)
+    }
+}
@@ -0,0 +1,282 @@
+use clap::ValueEnum;
+use std::path::Path;
+
+/// Supported programming languages
+#[derive(Debug, Clone, ValueEnum)]
+pub enum Language {
+    /// Python language support
+    Python,
+    /// Rust language support
+    Rust,
+    /// JavaScript language support
+    JavaScript,
+    /// TypeScript language support
+    TypeScript,
+    /// OpenAPI specification (YAML) support
+    #[value(name = "openapi")]
+    OpenApi,
+    /// Protocol Buffers support
+    Protobuf,
+    /// Objective-C support (HeaderDoc/AppleDoc comments)
+    #[value(name = "objc")]
+    ObjectiveC,
+    /// Scala support (Scaladoc comments)
+    Scala,
+    /// Groovy support, including Jenkinsfiles and Gradle plugins (Groovydoc comments)
+    Groovy,
+    /// PowerShell support (comment-based help)
+    #[value(name = "powershell")]
+    PowerShell,
+    /// Fortran support, free-form and fixed-form (Doxygen header comments)
+    Fortran,
+    /// MATLAB support (function help text)
+    Matlab,
+    /// CUDA support, including kernels (Doxygen comments)
+    Cuda,
+    /// OpenCL C kernel support (Doxygen comments)
+    #[value(name = "opencl")]
+    OpenCl,
+    /// Solidity support (NatSpec comments)
+    Solidity,
+    /// VB.NET support (XML doc comments)
+    #[value(name = "vbnet")]
+    VbNet,
+    /// VBScript and classic ASP support (comment blocks)
+    #[value(name = "vbscript")]
+    VbScript,
+    /// CMake function and macro support (comment headers)
+    #[value(name = "cmake")]
+    CMake,
+    /// Makefile target support (comment headers)
+    Makefile,
+    /// Dockerfile stage, ARG, and EXPOSE support (comments)
+    Dockerfile,
+    /// docker-compose service support (comments)
+    Compose,
+    /// Ansible playbook and role support (task names and role header comments)
+    Ansible,
+    /// Helm chart values support (helm-docs `# --` comments)
+    Helm,
+    /// GitHub Actions workflow and action support (descriptions and header comments)
+    #[value(name = "github-actions")]
+    GitHubActions,
+    /// JSON Schema support, including Swagger/OpenAPI JSON schemas (`description` keys)
+    #[value(name = "json-schema")]
+    JsonSchema,
+    /// Java support (Javadoc comments)
+    Java,
+    /// C# support (XML doc comments)
+    #[value(name = "csharp")]
+    CSharp,
+    /// C support (Doxygen comments)
+    C,
+    /// C++ support (Doxygen comments)
+    Cpp,
+    /// Swift support (markup doc comments)
+    Swift,
+    /// Shell script support (function header comments)
+    Bash,
+    /// SQL support for PostgreSQL functions and procedures (`--` comments)
+    Sql,
+    /// Automatically detect based on file extension
+    Auto,
+    /// A language whose parser was registered for a file extension with
+    /// `lang::register_parser`, by that extension
+    #[value(skip)]
+    Registered(String),
+}
+
+impl Language {
+    /// Lowercase name used in lockfiles and reports; a registered language is named
+    /// by its extension
+    pub fn name(&self) -> &str {
+        match self {
+            Language::Python => "python",
+            Language::Rust => "rust",
+            Language::JavaScript => "javascript",
+            Language::TypeScript => "typescript",
+            Language::OpenApi => "openapi",
+            Language::Protobuf => "protobuf",
+            Language::ObjectiveC => "objc",
+            Language::Scala => "scala",
+            Language::Groovy => "groovy",
+            Language::PowerShell => "powershell",
+            Language::Fortran => "fortran",
+            Language::Matlab => "matlab",
+            Language::Cuda => "cuda",
+            Language::OpenCl => "opencl",
+            Language::Solidity => "solidity",
+            Language::VbNet => "vbnet",
+            Language::VbScript => "vbscript",
+            Language::CMake => "cmake",
+            Language::Makefile => "makefile",
+            Language::Dockerfile => "dockerfile",
+            Language::Compose => "compose",
+            Language::Ansible => "ansible",
+            Language::Helm => "helm",
+            Language::GitHubActions => "github-actions",
+            Language::JsonSchema => "json-schema",
+            Language::Java => "java",
+            Language::CSharp => "csharp",
+            Language::C => "c",
+            Language::Cpp => "cpp",
+            Language::Swift => "swift",
+            Language::Bash => "bash",
+            Language::Sql => "sql",
+            Language::Auto => "auto",
+            Language::Registered(extension) => extension,
+        }
+    }
+}
+
+/// Detect programming language from file extension
+pub fn detect_language(file_path: &Path) -> Language {
+    supported_language(file_path).unwrap_or_else(|| {
+        log::warn!("Could not detect language for {}. Defaulting to Python.", file_path.display());
+        Language::Python
+    })
+}
+
+/// Language of a file from its extension, if it is one DocGen supports or a parser has
+/// been registered for. Extensions are matched regardless of case, as Windows
+/// filesystems do.
+pub fn supported_language(file_path: &Path) -> Option<Language> {
+    let extension = file_path.extension().and_then(|e| e.to_str()).map(str::to_lowercase);
+    if let Some(extension) = extension.as_deref().filter(|e| crate::lang::is_registered(e)) {
+        return Some(Language::Registered(extension.to_string()));
+    }
+    let language = match extension.as_deref() {
+        Some("py") => Language::Python,
+        Some("rs") => Language::Rust,
+        Some("js") | Some("jsx") | Some("mjs") | Some("cjs") => Language::JavaScript,
+        Some("ts") | Some("tsx") => Language::TypeScript,
+        Some("proto") => Language::Protobuf,
+        Some("m") if is_matlab_file(file_path) => Language::Matlab,
+        Some("m") | Some("mm") => Language::ObjectiveC,
+        Some("h") if is_objc_header(file_path) => Language::ObjectiveC,
+        Some("scala") | Some("sc") => Language::Scala,
+        Some("groovy") | Some("gvy") | Some("gradle") => Language::Groovy,
+        Some("java") => Language::Java,
+        Some("cs") => Language::CSharp,
+        Some("c") => Language::C,
+        Some("swift") => Language::Swift,
+        Some("sh") | Some("bash") => Language::Bash,
+        Some("sql") | Some("pgsql") => Language::Sql,
+        Some("h") | Some("cc") | Some("cpp") | Some("cxx") | Some("c++") | Some("hh") | Some("hpp") | Some("hxx") => Language::Cpp,
+        Some("ps1") | Some("psm1") => Language::PowerShell,
+        Some("cu") | Some("cuh") => Language::Cuda,
+        Some("cl") => Language::OpenCl,
+        Some("sol") => Language::Solidity,
+        Some("vb") => Language::VbNet,
+        Some("vbs") | Some("asp") | Some("asa") => Language::VbScript,
+        Some("cmake") => Language::CMake,
+        Some("txt") if file_path.file_name().is_some_and(|name| name == "CMakeLists.txt") => Language::CMake,
+        Some("mk") | Some("mak") => Language::Makefile,
+        None if file_path.file_name().is_some_and(|name| matches!(name.to_str(), Some("Makefile" | "makefile" | "GNUmakefile"))) => Language::Makefile,
+        Some("f90") | Some("f95") | Some("f03") | Some("f08") | Some("f") | Some("for") | Some("f77") => Language::Fortran,
+        None if file_path.file_name().is_some_and(|name| name == "Jenkinsfile") => Language::Groovy,
+        _ if is_dockerfile(file_path) => Language::Dockerfile,
+        Some("yaml") | Some("yml") if is_github_actions_file(file_path) => Language::GitHubActions,
+        Some("yaml") | Some("yml") if is_helm_values(file_path) => Language::Helm,
+        Some("yaml") | Some("yml") if is_compose_file(file_path) => Language::Compose,
+        Some("yaml") | Some("yml") if is_ansible_file(file_path) => Language::Ansible,
+        Some("yaml") | Some("yml") if is_openapi_file(file_path) => Language::OpenApi,
+        Some("json") if is_json_schema_file(file_path) => Language::JsonSchema,
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// Check whether a `.h` header declares Objective-C interfaces or protocols
+fn is_objc_header(file_path: &Path) -> bool {
+    std::fs::read_to_string(file_path)
+        .map(|content| content.lines().any(|line| {
+            let line = line.trim_start();
+            line.starts_with("@interface") || line.starts_with("@protocol")
+        }))
+        .unwrap_or(false)
+}
+
+/// Check whether a `.m` file is MATLAB rather than Objective-C, which would show
+/// preprocessor imports or Objective-C directives
+fn is_matlab_file(file_path: &Path) -> bool {
+    std::fs::read_to_string(file_path)
+        .map(|content| !content.lines().any(|line| {
+            let line = line.trim_start();
+            line.starts_with("#import") || line.starts_with("#include") || line.starts_with("@interface")
+                || line.starts_with("@implementation") || line.starts_with("@protocol")
+        }))
+        .unwrap_or(false)
+}
+
+/// Check whether a file is a Dockerfile: `Dockerfile`, `Dockerfile.<variant>`,
+/// `<name>.dockerfile`, or `Containerfile`
+fn is_dockerfile(file_path: &Path) -> bool {
+    let name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    name == "Dockerfile" || name.starts_with("Dockerfile.") || name == "Containerfile"
+        || file_path.extension().is_some_and(|e| e == "dockerfile")
+}
+
+/// Check whether a YAML file is a docker-compose file, by its conventional name
+fn is_compose_file(file_path: &Path) -> bool {
+    let stem = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    stem == "compose" || stem == "docker-compose" || stem.starts_with("docker-compose.") || stem.starts_with("compose.")
+}
+
+/// Check whether a YAML file is a GitHub Actions workflow under `.github/workflows/`
+/// or an action's `action.yml` metadata
+fn is_github_actions_file(file_path: &Path) -> bool {
+    let in_workflows = file_path.parent()
+        .is_some_and(|dir| dir.ends_with(".github/workflows"));
+    in_workflows || file_path.file_stem().is_some_and(|stem| stem == "action")
+}
+
+/// Check whether a YAML file is a Helm chart's `values.yaml`, next to its `Chart.yaml`
+fn is_helm_values(file_path: &Path) -> bool {
+    let is_values = file_path.file_stem().is_some_and(|stem| stem == "values");
+    is_values && file_path.with_file_name("Chart.yaml").exists()
+}
+
+/// Check whether a YAML file is Ansible: a task or handler file inside a role, or a
+/// playbook whose plays name their `hosts`
+fn is_ansible_file(file_path: &Path) -> bool {
+    let in_role = file_path.parent()
+        .and_then(|dir| dir.file_name())
+        .is_some_and(|dir| dir == "tasks" || dir == "handlers");
+    in_role || std::fs::read_to_string(file_path)
+        .map(|content| content.lines().any(|line| line.starts_with("- hosts:") || line.starts_with("  hosts:")))
+        .unwrap_or(false)
+}
+
+/// Check whether a YAML file looks like an OpenAPI or Swagger specification
+fn is_openapi_file(file_path: &Path) -> bool {
+    let stem = file_path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if stem.contains("openapi") || stem.contains("swagger") {
+        return true;
+    }
+    
+    // Fall back to sniffing the document's top-level version key
+    std::fs::read_to_string(file_path)
+        .map(|content| content.lines().any(|line| line.starts_with("openapi:") || line.starts_with("swagger:")))
+        .unwrap_or(false)
+}
+
+/// Check whether a JSON file is a JSON Schema (`*.schema.json`, or declaring `$schema`)
+/// or a Swagger/OpenAPI document with schemas
+fn is_json_schema_file(file_path: &Path) -> bool {
+    let stem = file_path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if stem == "schema" || stem.ends_with(".schema") || stem.contains("openapi") || stem.contains("swagger") {
+        return true;
+    }
+    
+    // Fall back to sniffing for the keys that identify each kind of document
+    std::fs::read_to_string(file_path)
+        .map(|content| ["\"$schema\"", "\"swagger\"", "\"openapi\""].iter().any(|key| content.contains(key)))
+        .unwrap_or(false)
+}
@@ -0,0 +1,54 @@
+//! DocGen's documentation engine: language parsers, documentation analysis, and LLM
+//! clients, for tools such as editors and bots to embed. The `docgen` command-line
+//! tool is built on this crate.
+
+pub mod capabilities;
+pub mod backup;
+pub mod budget;
+pub mod campaign;
+pub mod changes;
+pub mod config;
+pub mod coverage;
+pub mod diff;
+pub mod discover;
+pub mod docstring;
+pub mod doctest;
+pub mod error;
+pub mod heatmap;
+pub mod history;
+pub mod hook;
+pub mod interactive;
+pub mod io;
+pub mod llm;
+pub mod lock;
+pub mod logging;
+pub mod overlay;
+pub mod overview;
+pub mod parser;
+pub mod policy;
+pub mod priority;
+pub mod prompt;
+pub mod progress;
+pub mod provenance;
+pub mod report;
+pub mod resync;
+pub mod review;
+pub mod routing;
+pub mod rules;
+pub mod scheduler;
+pub mod sections;
+pub mod stats;
+pub mod symbols;
+pub mod tracker;
+pub mod updater;
+pub mod vendor;
+pub mod watch;
+pub mod lang;
+pub mod language;
+pub mod engine;
+
+pub use docstring::analyze;
+pub use engine::{process_file, process_source};
+pub use lang::LanguageParser;
+pub use language::Language;
+pub use llm::LlmClient;
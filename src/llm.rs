@@ -1,72 +1,491 @@
 use async_trait::async_trait;
-use anyhow::Result;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::time::Duration;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use futures::future::{join_all, BoxFuture};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 
+use crate::budget::Budget;
+use crate::capabilities::{self, ModelCapabilities};
 use crate::config::Config;
-use crate::docstring::{DocstringIssue, UpdatedDocstring};
+use crate::docstring::{DocstringIssue, DocstringStyle, IssueKind, UpdatedDocstring};
 use crate::error::{DocGenError, DocGenResult};
-use crate::parser::ParsedCode;
+use crate::lang::typescript::TagStyle;
+use crate::parser::{CodeItem, ItemKind, ParsedCode};
+use crate::prompt;
+use crate::scheduler::{Member, Scheduler};
 
 /// Trait for LLM clients
 #[async_trait]
-pub trait LlmClient {
+pub trait LlmClient: Send + Sync {
     async fn generate_docstrings(
         &self, 
         parsed_code: &ParsedCode, 
-        issues: &[DocstringIssue]
+        issues: &[DocstringIssue],
+        config: &Config
     ) -> DocGenResult<Vec<UpdatedDocstring>>;
+    
+    /// Answer a prompt asking for JSON that isn't about any one item, such as the
+    /// overview of a module written by `docgen summarize`
+    async fn summarize(&self, system: &str, prompt: &str, config: &Config) -> DocGenResult<String>;
+    
+    /// Total tokens consumed by this client so far
+    fn tokens_used(&self) -> u64 {
+        0
+    }
+    
+    /// Model the client's last docstrings came from, as recorded by `--track-generated`
+    fn model(&self) -> String;
 }
 
 /// Factory function to get the appropriate LLM client
-pub fn get_client(provider: &str) -> DocGenResult<Box<dyn LlmClient>> {
+pub fn get_client(config: &Config) -> DocGenResult<Box<dyn LlmClient>> {
+    // Template mode never contacts a model
+    if config.template_fix {
+        return Ok(Box::new(TemplateLlmClient::new()));
+    }
+    
+    // A provider pool spreads the requests across its providers
+    if let Some(scheduler) = &config.scheduler {
+        return Ok(Box::new(ScheduledClient::new(scheduler.clone(), config)?));
+    }
+    provider_client(config, &config.provider, config.model.clone(), None)
+}
+
+/// Client for one provider and model. A member of a provider pool may have its own API
+/// key variable, and is told about every response.
+fn provider_client(
+    config: &Config,
+    provider: &str,
+    model: Option<String>,
+    member: Option<Arc<Member>>,
+) -> DocGenResult<Box<dyn LlmClient>> {
+    let provider = provider.to_lowercase();
+    
     // For the "mock" provider, return our mock client for testing
-    if provider.to_lowercase() == "mock" {
+    if provider == "mock" {
         return Ok(Box::new(MockLlmClient::new()));
     }
     
-    match provider.to_lowercase().as_str() {
+    let schema = capabilities::schema(&provider)
+        .ok_or_else(|| DocGenError::ConfigError(format!("Unsupported LLM provider: {}", provider)))?;
+    // Summaries alone don't need the most capable model
+    let model = model.unwrap_or_else(|| {
+        if config.summary_only { schema.summary_model } else { schema.default_model }.to_string()
+    });
+    let model_capabilities = capabilities::resolve(&provider, &model);
+    if config.budget.limits_cost() && model_capabilities.cost_per_1k_input == 0.0 && model_capabilities.cost_per_1k_output == 0.0 {
+        return Err(DocGenError::ConfigError(format!(
+            "--max-cost needs the pricing of {} model '{}', which is unknown; use --max-tokens-per-run instead", provider, model)));
+    }
+    
+    log::debug!("Using {} model {} ({})", provider, model, model_capabilities.describe());
+    
+    // The key comes from the pool member's own variable, if it names one; the
+    // variable is named in errors about the key
+    let api_key = |default_variable: &str| {
+        let variable = member.as_ref()
+            .and_then(|member| member.settings.api_key_env.clone())
+            .unwrap_or_else(|| default_variable.to_string());
+        std::env::var(&variable)
+            .map(|key| (key, variable.clone()))
+            .map_err(|_| DocGenError::ConfigError(format!("{} environment variable is not set", variable)))
+    };
+    
+    match provider.as_str() {
         "openai" => {
-            let api_key = std::env::var("OPENAI_API_KEY")
-                .map_err(|_| DocGenError::ConfigError("OPENAI_API_KEY environment variable is not set".into()))?;
-            Ok(Box::new(OpenAiClient::new(api_key)))
+            let (api_key, key_variable) = api_key("OPENAI_API_KEY")?;
+            let mut client = OpenAiClient::new(api_key, model, model_capabilities, config.requests.clone(), config.budget.clone());
+            client.member = member;
+            client.key_variable = key_variable;
+            Ok(Box::new(client))
+        },
+        "azure-openai" => {
+            let (api_key, key_variable) = api_key("AZURE_OPENAI_API_KEY")?;
+            
+            // Environment variables take precedence over the project configuration
+            let setting = |var: &str, configured: &Option<String>| std::env::var(var).ok().or_else(|| configured.clone());
+            let endpoint = setting("AZURE_OPENAI_ENDPOINT", &config.azure.endpoint)
+                .ok_or_else(|| DocGenError::ConfigError("Set AZURE_OPENAI_ENDPOINT or azure.endpoint in docsherpa.toml".into()))?;
+            let deployment = setting("AZURE_OPENAI_DEPLOYMENT", &config.azure.deployment)
+                .ok_or_else(|| DocGenError::ConfigError("Set AZURE_OPENAI_DEPLOYMENT or azure.deployment in docsherpa.toml".into()))?;
+            let api_version = setting("AZURE_OPENAI_API_VERSION", &config.azure.api_version)
+                .unwrap_or_else(|| schema.api_version.to_string());
+            
+            log::debug!("Using Azure OpenAI deployment {} at {} (API version {})", deployment, endpoint, api_version);
+            let url = schema.endpoint
+                .replace("{endpoint}", endpoint.trim_end_matches('/'))
+                .replace("{deployment}", &deployment)
+                .replace("{api_version}", &api_version);
+            let mut client = AzureOpenAiClient::new(api_key, url, model, model_capabilities, config.requests.clone(), config.budget.clone());
+            client.inner.member = member;
+            client.inner.key_variable = key_variable;
+            Ok(Box::new(client))
         },
         "claude" => {
-            let api_key = std::env::var("ANTHROPIC_API_KEY")
-                .map_err(|_| DocGenError::ConfigError("ANTHROPIC_API_KEY environment variable is not set".into()))?;
-            Ok(Box::new(ClaudeClient::new(api_key)))
+            let (api_key, key_variable) = api_key("ANTHROPIC_API_KEY")?;
+            let mut client = ClaudeClient::new(api_key, model, model_capabilities, config.requests.clone(), config.budget.clone());
+            client.member = member;
+            client.key_variable = key_variable;
+            Ok(Box::new(client))
         },
         _ => Err(DocGenError::ConfigError(format!("Unsupported LLM provider: {}", provider))),
     }
 }
 
+/// Most tokens of the project's context (`--context`) sent with each request
+pub const MAX_CONTEXT_TOKENS: usize = 2_000;
+
+/// System prompt for documenting items in a language: the project's own, if it has
+/// one, or the built-in one, followed by the project's context
+fn system_prompt(language: &str, config: &Config, capabilities: &ModelCapabilities) -> String {
+    let system = config.system_prompt.clone().unwrap_or_else(|| prompt::system_prompt(language));
+    with_context(&system, config, capabilities)
+}
+
+/// A system prompt followed by the project's context, if it has any. Context longer than
+/// `MAX_CONTEXT_TOKENS`, or than a quarter of the model's context window, is cut off at
+/// the last line break that fits, leaving the rest of the window for the code.
+fn with_context(system: &str, config: &Config, capabilities: &ModelCapabilities) -> String {
+    let context = match config.context.as_deref().map(str::trim).filter(|context| !context.is_empty()) {
+        Some(context) => context,
+        None => return system.to_string(),
+    };
+    
+    let max_tokens = MAX_CONTEXT_TOKENS.min(capabilities.max_context_tokens / 4);
+    let context = if capabilities::estimate_tokens(context) <= max_tokens {
+        context.to_string()
+    } else {
+        // Estimated tokens are a quarter of the length, so this much of the text fits
+        let mut end = (max_tokens * 4).min(context.len());
+        while !context.is_char_boundary(end) {
+            end -= 1;
+        }
+        let end = context[..end].rfind('\n').unwrap_or(end);
+        format!("{}\n\n(The rest of the project context was left out for length.)", context[..end].trim_end())
+    };
+    format!("{}\n\nBackground on the project, so the documentation uses its terminology and names its \
+        concepts as the project does (don't describe the project itself):\n\n````\n{}\n````", system, context)
+}
+
+/// Keep the documentation from the requests that were sent, dropping the items skipped
+/// because the run's budget ran out; any other failure fails the whole file
+fn within_budget<T>(results: Vec<DocGenResult<T>>) -> DocGenResult<Vec<T>> {
+    let mut kept = Vec::new();
+    for result in results {
+        match result {
+            Ok(value) => kept.push(value),
+            Err(DocGenError::BudgetExhausted) => {},
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(kept)
+}
+
+/// Drop issues whose code is too large for the model's context window, warning about each
+fn issues_within_context(
+    model_capabilities: &ModelCapabilities,
+    parsed_code: &ParsedCode,
+    issues: &[DocstringIssue],
+) -> Vec<DocstringIssue> {
+    issues.iter()
+        .filter(|issue| {
+            let fits = model_capabilities.fits(&parsed_code.items[issue.item_index]);
+            if !fits {
+                log::warn!("Skipping {} '{}': its code does not fit in the model's {}-token context",
+                    issue.item_type, issue.name, model_capabilities.max_context_tokens);
+            }
+            fits
+        })
+        .cloned()
+        .collect()
+}
+
+/// Structured answer to a batch prompt
+#[derive(Deserialize)]
+struct BatchResponse {
+    docstrings: Vec<BatchEntry>,
+}
+
+#[derive(Deserialize)]
+struct BatchEntry {
+    index: usize,
+    docstring: String,
+}
+
+/// Error body sent with a failed response. OpenAI, Azure OpenAI, and Anthropic all
+/// nest the details under `error`.
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    #[serde(default)]
+    message: String,
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    /// A string for OpenAI and Azure OpenAI, e.g. `invalid_api_key`
+    #[serde(default)]
+    code: Option<Value>,
+}
+
+/// Error for a failed response that says what to do about it: a rejected key, a used-up
+/// quota, an unknown model, and a content filter refusal each get their own, and
+/// anything else keeps the provider's message. `provider` is the provider's name and
+/// `key_variable` the environment variable the key came from.
+fn api_error(provider: &str, key_variable: &str, status: u16, body: &str) -> DocGenError {
+    let detail = serde_json::from_str::<ErrorBody>(body).ok().map(|body| body.error);
+    let message = detail.as_ref()
+        .map(|detail| detail.message.trim().to_string())
+        .filter(|message| !message.is_empty())
+        .unwrap_or_else(|| body.trim().to_string());
+    let code = detail.as_ref()
+        .and_then(|detail| detail.code.as_ref())
+        .map(|code| code.as_str().map_or_else(|| code.to_string(), str::to_string))
+        .unwrap_or_default()
+        .to_lowercase();
+    let kind = detail.as_ref().and_then(|detail| detail.kind.clone()).unwrap_or_default().to_lowercase();
+    let is = |names: &[&str]| names.iter().any(|name| code == *name || kind == *name);
+    
+    if status == 401 || is(&["invalid_api_key", "authentication_error"]) {
+        DocGenError::InvalidApiKey(format!(
+            "{} rejected the key in {} ({}); set it to a valid API key", provider, key_variable, message))
+    } else if status == 402 || is(&["insufficient_quota", "billing_hard_limit_reached"]) || message.contains("credit balance") {
+        DocGenError::QuotaExceeded(format!(
+            "{} quota for the key in {} is used up ({}); add credits or raise the limit in the provider's billing settings, \
+            or use another provider", provider, key_variable, message))
+    } else if is(&["content_filter", "content_policy_violation"]) {
+        DocGenError::ContentFiltered(format!(
+            "{} refused the request under its content policy ({}); leave the file out with --exclude, or document it by hand",
+            provider, message))
+    } else if status == 404 || is(&["model_not_found", "deploymentnotfound", "not_found_error"]) {
+        DocGenError::ModelNotFound(format!(
+            "{} has no such model or deployment ({}); choose an available model with --model, or for Azure OpenAI \
+            check AZURE_OPENAI_DEPLOYMENT", provider, message))
+    } else {
+        DocGenError::LlmApiError(format!("API request failed with status {}: {}", status, message))
+    }
+}
+
+/// Wrap generated documentation text in the delimiters the language parser expects
+pub fn format_docstring(text: &str, item: &CodeItem, language: &str) -> String {
+    match language {
+        // Python docstrings carry their triple quotes; other parsers add comment markers themselves
+        "python" if !item.is_script_statement() => format!("\"\"\"{}\"\"\"", text),
+        _ => text.to_string(),
+    }
+}
+
+/// Documentation text without the delimiters added by `format_docstring`, for showing
+/// it to a person to edit
+pub fn unformat_docstring<'a>(docstring: &'a str, language: &str) -> &'a str {
+    let docstring = docstring.trim();
+    docstring.strip_prefix("\"\"\"")
+        .and_then(|d| d.strip_suffix("\"\"\""))
+        .filter(|_| language == "python")
+        .unwrap_or(docstring)
+}
+
 /// OpenAI client implementation
 pub struct OpenAiClient {
-    api_key: String,
+    endpoint: String,
+    /// Name and value of the header that authenticates requests
+    auth_header: (&'static str, String),
+    /// Provider's name, and the environment variable the key came from, for errors
+    provider: &'static str,
+    key_variable: String,
     client: Client,
+    model: String,
+    capabilities: &'static ModelCapabilities,
+    tokens_used: AtomicU64,
+    requests: Arc<Semaphore>,
+    budget: Arc<Budget>,
+    /// Pool member that responses are reported to, when the run uses a provider pool
+    member: Option<Arc<Member>>,
 }
 
 impl OpenAiClient {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(
+        api_key: String,
+        model: String,
+        capabilities: &'static ModelCapabilities,
+        requests: Arc<Semaphore>,
+        budget: Arc<Budget>,
+    ) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(60))
             .build()
             .unwrap();
         
-        Self { api_key, client }
+        let schema = capabilities::schema("openai").expect("openai schema is always registered");
+        Self {
+            endpoint: schema.endpoint.to_string(),
+            auth_header: ("Authorization", format!("Bearer {}", api_key)),
+            provider: "OpenAI",
+            key_variable: "OPENAI_API_KEY".to_string(),
+            client,
+            model,
+            capabilities,
+            tokens_used: AtomicU64::new(0),
+            requests,
+            budget,
+            member: None,
+        }
+    }
+    
+    /// Send one chat completion request and return the reply text
+    async fn complete(&self, system: &str, prompt: &str, json_mode: bool) -> DocGenResult<String> {
+        // Models without system prompt support get the instructions in the user turn
+        let messages = if self.capabilities.supports_system_prompt {
+            json!([
+                { "role": "system", "content": system },
+                { "role": "user", "content": prompt }
+            ])
+        } else {
+            json!([{ "role": "user", "content": format!("{}\n\n{}", system, prompt) }])
+        };
+        
+        let mut body = json!({
+            "model": self.model,
+            "messages": messages,
+            "temperature": 0.3,
+            "max_tokens": 1000
+        });
+        if json_mode {
+            body["response_format"] = json!({ "type": "json_object" });
+            body["max_tokens"] = json!(4000);
+        }
+        
+        // Make API request once a request slot is free, unless the budget ran out meanwhile
+        let _permit = self.requests.acquire().await.expect("request semaphore is never closed");
+        if self.budget.exhausted() {
+            return Err(DocGenError::BudgetExhausted);
+        }
+        let started = Instant::now();
+        let response = self.client.post(&self.endpoint)
+            .header(self.auth_header.0, &self.auth_header.1)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                self.member.iter().for_each(|member| member.observe_failure());
+                DocGenError::LlmApiError(e.to_string())
+            })?;
+        if let Some(member) = &self.member {
+            member.observe(response.status().as_u16(), response.headers());
+        }
+        
+        // Parse response
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(api_error(self.provider, &self.key_variable, status.as_u16(), &error_text));
+        }
+        
+        let response_json: OpenAiResponse = response.json().await
+            .map_err(|e| DocGenError::LlmApiError(format!("Failed to parse API response: {}", e)))?;
+        
+        let (input_tokens, output_tokens) = match &response_json.usage {
+            Some(usage) => {
+                self.tokens_used.fetch_add(usage.total_tokens, Ordering::Relaxed);
+                (usage.prompt_tokens, usage.completion_tokens)
+            },
+            None => (0, 0),
+        };
+        self.budget.record(self.provider, input_tokens, output_tokens, self.capabilities, started.elapsed());
+        
+        if response_json.choices.is_empty() {
+            return Err(DocGenError::LlmApiError("API response contained no choices".into()));
+        }
+        // A filtered answer is cut short, so it isn't used
+        if response_json.choices[0].finish_reason.as_deref() == Some("content_filter") {
+            return Err(DocGenError::ContentFiltered(format!(
+                "{} cut the answer off under its content policy; leave the file out with --exclude, or document it by hand",
+                self.provider)));
+        }
+        
+        Ok(response_json.choices[0].message.content.trim().to_string())
+    }
+    
+    /// Document one batch of items with a single JSON-mode request, falling back to
+    /// one request per item for items the answer left out
+    async fn document_batch(
+        &self,
+        system: &str,
+        parsed_code: &ParsedCode,
+        batch: &[DocstringIssue],
+        config: &Config,
+    ) -> DocGenResult<Vec<UpdatedDocstring>> {
+        let mut updated_docstrings = Vec::new();
+        let mut remaining: Vec<&DocstringIssue> = batch.iter().collect();
+        
+        if batch.len() > 1 {
+            let items: Vec<(usize, &CodeItem)> = batch.iter()
+                .map(|issue| (issue.item_index, &parsed_code.items[issue.item_index]))
+                .collect();
+            let prompt = prompt::batch_prompt(&items, &parsed_code.language, config);
+            let reply = self.complete(system, &prompt, true).await?;
+            
+            // Items the model left out of a malformed or partial answer are retried one at a time
+            if let Ok(answer) = serde_json::from_str::<BatchResponse>(&reply) {
+                for entry in answer.docstrings {
+                    if let Some(pos) = remaining.iter().position(|issue| issue.item_index == entry.index) {
+                        let item = &parsed_code.items[entry.index];
+                        updated_docstrings.push(UpdatedDocstring {
+                            item_index: entry.index,
+                            new_docstring: format_docstring(entry.docstring.trim(), item, &parsed_code.language),
+                            indentation: item.indentation.clone(),
+                        });
+                        remaining.remove(pos);
+                    }
+                }
+            }
+        }
+        
+        let singles = remaining.into_iter().map(|issue| async move {
+            let item = &parsed_code.items[issue.item_index];
+            
+            // Prepare prompt
+            let prompt = prompt::item_prompt(item, &parsed_code.language, config);
+            let docstring_text = self.complete(system, &prompt, false).await?;
+            
+            // Format the docstring for the target language
+            Ok::<_, DocGenError>(UpdatedDocstring {
+                item_index: issue.item_index,
+                new_docstring: format_docstring(&docstring_text, item, &parsed_code.language),
+                indentation: item.indentation.clone(),
+            })
+        });
+        updated_docstrings.extend(within_budget(join_all(singles).await)?);
+        
+        Ok(updated_docstrings)
     }
 }
 
 #[derive(Deserialize)]
 struct OpenAiResponse {
     choices: Vec<OpenAiChoice>,
+    usage: Option<OpenAiUsage>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    total_tokens: u64,
 }
 
 #[derive(Deserialize)]
 struct OpenAiChoice {
     message: OpenAiMessage,
+    #[serde(default)]
+    finish_reason: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -79,73 +498,148 @@ impl LlmClient for OpenAiClient {
     async fn generate_docstrings(
         &self, 
         parsed_code: &ParsedCode, 
-        issues: &[DocstringIssue]
+        issues: &[DocstringIssue],
+        config: &Config
     ) -> DocGenResult<Vec<UpdatedDocstring>> {
-        let mut updated_docstrings = Vec::new();
+        let system = system_prompt(&parsed_code.language, config, self.capabilities);
+        let issues = issues_within_context(self.capabilities, parsed_code, issues);
         
-        for issue in issues {
-            let item = &parsed_code.items[issue.item_index];
-            
-            // Prepare prompt
-            let prompt = format!(
-                "Generate a Python docstring for the following {} '{}'. \
-                Follow PEP 257 style guidelines.\
-                The docstring should be informative, accurate, and describe what the {} does.\
-                Include parameters, return values, and exceptions if applicable.\
-                Return ONLY the docstring text without the triple quotes or indentation.\n\n\
-                ```python\n{}\n```",
-                item.item_type, item.name, item.item_type, item.code
-            );
-            
-            // Make API request
-            let response = self.client.post("https://api.openai.com/v1/chat/completions")
-                .header("Authorization", format!("Bearer {}", self.api_key))
-                .header("Content-Type", "application/json")
-                .json(&json!({
-                    "model": "gpt-4",
-                    "messages": [
-                        {
-                            "role": "system",
-                            "content": "You are a Python documentation assistant. Generate clear, concise, and accurate docstrings for Python code."
-                        },
-                        {
-                            "role": "user",
-                            "content": prompt
-                        }
-                    ],
-                    "temperature": 0.3,
-                    "max_tokens": 1000
-                }))
-                .send()
-                .await
-                .map_err(|e| DocGenError::LlmApiError(e.to_string()))?;
-            
-            // Parse response
-            if !response.status().is_success() {
-                let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-                return Err(DocGenError::LlmApiError(format!("API request failed: {}", error_text)));
-            }
-            
-            let response_json: OpenAiResponse = response.json().await
-                .map_err(|e| DocGenError::LlmApiError(format!("Failed to parse API response: {}", e)))?;
+        // Models with JSON mode document several items per request. Batches are sent
+        // concurrently, as far as the request semaphore allows.
+        let batches = issues.chunks(self.capabilities.batch_size())
+            .map(|batch| self.document_batch(&system, parsed_code, batch, config));
+        let documented = within_budget(join_all(batches).await)?;
+        
+        Ok(documented.into_iter().flatten().collect())
+    }
+    
+    async fn summarize(&self, system: &str, prompt: &str, config: &Config) -> DocGenResult<String> {
+        self.complete(&with_context(system, config, self.capabilities), prompt, true).await
+    }
+    
+    fn tokens_used(&self) -> u64 {
+        self.tokens_used.load(Ordering::Relaxed)
+    }
+    
+    fn model(&self) -> String {
+        self.model.clone()
+    }
+}
+
+/// Azure OpenAI client implementation. Azure serves the OpenAI chat completions API
+/// from a deployment-specific URL, authenticated with an `api-key` header.
+pub struct AzureOpenAiClient {
+    inner: OpenAiClient,
+}
+
+impl AzureOpenAiClient {
+    pub fn new(
+        api_key: String,
+        url: String,
+        model: String,
+        capabilities: &'static ModelCapabilities,
+        requests: Arc<Semaphore>,
+        budget: Arc<Budget>,
+    ) -> Self {
+        let mut inner = OpenAiClient::new(String::new(), model, capabilities, requests, budget);
+        inner.endpoint = url;
+        inner.auth_header = ("api-key", api_key);
+        inner.provider = "Azure OpenAI";
+        inner.key_variable = "AZURE_OPENAI_API_KEY".to_string();
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl LlmClient for AzureOpenAiClient {
+    async fn generate_docstrings(
+        &self, 
+        parsed_code: &ParsedCode, 
+        issues: &[DocstringIssue],
+        config: &Config
+    ) -> DocGenResult<Vec<UpdatedDocstring>> {
+        self.inner.generate_docstrings(parsed_code, issues, config).await
+    }
+    
+    async fn summarize(&self, system: &str, prompt: &str, config: &Config) -> DocGenResult<String> {
+        self.inner.summarize(system, prompt, config).await
+    }
+    
+    fn tokens_used(&self) -> u64 {
+        self.inner.tokens_used()
+    }
+    
+    fn model(&self) -> String {
+        self.inner.model()
+    }
+}
+
+/// Client for a provider pool: each file's requests go to the provider the scheduler
+/// picks, and when one fails, to the next best provider not tried yet
+pub struct ScheduledClient {
+    scheduler: Arc<Scheduler>,
+    /// One client per pool member, in the scheduler's order
+    clients: Vec<Box<dyn LlmClient>>,
+    /// Member whose client answered last
+    last_used: AtomicUsize,
+}
+
+impl ScheduledClient {
+    pub fn new(scheduler: Arc<Scheduler>, config: &Config) -> DocGenResult<Self> {
+        let clients = scheduler.members().iter()
+            .map(|member| provider_client(config, &member.settings.provider, member.settings.model.clone(), Some(member.clone())))
+            .collect::<DocGenResult<Vec<_>>>()?;
+        Ok(Self { scheduler, clients, last_used: AtomicUsize::new(0) })
+    }
+    
+    /// Make a request with the client of the provider the scheduler picks, and when it
+    /// fails, with the next best provider not tried yet
+    async fn with_failover<'a, T>(
+        &'a self,
+        request: impl Fn(&'a dyn LlmClient) -> BoxFuture<'a, DocGenResult<T>>,
+    ) -> DocGenResult<T> {
+        let mut tried = Vec::new();
+        loop {
+            let slot = self.scheduler.acquire(&tried).await
+                .ok_or_else(|| DocGenError::LlmApiError("every provider in the pool failed".into()))?;
+            let index = slot.index;
+            let result = request(self.clients[index].as_ref()).await;
+            drop(slot);
+            self.last_used.store(index, Ordering::Relaxed);
             
-            if response_json.choices.is_empty() {
-                return Err(DocGenError::LlmApiError("API response contained no choices".into()));
+            match result {
+                Err(e) if e.is_provider_error() && tried.len() + 1 < self.clients.len() => {
+                    log::warn!("{} failed ({}); trying another provider",
+                        self.scheduler.members()[index].describe(), e);
+                    tried.push(index);
+                },
+                result => return result,
             }
-            
-            let docstring_text = response_json.choices[0].message.content.trim();
-            
-            // Format the docstring with triple quotes and proper indentation
-            let formatted_docstring = format!("\"\"\"{}\"\"\"", docstring_text);
-            
-            updated_docstrings.push(UpdatedDocstring {
-                item_index: issue.item_index,
-                new_docstring: formatted_docstring,
-                indentation: item.indentation.clone(),
-            });
         }
-        
-        Ok(updated_docstrings)
+    }
+}
+
+#[async_trait]
+impl LlmClient for ScheduledClient {
+    async fn generate_docstrings(
+        &self, 
+        parsed_code: &ParsedCode, 
+        issues: &[DocstringIssue],
+        config: &Config
+    ) -> DocGenResult<Vec<UpdatedDocstring>> {
+        self.with_failover(|client| client.generate_docstrings(parsed_code, issues, config)).await
+    }
+    
+    async fn summarize(&self, system: &str, prompt: &str, config: &Config) -> DocGenResult<String> {
+        self.with_failover(|client| client.summarize(system, prompt, config)).await
+    }
+    
+    fn tokens_used(&self) -> u64 {
+        self.clients.iter().map(|client| client.tokens_used()).sum()
+    }
+    
+    fn model(&self) -> String {
+        self.clients[self.last_used.load(Ordering::Relaxed)].model()
     }
 }
 
@@ -153,22 +647,476 @@ impl LlmClient for OpenAiClient {
 pub struct ClaudeClient {
     api_key: String,
     client: Client,
+    model: String,
+    capabilities: &'static ModelCapabilities,
+    tokens_used: AtomicU64,
+    requests: Arc<Semaphore>,
+    budget: Arc<Budget>,
+    /// Pool member that responses are reported to, when the run uses a provider pool
+    member: Option<Arc<Member>>,
+    /// Environment variable the key came from, for errors
+    key_variable: String,
 }
 
 impl ClaudeClient {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(
+        api_key: String,
+        model: String,
+        capabilities: &'static ModelCapabilities,
+        requests: Arc<Semaphore>,
+        budget: Arc<Budget>,
+    ) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(60))
             .build()
             .unwrap();
         
-        Self { api_key, client }
+        Self {
+            api_key,
+            client,
+            model,
+            capabilities,
+            tokens_used: AtomicU64::new(0),
+            requests,
+            budget,
+            member: None,
+            key_variable: "ANTHROPIC_API_KEY".to_string(),
+        }
+    }
+    
+    /// Send one messages request and return the reply text
+    async fn complete(&self, system: &str, prompt: &str, max_tokens: u32) -> DocGenResult<String> {
+        let schema = capabilities::schema("claude").expect("claude schema is always registered");
+        let mut body = json!({
+            "model": self.model,
+            "max_tokens": max_tokens,
+            "messages": [
+                {
+                    "role": "user",
+                    "content": prompt
+                }
+            ]
+        });
+        if self.capabilities.supports_system_prompt {
+            body["system"] = json!(system);
+        }
+        
+        // Make API request once a request slot is free, unless the budget ran out meanwhile
+        let _permit = self.requests.acquire().await.expect("request semaphore is never closed");
+        if self.budget.exhausted() {
+            return Err(DocGenError::BudgetExhausted);
+        }
+        let started = Instant::now();
+        let response = self.client.post(schema.endpoint)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", schema.api_version)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                self.member.iter().for_each(|member| member.observe_failure());
+                DocGenError::LlmApiError(e.to_string())
+            })?;
+        if let Some(member) = &self.member {
+            member.observe(response.status().as_u16(), response.headers());
+        }
+        
+        // Parse response
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(api_error("Anthropic", &self.key_variable, status.as_u16(), &error_text));
+        }
+        
+        let response_json: ClaudeResponse = response.json().await
+            .map_err(|e| DocGenError::LlmApiError(format!("Failed to parse API response: {}", e)))?;
+        
+        let (input_tokens, output_tokens) = match &response_json.usage {
+            Some(usage) => (usage.input_tokens, usage.output_tokens),
+            None => (0, 0),
+        };
+        self.tokens_used.fetch_add(input_tokens + output_tokens, Ordering::Relaxed);
+        self.budget.record("Anthropic", input_tokens, output_tokens, self.capabilities, started.elapsed());
+        
+        if response_json.content.is_empty() {
+            return Err(DocGenError::LlmApiError("API response contained no content".into()));
+        }
+        
+        Ok(response_json.content[0].text.trim().to_string())
+    }
+    
+    /// Document one item with a single request
+    async fn document_item(
+        &self,
+        parsed_code: &ParsedCode,
+        issue: &DocstringIssue,
+        config: &Config,
+    ) -> DocGenResult<UpdatedDocstring> {
+        let item = &parsed_code.items[issue.item_index];
+        
+        // Prepare prompt
+        let prompt = prompt::item_prompt(item, &parsed_code.language, config);
+        let system = system_prompt(&parsed_code.language, config, self.capabilities);
+        let docstring_text = self.complete(&system, &prompt, 1000).await?;
+        
+        // Format the docstring for the target language
+        let formatted_docstring = format_docstring(&docstring_text, item, &parsed_code.language);
+        
+        Ok(UpdatedDocstring {
+            item_index: issue.item_index,
+            new_docstring: formatted_docstring,
+            indentation: item.indentation.clone(),
+        })
+    }
+}
+
+/// Offline client that scaffolds skeleton documentation from the parsed signature,
+/// for environments where no model may be used
+pub struct TemplateLlmClient;
+
+impl Default for TemplateLlmClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateLlmClient {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    /// Parameters worth documenting, without keyword-only markers; parsers already
+    /// leave out receivers
+    fn documented_parameters(item: &CodeItem) -> Vec<String> {
+        item.parameters.iter()
+            .map(|p| p.trim_end_matches('=').to_string())
+            .collect()
+    }
+    
+    /// Build the skeleton for an item in the language's documentation convention, and
+    /// for Python in the chosen docstring style (Google by default). Summary-only mode
+    /// gets the placeholder summary alone.
+    fn skeleton(item: &CodeItem, language: &str, config: &Config) -> String {
+        let summary = format!("TODO: Summarize what `{}` does.", item.name);
+        if config.summary_only {
+            return summary;
+        }
+        let params = Self::documented_parameters(item);
+        let placeholder = "TODO: describe.";
+        let mut sections = vec![summary];
+        // Type annotation of a parameter, for the styles that document types
+        let annotation = |name: &str| item.parameter_details.iter()
+            .find(|p| p.name == name)
+            .and_then(|p| p.annotation.as_deref());
+        // A Python property is documented like an attribute, by the value it holds
+        let has_returns = item.returns.is_some() && item.item_type != ItemKind::Property;
+        
+        match language {
+            "python" if !item.is_script_statement() => match config.docstring_style.unwrap_or(DocstringStyle::Google) {
+                DocstringStyle::Google => {
+                    if !params.is_empty() {
+                        let args: Vec<String> = params.iter()
+                            .map(|p| match annotation(p) {
+                                Some(annotation) => format!("    {} ({}): {}", p, annotation, placeholder),
+                                None => format!("    {}: {}", p, placeholder),
+                            })
+                            .collect();
+                        sections.push(format!("Args:\n{}", args.join("\n")));
+                    }
+                    if item.is_generator {
+                        sections.push(format!("Yields:\n    {}", placeholder));
+                    } else if has_returns {
+                        sections.push(format!("Returns:\n    {}", placeholder));
+                    }
+                },
+                DocstringStyle::Numpy => {
+                    if !params.is_empty() {
+                        let args: Vec<String> = params.iter()
+                            .map(|p| match annotation(p) {
+                                Some(annotation) => format!("{} : {}\n    {}", p, annotation, placeholder),
+                                None => format!("{}\n    {}", p, placeholder),
+                            })
+                            .collect();
+                        sections.push(format!("Parameters\n----------\n{}", args.join("\n")));
+                    }
+                    if item.is_generator {
+                        sections.push(format!("Yields\n------\n{}", placeholder));
+                    } else if has_returns {
+                        sections.push(format!("Returns\n-------\n{}", placeholder));
+                    }
+                },
+                DocstringStyle::Sphinx => {
+                    let mut fields: Vec<String> = params.iter()
+                        .flat_map(|p| [Some(format!(":param {}: {}", p, placeholder)),
+                            annotation(p).map(|annotation| format!(":type {}: {}", p, annotation))])
+                        .flatten()
+                        .collect();
+                    if item.is_generator {
+                        fields.push(format!(":yields: {}", placeholder));
+                    } else if has_returns {
+                        fields.push(format!(":returns: {}", placeholder));
+                    }
+                    if !fields.is_empty() {
+                        sections.push(fields.join("\n"));
+                    }
+                },
+            },
+            "rust" => {
+                if !params.is_empty() {
+                    let args: Vec<String> = params.iter()
+                        .map(|p| format!("* `{}` - {}", p, placeholder))
+                        .collect();
+                    sections.push(format!("# Arguments\n\n{}", args.join("\n")));
+                }
+                if item.returns.is_some() {
+                    sections.push(format!("# Returns\n\n{}", placeholder));
+                }
+                if config.with_examples && crate::lang::rust::takes_example(item) {
+                    sections.push(format!("# Examples\n\n```\n// TODO: show how to use `{}`.\n```", item.name));
+                }
+            },
+            // A component's props are documented as properties of one `props` object
+            "javascript" | "typescript" if item.item_type == ItemKind::Component => {
+                let typed = language == "javascript" || config.ts_tag_style == TagStyle::Jsdoc;
+                let separator = if language == "typescript" { " - " } else { " " };
+                let type_of = |type_name: &str| if typed { format!("{{{}}} ", type_name) } else { String::new() };
+                let mut tags: Vec<String> = Vec::new();
+                if !params.is_empty() {
+                    let props_type = annotation("props").unwrap_or("object");
+                    tags.push(format!("@param {}props{}{}", type_of(props_type), separator, placeholder));
+                }
+                tags.extend(item.parameter_details.iter()
+                    .filter(|p| p.name.starts_with("props."))
+                    .map(|p| format!("@param {}{}{}{}", type_of("*"), p.name, separator, placeholder)));
+                tags.push(format!("@returns {}{}", type_of("JSX.Element"), placeholder));
+                sections.push(tags.join("\n"));
+            },
+            // TypeScript tags take the signature's types only in the JSDoc style
+            "javascript" | "typescript" => {
+                let typed = language == "typescript" && config.ts_tag_style == TagStyle::Jsdoc;
+                let separator = if language == "typescript" { " - " } else { " " };
+                let type_of = |type_name: Option<&str>| type_name
+                    .filter(|_| typed)
+                    .map(|t| format!("{{{}}} ", t))
+                    .unwrap_or_default();
+                let mut tags: Vec<String> = Vec::new();
+                if language == "typescript" {
+                    tags.extend(crate::lang::typescript::type_parameters(&item.name, &item.code).iter()
+                        .map(|t| if typed {
+                            format!("@template {} {}", t, placeholder)
+                        } else {
+                            format!("@typeParam {} - {}", t, placeholder)
+                        }));
+                }
+                tags.extend(params.iter()
+                    .map(|p| format!("@param {}{}{}{}", type_of(annotation(p)), p, separator, placeholder)));
+                if item.returns.is_some() {
+                    tags.push(format!("@returns {}{}", type_of(item.returns.as_deref()), placeholder));
+                }
+                if !tags.is_empty() {
+                    sections.push(tags.join("\n"));
+                }
+            },
+            "objc" | "scala" | "groovy" | "java" | "fortran" | "cuda" | "opencl" => {
+                let mut tags: Vec<String> = params.iter()
+                    .map(|p| format!("@param {} {}", p, placeholder))
+                    .collect();
+                if item.returns.is_some() {
+                    tags.push(format!("@return {}", placeholder));
+                }
+                tags.extend(item.throws.iter().map(|t| format!("@throws {} {}", t, placeholder)));
+                if !tags.is_empty() {
+                    sections.push(tags.join("\n"));
+                }
+            },
+            "powershell" => {
+                sections[0] = format!(".SYNOPSIS\n{}", sections[0]);
+                for param in &params {
+                    sections.push(format!(".PARAMETER {}\n{}", param, placeholder));
+                }
+                sections.push(format!(".EXAMPLE\n{}", item.name));
+                if let Some(returns) = &item.returns {
+                    sections.push(format!(".OUTPUTS\n{}", returns));
+                }
+            },
+            "matlab" => {
+                sections[0] = format!("{} {}", item.name.to_uppercase(), sections[0]);
+                if !params.is_empty() {
+                    let inputs: Vec<String> = params.iter()
+                        .map(|p| format!("  {} - {}", p, placeholder))
+                        .collect();
+                    sections.push(format!("Inputs:\n{}", inputs.join("\n")));
+                }
+                if let Some(returns) = &item.returns {
+                    let outputs: Vec<String> = returns.split(", ")
+                        .map(|o| format!("  {} - {}", o, placeholder))
+                        .collect();
+                    sections.push(format!("Outputs:\n{}", outputs.join("\n")));
+                }
+            },
+            "solidity" => {
+                // Internal and private functions are documented for developers only
+                let header = item.code.split('{').next().unwrap_or("");
+                let internal = header.split_whitespace().any(|w| w == "internal" || w == "private");
+                let mut tags = vec![format!("{} {}", if internal { "@dev" } else { "@notice" }, sections[0])];
+                tags.extend(params.iter().map(|p| format!("@param {} {}", p, placeholder)));
+                if let Some(returns) = &item.returns {
+                    // Named return values are tagged by name; unnamed ones by position
+                    tags.extend(returns.split(',').map(|r| match r.split_whitespace().collect::<Vec<_>>().as_slice() {
+                        [_, .., name] => format!("@return {} {}", name, placeholder),
+                        _ => format!("@return {}", placeholder),
+                    }));
+                }
+                sections = vec![tags.join("\n")];
+            },
+            "bash" => {
+                // The header's sections follow the description without blank lines
+                let entries = |names: Vec<String>, describe: bool| if names.is_empty() {
+                    "  None".to_string()
+                } else {
+                    names.iter()
+                        .map(|n| if describe { format!("  {} {}", n, placeholder) } else { format!("  {}", n) })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                sections = vec![[
+                    sections[0].clone(),
+                    format!("Globals:\n{}", entries(crate::lang::bash::globals(&item.code), false)),
+                    format!("Arguments:\n{}", entries(crate::lang::bash::positional_arguments(&item.code), true)),
+                    format!("Outputs:\n  {}", placeholder),
+                    format!("Returns:\n  {}", placeholder),
+                ].join("\n")];
+            },
+            "swift" => {
+                let mut fields: Vec<String> = params.iter()
+                    .map(|p| format!("- Parameter {}: {}", p, placeholder))
+                    .collect();
+                if item.returns.is_some() {
+                    fields.push(format!("- Returns: {}", placeholder));
+                }
+                if !item.throws.is_empty() {
+                    fields.push(format!("- Throws: {}", placeholder));
+                }
+                if !fields.is_empty() {
+                    sections.push(fields.join("\n"));
+                }
+            },
+            "c" | "cpp" => {
+                sections[0] = format!("@brief {}", sections[0]);
+                let mut tags: Vec<String> = params.iter()
+                    .map(|p| format!("@param {} {}", p, placeholder))
+                    .collect();
+                if item.returns.is_some() {
+                    tags.push(format!("@return {}", placeholder));
+                }
+                if !tags.is_empty() {
+                    sections.push(tags.join("\n"));
+                }
+            },
+            "vbnet" | "csharp" => {
+                // XML doc elements follow each other without blank comment lines
+                let mut elements = vec![format!("<summary>\n{}\n</summary>", sections[0])];
+                elements.extend(params.iter().map(|p| format!("<param name=\"{}\">{}</param>", p, placeholder)));
+                if item.returns.is_some() {
+                    elements.push(format!("<returns>{}</returns>", placeholder));
+                }
+                sections = vec![elements.join("\n")];
+            },
+            "vbscript" | "sql" => {
+                let mut lines: Vec<String> = params.iter()
+                    .map(|p| format!("{}: {}", p, placeholder))
+                    .collect();
+                if item.returns.is_some() {
+                    lines.push(format!("Returns: {}", placeholder));
+                }
+                if !lines.is_empty() {
+                    sections.push(lines.join("\n"));
+                }
+            },
+            "cmake" => {
+                if !params.is_empty() {
+                    let args: Vec<String> = params.iter()
+                        .map(|p| format!("  {} - {}", p, placeholder))
+                        .collect();
+                    sections.push(format!("Arguments:\n{}", args.join("\n")));
+                }
+                sections.push(format!("Usage:\n  {}({})", item.name, params.join(" ")));
+            },
+            "makefile" => {
+                if !params.is_empty() {
+                    let variables: Vec<String> = params.iter()
+                        .map(|p| format!("  {} - {}", p, placeholder))
+                        .collect();
+                    sections.push(format!("Variables:\n{}", variables.join("\n")));
+                }
+                sections.push(format!("Usage:\n  make {}", item.name.split_whitespace().next().unwrap_or(&item.name)));
+            },
+            "protobuf" if item.item_type == ItemKind::Rpc => {
+                let mut types = Vec::new();
+                if let Some(request) = params.first() {
+                    types.push(format!("Request: {}", request));
+                }
+                if let Some(response) = &item.returns {
+                    types.push(format!("Response: {}", response));
+                }
+                if !types.is_empty() {
+                    sections.push(types.join("\n"));
+                }
+            },
+            // OpenAPI descriptions, CLI help, and script comments are a single line of text
+            _ => {},
+        }
+        
+        sections.join("\n\n")
+    }
+}
+
+#[async_trait]
+impl LlmClient for TemplateLlmClient {
+    async fn generate_docstrings(
+        &self, 
+        parsed_code: &ParsedCode, 
+        issues: &[DocstringIssue],
+        config: &Config
+    ) -> DocGenResult<Vec<UpdatedDocstring>> {
+        let mut updated_docstrings = Vec::new();
+        
+        // Only missing documentation is scaffolded; a placeholder is never better than existing text
+        for issue in issues.iter().filter(|i| i.issue_type == IssueKind::Missing) {
+            let item = &parsed_code.items[issue.item_index];
+            let skeleton = Self::skeleton(item, &parsed_code.language, config);
+            
+            updated_docstrings.push(UpdatedDocstring {
+                item_index: issue.item_index,
+                new_docstring: format_docstring(&skeleton, item, &parsed_code.language),
+                indentation: item.indentation.clone(),
+            });
+        }
+        
+        Ok(updated_docstrings)
+    }
+    
+    /// No model is asked, so the summary has no overview and its index keeps the
+    /// items' own summary lines
+    async fn summarize(&self, _system: &str, _prompt: &str, _config: &Config) -> DocGenResult<String> {
+        Ok(String::new())
+    }
+    
+    fn model(&self) -> String {
+        "template".to_string()
     }
 }
 
 /// Mock LLM client for testing without API calls
 pub struct MockLlmClient;
 
+impl Default for MockLlmClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MockLlmClient {
     pub fn new() -> Self {
         Self
@@ -180,7 +1128,8 @@ impl LlmClient for MockLlmClient {
     async fn generate_docstrings(
         &self, 
         parsed_code: &ParsedCode, 
-        issues: &[DocstringIssue]
+        issues: &[DocstringIssue],
+        _config: &Config
     ) -> DocGenResult<Vec<UpdatedDocstring>> {
         let mut updated_docstrings = Vec::new();
         
@@ -188,23 +1137,23 @@ impl LlmClient for MockLlmClient {
             let item = &parsed_code.items[issue.item_index];
             
             // Generate a mock docstring based on item type
-            let mock_docstring = match item.item_type.as_str() {
-                "function" => {
+            let mock_docstring = match item.item_type {
+                ItemKind::Function => {
                     let params = item.parameters.join(", ");
                     format!("Mock docstring for function {}.\nParameters: {}", item.name, params)
                 },
-                "class" => {
+                ItemKind::Class => {
                     format!("Mock docstring for class {}.", item.name)
                 },
-                "method" => {
+                ItemKind::Method => {
                     let params = item.parameters.join(", ");
                     format!("Mock docstring for method {}.\nParameters: {}", item.name, params)
                 },
                 _ => format!("Mock docstring for {} {}.", item.item_type, item.name),
             };
             
-            // Format the docstring with triple quotes
-            let formatted_docstring = format!("\"\"\"{}\"\"\"", mock_docstring);
+            // Format the docstring for the target language
+            let formatted_docstring = format_docstring(&mock_docstring, item, &parsed_code.language);
             
             updated_docstrings.push(UpdatedDocstring {
                 item_index: issue.item_index,
@@ -215,11 +1164,26 @@ impl LlmClient for MockLlmClient {
         
         Ok(updated_docstrings)
     }
+    
+    async fn summarize(&self, _system: &str, _prompt: &str, _config: &Config) -> DocGenResult<String> {
+        Ok(json!({ "overview": "Mock overview.", "descriptions": [] }).to_string())
+    }
+    
+    fn model(&self) -> String {
+        "mock".to_string()
+    }
 }
 
 #[derive(Deserialize)]
 struct ClaudeResponse {
     content: Vec<ClaudeContent>,
+    usage: Option<ClaudeUsage>,
+}
+
+#[derive(Deserialize)]
+struct ClaudeUsage {
+    input_tokens: u64,
+    output_tokens: u64,
 }
 
 #[derive(Deserialize)]
@@ -232,68 +1196,25 @@ impl LlmClient for ClaudeClient {
     async fn generate_docstrings(
         &self, 
         parsed_code: &ParsedCode, 
-        issues: &[DocstringIssue]
+        issues: &[DocstringIssue],
+        config: &Config
     ) -> DocGenResult<Vec<UpdatedDocstring>> {
-        let mut updated_docstrings = Vec::new();
-        
-        for issue in issues {
-            let item = &parsed_code.items[issue.item_index];
-            
-            // Prepare prompt
-            let prompt = format!(
-                "Generate a Python docstring for the following {} '{}'. \
-                Follow PEP 257 style guidelines.\
-                The docstring should be informative, accurate, and describe what the {} does.\
-                Include parameters, return values, and exceptions if applicable.\
-                Return ONLY the docstring text without the triple quotes or indentation.\n\n\
-                ```python\n{}\n```",
-                item.item_type, item.name, item.item_type, item.code
-            );
-            
-            // Make API request
-            let response = self.client.post("https://api.anthropic.com/v1/messages")
-                .header("x-api-key", &self.api_key)
-                .header("anthropic-version", "2023-06-01")
-                .header("Content-Type", "application/json")
-                .json(&json!({
-                    "model": "claude-3-opus-20240229",
-                    "max_tokens": 1000,
-                    "messages": [
-                        {
-                            "role": "user",
-                            "content": prompt
-                        }
-                    ]
-                }))
-                .send()
-                .await
-                .map_err(|e| DocGenError::LlmApiError(e.to_string()))?;
-            
-            // Parse response
-            if !response.status().is_success() {
-                let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-                return Err(DocGenError::LlmApiError(format!("API request failed: {}", error_text)));
-            }
-            
-            let response_json: ClaudeResponse = response.json().await
-                .map_err(|e| DocGenError::LlmApiError(format!("Failed to parse API response: {}", e)))?;
-            
-            if response_json.content.is_empty() {
-                return Err(DocGenError::LlmApiError("API response contained no content".into()));
-            }
-            
-            let docstring_text = response_json.content[0].text.trim();
-            
-            // Format the docstring with triple quotes and proper indentation
-            let formatted_docstring = format!("\"\"\"{}\"\"\"", docstring_text);
-            
-            updated_docstrings.push(UpdatedDocstring {
-                item_index: issue.item_index,
-                new_docstring: formatted_docstring,
-                indentation: item.indentation.clone(),
-            });
-        }
+        let issues = issues_within_context(self.capabilities, parsed_code, issues);
         
-        Ok(updated_docstrings)
+        // One request per item, sent concurrently as far as the request semaphore allows
+        let requests = issues.iter().map(|issue| self.document_item(parsed_code, issue, config));
+        within_budget(join_all(requests).await)
+    }
+    
+    async fn summarize(&self, system: &str, prompt: &str, config: &Config) -> DocGenResult<String> {
+        self.complete(&with_context(system, config, self.capabilities), prompt, 4000).await
+    }
+    
+    fn tokens_used(&self) -> u64 {
+        self.tokens_used.load(Ordering::Relaxed)
+    }
+    
+    fn model(&self) -> String {
+        self.model.clone()
     }
 }
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::discover;
+use crate::error::{DocGenError, DocGenResult};
+
+/// Locks older than this are considered abandoned where there is no way to check whether
+/// their process is still running
+const STALE_AFTER_SECS: u64 = 60 * 60;
+
+/// Contents of a lockfile
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct LockInfo {
+    pid: u32,
+    started_at: u64,
+    language: String,
+}
+
+/// Guard holding one lockfile per language being written in this run.
+/// The lockfiles are removed when the guard is dropped.
+pub struct RunLock {
+    paths: Vec<PathBuf>,
+}
+
+impl RunLock {
+    /// Acquire a lock for each language, so that two runs writing files of the
+    /// same language in the same repository cannot interleave their writes.
+    pub fn acquire(root: &Path, languages: &[String], force: bool) -> DocGenResult<Self> {
+        let mut lock = RunLock { paths: Vec::new() };
+        
+        for language in languages {
+            let path = root.join(format!(".docgen-{}.lock", language));
+            let info = LockInfo {
+                pid: std::process::id(),
+                started_at: now(),
+                language: language.clone(),
+            };
+            
+            // Creating the lockfile is itself the check that no other run holds it, so two
+            // runs can't both find it free
+            match create_lock(&path, &info) {
+                Ok(()) => {},
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let held = read_lock(&path);
+                    match &held {
+                        Some(held) if !force && is_stale(held) => {
+                            log::warn!("Removing stale lockfile {} (pid {})", path.display(), held.pid);
+                        },
+                        // A lockfile that can't be read may be one another run has just
+                        // created and not yet written, so it counts as held
+                        _ if !force => return Err(held_error(&path, language, held.as_ref())),
+                        _ => {},
+                    }
+                    
+                    // Only the lock that was judged is removed, not one another run put in
+                    // its place meanwhile; if another run gets there first, it holds the lock
+                    if read_lock(&path) == held {
+                        let _ = std::fs::remove_file(&path);
+                    }
+                    create_lock(&path, &info).map_err(|e| match e.kind() {
+                        ErrorKind::AlreadyExists => held_error(&path, language, read_lock(&path).as_ref()),
+                        _ => create_error(&path, e),
+                    })?;
+                },
+                Err(e) => return Err(create_error(&path, e)),
+            }
+            
+            lock.paths.push(path);
+        }
+        
+        Ok(lock)
+    }
+}
+
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Find the repository root (nearest ancestor with a `.git` entry), falling back to `start`.
+/// The canonical form of `start` is searched, so every caller gets the same root, and the
+/// same lockfile, for a directory however it was reached.
+pub fn find_repo_root(start: &Path) -> PathBuf {
+    let start = discover::canonical(start);
+    start.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(&start)
+        .to_path_buf()
+}
+
+/// Create a lockfile and write its contents, failing if it already exists
+fn create_lock(path: &Path, info: &LockInfo) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    let written = file.write_all(serde_json::to_string(info).unwrap_or_default().as_bytes());
+    if written.is_err() {
+        let _ = std::fs::remove_file(path);
+    }
+    written
+}
+
+/// Error for a lock another run holds, naming its process when the lockfile says
+fn held_error(path: &Path, language: &str, held: Option<&LockInfo>) -> DocGenError {
+    let run = match held {
+        Some(held) => format!("another docgen run (pid {})", held.pid),
+        None => "another docgen run".to_string(),
+    };
+    DocGenError::LockError(format!("{} is writing {} files; lockfile {}. Use --force to override.",
+        run, language, path.display()))
+}
+
+fn create_error(path: &Path, e: std::io::Error) -> DocGenError {
+    DocGenError::LockError(format!("failed to create lockfile {}: {}", path.display(), e))
+}
+
+fn read_lock(path: &Path) -> Option<LockInfo> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// A lock is stale when its process has exited. Where that can't be checked, a lock
+/// older than `STALE_AFTER_SECS` is taken to be abandoned; a live run keeps its lock
+/// however long it takes.
+fn is_stale(info: &LockInfo) -> bool {
+    match process_running(info.pid) {
+        Some(running) => !running,
+        None => now().saturating_sub(info.started_at) > STALE_AFTER_SECS,
+    }
+}
+
+/// Whether a process is running, or None where that can't be told
+fn process_running(pid: u32) -> Option<bool> {
+    // On Linux a missing /proc entry means the process is gone
+    let proc_dir = Path::new("/proc");
+    proc_dir.exists().then(|| proc_dir.join(pid.to_string()).exists())
+}
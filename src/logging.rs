@@ -0,0 +1,90 @@
+use colored::Colorize;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::progress;
+
+/// Targets of DocGen's own messages, which `--verbose` and `--quiet` adjust; other
+/// crates' messages are only shown from warnings up unless RUST_LOG says otherwise
+const OWN_TARGETS: &[&str] = &["docsherpa", "docgen"];
+
+/// Logger for the `log` facade that writes messages to stderr, so stdout carries only
+/// a run's results, clearing the progress bar while it writes
+struct Logger {
+    /// Level for each target prefix, or for every target when there is none
+    directives: Vec<(Option<String>, LevelFilter)>,
+}
+
+impl Logger {
+    /// Level of the most specific directive matching a target, or errors only
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.directives.iter()
+            .filter(|(prefix, _)| prefix.as_deref().is_none_or(|prefix| {
+                target == prefix || target.strip_prefix(prefix).is_some_and(|rest| rest.starts_with("::"))
+            }))
+            .max_by_key(|(prefix, _)| prefix.as_ref().map_or(0, |prefix| prefix.len()))
+            .map_or(LevelFilter::Error, |(_, level)| *level)
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+    
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let prefix = match record.level() {
+            Level::Error => "Error:".red(),
+            Level::Warn => "Warning:".yellow(),
+            Level::Info => "DocGen:".blue(),
+            Level::Debug | Level::Trace => "DocGen:".dimmed(),
+        };
+        progress::suspend(|| eprintln!("{} {}", prefix, record.args()));
+    }
+    
+    fn flush(&self) {}
+}
+
+/// Send log messages to stderr: DocGen's own from info up, from debug up with
+/// `verbose`, or only warnings and errors with `quiet`. RUST_LOG, when set, takes
+/// precedence, as a comma-separated list of levels and `target=level` directives
+/// (`debug`, `docsherpa::llm=trace,warn`).
+pub fn init(verbose: bool, quiet: bool) {
+    let own_level = match (verbose, quiet) {
+        (true, _) => LevelFilter::Debug,
+        (_, true) => LevelFilter::Warn,
+        _ => LevelFilter::Info,
+    };
+    let directives = std::env::var("RUST_LOG").ok()
+        .map(|spec| parse_directives(&spec))
+        .filter(|directives| !directives.is_empty())
+        .unwrap_or_else(|| {
+            std::iter::once((None, LevelFilter::Warn))
+                .chain(OWN_TARGETS.iter().map(|target| (Some(target.to_string()), own_level)))
+                .collect()
+        });
+    
+    let max_level = directives.iter().map(|(_, level)| *level).max().unwrap_or(LevelFilter::Error);
+    // A logger already installed by an embedding program is left in place
+    if log::set_logger(Box::leak(Box::new(Logger { directives }))).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Directives from a RUST_LOG value, skipping any that don't parse
+fn parse_directives(spec: &str) -> Vec<(Option<String>, LevelFilter)> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .filter_map(|directive| match directive.split_once('=') {
+            Some((target, level)) => Some((Some(target.trim().to_string()), level.trim().parse().ok()?)),
+            // A bare target name turns on all of its messages
+            None => match directive.parse() {
+                Ok(level) => Some((None, level)),
+                Err(_) => Some((Some(directive.to_string()), LevelFilter::Trace)),
+            },
+        })
+        .collect()
+}
@@ -1,229 +1,1366 @@
-mod config;
-mod docstring;
-mod error;
-mod llm;
-mod parser;
-mod updater;
-mod lang;
-
-use crate::lang::LanguageParser;
-
-use clap::{Parser, ArgAction, ValueEnum};
+use clap::{Parser, Subcommand, ArgAction, ValueEnum};
 use colored::Colorize;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicUsize;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 use anyhow::Result;
 
-/// Supported programming languages
-#[derive(Debug, Clone, ValueEnum)]
-enum Language {
-    /// Python language support
-    Python,
-    /// Rust language support
-    Rust,
-    /// JavaScript language support
-    JavaScript,
-    /// TypeScript language support
-    TypeScript,
-    /// Automatically detect based on file extension
-    Auto,
-}
+use docsherpa::{
+    backup, budget, campaign, capabilities, config, coverage, diff, discover, docstring, error, heatmap, history, hook,
+    interactive, io, lang, llm, lock, logging, overlay, overview, priority, progress, provenance, report, resync, review,
+    routing, rules, stats, symbols, updater, vendor, watch, Language,
+};
+use docsherpa::engine::{mode_parser, process_file, read_source};
+use docsherpa::language::{detect_language, supported_language};
 
 /// DocGen: A tool to generate or update documentation in code files using LLM
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// Files to process
-    #[clap(required = true)]
+    #[clap(subcommand)]
+    command: Option<Command>,
+    
+    /// Files, directories, or glob patterns to process. Directories are searched
+    /// recursively for files in supported languages, respecting .gitignore
+    #[clap(required_unless_present_any = ["campaign_batch", "staged"])]
     files: Vec<PathBuf>,
 
     /// Programming language mode
-    #[clap(short, long, value_enum, default_value = "auto")]
+    #[clap(short, long, value_enum, default_value = "auto", global = true)]
     language: Language,
 
-    /// LLM provider to use (openai or claude)
-    #[clap(short, long, default_value = "openai")]
-    provider: String,
+    /// LLM provider to use (openai, azure-openai, or claude) [default: openai]
+    #[clap(short, long, global = true)]
+    provider: Option<String>,
+    
+    /// Model to use, e.g. gpt-4o or claude-3-5-sonnet-20240620 (defaults per provider)
+    #[clap(short, long, global = true)]
+    model: Option<String>,
 
     /// Check mode - only report issues without making changes
     #[clap(short, long, action = ArgAction::SetTrue)]
     check: bool,
 
-    /// Verbose mode - show more details
+    /// With --check, the number of issues allowed before exiting with status 1
+    #[clap(long, value_name = "N", default_value_t = 0)]
+    max_issues: usize,
+    
+    /// With --check, which issues count towards --max-issues
+    #[clap(long, value_enum, default_value = "any")]
+    fail_on: report::FailOn,
+
+    /// With --check, check the files staged for commit (narrowing down any files given),
+    /// reading their staged content rather than the working tree
+    #[clap(long, action = ArgAction::SetTrue, requires = "check")]
+    staged: bool,
+    
+    /// Only treat documentation as outdated where the item's signature changed since
+    /// this git revision (e.g. main or HEAD~5), and flag documentation left unchanged
+    /// when a signature changed
+    #[clap(long, value_name = "REF", global = true)]
+    since: Option<String>,
+    
+    /// Read unsaved editor buffers from stdin, one JSON object per line with the file's
+    /// `path` and buffer `content`, and analyze those instead of the files on disk
+    /// (needs --check, --test, --diff, --patch-file, --review-file, or --coverage, or
+    /// `item` without --apply)
+    #[clap(long, action = ArgAction::SetTrue, global = true)]
+    overlay: bool,
+
+    /// Coverage mode - print the share of items with documentation per file, per item
+    /// type, and for the whole project, without checking or changing anything
+    #[clap(long, action = ArgAction::SetTrue)]
+    coverage: bool,
+    
+    /// Also write the coverage report to an HTML page (implies --coverage)
+    #[clap(long, value_name = "PATH")]
+    coverage_html: Option<PathBuf>,
+    
+    /// Also write a treemap of documentation debt, sizing each directory and file by its
+    /// items and coloring it by coverage, to an HTML page, or as JSON when PATH ends in
+    /// .json (implies --coverage)
+    #[clap(long, value_name = "PATH")]
+    heatmap: Option<PathBuf>,
+
+    /// Diff mode - print the proposed changes as a colored unified diff instead of
+    /// writing them to the files
+    #[clap(long, action = ArgAction::SetTrue)]
+    diff: bool,
+    
+    /// Dry-run mode - generate documentation as usual but write nothing, printing a
+    /// summary of the docstrings each file would gain and the tokens, cost, and time
+    /// each provider took
+    #[clap(long, action = ArgAction::SetTrue)]
+    dry_run: bool,
+    
+    /// Also write the proposed changes to a patch file that `git apply` accepts
+    /// (implies --diff)
+    #[clap(long, value_name = "PATH")]
+    patch_file: Option<PathBuf>,
+    
+    /// Write the proposed changes to an editable TOML review file instead of the files;
+    /// apply the accepted ones afterwards with `docgen apply <PATH>`
+    #[clap(long, value_name = "PATH")]
+    review_file: Option<PathBuf>,
+    
+    /// Copy each file aside before writing it: to a `.orig` file next to it, or with
+    /// `--backup=dir` to a directory for the run under .docgen-backups; `docgen rollback`
+    /// restores them
+    #[clap(long, value_enum, value_name = "MODE", num_args = 0..=1, require_equals = true, default_missing_value = "orig", global = true)]
+    backup: Option<backup::BackupMode>,
+    
+    /// Interactive mode - show each proposed docstring and accept, reject, or edit it
+    /// in $EDITOR before anything is written (processes one file at a time)
     #[clap(short, long, action = ArgAction::SetTrue)]
+    interactive: bool,
+
+    /// Verbose mode - show more details
+    #[clap(short, long, action = ArgAction::SetTrue, global = true)]
     verbose: bool,
     
+    /// Quiet mode - only show warnings and errors on stderr, and no progress bar
+    #[clap(short, long, action = ArgAction::SetTrue, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    
     /// Test mode - analyze files without making API calls
     #[clap(long, action = ArgAction::SetTrue)]
     test: bool,
+    
+    /// CLI help mode - generate missing help text for argparse/click options and clap fields
+    #[clap(long, action = ArgAction::SetTrue)]
+    cli_help: bool,
+    
+    /// Script mode - also add `#` comment blocks above top-level Python script statements
+    /// (argparse setup, `if __name__ == "__main__":` guards)
+    #[clap(long, action = ArgAction::SetTrue)]
+    script_comments: bool,
+    
+    /// Also document module-level constants whose values aren't self-explanatory: Python
+    /// UPPER_CASE assignments, Rust `pub const` and `pub static`, TypeScript exported consts
+    /// [default: `constants` in docsherpa.toml]
+    #[clap(long, action = ArgAction::SetTrue)]
+    constants: bool,
+    
+    /// Also document private items: Python `_name` and `__dunder__` definitions, Rust items
+    /// without `pub`, and JavaScript `#private` class members
+    /// [default: `include_private` in docsherpa.toml]
+    #[clap(long, action = ArgAction::SetTrue, conflicts_with = "public_only")]
+    include_private: bool,
+    
+    /// Only document public items, even if docsherpa.toml sets `include_private`
+    #[clap(long, action = ArgAction::SetTrue)]
+    public_only: bool,
+    
+    /// Template mode - insert skeleton docstrings (placeholder summary, Args/Returns from the
+    /// signature) without calling any model
+    #[clap(long, action = ArgAction::SetTrue)]
+    template_fix: bool,
+    
+    /// Lint mode - also check docstring summary lines (capital, imperative mood, period,
+    /// at most 79 characters) and blank-line placement, and fix violations
+    #[clap(long, action = ArgAction::SetTrue)]
+    lint: bool,
+    
+    /// Print a Markdown summary of documented items after the run, suitable for a PR description
+    #[clap(long, action = ArgAction::SetTrue)]
+    summary: bool,
+    
+    /// Python docstring style to generate and to check existing docstrings against
+    /// [default: `style` in docsherpa.toml]
+    #[clap(long, value_enum)]
+    style: Option<docstring::DocstringStyle>,
+    
+    /// Don't check this analysis rule, e.g. short-docstring (repeatable); `[rules]` in
+    /// docsherpa.toml can also change a rule's severity
+    #[clap(long, value_enum, value_name = "RULE")]
+    disable: Vec<rules::Rule>,
+    
+    /// Summary-only mode - give items without documentation just a one-line summary, using
+    /// the provider's cheapest model unless --model is given
+    #[clap(long, action = ArgAction::SetTrue)]
+    summary_only: bool,
+    
+    /// Ask for a `# Examples` section with a compilable code block in the documentation
+    /// of public Rust items, which `cargo test --doc` runs as a doctest
+    #[clap(long, action = ArgAction::SetTrue)]
+    with_examples: bool,
+    
+    /// Run `cargo test --doc` for the crate after documenting a Rust file, and put the file
+    /// back as it was if the doctests fail
+    #[clap(long, action = ArgAction::SetTrue, requires = "with_examples")]
+    verify_doctests: bool,
+    
+    /// Regenerate outdated docstrings from scratch, instead of updating only their stale
+    /// sections and keeping the rest as written
+    #[clap(long, action = ArgAction::SetTrue)]
+    replace: bool,
+    
+    /// Maximum lines per generated docstring; longer ones are regenerated more concisely
+    #[clap(long, value_name = "N")]
+    max_doc_lines: Option<usize>,
+    
+    /// Document at most N items in this run, taking them in --order
+    #[clap(long, value_name = "N")]
+    max_fixes: Option<usize>,
+    
+    /// Which items to document first when --max-fixes or a budget stops the run early
+    #[clap(long, value_enum, default_value = "source")]
+    order: priority::Order,
+    
+    /// Stop sending LLM requests once the run has used N tokens
+    #[clap(long, value_name = "N")]
+    max_tokens_per_run: Option<u64>,
+    
+    /// Stop sending LLM requests once the run's estimated cost reaches this many dollars,
+    /// e.g. 5 or '$5' (needs a model with known pricing)
+    #[clap(long, value_name = "DOLLARS", value_parser = parse_dollars)]
+    max_cost: Option<f64>,
+    
+    /// Tell the model about the types and functions from other files that each item
+    /// uses, from an index of every file given (costs one extra parse per file)
+    #[clap(long, action = ArgAction::SetTrue)]
+    project_context: bool,
+    
+    /// File describing the project, such as docs/ARCHITECTURE.md, sent with every request
+    /// so documentation uses the project's terminology [default: `context` in docsherpa.toml]
+    #[clap(long, value_name = "FILE", global = true)]
+    context: Option<PathBuf>,
+    
+    /// Write files with many issues a chunk of N items at a time, re-parsing between
+    /// chunks, so progress survives an interruption (ignored by the preview modes)
+    #[clap(long, value_name = "N")]
+    chunk_size: Option<usize>,
+    
+    /// Only process the files in this batch of the campaign planned with `docgen campaign`
+    /// (all of them when no files are given)
+    #[clap(long, value_name = "N")]
+    campaign_batch: Option<usize>,
+    
+    /// Campaign tracking file [default: docsherpa-campaign.toml in the repository root]
+    #[clap(long, value_name = "PATH", global = true)]
+    campaign_file: Option<PathBuf>,
+    
+    /// Ignore lockfiles held by other runs writing the same languages
+    #[clap(long, action = ArgAction::SetTrue)]
+    force: bool,
+    
+    /// Issue-tracker URL template for linking issues, e.g. "https://tracker/new?title={title}"
+    /// (also read from DOCGEN_ISSUE_URL_TEMPLATE)
+    #[clap(long, value_name = "TEMPLATE")]
+    issue_url_template: Option<String>,
+    
+    /// Additional directory to treat as third-party code and skip (repeatable; also read
+    /// comma-separated from DOCGEN_VENDOR_DIRS)
+    #[clap(long = "vendor-dir", value_name = "DIR")]
+    vendor_dirs: Vec<String>,
+    
+    /// Process files in vendored and third-party directories (vendor/, site-packages/, target/, ...)
+    #[clap(long, action = ArgAction::SetTrue)]
+    include_vendored: bool,
+    
+    /// Only process files matching this glob, e.g. "**/*.py" (repeatable)
+    #[clap(long, value_name = "GLOB")]
+    include: Vec<String>,
+    
+    /// Skip files matching this glob, e.g. "**/tests/**" (repeatable)
+    #[clap(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+    
+    /// Don't read or update the signature history used to flag items changed since last documented
+    #[clap(long, action = ArgAction::SetTrue)]
+    no_history: bool,
+    
+    /// Record which docstrings were generated (model, time, content hash) in
+    /// .docgen-generated.json, and never overwrite documentation written or edited by hand
+    #[clap(long, action = ArgAction::SetTrue)]
+    track_generated: bool,
+    
+    /// Number of files, and LLM requests, to process at once [default: 1]
+    #[clap(short, long, value_name = "N", global = true)]
+    jobs: Option<usize>,
+    
+    /// Append local usage statistics for this run to a JSON Lines file
+    /// (also read from DOCGEN_STATS_FILE); nothing is sent over the network
+    #[clap(long, value_name = "PATH")]
+    stats_file: Option<PathBuf>,
+    
+    /// Emit newline-delimited JSON progress events on stderr for tool wrappers
+    #[clap(long, action = ArgAction::SetTrue, global = true)]
+    progress_json: bool,
+    
+    /// How to group the issue summary table
+    #[clap(long, value_enum, default_value = "rule")]
+    group_by: report::GroupBy,
+    
+    /// Output format: colored text, a JSON report of every issue (with the generated
+    /// documentation, when there is some) for CI, or aligned plain text without colors
+    /// or symbols for screen readers
+    #[clap(long, value_enum, default_value = "text", conflicts_with = "interactive")]
+    format: report::OutputFormat,
+}
+
+/// Subcommands for working on a single item or a reviewed set of changes
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Document only the item containing a line, e.g. the function under an editor's cursor
+    Item {
+        /// File containing the item
+        #[clap(long)]
+        file: PathBuf,
+        
+        /// Line number (1-based) anywhere inside the item
+        #[clap(long)]
+        line: usize,
+        
+        /// Write the edit to the file instead of printing it as a diff
+        #[clap(long, action = ArgAction::SetTrue)]
+        apply: bool,
+    },
+    
+    /// Apply the accepted changes from a review file written with --review-file
+    Apply {
+        /// Review file with `accept = true` set on the changes to apply
+        review_file: PathBuf,
+    },
+    
+    /// Split the documentation debt of a project into batches to document one run at a
+    /// time, written to a tracking file for `--campaign-batch`
+    Campaign {
+        /// Files, directories, or glob patterns to plan the campaign over
+        #[clap(required_unless_present = "status")]
+        files: Vec<PathBuf>,
+        
+        /// Number of batches to split the work into
+        #[clap(long, value_name = "N", default_value_t = 4)]
+        batches: usize,
+        
+        /// Show how many issues each batch of the planned campaign still has instead
+        #[clap(long, action = ArgAction::SetTrue)]
+        status: bool,
+    },
+    
+    /// After a rename refactor, rewrite just the parameter and function names that
+    /// documentation still refers to by their old names
+    Resync {
+        /// Files, directories, or glob patterns to re-sync
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+        
+        /// Print the edits as a diff instead of writing them to the files
+        #[clap(long, action = ArgAction::SetTrue)]
+        diff: bool,
+    },
+    
+    /// Install a git pre-commit hook that runs `--check --staged` and blocks commits
+    /// leaving items without documentation
+    InstallHook {
+        /// Add an entry to the repository's .pre-commit-hooks.yaml for the pre-commit
+        /// framework instead of writing a git hook
+        #[clap(long, action = ArgAction::SetTrue)]
+        pre_commit: bool,
+        
+        /// Replace a pre-commit hook that wasn't installed by docgen
+        #[clap(long, action = ArgAction::SetTrue)]
+        force: bool,
+    },
+    
+    /// Check files again each time they're saved, printing the results of each save,
+    /// until interrupted
+    Watch {
+        /// Files or directories to watch; directories are watched recursively
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+        
+        /// Document the missing or outdated items of saved files instead of only
+        /// checking them
+        #[clap(long, action = ArgAction::SetTrue)]
+        fix: bool,
+        
+        /// Milliseconds to wait after a file is written for further writes, so each
+        /// save is processed once
+        #[clap(long, value_name = "MS", default_value_t = 300)]
+        debounce: u64,
+    },
+    
+    /// Restore the files the last run with --backup wrote from their backups
+    Rollback {
+        /// Also restore files that were edited after the run wrote them
+        #[clap(long, action = ArgAction::SetTrue)]
+        force: bool,
+    },
+    
+    /// Write a Markdown overview of a package or module: a few paragraphs on what it's
+    /// for, then an index of its API with a one-line description of each item
+    Summarize {
+        /// Directory of the package or module to summarize
+        dir: PathBuf,
+        
+        /// Markdown file to write the overview to, or - to print it [default: API.md in the
+        /// directory]
+        #[clap(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
 }
 
+/// Exit with 0 when the run succeeded (and `--check` found no more issues than
+/// allowed), 1 when `--check` found too many issues, 3 to 6 when the provider rejected
+/// the API key, the quota is used up, the model wasn't found, or the content filter
+/// blocked a request, and 2 on other errors
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::from(e.downcast_ref::<error::DocGenError>().map_or(2, error::DocGenError::exit_code))
+        },
+    }
+}
+
+async fn run() -> Result<ExitCode> {
     // Load environment variables from .env file if present
     dotenv::dotenv().ok();
     
     // Parse command line arguments
     let args = Args::parse();
+    logging::init(args.verbose, args.quiet);
+    
+    let root = lock::find_repo_root(&std::env::current_dir()?);
     
-    // Create configuration
+    // Create configuration: command-line flags take precedence over the project's
+    // docsherpa.toml (or .docgenrc), which takes precedence over the defaults
+    let project_config = match config::Config::find_file(&root) {
+        Some(path) => config::Config::from_file(&path)?,
+        None => config::Config::default(),
+    };
     let config = config::Config {
-        provider: args.provider,
         check_only: args.check,
         verbose: args.verbose,
         test_mode: args.test,
+        diff_only: args.diff || args.patch_file.is_some(),
+        dry_run: args.dry_run,
+        staged: args.staged,
+        since: args.since.clone(),
+        overlay: if args.overlay {
+            Some(Arc::new(overlay::Overlay::from_reader(std::io::stdin().lock())?))
+        } else {
+            None
+        },
+        review_file: args.review_file.clone(),
+        interactive: args.interactive,
+        cli_help: args.cli_help,
+        script_comments: args.script_comments,
+        template_fix: args.template_fix,
+        lint: args.lint,
+        summary_only: args.summary_only,
+        with_examples: args.with_examples,
+        verify_doctests: args.verify_doctests,
+        replace: args.replace,
+        max_doc_lines: args.max_doc_lines,
+        chunk_size: args.chunk_size.map(|size| size.max(1)),
+        order: args.order,
+        fix_budget: args.max_fixes.map(|n| Arc::new(AtomicUsize::new(n))),
+        budget: Arc::new(budget::Budget::new(args.max_tokens_per_run, args.max_cost)),
+        backups: args.backup.map(|mode| Arc::new(backup::Backups::new(&root, mode))),
+        issue_url_template: args.issue_url_template
+            .or_else(|| std::env::var("DOCGEN_ISSUE_URL_TEMPLATE").ok()),
+        stats_file: args.stats_file
+            .or_else(|| std::env::var("DOCGEN_STATS_FILE").ok().map(PathBuf::from)),
+        progress_json: args.progress_json,
+        group_by: args.group_by,
+        format: args.format,
+        vendor_dirs: args.vendor_dirs.iter()
+            .cloned()
+            .chain(std::env::var("DOCGEN_VENDOR_DIRS").ok().into_iter()
+                .flat_map(|dirs| dirs.split(',').map(|d| d.trim().to_string()).collect::<Vec<_>>()))
+            .filter(|d| !d.is_empty())
+            .collect(),
+        ..project_config.merge_cli(config::CliOverrides {
+            provider: args.provider,
+            model: args.model,
+            jobs: args.jobs,
+            style: args.style,
+            constants: args.constants,
+            include_private: match (args.include_private, args.public_only) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            exclude: args.exclude,
+            disable: args.disable,
+            context: match &args.context {
+                Some(path) => Some(io::read_text(path).map_err(|e| error::DocGenError::ConfigError(
+                    format!("context file {}: {}", path.display(), e)))?),
+                None => None,
+            },
+        })
     };
+    let overrides = language_overrides(&config)?;
+    
+    // Plain output must read the same to a screen reader as on screen
+    if config.format == report::OutputFormat::Plain {
+        colored::control::set_override(false);
+    }
+    
+    if let Some(base) = &config.since {
+        discover::verify_revision(&root, base)?;
+    }
+    
+    if let Some(context) = &config.context {
+        let tokens = capabilities::estimate_tokens(context);
+        if tokens > llm::MAX_CONTEXT_TOKENS {
+            log::warn!("The project context is about {} tokens; only the first {} are sent with each request",
+                tokens, llm::MAX_CONTEXT_TOKENS);
+        }
+    }
+    
+    if let Some(overlay) = &config.overlay {
+        log::debug!("Read {} unsaved buffers", overlay.len());
+        if config.staged || config.interactive {
+            anyhow::bail!("--overlay can't be combined with --staged or --interactive");
+        }
+        
+        // Buffers are never written over the files they stand in for; the editor
+        // applies the edits itself
+        let writes = match &args.command {
+            Some(Command::Item { apply, .. }) => *apply,
+            Some(_) => false,
+            None => !(config.check_only || config.test_mode || config.diff_only || config.dry_run || config.review_file.is_some()
+                || args.coverage || args.coverage_html.is_some() || args.heatmap.is_some()),
+        };
+        if writes {
+            anyhow::bail!("--overlay doesn't write files; use --check, --diff, --dry-run, --patch-file, or --review-file \
+                (or `item` without --apply) and apply the edits in the editor");
+        }
+    }
     
-    if args.verbose {
-        println!("{}", "DocGen: Documentation Generator".green().bold());
-        println!("{} {:?}", "Processing files:".blue(), args.files);
+    if config.interactive {
+        interactive::ensure_terminal()?;
     }
     
-    // Process each file
-    for file_path in &args.files {
+    if let Some(Command::Item { file, line, apply }) = &args.command {
         let language = match args.language {
-            Language::Auto => detect_language(file_path),
+            Language::Auto => file_language(file, &overrides),
             _ => args.language.clone(),
         };
-        
-        if config.verbose {
-            println!("Detected language: {:?}", language);
+        let routed = route_files(&[(file.clone(), language.clone())], &root, &config)?;
+        document_item(file, *line, &language, *apply, args.force, &routed[0]).await?;
+        save_backups(&config)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    
+    if let Some(Command::Apply { review_file }) = &args.command {
+        apply_review(review_file, args.force, &config)?;
+        save_backups(&config)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    
+    if let Some(Command::InstallHook { pre_commit, force }) = &args.command {
+        install_hook(&root, *pre_commit, *force)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    
+    if let Some(Command::Resync { files, diff }) = &args.command {
+        let files = resolve_files(files, &[], false, &args.language, &overrides, &root, &config)?;
+        resync_files(&files, &root, *diff, !args.no_history, args.force, &config)?;
+        save_backups(&config)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    
+    if let Some(Command::Rollback { force }) = &args.command {
+        rollback(&root, *force)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    
+    if let Some(Command::Summarize { dir, output }) = &args.command {
+        let files = resolve_files(std::slice::from_ref(dir), &[], false, &args.language, &overrides, &root, &config)?;
+        let output = output.clone().unwrap_or_else(|| dir.join("API.md"));
+        summarize(dir, &files, &output, &config).await?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    
+    if let Some(Command::Watch { files, fix, debounce }) = &args.command {
+        watch_files(files, *fix, Duration::from_millis(*debounce), &args.language, &overrides, &root, &config).await?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    
+    let campaign_path = args.campaign_file.clone()
+        .unwrap_or_else(|| root.join(campaign::CAMPAIGN_FILE_NAME));
+    if let Some(Command::Campaign { files, batches, status }) = &args.command {
+        if *status {
+            campaign_status(&campaign_path, &root, &overrides, &config)?;
+        } else {
+            let files = resolve_files(files, &[], false, &args.language, &overrides, &root, &config)?;
+            plan_campaign(&files, *batches, &campaign_path, &root, &config)?;
         }
+        return Ok(ExitCode::SUCCESS);
+    }
+    
+    // A campaign batch, or the files staged for commit, name the files to process, or
+    // narrow down the ones given
+    let batch_files = match args.campaign_batch {
+        Some(number) => Some(campaign::Campaign::load(&campaign_path)?.batch(number)?.files.clone()),
+        None => None,
+    };
+    let staged_files = if args.staged { Some(discover::staged_files(&root)?) } else { None };
+    let patterns = match batch_files.as_ref().or(staged_files.as_ref()) {
+        Some(named_files) if args.files.is_empty() => {
+            let cwd = std::env::current_dir()?;
+            named_files.iter()
+                .map(|file| root.join(file))
+                .filter(|path| path.exists())
+                .filter(|path| !matches!(args.language, Language::Auto)
+                    || overrides.iter().any(|(glob, _)| glob.matches(path))
+                    || supported_language(path).is_some())
+                .map(|path| path.strip_prefix(&cwd).map(Path::to_path_buf).unwrap_or(path))
+                .collect()
+        },
+        _ => args.files.clone(),
+    };
+    let mut files = resolve_files(&patterns, &args.include, args.include_vendored, &args.language, &overrides, &root, &config)?;
+    for named_files in [&batch_files, &staged_files].into_iter().flatten() {
+        files.retain(|(file_path, _)| named_files.contains(&root_relative(file_path, &root)));
+    }
+    
+    // JSON and plain output replace the progress printed to stdout; plain output
+    // keeps the messages saying where results were written
+    let text_output = config.format == report::OutputFormat::Text;
+    let json_output = config.format == report::OutputFormat::Json;
+    
+    if args.coverage || args.coverage_html.is_some() || args.heatmap.is_some() {
+        coverage_report(&files, args.coverage_html.as_deref(), args.heatmap.as_deref(), &config)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    
+    // Index the project's symbols up front, since every file's prompts may refer to them
+    let config = if args.project_context && !config.check_only && !config.test_mode {
+        let symbols = symbol_index(&files, &root, &config)?;
+        config::Config { symbols: Some(Arc::new(symbols)), ..config }
+    } else {
+        config
+    };
+    
+    // Prevent concurrent runs from interleaving writes to files of the same language
+    let _lock = if config.check_only || config.test_mode || config.diff_only || config.dry_run || config.review_file.is_some() {
+        None
+    } else {
+        let mut languages: Vec<String> = files.iter().map(|(_, l)| l.name().to_string()).collect();
+        languages.sort();
+        languages.dedup();
+        Some(lock::RunLock::acquire(&root, &languages, args.force)?)
+    };
+    
+    // Signatures of documented items from previous runs, shared by the file tasks
+    let history_path = history::History::path(&root);
+    let history = if args.no_history { None } else { Some(Arc::new(Mutex::new(history::History::load(&history_path)))) };
+    
+    // Docstrings generated by earlier runs, which are the only ones that may be rewritten
+    let provenance_path = provenance::Provenance::path(&root);
+    let provenance = if args.track_generated {
+        Some(Arc::new(Mutex::new(provenance::Provenance::load(&provenance_path))))
+    } else {
+        None
+    };
+    
+    // Process up to `--jobs` files at once. Each task buffers its output, which is
+    // printed in file order as the tasks finish. Interactive prompts need the terminal
+    // to themselves, so files are then taken one at a time.
+    let started = Instant::now();
+    if !args.quiet && !config.progress_json && !config.interactive {
+        progress::start_bar(files.len());
+    }
+    let file_slots = Arc::new(Semaphore::new(if config.interactive { 1 } else { config.jobs }));
+    let file_configs = route_files(&files, &root, &config)?;
+    let mut tasks = Vec::new();
+    for ((file_path, language), config) in files.iter().cloned().zip(file_configs) {
+        let file_slots = file_slots.clone();
+        let history = history.clone();
+        let provenance = provenance.clone();
+        let file_key = discover::normalize(&root_relative(&file_path, &root));
         
-        process_file(file_path, &language, &config).await?;
+        tasks.push(tokio::spawn(async move {
+            let _slot = file_slots.acquire_owned().await.expect("file semaphore is never closed");
+            progress::emit(&config, progress::ProgressEvent::FileStarted { file: &file_path });
+            let mut output = Vec::new();
+            let history = history.as_deref().map(|h| (h, file_key.as_str()));
+            let provenance = provenance.as_deref().map(|p| (p, file_key.as_str()));
+            let result = process_file(&file_path, &language, &config, history, provenance, &mut output).await;
+            (output, result)
+        }));
+    }
+    
+    let mut reports = Vec::new();
+    let mut tasks = tasks.into_iter();
+    for (file_path, _) in &files {
+        let task = tasks.next().expect("one task per file");
+        let (output, result) = task.await?;
+        if text_output {
+            progress::suspend(|| std::io::stdout().write_all(&output))?;
+        }
+        progress::file_finished(file_path);
+        match result {
+            Ok(report) => reports.push(report),
+            Err(e) => {
+                progress::emit(&config, progress::ProgressEvent::Error { file: Some(file_path), message: e.to_string() });
+                progress::finish_bar();
+                tasks.for_each(|task| task.abort());
+                // Files already written can still be rolled back
+                save_backups(&config)?;
+                return Err(e);
+            },
+        }
+    }
+    
+    progress::finish_bar();
+    progress::emit(&config, progress::ProgressEvent::RunFinished {
+        files: reports.len(),
+        items: reports.iter().map(|r| r.documented.len()).sum(),
+    });
+    
+    // A dry run leaves the project's files as they were, DocGen's own included
+    if let Some(history) = history.as_ref().filter(|_| !config.dry_run) {
+        history.lock().expect("history lock poisoned").save(&history_path)?;
+    }
+    if let Some(provenance) = provenance.as_ref().filter(|_| !config.dry_run) {
+        provenance.lock().expect("provenance lock poisoned").save(&provenance_path)?;
+    }
+    save_backups(&config)?;
+    
+    // Show how the requests were spread across the provider pool
+    if let Some(scheduler) = &config.scheduler {
+        log::debug!("Provider pool:\n  {}", scheduler.summary().join("\n  "));
+    }
+    
+    // Say what an exhausted budget left undone, so the next run can pick it up
+    let unfinished: usize = reports.iter().map(|r| r.unfinished).sum();
+    if unfinished > 0 {
+        let files: Vec<String> = reports.iter()
+            .filter(|r| r.unfinished > 0)
+            .map(|r| format!("  {} {}: {} items", "→".yellow(), r.path.display(), r.unfinished))
+            .collect();
+        log::warn!("Budget reached after {} tokens (about ${:.4}); {} items were left undocumented:\n{}",
+            config.budget.tokens(),
+            config.budget.cost(),
+            unfinished,
+            files.join("\n"));
+    }
+    
+    match config.format {
+        report::OutputFormat::Json => println!("{}", report::json(&reports)),
+        report::OutputFormat::Plain => print!("{}", report::plain(&reports)),
+        report::OutputFormat::Text if config.group_by == report::GroupBy::File => {
+            println!("\n{}", report::files_table(&reports));
+        },
+        report::OutputFormat::Text => {},
+    }
+    
+    if config.dry_run && !json_output {
+        println!("\n{} Dry run; no files were written\n{}",
+            "DocGen:".blue(),
+            report::dry_run_summary(&reports, &config.budget, started.elapsed()));
+    }
+    
+    if let Some(review_path) = &config.review_file {
+        let review_file = review::ReviewFile {
+            cli_help: config.cli_help,
+            script_comments: config.script_comments,
+            constants: config.constants,
+            items: reports.iter().flat_map(|r| r.proposed.iter().cloned()).collect(),
+        };
+        review_file.save(review_path)?;
+        log::info!("Wrote {} proposed changes to {}; set `accept = true` on the ones to keep, then run `docgen apply {}`",
+            review_file.items.len(),
+            review_path.display(),
+            review_path.display());
+    }
+    
+    if let Some(patch_file) = &args.patch_file {
+        let patch: String = reports.iter().map(|r| r.diff.as_str()).collect();
+        std::fs::write(patch_file, patch)?;
+        log::info!("Wrote proposed changes to {}", patch_file.display());
+    }
+    
+    if args.summary && text_output && !config.check_only && !config.test_mode {
+        println!("\n{}", report::change_summary(&reports));
+    }
+    
+    if let Some(stats_file) = &config.stats_file {
+        if !config.test_mode {
+            stats::record_run(stats_file, &config, &reports)?;
+        }
     }
     
+    // In check mode, fail when more issues of the kinds asked for were found than allowed
+    if config.check_only {
+        let failing = report::count_failing(&reports, args.fail_on);
+        if failing > args.max_issues {
+            log::error!("{} documentation issues found, more than the {} allowed by --max-issues", failing, args.max_issues);
+            return Ok(ExitCode::from(1));
+        }
+    }
+    
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Parse a dollar amount for `--max-cost`, with or without a leading `$`
+fn parse_dollars(value: &str) -> std::result::Result<f64, String> {
+    let dollars: f64 = value.trim().trim_start_matches('$').parse()
+        .map_err(|_| format!("'{}' is not a dollar amount", value))?;
+    if dollars > 0.0 {
+        Ok(dollars)
+    } else {
+        Err("the cost cap must be more than $0".to_string())
+    }
+}
+
+/// Expand directories and glob patterns into the files to process, with the language
+/// of each, leaving out third-party code. When walking directories only files in a
+/// supported language are picked up, unless a language was given explicitly.
+fn resolve_files(
+    patterns: &[PathBuf],
+    include: &[String],
+    include_vendored: bool,
+    language: &Language,
+    overrides: &[(discover::Glob, Language)],
+    root: &Path,
+    config: &config::Config,
+) -> Result<Vec<(PathBuf, Language)>> {
+    let paths = discover::expand(patterns, root, include, &config.exclude, |path| {
+        !matches!(language, Language::Auto)
+            || overrides.iter().any(|(glob, _)| glob.matches(path))
+            || supported_language(path).is_some()
+    })?;
+    
+    log::debug!("Processing files: {:?}", paths);
+    
+    Ok(paths.iter()
+        .filter(|file_path| {
+            let skip = !include_vendored && vendor::is_third_party(file_path, &config.vendor_dirs);
+            if skip {
+                log::debug!("Skipping third-party file: {}", file_path.display());
+            }
+            !skip
+        })
+        .map(|file_path| {
+            let language = match language {
+                Language::Auto => file_language(file_path, overrides),
+                _ => language.clone(),
+            };
+            (file_path.clone(), language)
+        })
+        .collect())
+}
+
+/// Process files again each time they're saved (`watch`): check them, or document
+/// them with `fix`, printing each file's results as it's done. Files are picked out of
+/// the watched paths as they are for a run, so ignored and third-party files, and files
+/// in languages DocGen doesn't know, are left alone. Runs until interrupted.
+async fn watch_files(
+    paths: &[PathBuf],
+    fix: bool,
+    debounce: Duration,
+    language: &Language,
+    overrides: &[(discover::Glob, Language)],
+    root: &Path,
+    config: &config::Config,
+) -> Result<()> {
+    let config = config::Config { check_only: !fix, ..config.clone() };
+    let mut watcher = watch::Watcher::new(paths)?;
+    let watched: Vec<String> = paths.iter().map(|path| path.display().to_string()).collect();
+    log::info!("Watching {} for changes; press Ctrl-C to stop", watched.join(", "));
+    
+    // Contents each file had when it was last processed, so saves that change nothing,
+    // and the writes documenting a file makes, don't process it again
+    let mut processed: HashMap<PathBuf, String> = HashMap::new();
+    
+    while let Some(changed) = watcher.changed(debounce).await {
+        let changed: HashSet<PathBuf> = changed.iter().map(|path| discover::canonical(path)).collect();
+        let files: Vec<(PathBuf, Language)> = resolve_files(paths, &[], false, language, overrides, root, &config)?
+            .into_iter()
+            .filter(|(file_path, _)| changed.contains(&discover::canonical(file_path)))
+            .filter(|(file_path, _)| std::fs::read_to_string(file_path).ok().as_ref() != processed.get(file_path))
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        
+        // Prevent other runs from interleaving writes with the files being documented
+        let _lock = if fix {
+            let mut languages: Vec<String> = files.iter().map(|(_, l)| l.name().to_string()).collect();
+            languages.sort();
+            languages.dedup();
+            Some(lock::RunLock::acquire(root, &languages, false)?)
+        } else {
+            None
+        };
+        
+        let file_configs = route_files(&files, root, &config)?;
+        for ((file_path, language), file_config) in files.iter().zip(file_configs) {
+            let mut output = Vec::new();
+            let result = process_file(file_path, language, &file_config, None, None, &mut output).await;
+            std::io::stdout().write_all(&output)?;
+            match result {
+                Ok(report) if report.issues.is_empty() => {
+                    println!("{} {} is fully documented", "✓".green(), file_path.display());
+                },
+                Ok(_) => {},
+                // One file failing, e.g. saved half-written, doesn't stop the watch
+                Err(e) => log::error!("{}: {:?}", file_path.display(), e),
+            }
+            if let Ok(content) = std::fs::read_to_string(file_path) {
+                processed.insert(file_path.clone(), content);
+            }
+        }
+        // Every save's backups are kept, so rolling back undoes the whole watch
+        if let Some(backups) = &config.backups {
+            backups.save()?;
+        }
+    }
     Ok(())
 }
 
-/// Detect programming language from file extension
-fn detect_language(file_path: &PathBuf) -> Language {
-    match file_path.extension().and_then(|e| e.to_str()) {
-        Some("py") => Language::Python,
-        Some("rs") => Language::Rust,
-        Some("js") => Language::JavaScript,
-        Some("ts") | Some("tsx") => Language::TypeScript,
-        _ => {
-            eprintln!("Warning: Could not detect language for {}. Defaulting to Python.", 
-                     file_path.display());
-            Language::Python
+/// Install the pre-commit hook, as a git hook or a pre-commit framework entry
+fn install_hook(root: &Path, pre_commit: bool, force: bool) -> Result<()> {
+    if pre_commit {
+        match hook::install_pre_commit_entry(root)? {
+            Some(path) => log::info!("Added a docgen hook to {}", path.display()),
+            None => log::info!("{} already has a docgen hook", hook::PRE_COMMIT_HOOKS_FILE),
         }
+    } else {
+        let path = hook::install_git_hook(root, force)?;
+        log::info!("Installed a pre-commit hook at {}", path.display());
+    }
+    Ok(())
+}
+
+/// Record the backups the run took (`--backup`) as the ones `docgen rollback` restores
+fn save_backups(config: &config::Config) -> Result<()> {
+    let count = match &config.backups {
+        Some(backups) => backups.save()?,
+        None => return Ok(()),
+    };
+    if count > 0 {
+        log::info!("Backed up {} files; undo the changes with `docgen rollback`", count);
+    }
+    Ok(())
+}
+
+/// Restore the files the last run with `--backup` wrote, reporting the ones that were
+/// edited since, which are only restored when forced, and the ones whose backup is gone
+fn rollback(root: &Path, force: bool) -> Result<()> {
+    let rollback = match backup::rollback(root, force)? {
+        Some(rollback) => rollback,
+        None => {
+            println!("{} No backups to roll back", "DocGen:".yellow());
+            return Ok(());
+        },
+    };
+    for file_path in &rollback.restored {
+        println!("  {} {}", "↺".green(), file_path.display());
+    }
+    for file_path in &rollback.edited {
+        println!("  {} {} was edited since it was written; use --force to restore it anyway",
+            "!".yellow(),
+            file_path.display());
+    }
+    for file_path in &rollback.missing {
+        println!("  {} {} has no backup left to restore", "✗".red(), file_path.display());
     }
+    println!("{} Restored {} files", "DocGen:".green(), rollback.restored.len());
+    Ok(())
 }
 
-async fn process_file(file_path: &PathBuf, _language: &Language, config: &config::Config) -> Result<()> {
-    if config.verbose {
-        println!("\n{} {}", "Processing:".blue(), file_path.display());
+/// Configuration for each file after the project's provider routes. When the run sends
+/// requests to a model, where each file's requests go is appended to the routing log
+/// before any file is processed.
+fn route_files(files: &[(PathBuf, Language)], root: &Path, config: &config::Config) -> Result<Vec<Arc<config::Config>>> {
+    if config.routing.is_empty() {
+        let shared = Arc::new(config.clone());
+        return Ok(files.iter().map(|_| shared.clone()).collect());
     }
     
-    // Read file content
-    let content = std::fs::read_to_string(file_path)?;
+    let mut configs = Vec::new();
+    let mut decisions = Vec::new();
+    for (file_path, _) in files {
+        let (routed, decision) = config.routing.apply(config, &root_relative(file_path, root));
+        if let Some(rule) = &decision.rule {
+            log::debug!("Routing {} to {} (matches {})", file_path.display(), decision.provider, rule);
+        }
+        configs.push(Arc::new(routed));
+        decisions.push(decision);
+    }
     
-    // Parse code based on language
-    // For now, only Python is fully implemented
-    let parser = lang::python::PythonParser::new();
+    if !config.check_only && !config.test_mode && !config.template_fix {
+        routing::Routing::record(&config.routing.log_path(root), &decisions)?;
+    }
+    Ok(configs)
+}
+
+/// Path of a file relative to the repository root, as recorded in the history and
+/// campaign files. It is `/`-separated, so those files can be shared between Windows
+/// and other systems.
+fn root_relative(file_path: &Path, root: &Path) -> PathBuf {
+    let canonical = discover::canonical(file_path);
+    let relative = canonical.strip_prefix(root).unwrap_or(file_path);
+    PathBuf::from(discover::normalize(relative))
+}
+
+/// Compile the project configuration's language overrides
+fn language_overrides(config: &config::Config) -> Result<Vec<(discover::Glob, Language)>> {
+    config.language_overrides.iter()
+        .map(|(glob, name)| {
+            let language = Language::from_str(name, true)
+                .map_err(|_| error::DocGenError::ConfigError(format!("Unknown language '{}' for '{}'", name, glob)))?;
+            Ok((discover::Glob::new(glob)?, language))
+        })
+        .collect()
+}
+
+/// Language of a file from the first matching override, or else its extension
+fn file_language(file_path: &Path, overrides: &[(discover::Glob, Language)]) -> Language {
+    overrides.iter()
+        .find(|(glob, _)| glob.matches(file_path))
+        .map(|(_, language)| language.clone())
+        .unwrap_or_else(|| detect_language(file_path))
+}
+
+/// Document the innermost item containing `line` and print the edit as a diff, or
+/// write it to the file when `apply` is set
+async fn document_item(
+    file_path: &PathBuf,
+    line: usize,
+    language: &Language,
+    apply: bool,
+    force: bool,
+    config: &config::Config,
+) -> Result<()> {
+    let content = read_source(file_path, config)?;
+    // The item at the line is documented whether or not it is public
+    let parser = mode_parser(language, file_path, config.cli_help, config.script_comments, config.constants, true, config)?;
     let parsed_code = parser.parse(&content)?;
     
-    // Future implementation when tree-sitter is fixed:
-    // let parsed_code = match language {
-    //     Language::Python => {
-    //         let parser = lang::python::PythonParser::new();
-    //         parser.parse(&content)?
-    //     },
-    //     Language::Rust => {
-    //         let parser = lang::rust::RustParser::new();
-    //         parser.parse(&content)?
-    //     },
-    //     Language::JavaScript => {
-    //         let parser = lang::javascript::JavaScriptParser::new();
-    //         parser.parse(&content)?
-    //     },
-    //     Language::TypeScript => {
-    //         let parser = lang::typescript::TypeScriptParser::new();
-    //         parser.parse(&content)?
-    //     },
-    //     Language::Auto => unreachable!("Language should be resolved by this point"),
-    // };
-    
-    // Analyze docstrings
-    let docstring_issues = docstring::analyze(&parsed_code)?;
-    
-    if docstring_issues.is_empty() {
-        if config.verbose {
-            println!("{} {}", "✓".green(), "All items are properly documented".green());
-        }
+    // Nested items (methods, fields) are preferred over the items enclosing them
+    let (item_index, item) = parsed_code.items.iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            let end = item.line_number + item.code.lines().count().max(1) - 1;
+            (item.line_number..=end).contains(&line)
+        })
+        .min_by_key(|(_, item)| item.code.lines().count())
+        .ok_or_else(|| error::DocGenError::ConfigError(
+            format!("No documentable item contains line {} of {}", line, file_path.display())))?;
+    
+    log::debug!("Documenting {} '{}' at line {}", item.item_type, item.name, item.line_number);
+    
+    // The item is documented on request, whether or not the analyzer flags it
+    let issue = docstring::DocstringIssue {
+        item_type: item.item_type,
+        name: item.name.clone(),
+        line_number: item.line_number,
+        issue_type: if item.existing_docstring.is_some() { docstring::IssueKind::Outdated } else { docstring::IssueKind::Missing },
+        item_index,
+        reasons: Vec::new(),
+    };
+    
+    let llm_client = llm::get_client(config)?;
+    let updated_docstrings = llm_client.generate_docstrings(&parsed_code, &[issue], config).await?;
+    for updated in &updated_docstrings {
+        progress::emit(config, progress::ProgressEvent::ItemGenerated {
+            file: file_path,
+            item_type: item.item_type,
+            name: &item.name,
+            line: item.line_number,
+            lines: updated.line_count(),
+        });
+    }
+    let updated_content = parser.update_content(&content, &updated_docstrings)?;
+    
+    if apply {
+        let root = lock::find_repo_root(&std::env::current_dir()?);
+        let _lock = lock::RunLock::acquire(&root, &[language.name().to_string()], force)?;
+        updater::write_file(file_path, &updated_content, config)?;
+        progress::emit(config, progress::ProgressEvent::FileWritten { file: file_path, items: updated_docstrings.len() });
+        log::debug!("Updated documentation in {}", file_path.display());
+    } else {
+        print!("{}", diff::unified(file_path, &content, &updated_content));
+    }
+    
+    Ok(())
+}
+
+/// Apply the accepted changes from a review file, file by file. Items that can no
+/// longer be found, because they were renamed or removed since the review file was
+/// written, are reported and skipped.
+fn apply_review(review_path: &Path, force: bool, config: &config::Config) -> Result<()> {
+    let review_file = review::ReviewFile::load(review_path)?;
+    let files = review_file.accepted_by_file();
+    if files.is_empty() {
+        log::warn!("No accepted changes in {}", review_path.display());
         return Ok(());
     }
     
-    // Report issues
-    println!("{} found {} documentation issues in {}", 
-        "DocGen:".yellow(),
-        docstring_issues.len(),
-        file_path.display());
+    // Resolve languages up front so a bad review file fails before anything is written
+    let files = files.into_iter()
+        .map(|(file_path, items)| {
+            let language = Language::from_str(&items[0].language, true)
+                .map_err(|_| error::DocGenError::ConfigError(
+                    format!("Unknown language '{}' for {}", items[0].language, file_path.display())))?;
+            Ok((file_path, language, items))
+        })
+        .collect::<Result<Vec<_>>>()?;
     
-    for issue in &docstring_issues {
-        println!("  {} {}: {}", "→".yellow(), issue.item_type, issue.name);
-        if config.verbose {
-            println!("    Line {}: {}", issue.line_number, issue.issue_type);
+    let root = lock::find_repo_root(&std::env::current_dir()?);
+    let mut languages: Vec<String> = files.iter().map(|(_, l, _)| l.name().to_string()).collect();
+    languages.sort();
+    languages.dedup();
+    let _lock = lock::RunLock::acquire(&root, &languages, force)?;
+    
+    for (file_path, language, items) in files {
+        let content = io::read_text(file_path)?;
+        let parser = mode_parser(&language, file_path, review_file.cli_help, review_file.script_comments, review_file.constants, true, config)?;
+        let parsed_code = parser.parse(&content)?;
+        
+        let (updates, missing) = review::updates(&parsed_code, &items);
+        for item in missing {
+            log::warn!("{} {} is no longer in {}; skipping", item.item_type, item.name, file_path.display());
         }
+        if updates.is_empty() {
+            continue;
+        }
+        
+        let updated_content = parser.update_content(&content, &updates)?;
+        updater::write_file(file_path, &updated_content, config)?;
+        progress::emit(config, progress::ProgressEvent::FileWritten { file: file_path, items: updates.len() });
+        log::info!("Applied {} changes to {}", updates.len(), file_path.display());
     }
     
-    // Exit if we're just checking or in test mode
-    if config.check_only || config.test_mode {
-        if config.test_mode && config.verbose {
-            println!("{} Test mode - skipping LLM API calls", "DocGen:".blue());
+    Ok(())
+}
+
+/// Rewrite the names a rename left stale in the documentation of the given files:
+/// parameters renamed since the history recorded an item's signature (or, for items
+/// without a record, the one documented parameter that no longer exists), and
+/// references to functions and types that the history shows were renamed and that no
+/// longer name anything in the project
+fn resync_files(
+    files: &[(PathBuf, Language)],
+    root: &Path,
+    diff: bool,
+    use_history: bool,
+    force: bool,
+    config: &config::Config,
+) -> Result<()> {
+    let history_path = history::History::path(root);
+    let mut history = if use_history { history::History::load(&history_path) } else { history::History::default() };
+    
+    let mut parsed_files = Vec::new();
+    let mut symbols = symbols::SymbolIndex::default();
+    for (file_path, language) in files {
+        let content = io::read_text(file_path)?;
+        let parser = lang::get_file_parser(language, file_path);
+        let parsed_code = parser.parse(&content)?;
+        symbols.add(&root_relative(file_path, root), &parsed_code);
+        let file_key = discover::normalize(&root_relative(file_path, root));
+        parsed_files.push((file_path, file_key, content, parser, parsed_code));
+    }
+    
+    // Renames are only trusted when nothing in the project still has the old name
+    let renamed_items: Vec<resync::Rename> = parsed_files.iter()
+        .flat_map(|(_, file_key, _, _, parsed_code)| history.renamed_items(file_key, parsed_code))
+        .filter(|(old, _)| !symbols.contains(old))
+        .map(|(old, new)| resync::Rename { old, new })
+        .collect();
+    
+    let mut languages: Vec<String> = files.iter().map(|(_, l)| l.name().to_string()).collect();
+    languages.sort();
+    languages.dedup();
+    let _lock = if diff { None } else { Some(lock::RunLock::acquire(root, &languages, force)?) };
+    let config = config::Config { diff_only: diff, ..config.clone() };
+    
+    let mut total = 0;
+    for (file_path, file_key, content, parser, parsed_code) in &parsed_files {
+        let resynced = resync::resync(parsed_code, |item| history.previous_parameters(file_key, item), &renamed_items);
+        if resynced.is_empty() {
+            continue;
+        }
+        
+        let mut out = Vec::new();
+        let mut updates = Vec::new();
+        for resynced in resynced {
+            let item = &parsed_code.items[resynced.item_index];
+            let renames: Vec<String> = resynced.renames.iter().map(|rename| rename.to_string()).collect();
+            writeln!(out, "  {} {} {}: {}", "✎".blue(), item.item_type, item.name, renames.join(", "))?;
+            updates.push(docstring::UpdatedDocstring {
+                item_index: resynced.item_index,
+                new_docstring: llm::format_docstring(&resynced.docstring, item, &parsed_code.language),
+                indentation: item.indentation.clone(),
+            });
+        }
+        total += updates.len();
+        
+        let updated_content = parser.update_content(content, &updates)?;
+        if updater::write_update(file_path, content, &updated_content, &config, &mut out)?.is_none() {
+            writeln!(out, "{} Re-synced {} docstrings in {}", "DocGen:".green(), updates.len(), file_path.display())?;
             
-            // Print parsed code items for verification
-            println!("\n{} Parsed code items:", "DocGen:".blue());
-            for (index, item) in parsed_code.items.iter().enumerate() {
-                println!("  Item {}: {} '{}'", index, item.item_type, item.name);
-                println!("    Line: {}", item.line_number);
-                println!("    Parameters: {:?}", item.parameters);
-                println!("    Docstring: {}", item.existing_docstring.as_ref().map_or("None", |s| s));
-                println!();
-            }
+            // The rewritten documentation matches the current signatures again
+            let parsed_code = parser.parse(&updated_content)?;
+            let issues = docstring::analyze(&parsed_code, config.docstring_style)?;
+            history.record(file_key, &parsed_code, &issues);
         }
+        std::io::stdout().write_all(&out)?;
+    }
+    
+    if total == 0 {
+        log::info!("No documentation refers to renamed parameters or functions");
+    } else if use_history && !diff {
+        history.save(&history_path)?;
+    }
+    Ok(())
+}
+
+/// Number of documentation issues in a file, without history or lint checks
+fn count_issues(file_path: &Path, language: &Language, config: &config::Config) -> Result<usize> {
+    let content = io::read_text(file_path)?;
+    let parsed_code = mode_parser(language, file_path, false, false, config.constants, config.include_private, config)?.parse(&content)?;
+    let mut issues = docstring::analyze(&parsed_code, config.docstring_style)?;
+    config.rules.apply(&mut issues);
+    Ok(issues.len())
+}
+
+/// Measure the documentation debt of each file, split it into batches, and write the
+/// campaign tracking file
+fn plan_campaign(
+    files: &[(PathBuf, Language)],
+    batch_count: usize,
+    campaign_path: &Path,
+    root: &Path,
+    config: &config::Config,
+) -> Result<()> {
+    let mut debts = Vec::new();
+    for (file_path, language) in files {
+        debts.push((root_relative(file_path, root), count_issues(file_path, language, config)?));
+    }
+    
+    let campaign = campaign::Campaign::plan(&debts, batch_count);
+    if campaign.batches.is_empty() {
+        log::info!("No documentation issues found; there is nothing to plan");
         return Ok(());
     }
+    campaign.save(campaign_path)?;
     
-    // Use LLM to generate docstrings
-    println!("{} Generating documentation using {}...", 
-        "DocGen:".blue(),
-        config.provider);
+    print!("{}", campaign.table());
+    log::info!("Wrote {} batches to {}; document one with `docgen --campaign-batch <N>`",
+        campaign.batches.len(),
+        campaign_path.display());
+    Ok(())
+}
+
+/// Re-check the files of every batch in the campaign and report how many issues each
+/// still has
+fn campaign_status(
+    campaign_path: &Path,
+    root: &Path,
+    overrides: &[(discover::Glob, Language)],
+    config: &config::Config,
+) -> Result<()> {
+    let campaign = campaign::Campaign::load(campaign_path)?;
+    let mut remaining = Vec::new();
+    for batch in &campaign.batches {
+        let mut issues = 0;
+        // Files deleted since the campaign was planned have nothing left to document
+        for file_path in batch.files.iter().map(|file| root.join(file)).filter(|path| path.exists()) {
+            issues += count_issues(&file_path, &file_language(&file_path, overrides), config)?;
+        }
+        remaining.push(issues);
+    }
     
-    let llm_client = llm::get_client(&config.provider)?;
-    let updated_docstrings = llm_client.generate_docstrings(&parsed_code, &docstring_issues).await?;
+    print!("{}", campaign.status_table(&remaining));
+    Ok(())
+}
+
+/// Parse each file and report how many of its items are documented, as a table or
+/// JSON on stdout and optionally as an HTML page and a heatmap
+fn coverage_report(
+    files: &[(PathBuf, Language)],
+    html_path: Option<&Path>,
+    heatmap_path: Option<&Path>,
+    config: &config::Config,
+) -> Result<()> {
+    let mut coverage = Vec::new();
+    for (file_path, language) in files {
+        let content = read_source(file_path, config)?;
+        let parsed_code = mode_parser(language, file_path, false, false, false, config.include_private, config)?.parse(&content)?;
+        coverage.push(coverage::FileCoverage::new(file_path, &parsed_code));
+    }
     
-    // Update the file with new docstrings
-    // For now, only Python is fully implemented
-    let updated_content = parser.update_content(&content, &updated_docstrings)?;
+    match config.format {
+        report::OutputFormat::Text | report::OutputFormat::Plain => print!("{}", coverage::table(&coverage)),
+        report::OutputFormat::Json => println!("{}", coverage::json(&coverage)),
+    }
     
-    // Future implementation when tree-sitter is fixed:
-    // let updated_content = match language {
-    //     Language::Python => {
-    //         let parser = lang::python::PythonParser::new();
-    //         parser.update_content(&content, &updated_docstrings)?
-    //     },
-    //     Language::Rust => {
-    //         let parser = lang::rust::RustParser::new();
-    //         parser.update_content(&content, &updated_docstrings)?
-    //     },
-    //     Language::JavaScript => {
-    //         let parser = lang::javascript::JavaScriptParser::new();
-    //         parser.update_content(&content, &updated_docstrings)?
-    //     },
-    //     Language::TypeScript => {
-    //         let parser = lang::typescript::TypeScriptParser::new();
-    //         parser.update_content(&content, &updated_docstrings)?
-    //     },
-    //     Language::Auto => unreachable!("Language should be resolved by this point"),
-    // };
-    
-    // Write back to file
-    std::fs::write(file_path, updated_content)?;
-    
-    println!("{} Updated documentation in {}", 
-        "DocGen:".green(),
-        file_path.display());
+    if let Some(html_path) = html_path {
+        std::fs::write(html_path, coverage::html(&coverage))?;
+        log::info!("Wrote coverage report to {}", html_path.display());
+    }
+    
+    if let Some(heatmap_path) = heatmap_path {
+        let heatmap = if heatmap_path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            heatmap::json(&coverage)
+        } else {
+            heatmap::html(&coverage)
+        };
+        std::fs::write(heatmap_path, heatmap)?;
+        log::info!("Wrote documentation debt heatmap to {}", heatmap_path.display());
+    }
     
     Ok(())
 }
+
+/// Write an overview of the items across a directory's files to a Markdown file, or
+/// print it when the file is `-`. The model writes the overview and the descriptions in the
+/// index; items it doesn't describe keep the summary lines of their documentation.
+async fn summarize(dir: &Path, files: &[(PathBuf, Language)], output: &Path, config: &config::Config) -> Result<()> {
+    let mut overview = overview::Overview::new(dir);
+    for (file_path, language) in files {
+        let content = read_source(file_path, config)?;
+        let parsed_code = mode_parser(language, file_path, false, false, false, config.include_private, config)?.parse(&content)?;
+        overview.add(file_path.strip_prefix(dir).unwrap_or(file_path), &parsed_code);
+    }
+    if overview.entries.is_empty() {
+        anyhow::bail!("No documentable items found in {}", dir.display());
+    }
+    
+    let llm_client = llm::get_client(config)?;
+    let reply = llm_client.summarize(overview::SYSTEM_PROMPT, &overview.prompt(), config).await?;
+    overview.apply_answer(&reply);
+    
+    if output == Path::new("-") {
+        print!("{}", overview.markdown());
+    } else {
+        io::write_atomic(output, overview.markdown().as_bytes())?;
+        log::info!("Wrote an overview of {} items to {}", overview.entries.len(), output.display());
+    }
+    Ok(())
+}
+
+/// Index the types and functions of every file for `--project-context`. Files that
+/// fail to parse are left out of the index; their errors are reported when they are
+/// processed.
+fn symbol_index(files: &[(PathBuf, Language)], root: &Path, config: &config::Config) -> Result<symbols::SymbolIndex> {
+    let mut index = symbols::SymbolIndex::default();
+    for (file_path, language) in files {
+        let content = read_source(file_path, config)?;
+        if let Ok(parsed_code) = lang::get_file_parser(language, file_path).parse(&content) {
+            index.add(&root_relative(file_path, root), &parsed_code);
+        }
+    }
+    
+    log::debug!("Indexed {} symbols across {} files", index.count(), files.len());
+    Ok(index)
+}
@@ -0,0 +1,64 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use crate::discover;
+use crate::error::{DocGenError, DocGenResult};
+
+/// One line of the `--overlay` protocol on stdin
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OverlayEntry {
+    path: PathBuf,
+    content: String,
+}
+
+/// Unsaved editor buffers standing in for the files they belong to, so a run analyzes
+/// and documents what the user sees rather than what was last saved
+#[derive(Debug, Default)]
+pub struct Overlay {
+    buffers: HashMap<PathBuf, String>,
+}
+
+impl Overlay {
+    /// Read buffers from JSON Lines, one `{"path": ..., "content": ...}` object per
+    /// line, until the end of the input. Relative paths are taken from the current
+    /// directory; a later buffer for the same file replaces an earlier one.
+    pub fn from_reader(reader: impl BufRead) -> DocGenResult<Self> {
+        let mut overlay = Self::default();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: OverlayEntry = serde_json::from_str(&line)
+                .map_err(|e| DocGenError::ConfigError(format!("Invalid overlay entry on line {}: {}", index + 1, e)))?;
+            overlay.insert(&entry.path, entry.content);
+        }
+        Ok(overlay)
+    }
+    
+    /// Stand a buffer in for a file
+    pub fn insert(&mut self, path: &Path, content: String) {
+        self.buffers.insert(discover::canonical(path), content);
+    }
+    
+    /// Number of buffers
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+    
+    /// Whether there are no buffers
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+    
+    /// Content of a file: its buffer when there is one, or else what is on disk
+    pub fn read(&self, path: &Path) -> DocGenResult<String> {
+        match self.buffers.get(&discover::canonical(path)) {
+            Some(content) => Ok(content.clone()),
+            None => crate::io::read_text(path),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::llm::unformat_docstring;
+use crate::parser::{ItemKind, ParsedCode};
+use crate::symbols::truncate;
+
+/// Most items described to the model in one prompt; the index lists the rest with the
+/// summaries their documentation already has
+const MAX_PROMPT_ITEMS: usize = 300;
+
+/// Longest signature quoted for an item, in characters
+const MAX_SIGNATURE_LENGTH: usize = 120;
+
+/// Instructions sent as the system prompt for an overview
+pub const SYSTEM_PROMPT: &str = "You are a technical writer. Write clear, concise, and accurate \
+    overviews of software modules for their README files.";
+
+/// An item of a module's public API
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// File the item is defined in, relative to the summarized directory
+    pub file: PathBuf,
+    pub item_type: ItemKind,
+    /// Name of the item, after its parent's for members, e.g. `Parser.parse`
+    pub name: String,
+    /// First line of the item's code
+    pub signature: String,
+    /// First line of the item's documentation, if it has any
+    pub summary: Option<String>,
+    /// One-line description the model wrote for the index
+    pub description: Option<String>,
+}
+
+/// The items of a package or module, gathered across its files, for `docgen summarize`
+/// to write an overview and an API index of
+#[derive(Debug)]
+pub struct Overview {
+    /// Name of the module, used as the page title
+    pub title: String,
+    pub entries: Vec<Entry>,
+    /// Overview written by the model, in Markdown
+    pub text: String,
+}
+
+/// Answer to an overview prompt
+#[derive(Deserialize)]
+struct OverviewAnswer {
+    #[serde(default)]
+    overview: String,
+    #[serde(default)]
+    descriptions: Vec<Description>,
+}
+
+#[derive(Deserialize)]
+struct Description {
+    index: usize,
+    description: String,
+}
+
+impl Overview {
+    /// An overview titled after the directory it summarizes, with no items yet
+    pub fn new(dir: &Path) -> Self {
+        let title = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf())
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| dir.display().to_string());
+        Self { title, entries: Vec::new(), text: String::new() }
+    }
+    
+    /// Add the items of a parsed file, leaving out those excluded with a `docsherpa:
+    /// ignore` comment and script statements
+    pub fn add(&mut self, file: &Path, parsed_code: &ParsedCode) {
+        let ignored = parsed_code.ignored_items();
+        for (index, item) in parsed_code.items.iter().enumerate() {
+            if ignored[index] || item.is_script_statement() {
+                continue;
+            }
+            
+            let signature = item.code.lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .unwrap_or("");
+            let summary = item.existing_docstring.as_deref()
+                .map(|doc| unformat_docstring(doc, &parsed_code.language))
+                .and_then(|doc| doc.lines().map(str::trim).find(|line| !line.is_empty()))
+                .map(|line| line.to_string());
+            
+            self.entries.push(Entry {
+                file: file.to_path_buf(),
+                item_type: item.item_type,
+                name: match &item.parent {
+                    Some(parent) => format!("{}.{}", parent, item.name),
+                    None => item.name.clone(),
+                },
+                signature: truncate(signature, MAX_SIGNATURE_LENGTH),
+                summary,
+                description: None,
+            });
+        }
+    }
+    
+    /// Prompt asking for an overview of the module and a one-line description of each
+    /// of its first `MAX_PROMPT_ITEMS` items
+    pub fn prompt(&self) -> String {
+        let mut prompt = format!(
+            "Write an overview of the `{}` module for its README, from the index of its API below. \
+            Respond with a JSON object of the form \
+            {{\"overview\": \"<overview>\", \"descriptions\": [{{\"index\": <item index>, \"description\": \"<description>\"}}]}}. \
+            The overview is one to three paragraphs of Markdown, without a heading, saying what the module is for \
+            and how its main parts fit together. Each description is a single line of under 80 characters saying \
+            what the item does, without repeating its name. Describe each item exactly once.\n\nItems:",
+            self.title
+        );
+        for (index, entry) in self.entries.iter().take(MAX_PROMPT_ITEMS).enumerate() {
+            prompt.push_str(&format!("\n[{}] {} `{}` in {}: `{}`",
+                index,
+                entry.item_type,
+                entry.name,
+                entry.file.display(),
+                entry.signature));
+            if let Some(summary) = &entry.summary {
+                prompt.push_str(&format!(" - {}", summary));
+            }
+        }
+        if self.entries.len() > MAX_PROMPT_ITEMS {
+            prompt.push_str(&format!("\n(and {} more items, left out for length)", self.entries.len() - MAX_PROMPT_ITEMS));
+        }
+        prompt
+    }
+    
+    /// Take the overview and descriptions from the model's answer. An answer that isn't
+    /// the JSON asked for is used as the overview as it is.
+    pub fn apply_answer(&mut self, reply: &str) {
+        let json = reply.trim()
+            .strip_prefix("```json").or_else(|| reply.trim().strip_prefix("```"))
+            .and_then(|reply| reply.strip_suffix("```"))
+            .unwrap_or(reply);
+        match serde_json::from_str::<OverviewAnswer>(json) {
+            Ok(answer) => {
+                self.text = answer.overview.trim().to_string();
+                for described in answer.descriptions {
+                    if let Some(entry) = self.entries.get_mut(described.index).filter(|_| described.index < MAX_PROMPT_ITEMS) {
+                        let description = described.description.lines().next().unwrap_or("").trim();
+                        entry.description = Some(description.to_string()).filter(|d| !d.is_empty());
+                    }
+                }
+            },
+            Err(_) => self.text = reply.trim().to_string(),
+        }
+    }
+    
+    /// The overview as a Markdown page: the overview text, then the items of each file
+    /// with their descriptions, falling back to the summary lines of their documentation
+    pub fn markdown(&self) -> String {
+        let mut page = format!("# {}\n\n", self.title);
+        if !self.text.is_empty() {
+            page.push_str(&self.text);
+            page.push_str("\n\n");
+        }
+        page.push_str("## API\n");
+        
+        let mut file = None;
+        for entry in &self.entries {
+            if file != Some(&entry.file) {
+                page.push_str(&format!("\n### `{}`\n\n", entry.file.display()));
+                file = Some(&entry.file);
+            }
+            page.push_str(&format!("- `{}` ({})", entry.name, entry.item_type));
+            if let Some(description) = entry.description.as_ref().or(entry.summary.as_ref()) {
+                page.push_str(&format!(": {}", description));
+            }
+            page.push('\n');
+        }
+        page
+    }
+}
@@ -1,19 +1,453 @@
-use crate::error::DocGenResult;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::error::{DocGenError, DocGenResult};
 use crate::lang;
 use crate::lang::LanguageParser;
 
+/// Comment that excludes the item declared on its line, e.g. `# docsherpa: ignore`
+const IGNORE_DIRECTIVE: &str = r"(?:#|//|/\*|--|%|;)\s*docsherpa:\s*ignore\b";
+
+/// Comment on a line of its own that excludes the whole file, e.g.
+/// `# docsherpa: skip-file`
+const SKIP_FILE_DIRECTIVE: &str = r"(?m)^\s*(?:#|//|/\*|\*|--|%|;)\s*docsherpa:\s*skip-file\b";
+
+/// Kind of a documentable item, across every supported language. Kinds are written
+/// in snake_case (e.g. `enum_value`) in reports, review files, and the signature
+/// history, so those names must not change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    /// A function, or a Fortran, VB, or PowerShell function procedure
+    Function,
+    /// A method of a class, trait, or other type
+    Method,
+    Class,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    /// A TypeScript type alias
+    Type,
+    /// A Scala object
+    Object,
+    /// A Scala `def`
+    Def,
+    /// A VB module
+    Module,
+    /// A VB structure
+    Structure,
+    /// A VB Sub procedure
+    Sub,
+    /// A Fortran subroutine
+    Subroutine,
+    /// A PowerShell filter
+    Filter,
+    /// A CUDA or OpenCL kernel
+    Kernel,
+    Property,
+    Field,
+    /// A Protocol Buffers enum value
+    EnumValue,
+    Message,
+    Service,
+    /// A Protocol Buffers service method
+    Rpc,
+    /// An OpenAPI operation
+    Operation,
+    Schema,
+    Protocol,
+    Contract,
+    Library,
+    Modifier,
+    Event,
+    Constructor,
+    /// A Jenkins shared library pipeline step
+    Step,
+    /// A Makefile target
+    Target,
+    /// A CMake macro
+    Macro,
+    /// A Dockerfile build stage
+    Stage,
+    /// A Dockerfile build argument
+    Arg,
+    /// A Dockerfile exposed port
+    Expose,
+    /// An Ansible role
+    Role,
+    /// An Ansible block of tasks
+    Block,
+    /// An Ansible task
+    Task,
+    /// A Helm chart value
+    Value,
+    Workflow,
+    Action,
+    /// A GitHub Actions input
+    Input,
+    /// A GitHub Actions output
+    Output,
+    /// A GitHub Actions reusable workflow secret
+    Secret,
+    /// A Python script's argument parser setup
+    ArgparseSetup,
+    /// A Python script's `if __name__ == "__main__":` guard
+    MainGuard,
+    /// A command-line option or argument (`--cli-help`)
+    CliArgument,
+    /// A module-level constant or static (`--constants`)
+    Constant,
+    /// A C# record, whose positional parameters are its properties
+    Record,
+    /// A SQL stored procedure
+    Procedure,
+    /// A React function component: a capitalized function that returns JSX
+    Component,
+    /// A React custom hook: a function named `useX`
+    Hook,
+}
+
+impl ItemKind {
+    /// Every kind, in declaration order
+    pub const ALL: &'static [ItemKind] = &[
+        ItemKind::Function,
+        ItemKind::Method,
+        ItemKind::Class,
+        ItemKind::Struct,
+        ItemKind::Enum,
+        ItemKind::Trait,
+        ItemKind::Interface,
+        ItemKind::Type,
+        ItemKind::Object,
+        ItemKind::Def,
+        ItemKind::Module,
+        ItemKind::Structure,
+        ItemKind::Sub,
+        ItemKind::Subroutine,
+        ItemKind::Filter,
+        ItemKind::Kernel,
+        ItemKind::Property,
+        ItemKind::Field,
+        ItemKind::EnumValue,
+        ItemKind::Message,
+        ItemKind::Service,
+        ItemKind::Rpc,
+        ItemKind::Operation,
+        ItemKind::Schema,
+        ItemKind::Protocol,
+        ItemKind::Contract,
+        ItemKind::Library,
+        ItemKind::Modifier,
+        ItemKind::Event,
+        ItemKind::Constructor,
+        ItemKind::Step,
+        ItemKind::Target,
+        ItemKind::Macro,
+        ItemKind::Stage,
+        ItemKind::Arg,
+        ItemKind::Expose,
+        ItemKind::Role,
+        ItemKind::Block,
+        ItemKind::Task,
+        ItemKind::Value,
+        ItemKind::Workflow,
+        ItemKind::Action,
+        ItemKind::Input,
+        ItemKind::Output,
+        ItemKind::Secret,
+        ItemKind::ArgparseSetup,
+        ItemKind::MainGuard,
+        ItemKind::CliArgument,
+        ItemKind::Constant,
+        ItemKind::Record,
+        ItemKind::Procedure,
+        ItemKind::Component,
+        ItemKind::Hook,
+    ];
+    
+    /// Name of the kind as written in reports and prompts
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemKind::Function => "function",
+            ItemKind::Method => "method",
+            ItemKind::Class => "class",
+            ItemKind::Struct => "struct",
+            ItemKind::Enum => "enum",
+            ItemKind::Trait => "trait",
+            ItemKind::Interface => "interface",
+            ItemKind::Type => "type",
+            ItemKind::Object => "object",
+            ItemKind::Def => "def",
+            ItemKind::Module => "module",
+            ItemKind::Structure => "structure",
+            ItemKind::Sub => "sub",
+            ItemKind::Subroutine => "subroutine",
+            ItemKind::Filter => "filter",
+            ItemKind::Kernel => "kernel",
+            ItemKind::Property => "property",
+            ItemKind::Field => "field",
+            ItemKind::EnumValue => "enum_value",
+            ItemKind::Message => "message",
+            ItemKind::Service => "service",
+            ItemKind::Rpc => "rpc",
+            ItemKind::Operation => "operation",
+            ItemKind::Schema => "schema",
+            ItemKind::Protocol => "protocol",
+            ItemKind::Contract => "contract",
+            ItemKind::Library => "library",
+            ItemKind::Modifier => "modifier",
+            ItemKind::Event => "event",
+            ItemKind::Constructor => "constructor",
+            ItemKind::Step => "step",
+            ItemKind::Target => "target",
+            ItemKind::Macro => "macro",
+            ItemKind::Stage => "stage",
+            ItemKind::Arg => "arg",
+            ItemKind::Expose => "expose",
+            ItemKind::Role => "role",
+            ItemKind::Block => "block",
+            ItemKind::Task => "task",
+            ItemKind::Value => "value",
+            ItemKind::Workflow => "workflow",
+            ItemKind::Action => "action",
+            ItemKind::Input => "input",
+            ItemKind::Output => "output",
+            ItemKind::Secret => "secret",
+            ItemKind::ArgparseSetup => "argparse_setup",
+            ItemKind::MainGuard => "main_guard",
+            ItemKind::CliArgument => "cli_argument",
+            ItemKind::Constant => "constant",
+            ItemKind::Record => "record",
+            ItemKind::Procedure => "procedure",
+            ItemKind::Component => "component",
+            ItemKind::Hook => "hook",
+        }
+    }
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for ItemKind {
+    type Err = String;
+    
+    /// Parse a kind from its name, e.g. a keyword captured from a declaration
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        ItemKind::ALL.iter()
+            .find(|kind| kind.as_str() == name)
+            .copied()
+            .ok_or_else(|| format!("unknown item kind '{}'", name))
+    }
+}
+
+/// A parameter with its type annotation and default value, as written in the source
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    /// Name, with `*` or `**` in front for variadic parameters
+    pub name: String,
+    pub annotation: Option<String>,
+    pub default: Option<String>,
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(annotation) = &self.annotation {
+            write!(f, ": {}", annotation)?;
+        }
+        match (&self.annotation, &self.default) {
+            (Some(_), Some(default)) => write!(f, " = {}", default),
+            (None, Some(default)) => write!(f, "={}", default),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Whether an item is part of the interface its module or class offers. Items of
+/// languages without a notion of privacy are all public.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    /// A Python `_name` or `__dunder__`, a Rust item without `pub`, or a JavaScript
+    /// `#private` (or TypeScript `private`) class member
+    Private,
+}
+
+impl Visibility {
+    /// Visibility that Python gives a dotted name (`Outer.method`) by convention:
+    /// private when it, or anything it is defined in, starts with an underscore, which
+    /// includes `__dunder__` methods
+    pub fn of_python_name(path: &str) -> Self {
+        if path.split('.').any(|name| name.starts_with('_')) {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        }
+    }
+}
+
 /// Represents a code item that needs documentation
 #[derive(Debug, Clone)]
 pub struct CodeItem {
-    pub item_type: String,        // "function", "method", "class", etc.
+    pub item_type: ItemKind,      // Function, method, class, etc.
     pub name: String,             // Name of the function/class/method
     pub line_number: usize,       // Line number in the file
     pub code: String,             // The code for this item
     pub existing_docstring: Option<String>, // Existing docstring, if any
     pub parent: Option<String>,   // Parent type (e.g., class for methods)
     pub parameters: Vec<String>,  // Function/method parameters
+    /// Parameters with their annotations and defaults, for parsers that record them
+    pub parameter_details: Vec<Parameter>,
     pub returns: Option<String>,  // Return type annotation if available
     pub indentation: String,      // Indentation used for this item
+    /// Bytes of the item in the file, from the start of the line it (or its first
+    /// attribute or decorator) begins on to the end of its code
+    pub span: Range<usize>,
+    /// Bytes of the existing documentation that an update replaces, e.g. the whole
+    /// lines of a comment block, or the value of a description key
+    pub docstring_span: Option<Range<usize>>,
+    /// Whether the item is a coroutine function (`async def`), whose callers await it
+    pub is_async: bool,
+    /// Whether the item is a generator function, which yields its values
+    pub is_generator: bool,
+    /// Decorators applied to the item, as written without the `@`, outermost first
+    pub decorators: Vec<String>,
+    /// Whether code outside the item's module or class can use it
+    pub visibility: Visibility,
+    /// Exceptions the item declares it throws (a Java `throws` clause)
+    pub throws: Vec<String>,
+}
+
+impl CodeItem {
+    /// Whether this item is a top-level script statement documented with a
+    /// comment block rather than a docstring
+    pub fn is_script_statement(&self) -> bool {
+        matches!(self.item_type, ItemKind::ArgparseSetup | ItemKind::MainGuard)
+    }
+    
+    /// Bytes that new documentation written above the item replaces: the existing
+    /// documentation, or nothing at the start of the item
+    pub fn doc_range(&self) -> Range<usize> {
+        self.docstring_span.clone().unwrap_or(self.span.start..self.span.start)
+    }
+}
+
+/// Byte offset at which each line of a file starts, for turning the line numbers a
+/// parser works with into spans
+pub struct LineOffsets {
+    starts: Vec<usize>,
+    len: usize,
+}
+
+impl LineOffsets {
+    pub fn new(content: &str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+        Self { starts, len: content.len() }
+    }
+    
+    /// Offset of the start of a 0-based line; past the last line, the end of the file
+    pub fn start(&self, line: usize) -> usize {
+        self.starts.get(line).copied().unwrap_or(self.len).min(self.len)
+    }
+    
+    /// Offset just past a 0-based line's newline
+    pub fn end(&self, line: usize) -> usize {
+        self.start(line + 1)
+    }
+    
+    /// Bytes of the whole lines `first..=last`, including the last one's newline
+    pub fn lines(&self, first: usize, last: usize) -> Range<usize> {
+        self.start(first)..self.end(last)
+    }
+    
+    /// 0-based line that a byte offset falls on
+    pub fn line_of(&self, offset: usize) -> usize {
+        self.starts.partition_point(|&start| start <= offset).saturating_sub(1)
+    }
+}
+
+/// Replacement of a span of a file's content
+#[derive(Debug, Clone)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+impl Edit {
+    pub fn new(range: Range<usize>, text: impl Into<String>) -> Self {
+        Self { range, text: text.into() }
+    }
+    
+    /// Insertion of whole lines after a 0-based line, ending that line first when it
+    /// is the file's last and has no newline
+    pub fn after_line(content: &str, offsets: &LineOffsets, line: usize, text: impl Into<String>) -> Self {
+        let at = offsets.end(line);
+        let text = text.into();
+        if at == content.len() && !content.is_empty() && !content.ends_with('\n') {
+            Self::new(at..at, format!("\n{}", text))
+        } else {
+            Self::new(at..at, text)
+        }
+    }
+}
+
+/// Apply edits to the content they were computed against. Edits may come in any order
+/// but must not overlap; insertions at the same offset keep their order and go before
+/// a replacement starting there. Edit text is written with `\n` line endings, which
+/// become `\r\n` in files whose first line ends that way.
+pub fn apply_edits(content: &str, mut edits: Vec<Edit>) -> DocGenResult<String> {
+    edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+    for pair in edits.windows(2) {
+        if pair[0].range.end > pair[1].range.start {
+            return Err(DocGenError::UpdateError(format!(
+                "Overlapping edits at bytes {:?} and {:?}", pair[0].range, pair[1].range)));
+        }
+    }
+    
+    let crlf = content.find('\n').is_some_and(|end| content[..end].ends_with('\r'));
+    let mut result = String::with_capacity(content.len());
+    let mut copied = 0;
+    for edit in &edits {
+        if content.get(edit.range.clone()).is_none() {
+            return Err(DocGenError::UpdateError(format!("Edit at bytes {:?} is outside the file", edit.range)));
+        }
+        result.push_str(&content[copied..edit.range.start]);
+        if crlf {
+            result.push_str(&edit.text.replace("\r\n", "\n").replace('\n', "\r\n"));
+        } else {
+            result.push_str(&edit.text);
+        }
+        copied = edit.range.end;
+    }
+    result.push_str(&content[copied..]);
+    Ok(result)
+}
+
+/// Whether a constant's value says all there is to say about it, so the constant needs
+/// no documentation: a boolean or null value, an empty literal, 0 or 1, or another name
+pub fn is_obvious_value(value: &str) -> bool {
+    let value = value.trim().trim_end_matches(';').trim();
+    let is_name = value.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && value.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+    is_name || matches!(value,
+        "None" | "True" | "False" | "true" | "false" | "null" | "undefined"
+        | "0" | "1" | "-1" | "\"\"" | "''" | "[]" | "{}" | "()" | "Vec::new()" | "String::new()")
+}
+
+/// A syntax error in a file that was parsed around it; items inside it are left out
+#[derive(Debug, Clone, Serialize)]
+pub struct SyntaxError {
+    /// Line (1-based) the error starts on
+    pub line: usize,
+    pub message: String,
 }
 
 /// Represents the parsed code file
@@ -21,6 +455,53 @@ pub struct CodeItem {
 pub struct ParsedCode {
     pub items: Vec<CodeItem>,
     pub original_content: String,
+    pub language: String,         // Language name used for prompts and formatting
+    /// Syntax errors the parser recovered from, analyzing the rest of the file
+    pub syntax_errors: Vec<SyntaxError>,
+}
+
+impl ParsedCode {
+    /// Which items opt out of documentation, by index: every item of a file with a
+    /// `docsherpa: skip-file` comment, and otherwise those with a `docsherpa: ignore`
+    /// comment on their declaration line (or on a decorator or attribute line above it)
+    pub fn ignored_items(&self) -> Vec<bool> {
+        if Regex::new(SKIP_FILE_DIRECTIVE).unwrap().is_match(&self.original_content) {
+            return vec![true; self.items.len()];
+        }
+        
+        let ignore_re = Regex::new(IGNORE_DIRECTIVE).unwrap();
+        self.items.iter()
+            .map(|item| {
+                // A module's code is the whole file, whose items have directives of their own
+                if item.item_type == ItemKind::Module {
+                    return false;
+                }
+                // Whole lines of the file from the item's first attribute or decorator, so
+                // that a comment after a one-line item is read too
+                let code = self.original_content.get(item.span.start..)
+                    .filter(|_| !item.span.is_empty())
+                    .unwrap_or(&item.code);
+                let declaration = code.lines().position(|line| line.contains(&item.name)).unwrap_or(0);
+                code.lines().take(declaration + 1).any(|line| ignore_re.is_match(line))
+            })
+            .collect()
+    }
+    
+    /// Find the item that was at `index` in an earlier parse of the same file, by its
+    /// type, name, parent, and position among the items sharing them
+    pub fn find_item(&self, earlier: &ParsedCode, index: usize) -> Option<usize> {
+        let target = &earlier.items[index];
+        let same = |item: &&CodeItem| item.item_type == target.item_type
+            && item.name == target.name
+            && item.parent == target.parent;
+        let occurrence = earlier.items[..index].iter().filter(same).count();
+        
+        self.items.iter()
+            .enumerate()
+            .filter(|(_, item)| same(item))
+            .nth(occurrence)
+            .map(|(index, _)| index)
+    }
 }
 
 /// Parse a Python file and extract code items
@@ -30,40 +511,19 @@ pub fn parse_python(content: &str) -> DocGenResult<ParsedCode> {
 }
 
 /// Parse a Rust file and extract code items
-/// 
-/// Note: Currently disabled until tree-sitter issues are resolved.
-/// All calls will redirect to the Python parser for now.
 pub fn parse_rust(content: &str) -> DocGenResult<ParsedCode> {
-    // Temporarily using Python parser until Rust parser is fixed
-    println!("Warning: Rust parser is not yet implemented. Using Python parser instead.");
-    parse_python(content)
-    // Uncomment when ready:
-    // let parser = lang::rust::RustParser::new();
-    // parser.parse(content)
+    let parser = lang::rust::RustParser::new();
+    parser.parse(content)
 }
 
 /// Parse a JavaScript file and extract code items
-/// 
-/// Note: Currently disabled until tree-sitter issues are resolved.
-/// All calls will redirect to the Python parser for now.
 pub fn parse_javascript(content: &str) -> DocGenResult<ParsedCode> {
-    // Temporarily using Python parser until JavaScript parser is fixed
-    println!("Warning: JavaScript parser is not yet implemented. Using Python parser instead.");
-    parse_python(content)
-    // Uncomment when ready:
-    // let parser = lang::javascript::JavaScriptParser::new();
-    // parser.parse(content)
+    let parser = lang::javascript::JavaScriptParser::new();
+    parser.parse(content)
 }
 
 /// Parse a TypeScript file and extract code items
-/// 
-/// Note: Currently disabled until tree-sitter issues are resolved.
-/// All calls will redirect to the Python parser for now.
 pub fn parse_typescript(content: &str) -> DocGenResult<ParsedCode> {
-    // Temporarily using Python parser until TypeScript parser is fixed
-    println!("Warning: TypeScript parser is not yet implemented. Using Python parser instead.");
-    parse_python(content)
-    // Uncomment when ready:
-    // let parser = lang::typescript::TypeScriptParser::new();
-    // parser.parse(content)
+    let parser = lang::typescript::TypeScriptParser::new();
+    parser.parse(content)
 }
@@ -0,0 +1,109 @@
+use regex::Regex;
+use serde::Deserialize;
+
+/// Content rules from the `[policy]` table of the project configuration
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicySettings {
+    /// Reject links to any domain not in `allowed_domains`
+    pub block_external_urls: bool,
+
+    /// Domains (and their subdomains) that links may point to
+    pub allowed_domains: Vec<String>,
+
+    /// Names that must not appear, matched as whole words regardless of case
+    pub denied_terms: Vec<String>,
+
+    /// Regular expressions that must not match, e.g. "TODO"
+    pub denied_patterns: Vec<String>,
+}
+
+/// Organization rules that generated documentation must follow before it is written
+#[derive(Clone, Default)]
+pub struct Policy {
+    block_external_urls: bool,
+    allowed_domains: Vec<String>,
+    denied_terms: Vec<(String, Regex)>,
+    denied_patterns: Vec<Regex>,
+}
+
+impl Policy {
+    /// Compile the configured rules, failing on invalid patterns
+    pub fn from_settings(settings: PolicySettings) -> Result<Self, String> {
+        let denied_terms = settings.denied_terms.into_iter()
+            .map(|term| {
+                let regex = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(&term)))
+                    .map_err(|e| format!("invalid denied term '{}': {}", term, e))?;
+                Ok((term, regex))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let denied_patterns = settings.denied_patterns.iter()
+            .map(|pattern| Regex::new(pattern)
+                .map_err(|e| format!("invalid denied pattern '{}': {}", pattern, e)))
+            .collect::<Result<Vec<_>, String>>()?;
+        
+        Ok(Self {
+            block_external_urls: settings.block_external_urls,
+            allowed_domains: settings.allowed_domains.iter().map(|d| d.to_lowercase()).collect(),
+            denied_terms,
+            denied_patterns,
+        })
+    }
+    
+    /// Whether there are no rules to enforce
+    pub fn is_empty(&self) -> bool {
+        !self.block_external_urls && self.denied_terms.is_empty() && self.denied_patterns.is_empty()
+    }
+    
+    /// Ways the text breaks the policy; empty when it complies
+    pub fn violations(&self, text: &str) -> Vec<String> {
+        let mut violations = Vec::new();
+        
+        if self.block_external_urls {
+            let url_re = Regex::new(r"(?i)\b(?:https?|ftp)://([a-z0-9.-]+)").unwrap();
+            for caps in url_re.captures_iter(text) {
+                let domain = caps[1].to_lowercase();
+                let allowed = self.allowed_domains.iter()
+                    .any(|d| domain == *d || domain.ends_with(&format!(".{}", d)));
+                let violation = format!("links to external domain {}", domain);
+                if !allowed && !violations.contains(&violation) {
+                    violations.push(violation);
+                }
+            }
+        }
+        for (term, regex) in &self.denied_terms {
+            if regex.is_match(text) {
+                violations.push(format!("mentions denied term '{}'", term));
+            }
+        }
+        for pattern in &self.denied_patterns {
+            if pattern.is_match(text) {
+                violations.push(format!("matches denied pattern '{}'", pattern.as_str()));
+            }
+        }
+        
+        violations
+    }
+    
+    /// Prompt instruction describing the rules, for regenerating documentation that broke
+    /// them. Denied terms are described rather than listed, so they are never sent to
+    /// the provider.
+    pub fn instructions(&self) -> String {
+        let mut rules = Vec::new();
+        if self.block_external_urls {
+            if self.allowed_domains.is_empty() {
+                rules.push("include no URLs".to_string());
+            } else {
+                rules.push(format!("include no URLs except to {}", self.allowed_domains.join(", ")));
+            }
+        }
+        if !self.denied_terms.is_empty() {
+            rules.push("not mention customers or other organizations by name".to_string());
+        }
+        if !self.denied_patterns.is_empty() {
+            let patterns: Vec<&str> = self.denied_patterns.iter().map(|p| p.as_str()).collect();
+            rules.push(format!("contain no text matching these patterns: {}", patterns.join(", ")));
+        }
+        format!("The previous attempt broke the documentation policy. The documentation MUST {}.", rules.join("; and MUST "))
+    }
+}
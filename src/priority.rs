@@ -0,0 +1,113 @@
+use clap::ValueEnum;
+use regex::Regex;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::docstring::DocstringIssue;
+use crate::parser::{CodeItem, ParsedCode, Visibility};
+
+/// Which items are documented first, which matters when `--max-fixes` or a budget
+/// stops the run before every item is done
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Order {
+    /// In the order the items appear in each file
+    Source,
+    /// Items with the most branches and loops first
+    Complexity,
+    /// Public items first, then private and internal ones
+    PublicFirst,
+    /// Items whose lines were changed most recently in git first
+    RecentlyChanged,
+}
+
+/// Reorder the issues of a file so the items to document first come first. Items that
+/// rank the same keep their order in the file.
+pub fn order(issues: &mut [DocstringIssue], parsed_code: &ParsedCode, order: Order, file_path: &Path) {
+    match order {
+        Order::Source => {},
+        Order::Complexity => {
+            issues.sort_by_cached_key(|issue| std::cmp::Reverse(complexity(&parsed_code.items[issue.item_index])));
+        },
+        Order::PublicFirst => {
+            issues.sort_by_key(|issue| !is_public(&parsed_code.items[issue.item_index], &parsed_code.language));
+        },
+        Order::RecentlyChanged => {
+            // Without git history there is nothing to rank by
+            if let Some(line_times) = line_commit_times(file_path) {
+                issues.sort_by_cached_key(|issue| {
+                    let item = &parsed_code.items[issue.item_index];
+                    let first = item.line_number.saturating_sub(1);
+                    let last = first + item.code.lines().count().max(1);
+                    std::cmp::Reverse(line_times.get(first..last.min(line_times.len()))
+                        .and_then(|times| times.iter().max().copied())
+                        .unwrap_or(0))
+                });
+            }
+        },
+    }
+}
+
+/// Take up to `wanted` fixes from the run's remaining `--max-fixes`, returning how
+/// many were granted
+pub fn claim(budget: &AtomicUsize, wanted: usize) -> usize {
+    let mut granted = 0;
+    let _ = budget.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+        granted = left.min(wanted);
+        Some(left - granted)
+    });
+    granted
+}
+
+/// Rough cyclomatic complexity: one plus the number of branches, loops, and boolean
+/// operators in the item's code, whatever the language
+fn complexity(item: &CodeItem) -> usize {
+    let decision_re = Regex::new(
+        r"\b(?:if|elif|elsif|else\s+if|for|foreach|while|until|case|when|catch|except|rescue)\b|&&|\|\||\band\b|\bor\b|\?"
+    ).unwrap();
+    1 + decision_re.find_iter(&item.code).count()
+}
+
+/// Whether an item is part of the public interface: not private to its parser, and
+/// judged from its name and the modifiers on its first line
+fn is_public(item: &CodeItem, language: &str) -> bool {
+    if item.visibility == Visibility::Private {
+        return false;
+    }
+    let declaration = item.code.lines().next().unwrap_or("").trim_start();
+    if language == "rust" {
+        return declaration.starts_with("pub ");
+    }
+    if item.name.starts_with('_') || item.name.starts_with('#') {
+        return false;
+    }
+    let private_re = Regex::new(r"\b(?:private|protected|internal|fileprivate|Private|Friend)\b").unwrap();
+    !private_re.is_match(declaration)
+}
+
+/// Commit time of every line in the file from `git blame`, with uncommitted lines
+/// counting as the most recent. None when the file isn't tracked by git.
+fn line_commit_times(file_path: &Path) -> Option<Vec<u64>> {
+    let dir = file_path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let output = Command::new("git")
+        .arg("-C").arg(dir)
+        .args(["blame", "--line-porcelain", "--"])
+        .arg(file_path.file_name()?)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    
+    let mut times = Vec::new();
+    let mut uncommitted = false;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if line.starts_with("0000000000000000000000000000000000000000 ") {
+            uncommitted = true;
+        } else if let Some(time) = line.strip_prefix("committer-time ") {
+            times.push(if uncommitted { u64::MAX } else { time.parse().unwrap_or(0) });
+            uncommitted = false;
+        }
+    }
+    Some(times)
+}
@@ -0,0 +1,176 @@
+use serde::Serialize;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::config::Config;
+use crate::parser::ItemKind;
+
+/// A progress event, written to stderr as one JSON object per line so tool
+/// wrappers can follow a run without parsing the colored output
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent<'a> {
+    FileStarted {
+        file: &'a Path,
+    },
+    ItemGenerated {
+        file: &'a Path,
+        item_type: ItemKind,
+        name: &'a str,
+        line: usize,
+        lines: usize,
+    },
+    FileWritten {
+        file: &'a Path,
+        items: usize,
+    },
+    Error {
+        file: Option<&'a Path>,
+        message: String,
+    },
+    RunFinished {
+        files: usize,
+        items: usize,
+    },
+}
+
+/// Emit an event if `--progress-json` is enabled, and move the progress bar along
+pub fn emit(config: &Config, event: ProgressEvent) {
+    update_bar(&event);
+    if !config.progress_json {
+        return;
+    }
+    
+    if let Ok(line) = serde_json::to_string(&event) {
+        let mut stderr = std::io::stderr().lock();
+        let _ = writeln!(stderr, "{}", line);
+        let _ = stderr.flush();
+    }
+}
+
+/// Width of the progress bar itself, in characters
+const BAR_WIDTH: usize = 24;
+
+/// The progress bar drawn on stderr while a run processes files, if one is shown
+static BAR: Mutex<Option<Bar>> = Mutex::new(None);
+
+/// State of the progress bar
+struct Bar {
+    files: usize,
+    finished: usize,
+    /// Docstrings generated so far in the whole run
+    items: usize,
+    /// Files being processed, in the order they started, with the docstrings generated
+    /// for each so far and the number it needs, once known
+    active: Vec<(PathBuf, usize, usize)>,
+}
+
+impl Bar {
+    /// The bar's line: how many files are done, how many docstrings were generated,
+    /// and the latest file started, cut to the terminal's width
+    fn line(&self) -> String {
+        let filled = (BAR_WIDTH * self.finished).checked_div(self.files).unwrap_or(BAR_WIDTH);
+        let mut line = format!("[{}{}] {}/{} files, {} docstrings",
+            "=".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled),
+            self.finished,
+            self.files,
+            self.items);
+        if let Some((file, done, planned)) = self.active.last() {
+            line.push_str(&format!(" | {}", file.display()));
+            if *planned > 0 {
+                line.push_str(&format!(" ({}/{})", done, planned));
+            }
+        }
+        let columns = std::env::var("COLUMNS").ok().and_then(|c| c.parse::<usize>().ok()).unwrap_or(80);
+        line.chars().take(columns.saturating_sub(1)).collect()
+    }
+    
+    fn draw(&self) {
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K{}", self.line());
+        let _ = stderr.flush();
+    }
+    
+    fn clear(&self) {
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K");
+        let _ = stderr.flush();
+    }
+    
+    /// Entry of a file being processed
+    fn active(&mut self, file: &Path) -> Option<&mut (PathBuf, usize, usize)> {
+        self.active.iter_mut().find(|(active, _, _)| active == file)
+    }
+}
+
+/// Show a progress bar for a run over some number of files, when stderr is a terminal
+pub fn start_bar(files: usize) {
+    if !std::io::stderr().is_terminal() {
+        return;
+    }
+    let bar = Bar { files, finished: 0, items: 0, active: Vec::new() };
+    bar.draw();
+    *BAR.lock().expect("progress bar lock poisoned") = Some(bar);
+}
+
+/// Take the progress bar off the screen for good
+pub fn finish_bar() {
+    if let Some(bar) = BAR.lock().expect("progress bar lock poisoned").take() {
+        bar.clear();
+    }
+}
+
+/// Count a file as finished on the progress bar
+pub fn file_finished(file: &Path) {
+    if let Some(bar) = BAR.lock().expect("progress bar lock poisoned").as_mut() {
+        bar.finished += 1;
+        bar.active.retain(|(active, _, _)| active != file);
+        bar.draw();
+    }
+}
+
+/// Set how many docstrings a file being processed needs, shown next to its name
+pub fn items_planned(file: &Path, items: usize) {
+    if let Some(bar) = BAR.lock().expect("progress bar lock poisoned").as_mut() {
+        if let Some(entry) = bar.active(file) {
+            entry.2 = items;
+        }
+        bar.draw();
+    }
+}
+
+/// Write something to the terminal with the progress bar out of the way, drawing it
+/// again afterwards
+pub fn suspend<T>(write: impl FnOnce() -> T) -> T {
+    let bar = BAR.lock().expect("progress bar lock poisoned");
+    if let Some(bar) = bar.as_ref() {
+        bar.clear();
+    }
+    let result = write();
+    if let Some(bar) = bar.as_ref() {
+        bar.draw();
+    }
+    result
+}
+
+/// Move the progress bar along for an event
+fn update_bar(event: &ProgressEvent) {
+    let mut bar = BAR.lock().expect("progress bar lock poisoned");
+    let bar = match bar.as_mut() {
+        Some(bar) => bar,
+        None => return,
+    };
+    match event {
+        ProgressEvent::FileStarted { file } => bar.active.push((file.to_path_buf(), 0, 0)),
+        ProgressEvent::ItemGenerated { file, .. } => {
+            bar.items += 1;
+            if let Some(entry) = bar.active(file) {
+                entry.1 += 1;
+            }
+        },
+        _ => return,
+    }
+    bar.draw();
+}
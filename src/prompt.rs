@@ -0,0 +1,325 @@
+use regex::{Captures, Regex};
+
+use crate::config::Config;
+use crate::lang::typescript::TagStyle;
+use crate::parser::{CodeItem, ItemKind};
+
+/// Placeholders a prompt template may use, written `{{name}}`. The original four may
+/// also be written with single braces, e.g. `{name}`.
+const PLACEHOLDERS: &[&str] = &[
+    "item_type", "name", "language", "params", "returns", "code", "style", "convention", "guideline",
+];
+
+/// Built-in prompt for Python functions, methods, and classes
+const PYTHON_TEMPLATE: &str = "\
+Generate a Python docstring for the following {{item_type}} '{{name}}'. \
+Follow PEP 257 style guidelines.\
+The docstring should be informative, accurate, and describe what the {{item_type}} does.\
+Include parameters, return values, and exceptions if applicable.\
+Return ONLY the docstring text without the triple quotes or indentation.
+
+```python
+{{code}}
+```";
+
+/// Built-in prompt for Python module docstrings, given an outline of the module's
+/// public API rather than all of its code
+const PYTHON_MODULE_TEMPLATE: &str = "\
+Generate a Python module docstring for the module outlined below. Follow PEP 257 style guidelines. \
+Start with a one-line summary of what the module is for, then briefly describe the public \
+functions and classes it provides and how they fit together. \
+Return ONLY the docstring text without the triple quotes or indentation.
+
+```python
+{{code}}
+```";
+
+/// Built-in prompt for Rust crate and module documentation, given an outline of the
+/// module's public items
+const RUST_MODULE_TEMPLATE: &str = "\
+Generate a Rust inner doc comment (`//!`) for the module '{{name}}' outlined below. Follow rustdoc conventions. \
+Start with a one-line summary of what the module is for, then briefly describe the public \
+items it provides and how they fit together. \
+Return ONLY the documentation text without comment markers or indentation.
+
+```rust
+{{code}}
+```";
+
+/// Built-in prompt for comments on top-level Python script statements (`--script-comments`)
+const PYTHON_SCRIPT_TEMPLATE: &str = "\
+Write a short comment explaining what the following top-level Python script code does \
+({{item_type}}). Describe its purpose for someone reading the script, not line-by-line mechanics. \
+Return ONLY the comment text without '#' markers or indentation.
+
+```python
+{{code}}
+```";
+
+/// Built-in prompt for every other language, filled in from `doc_convention`
+const DEFAULT_TEMPLATE: &str = "\
+Generate a {{convention}} for the following {{item_type}} '{{name}}'. {{guideline}} \
+The documentation should be informative, accurate, and describe what the {{item_type}} does.\
+Return ONLY the documentation text without comment markers, quotes, or indentation.
+
+```{{language}}
+{{code}}
+```";
+
+/// Documentation convention and style guideline used when prompting for a language
+pub fn doc_convention(language: &str) -> (&'static str, &'static str) {
+    match language {
+        "rust" => ("Rust doc comment", "Follow rustdoc conventions."),
+        "javascript" => ("JSDoc comment", "Use JSDoc tags such as `@param {type} name - description` and `@returns {type} description`."),
+        "typescript" => ("TSDoc comment", "Use tags such as @param and @returns."),
+        "openapi" => ("OpenAPI description", "Write plain text suitable for a `description` field."),
+        "protobuf" => ("Protocol Buffers comment", "Describe the purpose of the message, field, service, or RPC."),
+        "objc" => ("HeaderDoc comment", "Use AppleDoc/HeaderDoc tags such as @param and @return."),
+        "scala" => ("Scaladoc comment", "Use Scaladoc tags such as @param, @tparam, and @return, with one @param per parameter across all parameter lists."),
+        "java" => ("Javadoc comment", "Start with a summary sentence, then one @param per parameter, @return unless the method returns void, and one @throws per exception it declares or throws."),
+        "groovy" => ("Groovydoc comment", "Use Groovydoc tags such as @param and @return. For a pipeline step (a `call` method), describe how it is used in a Jenkinsfile."),
+        "powershell" => ("comment-based help block", "Use a .SYNOPSIS line, a .DESCRIPTION, one .PARAMETER <Name> section per parameter, and at least one .EXAMPLE."),
+        "fortran" => ("Doxygen Fortran header comment", "Start with a one-line summary, then one @param line per dummy argument noting whether it is input or output, and @return for functions."),
+        "matlab" => ("MATLAB help text", "Start with an H1 line: the function name in capitals followed by a one-line summary (e.g. 'MEAN_FLUX Average flux over a grid'). Then describe the calling syntax, inputs, and outputs."),
+        "c" | "cpp" => ("Doxygen comment", "Start with @brief and a one-line summary, then one @param per parameter and @return unless the function returns void."),
+        "cuda" | "opencl" => ("Doxygen comment", "Use Doxygen tags such as @param and @return. For a kernel, explain the thread/block (work-item/work-group) mapping and the memory space of pointer parameters."),
+        "solidity" => ("NatSpec comment", "Start with @notice explaining the behavior to callers of public and external functions, or @dev for internal and private ones, then one @param per parameter and one @return per return value."),
+        "swift" => ("Swift documentation comment", "Write Swift markup without the /// prefix: a summary sentence, then one `- Parameter name:` line per parameter, `- Returns:` unless it returns Void, and `- Throws:` if it throws."),
+        "bash" => ("shell function header comment", "Write plain text without # prefixes: a one-line description, then `Globals:`, `Arguments:`, `Outputs:`, and `Returns:` sections with entries indented two spaces (`None` when there are none): the global variables the function reads or modifies, each positional argument as `$1 description`, what it writes to stdout or stderr, and its exit status."),
+        "sql" => ("SQL comment block", "Write plain text without -- prefixes: a one-line summary of the routine's purpose, then one 'name: description' line per parameter (saying which are OUT or INOUT), and for functions a 'Returns:' line describing the value, or the columns of the rows a set-returning function produces."),
+        "csharp" => ("C# XML documentation comment", "Write only the XML elements, without the /// prefix: <summary>, one <param name=\"...\"> per parameter, and <returns> for methods that return a value."),
+        "vbnet" => ("VB.NET XML documentation comment", "Write only the XML elements, without the ''' prefix: <summary>, one <param name=\"...\"> per parameter, and <returns> for Functions."),
+        "vbscript" => ("VBScript comment block", "Write plain text without the ' prefix: a one-line summary, then one 'name: description' line per parameter and a 'Returns:' line for Functions."),
+        "cmake" => ("CMake comment header", "Write plain text without # prefixes: a one-line summary of the purpose, an 'Arguments:' section with one indented 'NAME - description' line per argument and keyword (noting which are optional), and a 'Usage:' section with an indented example call."),
+        "makefile" => ("Makefile comment header", "Write plain text without # prefixes: a one-line summary of what the target builds or does, a 'Variables:' section with one indented 'NAME - description' line per variable the target reads that can be overridden, and a 'Usage:' section with an indented example `make` invocation."),
+        "dockerfile" => ("Dockerfile comment", "Write one or two short lines of plain text without # prefixes for an infrastructure reviewer: why a build stage exists and what it produces, what a build argument controls and its default, or what an exposed port serves."),
+        "compose" => ("docker-compose comment", "Write one or two short lines of plain text without # prefixes for an infrastructure reviewer: what the service does and how the other services use it."),
+        "ansible" => ("Ansible task name or role header", "For a task or block, write one short imperative phrase for its `name:` field (e.g. 'Install nginx from the distribution repositories'), based on the module and parameters shown. For a role, write plain text without # prefixes: a one-line summary, then the role's main steps and the variables it expects."),
+        "helm" => ("helm-docs value description", "Write one short sentence of plain text, without # or -- prefixes, describing what the value configures for someone installing the chart. Mention accepted values or units when the key name doesn't make them obvious."),
+        "github-actions" => ("GitHub Actions documentation", "For a workflow, write a header comment of plain text without # prefixes: a one-line summary, then what triggers it and what each job does. For an action, input, output, or secret, write one short sentence for its `description` field."),
+        "json-schema" => ("JSON Schema description", "Write one or two sentences of plain text for a `description` key: what the schema represents or what the property holds, with units, formats, or accepted values when the name and type don't make them obvious."),
+        "cli" => ("command-line help string", "Write one short sentence suitable for --help output, without a trailing period."),
+        _ => ("Python docstring", "Follow PEP 257 style guidelines."),
+    }
+}
+
+/// Build the system prompt for a language
+pub fn system_prompt(language: &str) -> String {
+    if language == "python" {
+        return "You are a Python documentation assistant. Generate clear, concise, and accurate docstrings for Python code.".to_string();
+    }
+    
+    let (convention, _) = doc_convention(language);
+    format!(
+        "You are a documentation assistant. Generate clear, concise, and accurate {}s.",
+        convention
+    )
+}
+
+/// Template for an item: the project's template for its language and item type (e.g.
+/// `python.class`), then for its language, then the built-in one
+fn template<'a>(item: &CodeItem, language: &str, config: &'a Config) -> &'a str {
+    config.prompt_templates.get(&format!("{}.{}", language, item.item_type))
+        .or_else(|| config.prompt_templates.get(language))
+        .map(|template| template.as_str())
+        .unwrap_or(match language {
+            "python" if item.is_script_statement() => PYTHON_SCRIPT_TEMPLATE,
+            "python" if item.item_type == ItemKind::Module => PYTHON_MODULE_TEMPLATE,
+            "python" => PYTHON_TEMPLATE,
+            "rust" if item.item_type == ItemKind::Module => RUST_MODULE_TEMPLATE,
+            _ => DEFAULT_TEMPLATE,
+        })
+}
+
+/// Fill in a template's placeholders; unknown ones are left as written
+fn render(template: &str, values: &[(&str, &str)]) -> String {
+    let placeholder_re = Regex::new(r"\{\{\s*(\w+)\s*\}\}|\{(item_type|name|language|code)\}").unwrap();
+    placeholder_re.replace_all(template, |caps: &Captures| {
+        let key = caps.get(1).or_else(|| caps.get(2)).map_or("", |m| m.as_str());
+        values.iter()
+            .find(|(name, _)| *name == key)
+            .map_or_else(|| caps[0].to_string(), |(_, value)| value.to_string())
+    }).into_owned()
+}
+
+/// Check that a template only uses known placeholders
+pub fn check_template(template: &str) -> Result<(), String> {
+    let placeholder_re = Regex::new(r"\{\{\s*(\w+)\s*\}\}").unwrap();
+    for caps in placeholder_re.captures_iter(template) {
+        if !PLACEHOLDERS.contains(&&caps[1]) {
+            return Err(format!("unknown placeholder {{{{{}}}}}; expected one of {}", &caps[1], PLACEHOLDERS.join(", ")));
+        }
+    }
+    Ok(())
+}
+
+/// Build the user prompt requesting documentation for a code item
+pub fn item_prompt(item: &CodeItem, language: &str, config: &Config) -> String {
+    let template = template(item, language, config);
+    
+    // The style instruction goes where the template asks for it, or at the end
+    let style = config.docstring_style
+        .filter(|_| language == "python" && !item.is_script_statement() && !config.summary_only)
+        .map(|style| format!("\n\n{}", style.instructions()))
+        .unwrap_or_default();
+    let (convention, guideline) = doc_convention(language);
+    // Parameters are given with their types and defaults where the parser knows them
+    let params = if item.parameter_details.is_empty() {
+        item.parameters.join(", ")
+    } else {
+        item.parameter_details.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(", ")
+    };
+    let returns = item.returns.clone().unwrap_or_default();
+    // A module is described by an outline of its public API rather than all of its code
+    let outline;
+    let code = match language {
+        "python" if item.item_type == ItemKind::Module => {
+            outline = crate::lang::python::module_outline(&item.code);
+            &outline
+        },
+        "rust" if item.item_type == ItemKind::Module => {
+            outline = crate::lang::rust::module_outline(&item.code);
+            &outline
+        },
+        _ => &item.code,
+    };
+    let mut prompt = render(template, &[
+        ("item_type", item.item_type.as_str()),
+        ("name", &item.name),
+        ("language", language),
+        ("params", &params),
+        ("returns", &returns),
+        ("code", code),
+        ("style", style.trim_start()),
+        ("convention", convention),
+        ("guideline", guideline),
+    ]);
+    
+    // GPU kernels get the launch dimensions and memory spaces their code implies
+    if item.item_type == ItemKind::Kernel {
+        prompt.push_str(&crate::lang::gpu::kernel_context(&item.code, language));
+    }
+    
+    // Shell functions take their arguments and globals implicitly, which the header lists
+    if language == "bash" && !config.summary_only {
+        prompt.push_str(&crate::lang::bash::function_context(&item.code));
+    }
+    
+    // Coroutines and generators are used differently from plain functions, which the
+    // documentation needs to say
+    if !config.summary_only {
+        prompt.push_str(match (item.is_async, item.is_generator) {
+            (true, true) => "\n\nThis is an asynchronous generator: say that callers iterate it with `async for`, \
+                and describe the values it yields in a Yields section (or the style's equivalent) instead of Returns.",
+            (true, false) => "\n\nThis is a coroutine function: say that callers must await it, and describe what \
+                the awaited call returns rather than the coroutine object.",
+            (false, true) => "\n\nThis is a generator: describe the values it yields in a Yields section (or the \
+                style's equivalent) instead of Returns, and what it returns when exhausted, if anything.",
+            (false, false) => "",
+        });
+    }
+    
+    // Checked exceptions are part of the signature callers must handle
+    if !item.throws.is_empty() && !config.summary_only {
+        let throws: Vec<String> = item.throws.iter().map(|t| format!("`{}`", t)).collect();
+        prompt.push_str(&format!("\n\nIt declares that it throws {}.", throws.join(", ")));
+    }
+    
+    // Decorators can change how the item is called, or what it is
+    if !item.decorators.is_empty() {
+        let decorators: Vec<String> = item.decorators.iter().map(|d| format!("`@{}`", d)).collect();
+        prompt.push_str(&format!("\n\nIt is decorated with {}.", decorators.join(", ")));
+    }
+    if language == "python" && item.item_type == ItemKind::Property && !config.summary_only {
+        prompt.push_str("\n\nThis is a property, read like an attribute: describe the value it holds, \
+            and what assigning to it does if it has a setter, without Args or Returns sections.");
+    }
+    if item.item_type == ItemKind::Constant && !config.summary_only {
+        prompt.push_str("\n\nThis is a constant: in a sentence or two, say what its value means and why it is \
+            that value, with its units if it has any, rather than restating it. Add no sections.");
+    }
+    
+    // TypeScript signatures give the types, which tags repeat only in the JSDoc style
+    if language == "typescript" && !config.summary_only {
+        let type_parameters: Vec<String> = crate::lang::typescript::type_parameters(&item.name, &item.code)
+            .iter()
+            .map(|t| format!("`{}`", t))
+            .collect();
+        match type_parameters.len() {
+            0 => {},
+            1 => prompt.push_str(&format!("\n\nIt has the type parameter {}; say what it stands for.", type_parameters[0])),
+            _ => prompt.push_str(&format!("\n\nIt has the type parameters {}; say what each stands for.", type_parameters.join(", "))),
+        }
+        prompt.push_str("\n\n");
+        prompt.push_str(config.ts_tag_style.instructions());
+    }
+    
+    // React components and hooks are used by rendering and calling them from components,
+    // not like plain functions
+    if item.item_type == ItemKind::Component && !config.summary_only {
+        let (tags, returns) = if language == "typescript" && config.ts_tag_style == TagStyle::Tsdoc {
+            ("a `@param props` tag, then a `@param props.name` tag", "")
+        } else {
+            ("a `@param {object} props` tag, then a `@param {type} props.name` tag", " Add `@returns {JSX.Element}`.")
+        };
+        prompt.push_str(&format!("\n\nThis is a React component: describe what it renders and how its props \
+            change that, rather than what it computes. Document its props with {} for each prop it reads, \
+            saying which are optional and what they default to.{}", tags, returns));
+    }
+    if item.item_type == ItemKind::Hook && !config.summary_only {
+        prompt.push_str("\n\nThis is a React hook: describe the state or behavior it gives the component that \
+            calls it, what it returns, and any effects it runs and when they re-run. Note that, like any hook, \
+            it must be called at the top level of a function component or another hook.");
+    }
+    
+    // Rust examples are compiled and run by `cargo test --doc`
+    if config.with_examples && language == "rust" && !config.summary_only && crate::lang::rust::takes_example(item) {
+        prompt.push_str("\n\nInclude a `# Examples` section with a ```rust code block that compiles and passes as \
+            a doctest: bring the item into scope with a `use` of its path from the crate root, show a typical \
+            call, and check its result with `assert_eq!` or `assert!`. Hide setup lines that aren't part of \
+            the example by starting them with `# `.");
+    }
+    
+    // Existing documentation is revised rather than replaced, so hand-written examples
+    // and notes survive. Its fence is longer than the code fences it may contain.
+    if let Some(existing) = item.existing_docstring.as_deref().filter(|_| !config.replace) {
+        prompt.push_str(&format!("\n\nIt already has this documentation, which may be out of date:\n\n\
+            ````\n{}\n````\n\nRevise it rather than writing new documentation: update only what no longer \
+            matches the code, such as the parameters, return value, and errors, and keep the rest of the \
+            text, including examples and notes, as written.", existing.trim()));
+    }
+    
+    // Say what the project's other types and functions that the item uses are
+    if let Some(symbols) = &config.symbols {
+        prompt.push_str(&symbols.context(item));
+    }
+    if !Regex::new(r"\{\{\s*style\s*\}\}").unwrap().is_match(template) {
+        prompt.push_str(&style);
+    }
+    if config.summary_only {
+        prompt.push_str("\n\nWrite ONLY a one-line summary of what it does, leaving out any sections asked \
+            for above (parameters, return values, errors, examples).");
+    } else if let Some(max_lines) = config.max_doc_lines {
+        prompt.push_str(&format!("\n\nKeep the documentation to at most {} lines.", max_lines));
+    }
+    for instruction in &config.extra_instructions {
+        prompt.push_str("\n\n");
+        prompt.push_str(instruction);
+    }
+    
+    prompt
+}
+
+/// Build one prompt requesting documentation for several items, answered as a JSON object
+pub fn batch_prompt(items: &[(usize, &CodeItem)], language: &str, config: &Config) -> String {
+    let mut prompt = format!(
+        "Document each of the following {} items. Respond with a JSON object of the form \
+        {{\"docstrings\": [{{\"index\": <item index>, \"docstring\": \"<documentation text>\"}}]}} \
+        containing exactly one entry per item.",
+        items.len()
+    );
+    for (index, item) in items {
+        prompt.push_str(&format!("\n\n## Item {}\n\n{}", index, item_prompt(item, language, config)));
+    }
+    prompt
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::docstring::DocstringIssue;
+use crate::error::DocGenResult;
+use crate::history::{fnv1a, item_key};
+use crate::parser::ParsedCode;
+
+/// Name of the file kept in the repository root that records generated documentation
+const PROVENANCE_FILE: &str = ".docgen-generated.json";
+
+/// How a docstring was generated
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct GeneratedRecord {
+    /// Model the docstring came from, or "docgen" for mechanical style fixes
+    model: String,
+    /// When it was written, in seconds since the Unix epoch
+    generated_at: u64,
+    /// Hash of the docstring as written, which stops matching once someone edits it
+    docstring_hash: u64,
+}
+
+/// Docstrings written by DocGen, keyed by file then item. Documentation without a
+/// record, or whose text no longer matches its record, was written or edited by a
+/// person.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Provenance {
+    files: BTreeMap<String, BTreeMap<String, GeneratedRecord>>,
+}
+
+impl Provenance {
+    /// Path of the provenance file for a repository root
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(PROVENANCE_FILE)
+    }
+    
+    /// Load the records, starting fresh if the file is missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable provenance file {}: {}", path.display(), e);
+                Provenance::default()
+            }),
+            Err(_) => Provenance::default(),
+        }
+    }
+    
+    /// Write the records back to disk
+    pub fn save(&self, path: &Path) -> DocGenResult<()> {
+        let content = serde_json::to_string_pretty(self).unwrap_or_default();
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+    
+    /// Drop the issues of items whose documentation a person wrote or edited, so it is
+    /// reported but never overwritten, returning how many were dropped. Missing
+    /// documentation and unedited generated documentation are left to fix.
+    pub fn protect(&self, file_key: &str, parsed_code: &ParsedCode, issues: &mut Vec<DocstringIssue>) -> usize {
+        let records = self.files.get(file_key);
+        let before = issues.len();
+        issues.retain(|issue| {
+            let item = &parsed_code.items[issue.item_index];
+            match &item.existing_docstring {
+                Some(docstring) => records
+                    .and_then(|records| records.get(&item_key(item)))
+                    .is_some_and(|record| record.docstring_hash == fnv1a(docstring)),
+                None => true,
+            }
+        });
+        before - issues.len()
+    }
+    
+    /// Record the docstrings just written, given the file as written and the index and
+    /// model of each generated item in it
+    pub fn record(&mut self, file_key: &str, parsed_code: &ParsedCode, generated: &[(usize, String)]) {
+        let generated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let records = self.files.entry(file_key.to_string()).or_default();
+        
+        for (index, model) in generated {
+            let item = &parsed_code.items[*index];
+            if let Some(docstring) = &item.existing_docstring {
+                records.insert(item_key(item), GeneratedRecord {
+                    model: model.clone(),
+                    generated_at,
+                    docstring_hash: fnv1a(docstring),
+                });
+            }
+        }
+    }
+}
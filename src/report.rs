@@ -0,0 +1,501 @@
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::budget::Budget;
+use crate::coverage::Counts;
+use crate::docstring::{DocstringIssue, IssueKind};
+use crate::parser::{ItemKind, SyntaxError};
+use crate::rules::Reason;
+
+/// How the issue summary table groups its rows
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum GroupBy {
+    /// One table per file, one row per rule (missing-docstring, undocumented-param, ...)
+    Rule,
+    /// One table for the whole run, one row per file
+    File,
+    /// One table per file, one row per item type
+    Type,
+}
+
+/// How the results of a run are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Colored tables and progress messages
+    Text,
+    /// A single JSON document for CI and other tools
+    Json,
+    /// Aligned plain text without colors or symbols, one line per issue, for screen
+    /// readers and for pasting where formatting is stripped
+    Plain,
+}
+
+/// Which issues make `--check` fail
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum FailOn {
+    /// Only items with no documentation
+    Missing,
+    /// Only documentation that looks out of date, or whose item changed since it was written
+    Outdated,
+    /// Every issue, including style issues found with --lint
+    Any,
+}
+
+impl FailOn {
+    /// Whether an issue counts towards the failure threshold
+    pub fn counts(self, issue: &DocstringIssue) -> bool {
+        match self {
+            FailOn::Missing => issue.issue_type == IssueKind::Missing,
+            FailOn::Outdated => matches!(issue.issue_type, IssueKind::Outdated | IssueKind::Changed),
+            FailOn::Any => true,
+        }
+    }
+}
+
+/// Number of issues in the run that count towards the `--check` failure threshold
+pub fn count_failing(reports: &[FileReport], fail_on: FailOn) -> usize {
+    reports.iter()
+        .flat_map(|r| &r.issues)
+        .filter(|issue| fail_on.counts(issue))
+        .count()
+}
+
+/// An item that received new or updated documentation during a run
+#[derive(Debug, Clone)]
+pub struct DocumentedItem {
+    pub item_type: ItemKind,
+    pub name: String,
+    pub parent: Option<String>,
+    pub issue_type: IssueKind,    // The issue the documentation fixed
+}
+
+/// Outcome of processing a single file
+#[derive(Debug)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub total_items: usize,
+    /// Items with any documentation, whether or not it needs updating, counted as
+    /// `--coverage` counts them
+    pub documented_items: usize,
+    pub issues: Vec<DocstringIssue>,
+    pub documented: Vec<DocumentedItem>,
+    pub tokens_used: u64,
+    /// Unified diff of the proposed changes, in diff mode
+    pub diff: String,
+    /// Proposed changes awaiting approval, in review mode
+    pub proposed: Vec<crate::review::ReviewItem>,
+    /// Generated documentation text by item index, once generated
+    pub suggestions: BTreeMap<usize, String>,
+    /// Items left undocumented because the run's budget ran out
+    pub unfinished: usize,
+    /// Syntax errors parsed around; items inside them weren't analyzed
+    pub syntax_errors: Vec<SyntaxError>,
+}
+
+impl FileReport {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            total_items: 0,
+            documented_items: 0,
+            issues: Vec::new(),
+            documented: Vec::new(),
+            tokens_used: 0,
+            diff: String::new(),
+            proposed: Vec::new(),
+            suggestions: BTreeMap::new(),
+            unfinished: 0,
+            syntax_errors: Vec::new(),
+        }
+    }
+    
+    /// Number of issues with the given severity
+    pub fn count_severity(&self, severity: &str) -> usize {
+        self.issues.iter().filter(|i| i.severity() == severity).count()
+    }
+    
+    /// Documented and total item counts
+    pub fn counts(&self) -> Counts {
+        Counts { documented: self.documented_items, items: self.total_items }
+    }
+    
+    /// Percentage of items with documentation, as `--coverage` reports it
+    pub fn coverage(&self) -> f64 {
+        self.counts().percent()
+    }
+}
+
+/// Color a padded severity label so column alignment is unaffected by escape codes
+fn severity_label(severity: &str, width: usize) -> String {
+    let padded = format!("{:<width$}", severity, width = width);
+    match severity {
+        "error" => padded.red().bold().to_string(),
+        "info" => padded.blue().to_string(),
+        _ => padded.yellow().to_string(),
+    }
+}
+
+/// Format a coverage percentage, colored by how complete it is
+pub fn coverage_label(coverage: f64, width: usize) -> String {
+    let padded = format!("{:>width$}", format!("{:.1}%", coverage), width = width);
+    if coverage >= 90.0 {
+        padded.green().to_string()
+    } else if coverage >= 50.0 {
+        padded.yellow().to_string()
+    } else {
+        padded.red().to_string()
+    }
+}
+
+/// Render the issue table for a single file, grouped by rule or by item type
+pub fn file_table(report: &FileReport, group_by: GroupBy) -> String {
+    let mut table = String::new();
+    
+    match group_by {
+        GroupBy::Type => {
+            // item type -> (missing, outdated, style)
+            let mut rows: BTreeMap<&str, (usize, usize, usize)> = BTreeMap::new();
+            for issue in &report.issues {
+                let row = rows.entry(issue.item_type.as_str()).or_default();
+                match issue.issue_type {
+                    IssueKind::Missing => row.0 += 1,
+                    IssueKind::Style => row.2 += 1,
+                    IssueKind::Outdated | IssueKind::Changed => row.1 += 1,
+                }
+            }
+            
+            let width = rows.keys().map(|t| t.len()).chain(["TYPE".len()]).max().unwrap_or(0);
+            let header = format!("{:<width$}  {:>7}  {:>8}  {:>5}", "TYPE", "MISSING", "OUTDATED", "STYLE", width = width);
+            table.push_str(&format!("  {}\n", header.bold()));
+            for (item_type, (missing, outdated, style)) in rows {
+                table.push_str(&format!("  {:<width$}  {:>7}  {:>8}  {:>5}\n", item_type, missing, outdated, style, width = width));
+            }
+        },
+        GroupBy::Rule | GroupBy::File => {
+            // (severity, rule) -> count, errors first; issues count once per rule broken
+            let mut rows: BTreeMap<(&str, String), usize> = BTreeMap::new();
+            for issue in &report.issues {
+                let broken: BTreeMap<_, _> = issue.reasons.iter().map(|r| (r.rule, r.severity)).collect();
+                for (rule, severity) in broken {
+                    *rows.entry((severity.as_str(), rule.to_string())).or_default() += 1;
+                }
+                if issue.reasons.is_empty() {
+                    *rows.entry((issue.severity(), issue.issue_type.to_string())).or_default() += 1;
+                }
+            }
+            
+            let width = rows.keys().map(|(_, r)| r.len()).chain(["RULE".len()]).max().unwrap_or(0);
+            let header = format!("{:<8}  {:<width$}  {:>5}", "SEVERITY", "RULE", "COUNT", width = width);
+            table.push_str(&format!("  {}\n", header.bold()));
+            for ((severity, rule), count) in rows {
+                table.push_str(&format!("  {}  {:<width$}  {:>5}\n", severity_label(severity, 8), rule, count, width = width));
+            }
+        },
+    }
+    
+    table.push_str(&format!("  coverage: {} ({}/{} items documented)\n",
+        coverage_label(report.coverage(), 0),
+        report.documented_items,
+        report.total_items));
+    table
+}
+
+/// Render one table for the whole run with a row per file and a total row
+pub fn files_table(reports: &[FileReport]) -> String {
+    let paths: Vec<String> = reports.iter().map(|r| r.path.display().to_string()).collect();
+    let width = paths.iter().map(|p| p.len()).chain(["TOTAL".len()]).max().unwrap_or(0);
+    
+    let header = format!("{:<width$}  {:>6}  {:>8}  {:>8}", "FILE", "ERRORS", "WARNINGS", "COVERAGE", width = width);
+    let mut table = format!("{}\n", header.bold());
+    
+    for (report, path) in reports.iter().zip(&paths) {
+        table.push_str(&format!("{:<width$}  {:>6}  {:>8}  {}\n",
+            path,
+            report.count_severity("error"),
+            report.count_severity("warning"),
+            coverage_label(report.coverage(), 8),
+            width = width));
+    }
+    
+    let total = FileReport {
+        path: PathBuf::new(),
+        total_items: reports.iter().map(|r| r.total_items).sum(),
+        documented_items: reports.iter().map(|r| r.documented_items).sum(),
+        issues: reports.iter().flat_map(|r| r.issues.iter().cloned()).collect(),
+        documented: Vec::new(),
+        tokens_used: 0,
+        diff: String::new(),
+        proposed: Vec::new(),
+        suggestions: BTreeMap::new(),
+        unfinished: 0,
+        syntax_errors: Vec::new(),
+    };
+    table.push_str(&format!("{}  {:>6}  {:>8}  {}\n",
+        format!("{:<width$}", "TOTAL", width = width).bold(),
+        total.count_severity("error"),
+        total.count_severity("warning"),
+        coverage_label(total.coverage(), 8)));
+    table
+}
+
+/// Render a short Markdown summary of what was documented, grouped by module
+/// (the directory containing each file), suitable for a PR description
+pub fn change_summary(reports: &[FileReport]) -> String {
+    let documented_files: Vec<&FileReport> = reports.iter()
+        .filter(|r| !r.documented.is_empty())
+        .collect();
+    
+    if documented_files.is_empty() {
+        return "No documentation changes were made.\n".to_string();
+    }
+    
+    let total: usize = documented_files.iter().map(|r| r.documented.len()).sum();
+    let mut summary = format!(
+        "Documented {} item{} across {} file{}.\n",
+        total,
+        if total == 1 { "" } else { "s" },
+        documented_files.len(),
+        if documented_files.len() == 1 { "" } else { "s" },
+    );
+    
+    let mut modules: BTreeMap<String, Vec<&FileReport>> = BTreeMap::new();
+    for report in documented_files {
+        let module = report.path.parent()
+            .map(|p| p.display().to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+        modules.entry(module).or_default().push(report);
+    }
+    
+    for (module, files) in modules {
+        summary.push_str(&format!("\n### {}\n", module));
+        for report in files {
+            let file_name = report.path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| report.path.display().to_string());
+            
+            let mut parts = Vec::new();
+            for (issue_type, verb) in [
+                (IssueKind::Missing, "added docs for"),
+                (IssueKind::Outdated, "updated docs for"),
+                (IssueKind::Changed, "refreshed docs after signature changes for"),
+                (IssueKind::Style, "fixed docstring style for"),
+            ] {
+                let names: Vec<String> = report.documented.iter()
+                    .filter(|item| item.issue_type == issue_type)
+                    .map(|item| match &item.parent {
+                        Some(parent) => format!("`{}.{}` ({})", parent, item.name, item.item_type),
+                        None => format!("`{}` ({})", item.name, item.item_type),
+                    })
+                    .collect();
+                if !names.is_empty() {
+                    parts.push(format!("{} {}", verb, names.join(", ")));
+                }
+            }
+            
+            summary.push_str(&format!("- `{}`: {}\n", file_name, parts.join("; ")));
+        }
+    }
+    
+    summary
+}
+
+/// Render what a dry run (`--dry-run`) would have changed: a row per file with the
+/// docstrings it would add and update, the run's totals, and the requests, tokens,
+/// estimated cost, and time of each provider that answered
+pub fn dry_run_summary(reports: &[FileReport], budget: &Budget, elapsed: Duration) -> String {
+    let paths: Vec<String> = reports.iter().map(|r| r.path.display().to_string()).collect();
+    let width = paths.iter().map(|p| p.len()).chain(["TOTAL".len()]).max().unwrap_or(0);
+    let counts = |report: &FileReport| {
+        let added = report.documented.iter().filter(|item| item.issue_type == IssueKind::Missing).count();
+        (added, report.documented.len() - added)
+    };
+    
+    let header = format!("{:<width$}  {:>5}  {:>5}  {:>6}  {:>8}", "FILE", "ITEMS", "ADD", "UPDATE", "TOKENS", width = width);
+    let mut summary = format!("{}\n", header.bold());
+    for (report, path) in reports.iter().zip(&paths) {
+        let (added, updated) = counts(report);
+        summary.push_str(&format!("{:<width$}  {:>5}  {:>5}  {:>6}  {:>8}\n",
+            path, report.total_items, added, updated, report.tokens_used, width = width));
+    }
+    let items: usize = reports.iter().map(|r| r.total_items).sum();
+    let (added, updated) = reports.iter().map(counts).fold((0, 0), |(a, u), (added, updated)| (a + added, u + updated));
+    summary.push_str(&format!("{}  {:>5}  {:>5}  {:>6}  {:>8}\n",
+        format!("{:<width$}", "TOTAL", width = width).bold(),
+        items,
+        added,
+        updated,
+        reports.iter().map(|r| r.tokens_used).sum::<u64>()));
+    
+    summary.push_str(&format!("\nFiles scanned:         {}\n", reports.len()));
+    summary.push_str(&format!("Items analyzed:        {}\n", items));
+    summary.push_str(&format!("Docstrings to add:     {}\n", added));
+    summary.push_str(&format!("Docstrings to update:  {}\n", updated));
+    summary.push_str(&format!("Tokens used:           {}\n", budget.tokens()));
+    summary.push_str(&format!("Estimated cost:        ${:.4}\n", budget.cost()));
+    summary.push_str(&format!("Elapsed:               {:.1}s\n", elapsed.as_secs_f64()));
+    
+    let providers = budget.providers();
+    if !providers.is_empty() {
+        let width = providers.keys().map(|p| p.len()).chain(["PROVIDER".len()]).max().unwrap_or(0);
+        let header = format!("{:<width$}  {:>8}  {:>8}  {:>9}  {:>7}", "PROVIDER", "REQUESTS", "TOKENS", "COST", "TIME", width = width);
+        summary.push_str(&format!("\n{}\n", header.bold()));
+        for (provider, usage) in providers {
+            summary.push_str(&format!("{:<width$}  {:>8}  {:>8}  {:>9}  {:>7}\n",
+                provider,
+                usage.requests,
+                usage.tokens,
+                format!("${:.4}", usage.cost),
+                format!("{:.1}s", usage.elapsed.as_secs_f64()),
+                width = width));
+        }
+    }
+    summary
+}
+
+/// Render the results of a run as plain text, printed by `--format plain`: one line
+/// per issue with its severity spelled out and the file's full path, in aligned
+/// columns without colors or symbols, then the totals in words
+pub fn plain(reports: &[FileReport]) -> String {
+    let mut rows: Vec<[String; 5]> = Vec::new();
+    for report in reports {
+        let path = std::path::absolute(&report.path).unwrap_or_else(|_| report.path.clone());
+        for error in &report.syntax_errors {
+            rows.push([
+                "error".to_string(),
+                format!("{} line {}", path.display(), error.line),
+                String::new(),
+                "syntax error".to_string(),
+                error.message.clone(),
+            ]);
+        }
+        for issue in &report.issues {
+            let details: Vec<String> = issue.reasons.iter().map(|r| r.to_string()).collect();
+            rows.push([
+                issue.severity().to_string(),
+                format!("{} line {}", path.display(), issue.line_number),
+                format!("{} {}", issue.item_type, issue.name),
+                issue.issue_type.to_string(),
+                details.join(", "),
+            ]);
+        }
+    }
+    
+    let total = FileReport {
+        total_items: reports.iter().map(|r| r.total_items).sum(),
+        documented_items: reports.iter().map(|r| r.documented_items).sum(),
+        issues: reports.iter().flat_map(|r| r.issues.iter().cloned()).collect(),
+        ..FileReport::new(Path::new(""))
+    };
+    let mut text = String::new();
+    if rows.is_empty() {
+        text.push_str("No documentation issues found.\n");
+    } else {
+        let header = ["SEVERITY", "LOCATION", "ITEM", "ISSUE", "DETAILS"].map(String::from);
+        let mut widths = [0; 5];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        for row in std::iter::once(&header).chain(&rows) {
+            let line = row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ");
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        let errors = total.count_severity("error") + reports.iter().map(|r| r.syntax_errors.len()).sum::<usize>();
+        text.push_str(&format!("\n{} in {}: {}, {}, {} info.\n",
+            counted(rows.len(), "issue"),
+            counted(reports.len(), "file"),
+            counted(errors, "error"),
+            counted(total.count_severity("warning"), "warning"),
+            total.count_severity("info")));
+    }
+    text.push_str(&format!("Coverage {:.1} percent: {} of {} items documented.\n",
+        total.coverage(),
+        total.documented_items,
+        total.total_items));
+    
+    let documented: usize = reports.iter().map(|r| r.documented.len()).sum();
+    if documented > 0 {
+        text.push_str(&format!("Documented {}.\n", counted(documented, "item")));
+    }
+    text
+}
+
+/// A count followed by a noun, plural unless the count is one
+fn counted(count: usize, noun: &str) -> String {
+    format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+}
+
+/// Machine-readable results of a run, printed by `--format json`. Check mode and the
+/// generating modes share this shape; suggestions are filled in once generated.
+#[derive(Serialize)]
+struct RunRecord<'a> {
+    files: Vec<FileRecord<'a>>,
+    issues: Vec<IssueRecord<'a>>,
+}
+
+/// Totals for one file
+#[derive(Serialize)]
+struct FileRecord<'a> {
+    file: &'a Path,
+    items: usize,
+    issues: usize,
+    coverage: f64,
+    documented: usize,
+    tokens_used: u64,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    syntax_errors: &'a [SyntaxError],
+}
+
+/// One documentation issue, with the documentation generated for it if any
+#[derive(Serialize)]
+struct IssueRecord<'a> {
+    file: &'a Path,
+    name: &'a str,
+    item_type: ItemKind,
+    line: usize,
+    issue: IssueKind,
+    severity: &'static str,
+    reasons: &'a [Reason],
+    suggestion: Option<&'a str>,
+}
+
+/// Render the results of a run as a JSON document
+pub fn json(reports: &[FileReport]) -> String {
+    let record = RunRecord {
+        files: reports.iter()
+            .map(|report| FileRecord {
+                file: &report.path,
+                items: report.total_items,
+                issues: report.issues.len(),
+                coverage: (report.coverage() * 10.0).round() / 10.0,
+                documented: report.documented.len(),
+                tokens_used: report.tokens_used,
+                syntax_errors: &report.syntax_errors,
+            })
+            .collect(),
+        issues: reports.iter()
+            .flat_map(|report| report.issues.iter().map(move |issue| IssueRecord {
+                file: &report.path,
+                name: &issue.name,
+                item_type: issue.item_type,
+                line: issue.line_number,
+                issue: issue.issue_type,
+                severity: issue.severity(),
+                reasons: &issue.reasons,
+                suggestion: report.suggestions.get(&issue.item_index).map(|s| s.as_str()),
+            }))
+            .collect(),
+    };
+    serde_json::to_string_pretty(&record).unwrap_or_default()
+}
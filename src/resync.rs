@@ -0,0 +1,135 @@
+use regex::{Captures, NoExpand, Regex};
+use std::fmt;
+
+use crate::parser::{CodeItem, ParsedCode};
+use crate::sections::Sections;
+
+/// A name that a rename left stale in documentation, and the name that replaced it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rename {
+    pub old: String,
+    pub new: String,
+}
+
+impl fmt::Display for Rename {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} → {}", self.old, self.new)
+    }
+}
+
+/// An item's documentation with its stale names replaced
+pub struct Resynced {
+    pub item_index: usize,
+    pub docstring: String,
+    pub renames: Vec<Rename>,
+}
+
+/// Replace the stale names in a file's documentation: parameters renamed since
+/// `previous` recorded an item's parameters, and references to the renamed items
+/// (functions, types, ...) of the project. Nothing else in the text changes.
+pub fn resync(
+    parsed_code: &ParsedCode,
+    previous: impl Fn(&CodeItem) -> Option<Vec<String>>,
+    renamed_items: &[Rename],
+) -> Vec<Resynced> {
+    let mut resynced = Vec::new();
+    
+    for (index, item) in parsed_code.items.iter().enumerate() {
+        let docstring = match &item.existing_docstring {
+            Some(docstring) => docstring,
+            None => continue,
+        };
+        let mut text = docstring.clone();
+        let mut renames = Vec::new();
+        
+        for rename in parameter_renames(item, docstring, previous(item)) {
+            if let Some(replaced) = replace_parameter(&text, &rename) {
+                text = replaced;
+                renames.push(rename);
+            }
+        }
+        for rename in renamed_items {
+            if let Some(replaced) = replace_reference(&text, rename) {
+                text = replaced;
+                renames.push(rename.clone());
+            }
+        }
+        
+        if !renames.is_empty() {
+            resynced.push(Resynced { item_index: index, docstring: text, renames });
+        }
+    }
+    
+    resynced
+}
+
+/// Parameters renamed since an item's documentation was written. Given the parameters
+/// it had then, each one at a position whose name is gone was renamed to the new one
+/// there; when the count changed, or only the documented parameters are known, a
+/// rename is only assumed when exactly one name is gone and one is new.
+fn parameter_renames(item: &CodeItem, docstring: &str, previous: Option<Vec<String>>) -> Vec<Rename> {
+    let current: Vec<&str> = item.parameters.iter().map(|p| parameter_name(p)).collect();
+    let recorded = previous.is_some();
+    let previous = previous.unwrap_or_else(|| {
+        Sections::parse(docstring).parameters.into_iter().map(|p| p.name).collect()
+    });
+    let previous: Vec<&str> = previous.iter().map(|p| parameter_name(p)).collect();
+    let gone = |name: &&str| !current.contains(name);
+    let new = |name: &&str| !previous.contains(name);
+    
+    let pairs: Vec<(&str, &str)> = if recorded && previous.len() == current.len() {
+        previous.iter()
+            .zip(&current)
+            .filter(|(old, new_name)| gone(old) && new(new_name))
+            .map(|(old, new_name)| (*old, *new_name))
+            .collect()
+    } else {
+        let removed: Vec<&str> = previous.iter().copied().filter(gone).collect();
+        let added: Vec<&str> = current.iter().copied().filter(new).collect();
+        match (&removed[..], &added[..]) {
+            ([old], [new_name]) => vec![(*old, *new_name)],
+            _ => Vec::new(),
+        }
+    };
+    
+    pairs.into_iter()
+        .map(|(old, new)| Rename { old: old.to_string(), new: new.to_string() })
+        .collect()
+}
+
+/// Name of a parameter without the `*` or `=` some parsers keep on it
+fn parameter_name(parameter: &str) -> &str {
+    parameter.trim_matches(|c| c == '*' || c == '=')
+}
+
+/// Pattern matching a name as a whole word
+fn word_pattern(name: &str) -> String {
+    let boundary = |c: Option<char>| if c.is_some_and(|c| c.is_alphanumeric() || c == '_') { r"\b" } else { "" };
+    format!("{}{}{}", boundary(name.chars().next()), regex::escape(name), boundary(name.chars().last()))
+}
+
+/// Replace a renamed parameter wherever the docstring names it, or None if it doesn't
+fn replace_parameter(text: &str, rename: &Rename) -> Option<String> {
+    let re = Regex::new(&word_pattern(&rename.old)).ok()?;
+    re.is_match(text).then(|| re.replace_all(text, NoExpand(&rename.new)).into_owned())
+}
+
+/// Replace the references to a renamed item, or None if there are none. Names that
+/// can only be identifiers (with an underscore, a digit, or inner capitals) are
+/// replaced everywhere; other names, which may be ordinary words, only where they are
+/// written as code: in backticks or brackets, after a `.`, or called.
+fn replace_reference(text: &str, rename: &Rename) -> Option<String> {
+    let identifier = rename.old.chars().skip(1).any(|c| c == '_' || c.is_ascii_digit() || c.is_uppercase());
+    let pattern = if identifier {
+        word_pattern(&rename.old)
+    } else {
+        format!(r"(?:[`\[.]{0}|{0}\()", word_pattern(&rename.old))
+    };
+    let re = Regex::new(&pattern).ok()?;
+    if !re.is_match(text) {
+        return None;
+    }
+    
+    // Only the name itself changes, not the code around it
+    Some(re.replace_all(text, |captures: &Captures| captures[0].replacen(&rename.old, &rename.new, 1)).into_owned())
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::discover;
+use crate::docstring::UpdatedDocstring;
+use crate::error::{DocGenError, DocGenResult};
+use crate::llm;
+use crate::parser::{CodeItem, ItemKind, ParsedCode};
+
+/// Instructions written at the top of every review file
+const REVIEW_HEADER: &str = "\
+# Proposed documentation changes. Set `accept = true` on each item to keep, edit its
+# `docstring` if needed, then apply the accepted items with `docgen apply <this file>`.
+
+";
+
+/// Proposed changes from a run, written out for review instead of being applied
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct ReviewFile {
+    /// Whether the changes document command-line arguments (`--cli-help`)
+    pub cli_help: bool,
+
+    /// Whether the changes include Python script statement comments (`--script-comments`)
+    pub script_comments: bool,
+
+    /// Whether the changes include module-level constants (`--constants`)
+    pub constants: bool,
+
+    #[serde(rename = "item")]
+    pub items: Vec<ReviewItem>,
+}
+
+/// One proposed docstring, identified by the item it documents
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReviewItem {
+    /// File with the item, relative to where the review file was written and `/`-separated
+    pub file: PathBuf,
+    pub language: String,
+    pub item_type: ItemKind,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Line the item started on when the change was proposed
+    pub line: usize,
+    #[serde(default)]
+    pub accept: bool,
+    pub docstring: String,
+}
+
+impl ReviewItem {
+    /// Review entry for a proposed docstring, awaiting approval. Python docstrings are
+    /// shown without their triple quotes, which are added back when applied.
+    pub fn new(file_path: &Path, language: &str, item: &CodeItem, updated: &UpdatedDocstring) -> Self {
+        let docstring = llm::unformat_docstring(&updated.new_docstring, language);
+        
+        Self {
+            file: PathBuf::from(discover::normalize(file_path)),
+            language: language.to_string(),
+            item_type: item.item_type,
+            name: item.name.clone(),
+            parent: item.parent.clone(),
+            line: item.line_number,
+            accept: false,
+            docstring: docstring.to_string(),
+        }
+    }
+}
+
+impl ReviewFile {
+    /// Read a review file back after it has been edited
+    pub fn load(path: &Path) -> DocGenResult<Self> {
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content)
+            .map_err(|e| DocGenError::ConfigError(format!("{}: {}", path.display(), e)))
+    }
+    
+    /// Write the review file with instructions for editing it
+    pub fn save(&self, path: &Path) -> DocGenResult<()> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| DocGenError::UpdateError(e.to_string()))?;
+        std::fs::write(path, format!("{}{}", REVIEW_HEADER, content))?;
+        Ok(())
+    }
+    
+    /// Accepted items grouped by file, in the order the files first appear
+    pub fn accepted_by_file(&self) -> Vec<(&Path, Vec<&ReviewItem>)> {
+        let mut files: Vec<(&Path, Vec<&ReviewItem>)> = Vec::new();
+        for item in self.items.iter().filter(|item| item.accept) {
+            match files.iter_mut().find(|(file, _)| *file == item.file.as_path()) {
+                Some((_, items)) => items.push(item),
+                None => files.push((item.file.as_path(), vec![item])),
+            }
+        }
+        files
+    }
+}
+
+/// Match accepted review items to the items of a freshly parsed file and turn them
+/// into updates. Items are found by type, name, and parent, preferring the one closest
+/// to the recorded line, so edits made since the review was written are tolerated.
+/// Returns the updates and the review items that no longer match anything.
+pub fn updates<'a>(parsed_code: &ParsedCode, review_items: &[&'a ReviewItem]) -> (Vec<UpdatedDocstring>, Vec<&'a ReviewItem>) {
+    let mut updates = Vec::new();
+    let mut missing = Vec::new();
+    
+    for review_item in review_items {
+        let found = parsed_code.items.iter()
+            .enumerate()
+            .filter(|(_, item)| item.item_type == review_item.item_type
+                && item.name == review_item.name
+                && item.parent == review_item.parent)
+            .min_by_key(|(_, item)| item.line_number.abs_diff(review_item.line));
+        
+        match found {
+            Some((item_index, item)) => updates.push(UpdatedDocstring {
+                item_index,
+                new_docstring: llm::format_docstring(&review_item.docstring, item, &parsed_code.language),
+                indentation: item.indentation.clone(),
+            }),
+            None => missing.push(*review_item),
+        }
+    }
+    
+    (updates, missing)
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::capabilities;
+use crate::config::Config;
+use crate::discover::Glob;
+use crate::error::DocGenResult;
+
+/// Routing log written to the repository root unless `[routing] log` is set
+const ROUTING_LOG: &str = "docsherpa-routing.jsonl";
+
+/// Provider routes from the `[routing]` table of the project configuration
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RoutingSettings {
+    /// JSON Lines file routing decisions are appended to
+    pub log: Option<PathBuf>,
+
+    /// Routes tried in order; the first one matching a file decides its provider
+    pub routes: Vec<RouteSettings>,
+}
+
+/// One `[[routing.routes]]` entry
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteSettings {
+    /// Globs of files, relative to the repository root, e.g. "payments/**"
+    pub paths: Vec<String>,
+
+    /// Provider the matching files must be sent to
+    pub provider: String,
+
+    /// Model to request; None uses the provider's default
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// A compiled route
+#[derive(Clone)]
+struct Route {
+    paths: Vec<(String, Glob)>,
+    provider: String,
+    model: Option<String>,
+}
+
+/// Rules sending sensitive paths to particular providers, such as an on-premises
+/// deployment, whatever provider the rest of the run uses
+#[derive(Clone, Default)]
+pub struct Routing {
+    routes: Vec<Route>,
+    log: Option<PathBuf>,
+}
+
+/// Where one file's requests were sent, as recorded in the routing log
+#[derive(Serialize, Debug)]
+pub struct Decision {
+    pub timestamp: u64,
+    pub file: PathBuf,
+    pub provider: String,
+    pub model: Option<String>,
+    /// Glob of the route that matched; None when the file uses the run's provider
+    pub rule: Option<String>,
+}
+
+impl Routing {
+    /// Compile the configured routes, failing on invalid globs and unknown providers.
+    /// A relative log path is resolved against `base_dir`.
+    pub fn from_settings(settings: RoutingSettings, base_dir: &Path) -> Result<Self, String> {
+        let mut routes = Vec::new();
+        for route in settings.routes {
+            let provider = route.provider.to_lowercase();
+            if provider != "mock" && capabilities::schema(&provider).is_none() {
+                return Err(format!("unknown provider '{}' in a routing rule", route.provider));
+            }
+            let paths = route.paths.into_iter()
+                .map(|pattern| Glob::new(&pattern).map(|glob| (pattern, glob)).map_err(|e| e.to_string()))
+                .collect::<Result<Vec<_>, String>>()?;
+            routes.push(Route { paths, provider, model: route.model });
+        }
+        
+        Ok(Self {
+            routes,
+            log: settings.log.map(|log| base_dir.join(log)),
+        })
+    }
+    
+    /// Whether there are no routes to apply
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+    
+    /// Path of the routing log for a repository root
+    pub fn log_path(&self, root: &Path) -> PathBuf {
+        self.log.clone().unwrap_or_else(|| root.join(ROUTING_LOG))
+    }
+    
+    /// Configuration for a file, given relative to the repository root: the run's own,
+    /// or with the provider and model of the first route matching the file. A route
+    /// takes precedence over `--provider` and the provider pool, so sensitive files
+    /// can't be sent elsewhere by accident. The model carries over only when the
+    /// provider stays the same.
+    pub fn apply(&self, config: &Config, file: &Path) -> (Config, Decision) {
+        let matched = self.routes.iter().find_map(|route| {
+            route.paths.iter()
+                .find(|(_, glob)| glob.matches(file))
+                .map(|(pattern, _)| (route, pattern))
+        });
+        
+        let mut routed = config.clone();
+        if let Some((route, _)) = matched {
+            if route.model.is_some() || route.provider != config.provider.to_lowercase() {
+                routed.model = route.model.clone();
+            }
+            routed.provider = route.provider.clone();
+            routed.scheduler = None;
+        }
+        
+        let decision = Decision {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            file: file.to_path_buf(),
+            provider: routed.provider.to_lowercase(),
+            model: routed.model.clone(),
+            rule: matched.map(|(_, pattern)| pattern.clone()),
+        };
+        (routed, decision)
+    }
+    
+    /// Append routing decisions to the log, one JSON object per line
+    pub fn record(path: &Path, decisions: &[Decision]) -> DocGenResult<()> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        for decision in decisions {
+            writeln!(file, "{}", serde_json::to_string(decision).unwrap_or_default())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::docstring::DocstringIssue;
+
+/// A named check that existing documentation is analyzed against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, ValueEnum)]
+pub enum Rule {
+    /// The item has no documentation
+    MissingDocstring,
+    /// A parameter of the signature isn't documented
+    UndocumentedParam,
+    /// A documented parameter is no longer in the signature
+    ExtraParam,
+    /// A documented parameter type doesn't match its annotation
+    ParamTypeMismatch,
+    /// What the item returns (or, for a generator, yields) isn't documented
+    MissingReturn,
+    /// A listed set of exceptions leaves out one the code raises
+    MissingRaises,
+    /// The documentation is too short to be more than a placeholder
+    ShortDocstring,
+    /// A Python docstring isn't written in the chosen `--style`
+    WrongStyle,
+    /// The signature changed since the item was last documented
+    SignatureChanged,
+    /// The summary line starts with a lowercase letter (`--lint`)
+    SummaryCapital,
+    /// The summary line starts with a third-person verb, e.g. "Returns" (`--lint`)
+    SummaryImperative,
+    /// The summary line doesn't end with a period (`--lint`)
+    SummaryPeriod,
+    /// The summary line is longer than 79 characters (`--lint`)
+    SummaryLength,
+    /// The summary line isn't followed by a blank line (`--lint`)
+    BlankLine,
+}
+
+impl Rule {
+    /// Identifier of the rule as written in reports, `--disable`, and `[rules]`
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::MissingDocstring => "missing-docstring",
+            Rule::UndocumentedParam => "undocumented-param",
+            Rule::ExtraParam => "extra-param",
+            Rule::ParamTypeMismatch => "param-type-mismatch",
+            Rule::MissingReturn => "missing-return",
+            Rule::MissingRaises => "missing-raises",
+            Rule::ShortDocstring => "short-docstring",
+            Rule::WrongStyle => "wrong-style",
+            Rule::SignatureChanged => "signature-changed",
+            Rule::SummaryCapital => "summary-capital",
+            Rule::SummaryImperative => "summary-imperative",
+            Rule::SummaryPeriod => "summary-period",
+            Rule::SummaryLength => "summary-length",
+            Rule::BlankLine => "blank-line",
+        }
+    }
+    
+    /// Severity of the rule unless the project configuration gives another: missing
+    /// documentation is an error, documentation that may be out of date a warning,
+    /// and a `--lint` style violation info
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            Rule::MissingDocstring => Severity::Error,
+            Rule::SummaryCapital | Rule::SummaryImperative | Rule::SummaryPeriod | Rule::SummaryLength | Rule::BlankLine => Severity::Info,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.id())
+    }
+}
+
+/// How serious an issue is, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Name of the severity as written in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// One way an item's documentation breaks a rule, e.g. `undocumented-param: foo`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reason {
+    pub rule: Rule,
+    /// The parameter or exception the reason is about, if any
+    pub subject: Option<String>,
+    pub severity: Severity,
+}
+
+impl Reason {
+    /// A reason for the whole item, at the rule's default severity
+    pub fn new(rule: Rule) -> Self {
+        Self { rule, subject: None, severity: rule.default_severity() }
+    }
+    
+    /// A reason about one parameter or exception, at the rule's default severity
+    pub fn about(rule: Rule, subject: &str) -> Self {
+        Self { subject: Some(subject.to_string()), ..Self::new(rule) }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.subject {
+            Some(subject) => write!(f, "{}: {}", self.rule, subject),
+            None => write!(f, "{}", self.rule),
+        }
+    }
+}
+
+impl Serialize for Reason {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// What a rule is set to in the `[rules]` table of the project configuration
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+/// Which rules are checked and how serious breaking each one is, from the `[rules]`
+/// table of the project configuration and `--disable`
+#[derive(Clone, Default)]
+pub struct Rules {
+    levels: HashMap<Rule, RuleLevel>,
+}
+
+impl Rules {
+    /// Read the configured levels, failing on rules that don't exist
+    pub fn from_settings(settings: BTreeMap<String, RuleLevel>) -> Result<Self, String> {
+        let mut levels = HashMap::new();
+        for (id, level) in settings {
+            let rule = Rule::from_str(&id, true).map_err(|_| {
+                let known: Vec<&str> = Rule::value_variants().iter().map(|r| r.id()).collect();
+                format!("unknown rule '{}' in [rules]; expected one of {}", id, known.join(", "))
+            })?;
+            levels.insert(rule, level);
+        }
+        Ok(Self { levels })
+    }
+    
+    /// Stop checking a rule
+    pub fn disable(&mut self, rule: Rule) {
+        self.levels.insert(rule, RuleLevel::Off);
+    }
+    
+    /// Severity of breaking a rule, or None if it is turned off
+    pub fn severity(&self, rule: Rule) -> Option<Severity> {
+        match self.levels.get(&rule) {
+            Some(RuleLevel::Off) => None,
+            Some(RuleLevel::Info) => Some(Severity::Info),
+            Some(RuleLevel::Warning) => Some(Severity::Warning),
+            Some(RuleLevel::Error) => Some(Severity::Error),
+            None => Some(rule.default_severity()),
+        }
+    }
+    
+    /// Drop the reasons of turned-off rules from the issues, and the issues left with
+    /// no reason at all, and give the rest their configured severity
+    pub fn apply(&self, issues: &mut Vec<DocstringIssue>) {
+        issues.retain_mut(|issue| {
+            issue.reasons.retain_mut(|reason| match self.severity(reason.rule) {
+                Some(severity) => {
+                    reason.severity = severity;
+                    true
+                },
+                None => false,
+            });
+            !issue.reasons.is_empty()
+        });
+    }
+}
@@ -0,0 +1,255 @@
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::capabilities;
+
+/// How long a provider is left alone after a rate-limit or server error that didn't
+/// say when to retry
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Weight of the latest request in a provider's error rate
+const ERROR_WEIGHT: f64 = 0.2;
+
+/// Rate-limit headers giving what is left of an allowance, and its size, as sent by
+/// OpenAI (and Azure OpenAI) and by Anthropic
+const ALLOWANCE_HEADERS: &[(&str, &str)] = &[
+    ("x-ratelimit-remaining-requests", "x-ratelimit-limit-requests"),
+    ("x-ratelimit-remaining-tokens", "x-ratelimit-limit-tokens"),
+    ("anthropic-ratelimit-requests-remaining", "anthropic-ratelimit-requests-limit"),
+    ("anthropic-ratelimit-tokens-remaining", "anthropic-ratelimit-tokens-limit"),
+];
+
+/// Rate-limit headers giving how long until an allowance resets, e.g. `6m0s`
+const RESET_HEADERS: &[&str] = &["x-ratelimit-reset-requests", "x-ratelimit-reset-tokens"];
+
+/// One `[[providers]]` entry of the project configuration
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderSettings {
+    /// Provider to send requests to, e.g. "openai"
+    pub provider: String,
+
+    /// Model to request; None uses the provider's default
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Environment variable holding the API key; None uses the provider's usual one,
+    /// e.g. OPENAI_API_KEY
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+}
+
+/// What has been observed of a provider's rate limits and reliability
+#[derive(Debug, Default)]
+struct Health {
+    /// Smallest share of its allowances left, from the last response's rate-limit headers
+    headroom: Option<f64>,
+    /// No files are sent to the provider before this
+    cooldown_until: Option<Instant>,
+    /// Exponentially weighted share of recent requests that failed
+    error_rate: f64,
+    /// Files being documented with the provider right now
+    in_flight: usize,
+    requests: u64,
+    failures: u64,
+}
+
+impl Health {
+    /// How good a choice the provider is: more headroom and fewer recent errors are
+    /// better, and the files already in flight share it
+    fn score(&self) -> f64 {
+        self.headroom.unwrap_or(1.0) * (1.0 - self.error_rate) / (self.in_flight + 1) as f64
+    }
+    
+    /// Count a finished request in the error rate
+    fn record(&mut self, failed: bool) {
+        self.requests += 1;
+        self.error_rate *= 1.0 - ERROR_WEIGHT;
+        if failed {
+            self.failures += 1;
+            self.error_rate += ERROR_WEIGHT;
+        }
+    }
+}
+
+/// A provider of a pool, which its client reports every response to
+pub struct Member {
+    pub settings: ProviderSettings,
+    health: Mutex<Health>,
+}
+
+impl Member {
+    /// Provider, model, and key variable, for messages
+    pub fn describe(&self) -> String {
+        let mut description = self.settings.provider.clone();
+        if let Some(model) = &self.settings.model {
+            description.push_str(&format!(" {}", model));
+        }
+        if let Some(variable) = &self.settings.api_key_env {
+            description.push_str(&format!(" ({})", variable));
+        }
+        description
+    }
+    
+    /// Record a response: its rate-limit headers, and whether it failed. A rate-limit
+    /// or server error, or a used-up allowance, cools the provider down until the
+    /// limit resets (by `retry-after` or the reset headers).
+    pub fn observe(&self, status: u16, headers: &HeaderMap) {
+        let failed = status == 429 || status >= 500;
+        let mut health = self.health.lock().expect("provider health lock poisoned");
+        health.record(failed);
+        
+        let headroom = ALLOWANCE_HEADERS.iter()
+            .filter_map(|(remaining, limit)| {
+                let limit = header_number(headers, limit).filter(|limit| *limit > 0.0)?;
+                Some(header_number(headers, remaining)? / limit)
+            })
+            .reduce(f64::min);
+        if headroom.is_some() {
+            health.headroom = headroom;
+        }
+        
+        if failed || headroom == Some(0.0) {
+            let wait = retry_after(headers)
+                .or_else(|| RESET_HEADERS.iter().filter_map(|name| header_duration(headers, name)).max())
+                .unwrap_or(DEFAULT_COOLDOWN);
+            health.cooldown_until = Some(Instant::now() + wait);
+        }
+    }
+    
+    /// Record a request that got no response, such as a timeout
+    pub fn observe_failure(&self) {
+        self.health.lock().expect("provider health lock poisoned").record(true);
+    }
+}
+
+/// Spreads a run's requests across several providers or API keys (`[[providers]]` in
+/// the project configuration), so large runs aren't held back by one key's rate
+/// limits. Each file goes to the provider with the most rate-limit headroom and fewest
+/// recent errors; providers that hit their limits are left alone until the limits
+/// reset. Shared by every client of the run.
+pub struct Scheduler {
+    members: Vec<Arc<Member>>,
+}
+
+/// A provider picked for a file, counted as in flight until dropped
+pub struct Slot<'a> {
+    member: &'a Member,
+    pub index: usize,
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        self.member.health.lock().expect("provider health lock poisoned").in_flight -= 1;
+    }
+}
+
+impl Scheduler {
+    /// Pool of the configured providers, failing on unknown providers; None when none
+    /// are configured
+    pub fn from_settings(settings: Vec<ProviderSettings>) -> Result<Option<Self>, String> {
+        let mut members = Vec::new();
+        for mut settings in settings {
+            settings.provider = settings.provider.to_lowercase();
+            if settings.provider != "mock" && capabilities::schema(&settings.provider).is_none() {
+                return Err(format!("unknown provider '{}' in [[providers]]", settings.provider));
+            }
+            members.push(Arc::new(Member { settings, health: Mutex::new(Health::default()) }));
+        }
+        Ok(if members.is_empty() { None } else { Some(Self { members }) })
+    }
+    
+    /// Providers of the pool, in configuration order
+    pub fn members(&self) -> &[Arc<Member>] {
+        &self.members
+    }
+    
+    /// Pick the provider for a file's requests among those not tried for it yet,
+    /// waiting for the first cooldown to end when all of them are cooling down. None
+    /// once every provider has been tried. Ties go to the provider listed first.
+    pub async fn acquire(&self, tried: &[usize]) -> Option<Slot<'_>> {
+        loop {
+            let now = Instant::now();
+            let mut best: Option<(usize, f64)> = None;
+            let mut ready_at: Option<Instant> = None;
+            for (index, member) in self.members.iter().enumerate().filter(|(index, _)| !tried.contains(index)) {
+                let health = member.health.lock().expect("provider health lock poisoned");
+                match health.cooldown_until.filter(|until| *until > now) {
+                    Some(until) => ready_at = Some(ready_at.map_or(until, |ready| ready.min(until))),
+                    None => {
+                        let score = health.score();
+                        if best.is_none_or(|(_, best_score)| score > best_score) {
+                            best = Some((index, score));
+                        }
+                    },
+                }
+            }
+            
+            match (best, ready_at) {
+                (Some((index, _)), _) => {
+                    let member = &self.members[index];
+                    member.health.lock().expect("provider health lock poisoned").in_flight += 1;
+                    return Some(Slot { member, index });
+                },
+                (None, Some(until)) => tokio::time::sleep_until(until.into()).await,
+                (None, None) => return None,
+            }
+        }
+    }
+    
+    /// One line per provider with its requests and failures, for the end of a run
+    pub fn summary(&self) -> Vec<String> {
+        self.members.iter()
+            .map(|member| {
+                let health = member.health.lock().expect("provider health lock poisoned");
+                format!("{}: {} requests, {} failed", member.describe(), health.requests, health.failures)
+            })
+            .collect()
+    }
+}
+
+/// Numeric value of a header
+fn header_number(headers: &HeaderMap, name: &str) -> Option<f64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+/// Wait asked for by `retry-after-ms` or `retry-after` (in seconds)
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    header_number(headers, "retry-after-ms")
+        .map(|ms| ms / 1000.0)
+        .or_else(|| header_number(headers, "retry-after"))
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+/// Duration written like `1h2m3.5s` or `20ms`, as in OpenAI's reset headers
+fn header_duration(headers: &HeaderMap, name: &str) -> Option<Duration> {
+    let text = headers.get(name)?.to_str().ok()?.trim();
+    let mut seconds = 0.0;
+    let mut number = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let value: f64 = number.parse().ok()?;
+        number.clear();
+        seconds += value * match c {
+            'h' => 3600.0,
+            'm' if chars.peek() == Some(&'s') => {
+                chars.next();
+                0.001
+            },
+            'm' => 60.0,
+            's' => 1.0,
+            _ => return None,
+        };
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(Duration::from_secs_f64(seconds))
+}
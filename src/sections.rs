@@ -0,0 +1,335 @@
+use regex::Regex;
+
+use crate::parser::{CodeItem, ItemKind};
+use crate::rules::{Reason, Rule};
+
+/// What a section of a docstring describes
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SectionKind {
+    Parameters,
+    Returns,
+    Raises,
+    Other,
+}
+
+/// How a section heading is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Layout {
+    /// Google style, `Args:`, with the entries indented under it
+    Colon,
+    /// NumPy style, `Parameters` underlined with dashes
+    Underline,
+    /// Markdown, as in rustdoc's `# Arguments`
+    Hash,
+}
+
+/// A section being read: its kind and layout, the heading's indentation, and that of
+/// its entries once the first one is seen
+struct Section {
+    kind: SectionKind,
+    layout: Layout,
+    indent: usize,
+    entry_indent: Option<usize>,
+}
+
+/// Patterns for the fields, tags, headings, and entries of every convention, compiled
+/// once per docstring
+struct Patterns {
+    param_field: Regex,
+    type_field: Regex,
+    returns_field: Regex,
+    raises_field: Regex,
+    param_tag: Regex,
+    returns_tag: Regex,
+    raises_tag: Regex,
+    colon_heading: Regex,
+    hash_heading: Regex,
+    plain_heading: Regex,
+    underline: Regex,
+    google_entry: Regex,
+    numpy_entry: Regex,
+    bullet_entry: Regex,
+    exception_entry: Regex,
+}
+
+impl Patterns {
+    fn new() -> Self {
+        Self {
+            param_field: Regex::new(r"^\s*:param\s+(?:(.+?)\s+)?\**(\w+)\s*:").unwrap(),
+            type_field: Regex::new(r"^\s*:type\s+\**(\w+)\s*:\s*(.+?)\s*$").unwrap(),
+            returns_field: Regex::new(r"^\s*:(?:returns?|rtype|yields?)\s*:").unwrap(),
+            raises_field: Regex::new(r"^\s*:raises?\s+([\w.]+)\s*:").unwrap(),
+            param_tag: Regex::new(r"[@\\]param(?:\[[^\]]*\])?\s+(?:\{([^}]*)\}\s*)?\[?\**(\w+)|(?i)^\s*\.PARAMETER\s+(\w+)|<param\s+name=.(\w+)|^\s*-\s+Parameter\s+(\w+)\s*:").unwrap(),
+            returns_tag: Regex::new(r"[@\\](?:returns?|result)\b|(?i)^\s*\.OUTPUTS\b|<returns>|^\s*-\s+Returns\s*:").unwrap(),
+            raises_tag: Regex::new(r"[@\\](?:throws|exception|raises?)\s+(?:\{([^}]*)\}|([\w.]+))|<exception\s+cref=.([\w.]+)").unwrap(),
+            colon_heading: Regex::new(r"^(\s*)([A-Za-z][A-Za-z ]*?):\s*$").unwrap(),
+            hash_heading: Regex::new(r"^(\s*)#+\s*([A-Za-z][A-Za-z ]*?)\s*$").unwrap(),
+            plain_heading: Regex::new(r"^(\s*)([A-Za-z][A-Za-z ]*?)\s*$").unwrap(),
+            underline: Regex::new(r"^\s*-{3,}\s*$").unwrap(),
+            google_entry: Regex::new(r"^\s*\**(\w+)\s*(?:\(([^)]*)\))?\s*:").unwrap(),
+            numpy_entry: Regex::new(r"^\s*(\**\w+(?:\s*,\s*\**\w+)*)\s*(?::\s*(.*?))?\s*$").unwrap(),
+            bullet_entry: Regex::new(r"^\s*[*-]\s+`?\**(\w+)`?\s*(?:[-:–]|$)").unwrap(),
+            exception_entry: Regex::new(r"^\s*([A-Za-z_][\w.]*)\s*(?::|$)").unwrap(),
+        }
+    }
+}
+
+/// A parameter a docstring describes, with the type it gives if any
+#[derive(Debug, Clone)]
+pub struct DocumentedParameter {
+    pub name: String,
+    pub type_name: Option<String>,
+}
+
+/// The structured parts of a docstring, in whichever convention it is written: Google,
+/// NumPy, and rustdoc sections, Sphinx fields, `@param`-style tags (JSDoc, Javadoc,
+/// Doxygen, NatSpec, ...), PowerShell help keywords, XML documentation elements, and
+/// Swift `- Parameter x:` markup
+#[derive(Debug, Default)]
+pub struct Sections {
+    /// Parameters described, in order
+    pub parameters: Vec<DocumentedParameter>,
+    /// Whether the docstring has a place for parameters, even an empty one
+    pub lists_parameters: bool,
+    /// Whether the return value, or the values yielded, are described
+    pub returns: bool,
+    /// Exception types said to be raised
+    pub raises: Vec<String>,
+    /// Whether the docstring uses a convention with its own place for the return value,
+    /// where a mention in the prose doesn't count
+    pub tagged: bool,
+}
+
+impl Sections {
+    /// Read the sections, fields, and tags of a docstring
+    pub fn parse(docstring: &str) -> Self {
+        let patterns = Patterns::new();
+        let lines: Vec<&str> = docstring.lines().collect();
+        let mut sections = Sections::default();
+        let mut current: Option<Section> = None;
+        let mut index = 0;
+        while index < lines.len() {
+            let line = lines[index];
+            
+            if let Some((section, heading_lines)) = heading(&patterns, &lines, index) {
+                match section.kind {
+                    SectionKind::Parameters => sections.lists_parameters = true,
+                    SectionKind::Returns => sections.returns = true,
+                    _ => {},
+                }
+                sections.tagged |= section.layout != Layout::Hash;
+                current = Some(section);
+                index += heading_lines;
+                continue;
+            }
+            index += 1;
+            
+            // Fields and tags count wherever they are
+            if let Some(captures) = patterns.param_field.captures(line) {
+                sections.add_parameter(&captures[2], captures.get(1).map(|m| m.as_str()));
+                sections.tagged = true;
+            } else if let Some(captures) = patterns.type_field.captures(line) {
+                sections.add_parameter(&captures[1], Some(&captures[2]));
+                sections.tagged = true;
+            } else if let Some(captures) = patterns.raises_field.captures(line) {
+                sections.raises.push(captures[1].to_string());
+                sections.tagged = true;
+            } else if let Some(captures) = patterns.param_tag.captures(line) {
+                let name = captures.get(2).or_else(|| captures.get(3)).or_else(|| captures.get(4)).or_else(|| captures.get(5)).map_or("", |m| m.as_str());
+                sections.add_parameter(name, captures.get(1).map(|m| m.as_str()));
+                sections.tagged = true;
+            }
+            if patterns.returns_field.is_match(line) || patterns.returns_tag.is_match(line) {
+                sections.returns = true;
+                sections.tagged = true;
+            }
+            for captures in patterns.raises_tag.captures_iter(line) {
+                if let Some(name) = captures.iter().skip(1).flatten().next() {
+                    sections.raises.push(name.as_str().trim().to_string());
+                    sections.tagged = true;
+                }
+            }
+            
+            // Entries are the lines at the indentation of a section's first line; deeper
+            // lines continue their descriptions
+            let section = match &mut current {
+                Some(section) if !line.trim().is_empty() => section,
+                _ => continue,
+            };
+            let indent = line.len() - line.trim_start().len();
+            if section.layout == Layout::Colon && indent <= section.indent {
+                current = None;
+                continue;
+            }
+            if *section.entry_indent.get_or_insert(indent) == indent {
+                let (kind, layout) = (section.kind, section.layout);
+                sections.add_entry(&patterns, kind, layout, line);
+            }
+        }
+        
+        sections
+    }
+    
+    /// Record a documented parameter, or the type of one already recorded
+    fn add_parameter(&mut self, name: &str, type_name: Option<&str>) {
+        self.lists_parameters = true;
+        let name = name.trim_start_matches('*');
+        let type_name = type_name.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        match self.parameters.iter_mut().find(|p| p.name == name) {
+            Some(parameter) => {
+                if type_name.is_some() {
+                    parameter.type_name = type_name;
+                }
+            },
+            None => self.parameters.push(DocumentedParameter { name: name.to_string(), type_name }),
+        }
+    }
+    
+    /// Read one entry of a section: a parameter (`name (type): ...`, `name : type`, or
+    /// a `` * `name` - ... `` bullet) or an exception type (`ValueError: ...`)
+    fn add_entry(&mut self, patterns: &Patterns, kind: SectionKind, layout: Layout, line: &str) {
+        match (kind, layout) {
+            (SectionKind::Parameters, Layout::Underline) => {
+                if let Some(captures) = patterns.numpy_entry.captures(line) {
+                    let type_name = captures.get(2).map(|m| m.as_str());
+                    for name in captures[1].split(',') {
+                        self.add_parameter(name.trim(), type_name);
+                    }
+                }
+            },
+            (SectionKind::Parameters, _) => {
+                if let Some(captures) = patterns.bullet_entry.captures(line) {
+                    self.add_parameter(&captures[1], None);
+                } else if let Some(captures) = patterns.google_entry.captures(line).filter(|_| layout == Layout::Colon) {
+                    self.add_parameter(&captures[1], captures.get(2).map(|m| m.as_str()));
+                }
+            },
+            (SectionKind::Raises, Layout::Colon | Layout::Underline) => {
+                if let Some(captures) = patterns.exception_entry.captures(line) {
+                    self.raises.push(captures[1].to_string());
+                }
+            },
+            _ => {},
+        }
+    }
+    
+    /// The documented parameter with this name
+    pub fn parameter(&self, name: &str) -> Option<&DocumentedParameter> {
+        self.parameters.iter().find(|p| p.name == name)
+    }
+}
+
+/// The section a line starts, if it is a heading, with the number of lines the
+/// heading takes
+fn heading(patterns: &Patterns, lines: &[&str], index: usize) -> Option<(Section, usize)> {
+    let line = lines[index];
+    let (captures, layout, heading_lines) = if let Some(captures) = patterns.colon_heading.captures(line) {
+        (captures, Layout::Colon, 1)
+    } else if let Some(captures) = patterns.hash_heading.captures(line) {
+        (captures, Layout::Hash, 1)
+    } else {
+        let underlined = lines.get(index + 1).is_some_and(|next| patterns.underline.is_match(next));
+        (patterns.plain_heading.captures(line).filter(|_| underlined)?, Layout::Underline, 2)
+    };
+    let kind = section_kind(&captures[2])?;
+    Some((Section { kind, layout, indent: captures[1].len(), entry_indent: None }, heading_lines))
+}
+
+/// Kind of section a heading starts, e.g. `Args` or `Returns`. Keyword arguments are
+/// options passed through `**kwargs` rather than parameters of their own.
+fn section_kind(heading: &str) -> Option<SectionKind> {
+    match heading.to_lowercase().as_str() {
+        "args" | "arguments" | "parameters" | "params" => Some(SectionKind::Parameters),
+        "returns" | "return" | "yields" | "yield" => Some(SectionKind::Returns),
+        "raises" | "raise" | "throws" | "exceptions" | "errors" | "panics" => Some(SectionKind::Raises),
+        "keyword args" | "keyword arguments" | "other parameters" | "attributes" | "examples" | "example"
+            | "notes" | "note" | "see also" | "references" | "warnings" | "warning" | "safety" | "todo" => Some(SectionKind::Other),
+        _ => None,
+    }
+}
+
+/// Why an item's documentation looks out of date, one reason each, e.g.
+/// `undocumented-param: foo`, `extra-param: bar`, or `missing-return`. Empty when it
+/// looks up to date.
+pub fn outdated_reasons(item: &CodeItem, docstring: &str) -> Vec<Reason> {
+    let sections = Sections::parse(docstring);
+    let parameters: Vec<&str> = item.parameters.iter()
+        .map(|p| p.trim_matches(|c| c == '*' || c == '='))
+        .collect();
+    let mut reasons = Vec::new();
+    
+    // Where the docstring has a place for parameters, only what is listed there counts;
+    // otherwise a mention in the prose does. Parsers leave out receivers such as `self`.
+    for name in &parameters {
+        let documented = if sections.lists_parameters {
+            sections.parameter(name).is_some()
+        } else {
+            Regex::new(&format!(r"\b{}\b", regex::escape(name))).is_ok_and(|re| re.is_match(docstring))
+        };
+        if !documented {
+            reasons.push(Reason::about(Rule::UndocumentedParam, name));
+        }
+    }
+    
+    // Names listed beyond the signature were removed or renamed, unless `**kwargs`
+    // takes them
+    let takes_keywords = item.parameters.iter().any(|p| p.starts_with("**"));
+    for documented in sections.parameters.iter().filter(|_| !takes_keywords) {
+        if !parameters.contains(&documented.name.as_str()) {
+            reasons.push(Reason::about(Rule::ExtraParam, &documented.name));
+        }
+    }
+    
+    // Documented types must still match the annotations
+    for param in &item.parameter_details {
+        let name = param.name.trim_start_matches('*');
+        let documented = sections.parameter(name).and_then(|p| p.type_name.as_deref());
+        if let (Some(annotation), Some(documented)) = (&param.annotation, documented) {
+            if normalize_type(documented) != normalize_type(annotation) {
+                reasons.push(Reason::about(Rule::ParamTypeMismatch, name));
+            }
+        }
+    }
+    
+    // Return annotations need describing, which for generators means what they yield;
+    // a property describes its value instead
+    if item.returns.is_some() && item.item_type != ItemKind::Property {
+        let keyword = if item.is_generator { "yield" } else { "return" };
+        if !sections.returns && (sections.tagged || !docstring.to_lowercase().contains(keyword)) {
+            reasons.push(Reason::new(Rule::MissingReturn));
+        }
+    }
+    
+    // A list of exceptions should name every one the code raises itself, or declares
+    // in a `throws` clause
+    if !sections.raises.is_empty() {
+        let raise_re = Regex::new(r"\b(?:raise|throw\s+new)\s+([A-Za-z_][\w.]*)").unwrap();
+        let short = |name: &str| name.rsplit('.').next().unwrap_or(name).to_string();
+        let documented: Vec<String> = sections.raises.iter().map(|name| short(name)).collect();
+        let mut raised: Vec<String> = Vec::new();
+        let names = item.throws.iter().map(|name| short(name))
+            .chain(raise_re.captures_iter(&item.code).map(|captures| short(&captures[1])));
+        for name in names {
+            if !raised.contains(&name) {
+                raised.push(name);
+            }
+        }
+        for name in raised.iter().filter(|name| !documented.contains(name)) {
+            reasons.push(Reason::about(Rule::MissingRaises, name));
+        }
+    }
+    
+    // Very short documentation is likely a placeholder
+    if docstring.trim().len() < 10 {
+        reasons.push(Reason::new(Rule::ShortDocstring));
+    }
+    
+    reasons
+}
+
+/// Type written for comparison: without whitespace, case, `typing.` prefixes, or the
+/// `, optional` that marks parameters with defaults
+fn normalize_type(text: &str) -> String {
+    let text: String = text.split_whitespace().collect::<String>().to_lowercase();
+    text.trim_end_matches(",optional").replace("typing.", "")
+}
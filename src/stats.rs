@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::error::DocGenResult;
+use crate::report::FileReport;
+
+/// One line of the local stats file, describing a single run.
+/// Files from several machines can be concatenated and aggregated by a team lead;
+/// nothing here is ever sent over the network.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RunStats {
+    pub timestamp: u64,
+    pub machine: String,
+    pub provider: String,
+    pub mode: String,               // "write" or "check"
+    pub files_processed: usize,
+    pub issues_found: usize,
+    pub items_fixed: usize,
+    pub tokens: u64,
+}
+
+impl RunStats {
+    /// Summarize a run from its per-file reports
+    pub fn from_reports(config: &Config, reports: &[FileReport]) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            machine: machine_name(),
+            provider: config.provider.to_lowercase(),
+            mode: if config.check_only { "check" } else { "write" }.to_string(),
+            files_processed: reports.len(),
+            issues_found: reports.iter().map(|r| r.issues.len()).sum(),
+            items_fixed: reports.iter().map(|r| r.documented.len()).sum(),
+            tokens: reports.iter().map(|r| r.tokens_used).sum(),
+        }
+    }
+}
+
+/// Append this run's statistics as a JSON line to `path`, creating the file if needed
+pub fn record_run(path: &Path, config: &Config, reports: &[FileReport]) -> DocGenResult<()> {
+    let stats = RunStats::from_reports(config, reports);
+    
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(&stats).unwrap_or_default())?;
+    
+    log::debug!("Recorded usage statistics in {}", path.display());
+    
+    Ok(())
+}
+
+/// Best-effort machine name from the environment, so runs can be told apart when aggregated
+fn machine_name() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .filter(|name| !name.is_empty())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok().map(|s| s.trim().to_string()))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
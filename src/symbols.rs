@@ -0,0 +1,122 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::parser::{CodeItem, ItemKind, ParsedCode};
+
+/// Most collaborators described in one prompt
+const MAX_COLLABORATORS: usize = 8;
+
+/// Longest signature quoted for a collaborator, in characters
+const MAX_SIGNATURE_LENGTH: usize = 120;
+
+/// A named item defined somewhere in the project
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub item_type: ItemKind,
+    /// File the item is defined in, relative to the repository root
+    pub file: PathBuf,
+    /// First line of the item's code
+    pub signature: String,
+    /// First line of the item's documentation, if it has any
+    pub summary: Option<String>,
+}
+
+/// Names, signatures, and one-line summaries of the types and functions across the
+/// project's files (`--project-context`), so prompts can say what the other items an
+/// item uses are
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    symbols: HashMap<String, Vec<Symbol>>,
+}
+
+impl SymbolIndex {
+    /// Add the types and functions of a parsed file. Methods, fields, and other members
+    /// are left out: their names are too common to tell apart by name alone.
+    pub fn add(&mut self, file: &Path, parsed_code: &ParsedCode) {
+        for item in parsed_code.items.iter().filter(|item| indexed(item.item_type)) {
+            let signature = item.code.lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .unwrap_or("");
+            let summary = item.existing_docstring.as_deref()
+                .and_then(|doc| doc.lines().map(str::trim).find(|line| !line.is_empty()))
+                .map(|line| line.to_string());
+            
+            self.symbols.entry(item.name.clone()).or_default().push(Symbol {
+                name: item.name.clone(),
+                item_type: item.item_type,
+                file: file.to_path_buf(),
+                signature: truncate(signature, MAX_SIGNATURE_LENGTH),
+                summary,
+            });
+        }
+    }
+    
+    /// Whether any indexed type or function has this name
+    pub fn contains(&self, name: &str) -> bool {
+        self.symbols.contains_key(name)
+    }
+    
+    /// Number of indexed symbols
+    pub fn count(&self) -> usize {
+        self.symbols.values().map(Vec::len).sum()
+    }
+    
+    /// Symbols defined elsewhere that an item's code mentions by name, in the order
+    /// they are first mentioned
+    pub fn collaborators(&self, item: &CodeItem) -> Vec<&Symbol> {
+        let identifier_re = Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap();
+        let mut seen = HashSet::new();
+        identifier_re.find_iter(&item.code)
+            .map(|m| m.as_str())
+            .filter(|name| *name != item.name && seen.insert(*name))
+            .filter_map(|name| self.symbols.get(name))
+            .flatten()
+            .take(MAX_COLLABORATORS)
+            .collect()
+    }
+    
+    /// Prompt section describing an item's collaborators, or nothing when it has none
+    pub fn context(&self, item: &CodeItem) -> String {
+        let collaborators = self.collaborators(item);
+        if collaborators.is_empty() {
+            return String::new();
+        }
+        
+        let mut context = String::from("\n\nOther items in the project that this code uses, \
+            for context (mention them briefly where it helps; do not document them):");
+        for symbol in collaborators {
+            context.push_str(&format!("\n- {} `{}` in {}: `{}`",
+                symbol.item_type,
+                symbol.name,
+                symbol.file.display(),
+                symbol.signature));
+            if let Some(summary) = &symbol.summary {
+                context.push_str(&format!(" - {}", summary));
+            }
+        }
+        context
+    }
+}
+
+/// Whether items of a kind are indexed: types and free-standing functions
+fn indexed(kind: ItemKind) -> bool {
+    matches!(kind,
+        ItemKind::Function | ItemKind::Class | ItemKind::Struct | ItemKind::Enum | ItemKind::Trait
+        | ItemKind::Interface | ItemKind::Type | ItemKind::Object | ItemKind::Module | ItemKind::Structure
+        | ItemKind::Sub | ItemKind::Subroutine | ItemKind::Kernel | ItemKind::Message | ItemKind::Service
+        | ItemKind::Schema | ItemKind::Protocol | ItemKind::Contract | ItemKind::Library | ItemKind::Component
+        | ItemKind::Hook)
+}
+
+/// Shorten text to at most `max` characters, marking the cut with an ellipsis
+pub fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max - 1).collect();
+    truncated.push('…');
+    truncated
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use crate::docstring::DocstringIssue;
+
+/// Expand an issue-tracker URL template for a documentation issue.
+///
+/// Supported placeholders (values are URL-encoded): `{file}`, `{line}`,
+/// `{name}`, `{item_type}`, `{issue_type}`, and `{title}`, e.g.
+/// `https://github.com/org/repo/issues/new?title={title}&body={file}:{line}`.
+pub fn issue_url(template: &str, file_path: &Path, issue: &DocstringIssue) -> String {
+    let file = file_path.display().to_string();
+    let title = format!("Document {} {} in {}", issue.item_type, issue.name, file);
+    
+    template
+        .replace("{file}", &url_encode(&file))
+        .replace("{line}", &issue.line_number.to_string())
+        .replace("{name}", &url_encode(&issue.name))
+        .replace("{item_type}", &url_encode(issue.item_type.as_str()))
+        .replace("{issue_type}", &url_encode(issue.issue_type.as_str()))
+        .replace("{title}", &url_encode(&title))
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters
+fn url_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -1,6 +1,11 @@
+use crate::config::Config;
+use crate::diff;
 use crate::docstring::UpdatedDocstring;
 use crate::error::DocGenResult;
+use crate::io;
 use crate::lang;
+use std::io::Write;
+use std::path::Path;
 
 /// Update the file content with new docstrings
 pub fn update_file_content(
@@ -11,3 +16,40 @@ pub fn update_file_content(
     let parser = lang::get_parser(language);
     parser.update_content(original_content, updated_docstrings)
 }
+
+/// Write updated content back to the file, or in diff mode leave the file untouched
+/// and print the change to `out` as a colored diff; a dry run leaves the file
+/// untouched without printing. Returns the uncolored diff when the file isn't
+/// written, for the patch file, and None otherwise.
+pub fn write_update(
+    file_path: &Path,
+    original_content: &str,
+    updated_content: &str,
+    config: &Config,
+    out: &mut Vec<u8>,
+) -> DocGenResult<Option<String>> {
+    if !config.diff_only && !config.dry_run {
+        write_file(file_path, updated_content, config)?;
+        return Ok(None);
+    }
+    
+    let patch = diff::unified(file_path, original_content, updated_content);
+    if config.diff_only {
+        write!(out, "{}", diff::colorize(&patch))?;
+    }
+    Ok(Some(patch))
+}
+
+/// Write updated content to a file in the format it already has (its byte order mark,
+/// line endings, and final newline), first copying the file aside when the run keeps
+/// backups (`--backup`)
+pub fn write_file(file_path: &Path, updated_content: &str, config: &Config) -> DocGenResult<()> {
+    let text = match io::TextFormat::of_file(file_path)? {
+        Some(format) => format.apply(updated_content),
+        None => updated_content.to_string(),
+    };
+    if let Some(backups) = &config.backups {
+        backups.keep(file_path, &text)?;
+    }
+    io::write_atomic(file_path, text.as_bytes())
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use crate::discover;
+
+/// Directory names that hold third-party code in common ecosystems
+const DEFAULT_VENDOR_DIRS: &[&str] = &[
+    "vendor",
+    "third_party",
+    "third-party",
+    "site-packages",
+    "dist-packages",
+    "node_modules",
+    "target",
+    ".venv",
+    "venv",
+];
+
+/// Check whether a path lies inside a vendored or third-party directory, either one of
+/// the defaults or one of `extra_dirs`. Extra entries containing a `/` match as a
+/// path prefix (e.g. `libs/external`); others match any directory component by name.
+/// Names are compared regardless of case on case-insensitive filesystems.
+pub fn is_third_party(path: &Path, extra_dirs: &[String]) -> bool {
+    let same = |a: &str, b: &str| if discover::CASE_INSENSITIVE { a.eq_ignore_ascii_case(b) } else { a == b };
+    let normalized = discover::normalize(path);
+    let mut components: Vec<&str> = normalized.split('/').collect();
+    components.pop();
+    
+    let matches_name = |name: &str| components.iter().any(|c| same(c, name));
+    
+    DEFAULT_VENDOR_DIRS.iter().any(|dir| matches_name(dir))
+        || extra_dirs.iter().any(|dir| {
+            let dir = discover::normalize(Path::new(dir.trim_matches('/')));
+            if dir.contains('/') {
+                let prefix: Vec<&str> = dir.split('/').collect();
+                components.len() >= prefix.len() && prefix.iter().zip(&components).all(|(p, c)| same(p, c))
+            } else {
+                matches_name(&dir)
+            }
+        })
+}
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use notify::event::{EventKind, ModifyKind};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Filesystem notifications for `docgen watch`, gathered into batches of the files
+/// that were saved
+pub struct Watcher {
+    /// Kept alive for as long as notifications are wanted
+    _watcher: RecommendedWatcher,
+    changes: mpsc::UnboundedReceiver<PathBuf>,
+}
+
+impl Watcher {
+    /// Watch files, and directories recursively, for files being created or written
+    pub fn new(paths: &[PathBuf]) -> Result<Self> {
+        let (sender, changes) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let event = match event {
+                Ok(event) => event,
+                Err(_) => return,
+            };
+            // Reads and metadata changes, such as a touched timestamp, don't change
+            // what a file documents
+            let written = match event.kind {
+                EventKind::Create(_) => true,
+                EventKind::Modify(ModifyKind::Metadata(_)) => false,
+                EventKind::Modify(_) => true,
+                _ => false,
+            };
+            if written {
+                for path in event.paths {
+                    // The receiver is only dropped when watching stops
+                    let _ = sender.send(path);
+                }
+            }
+        })?;
+        for path in paths {
+            watcher.watch(path, RecursiveMode::Recursive)?;
+        }
+        Ok(Self { _watcher: watcher, changes })
+    }
+    
+    /// Wait for a file to be written, then keep collecting files until none has been
+    /// written for `quiet`, so an editor's several writes of one save are seen once.
+    /// Returns the files that still exist, in path order, or None if notifications
+    /// stopped.
+    pub async fn changed(&mut self, quiet: Duration) -> Option<Vec<PathBuf>> {
+        let mut changed = BTreeSet::new();
+        changed.insert(self.changes.recv().await?);
+        while let Ok(Some(path)) = tokio::time::timeout(quiet, self.changes.recv()).await {
+            changed.insert(path);
+        }
+        Some(changed.into_iter().filter(|path| path.is_file()).collect())
+    }
+}